@@ -1,6 +1,7 @@
 use pyo3::{pyclass, pymethods, PyResult};
 
 use crate::ecore;
+use crate::CharSet;
 
 /// OCR Strategy for PDF parsing
 #[pyclass(eq, eq_int)]
@@ -26,6 +27,46 @@ impl From<PdfOcrStrategy> for ecore::PdfOcrStrategy {
     }
 }
 
+/// What the formula cells of spreadsheets become in the extracted content
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum FormulaOutput {
+    ComputedValue,
+    Formula,
+    Both,
+}
+
+impl From<FormulaOutput> for ecore::FormulaOutput {
+    fn from(output: FormulaOutput) -> Self {
+        match output {
+            FormulaOutput::ComputedValue => ecore::FormulaOutput::ComputedValue,
+            FormulaOutput::Formula => ecore::FormulaOutput::Formula,
+            FormulaOutput::Both => ecore::FormulaOutput::Both,
+        }
+    }
+}
+
+/// Color type of the images PDF pages are rendered to before they are OCR'd
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum PdfOcrImageType {
+    Binary,
+    Gray,
+    Rgb,
+    Argb,
+}
+
+impl From<PdfOcrImageType> for ecore::PdfOcrImageType {
+    fn from(image_type: PdfOcrImageType) -> Self {
+        match image_type {
+            PdfOcrImageType::Binary => ecore::PdfOcrImageType::Binary,
+            PdfOcrImageType::Gray => ecore::PdfOcrImageType::Gray,
+            PdfOcrImageType::Rgb => ecore::PdfOcrImageType::Rgb,
+            PdfOcrImageType::Argb => ecore::PdfOcrImageType::Argb,
+        }
+    }
+}
+
 /// PDF parsing configuration settings
 ///
 /// These settings are used to configure the behavior of the PDF parsing.
@@ -97,6 +138,93 @@ impl PdfParserConfig {
         Ok(Self(inner))
     }
 
+    /// Sets whether inline images are OCR'd when they are extracted with
+    /// `extract_inline_images`, independently of the OCR of whole pages.
+    /// Default: true.
+    pub fn set_ocr_inline_images(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_inline_images(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, the text of each page is sorted by its position, top to bottom and left to
+    /// right, instead of following the order it is drawn in.
+    /// Default: false.
+    pub fn set_sort_by_position(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_sort_by_position(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the tolerance, as a fraction of the average character width, within which
+    /// consecutive characters are considered part of the same word.
+    /// Default: 0.3.
+    pub fn set_average_char_tolerance(&self, val: f32) -> PyResult<Self> {
+        let inner = self.0.clone().set_average_char_tolerance(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the tolerance, as a fraction of the width of a space, from which the gap between
+    /// two characters is considered a space.
+    /// Default: 0.5.
+    pub fn set_spacing_tolerance(&self, val: f32) -> PyResult<Self> {
+        let inner = self.0.clone().set_spacing_tolerance(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, characters drawn on top of an identical character, such as fake bold text or
+    /// stacked text layers, are only extracted once.
+    /// Default: false.
+    pub fn set_suppress_duplicate_overlapping_text(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_suppress_duplicate_overlapping_text(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, text drawn at an angle, such as a rotated stamp or watermark, is extracted as
+    /// lines of its own instead of being broken into single characters.
+    /// Default: false.
+    pub fn set_detect_angles(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_detect_angles(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the resolution, in dots per inch, at which PDF pages are rendered to images before
+    /// they are OCR'd. A higher DPI helps with small fonts at the cost of speed and memory.
+    /// Values below 1 are raised to 1.
+    /// Default: None, which keeps Tika's default of 300.
+    pub fn set_ocr_render_dpi(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_render_dpi(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the color type of the images PDF pages are rendered to before they are OCR'd.
+    /// Default: PdfOcrImageType.Gray
+    pub fn set_ocr_image_type(&self, val: PdfOcrImageType) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_image_type(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Sets the quality, between 0.0 and 1.0, at which the rendered PDF pages are encoded
+    /// before they are OCR'd. Lower values make smaller images at the cost of detail.
+    /// Default: 1.0.
+    pub fn set_ocr_image_quality(&self, val: f32) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_image_quality(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the password used to decrypt password protected PDFs
+    /// Default: None.
+    pub fn set_password(&self, secret: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_password(secret);
+        Ok(Self(inner))
+    }
+
+    /// Sets the pages parsed, from `start` to `end` inclusive and counted from 1, so the
+    /// other pages are neither parsed nor OCR'd.
+    /// Default: all pages.
+    pub fn set_page_range(&self, start: u32, end: u32) -> PyResult<Self> {
+        let inner = self.0.clone().set_page_range(start..=end);
+        Ok(Self(inner))
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -207,6 +335,40 @@ impl OfficeParserConfig {
         Ok(Self(inner))
     }
 
+    /// Office documents can carry custom (user defined) properties besides the standard ones
+    /// such as author or title. Custom properties are returned in the metadata under the
+    /// `custom:` prefix, e.g. `custom:DocumentClassification`. Set this to false to drop all
+    /// custom properties from the metadata.
+    /// Default: true
+    pub fn set_extract_custom_properties(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_extract_custom_properties(val);
+        Ok(Self(inner))
+    }
+
+    /// Whether to include the text of the charts of docx, pptx and xlsx files, independently
+    /// of the shape based content.
+    /// Default: true
+    pub fn set_include_chart_content(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_include_chart_content(val);
+        Ok(Self(inner))
+    }
+
+    /// Whether to include the text of the SmartArt diagrams of docx and pptx files,
+    /// independently of the shape based content.
+    /// Default: true
+    pub fn set_include_smartart_content(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_include_smartart_content(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets what the formula cells of xls, xlsx and ods spreadsheets become in the extracted
+    /// content: the computed value, the formula, or both.
+    /// Default: FormulaOutput.ComputedValue
+    pub fn set_formula_output(&self, val: FormulaOutput) -> PyResult<Self> {
+        let inner = self.0.clone().set_formula_output(val.into());
+        Ok(Self(inner))
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -271,6 +433,39 @@ impl TesseractOcrConfig {
         Ok(Self(inner))
     }
 
+    /// Sets a prioritized list of languages to try when the language of the documents is not
+    /// known. Every image is OCR'd with the languages in order and the text with the highest
+    /// mean word confidence is kept. The kept language and its confidence are listed in the
+    /// `X-OCR-Language` and `X-OCR-Confidence` metadata.
+    /// Default: empty, only the language set by set_language is used.
+    pub fn set_language_fallback(&self, val: Vec<String>) -> PyResult<Self> {
+        let inner = self.0.clone().set_language_fallback(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum number of languages of the fallback list tried for each image.
+    /// Default: 3.
+    pub fn set_language_fallback_max_attempts(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_language_fallback_max_attempts(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the mean word confidence, from 0 to 1, at which the text of a fallback language is
+    /// kept without trying the next languages.
+    /// Default: 0.9.
+    pub fn set_language_fallback_min_confidence(&self, val: f32) -> PyResult<Self> {
+        let inner = self.0.clone().set_language_fallback_min_confidence(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets whether a language that is not installed is dropped, with a warning in the
+    /// X-OCR-Warning metadata, rather than failing the extraction.
+    /// Default: true.
+    pub fn set_fallback_on_missing_language(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_fallback_on_missing_language(val);
+        Ok(Self(inner))
+    }
+
     /// Sets the maximum time in seconds that Tesseract should spend on OCR.
     /// Default: 120.
     pub fn set_timeout_seconds(&self, val: i32) -> PyResult<Self> {
@@ -282,3 +477,207 @@ impl TesseractOcrConfig {
         format!("{:?}", self.0)
     }
 }
+
+/// What the url extractions do when the server answers with a redirect
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum RedirectPolicy {
+    Follow,
+    SameOrigin,
+    NoFollow,
+}
+
+impl From<RedirectPolicy> for ecore::RedirectPolicy {
+    fn from(policy: RedirectPolicy) -> Self {
+        match policy {
+            RedirectPolicy::Follow => ecore::RedirectPolicy::Follow,
+            RedirectPolicy::SameOrigin => ecore::RedirectPolicy::SameOrigin,
+            RedirectPolicy::NoFollow => ecore::RedirectPolicy::NoFollow,
+        }
+    }
+}
+
+/// HTTP configuration settings of the url extractions
+///
+/// These settings configure the requests of the extract_url functions, e.g. to pull documents
+/// from endpoints that require authentication.
+#[pyclass]
+#[derive(Clone, PartialEq)]
+pub struct HttpConfig(ecore::HttpConfig);
+
+impl From<HttpConfig> for ecore::HttpConfig {
+    fn from(config: HttpConfig) -> Self {
+        config.0
+    }
+}
+
+#[pymethods]
+impl HttpConfig {
+    /// Creates a new instance of HttpConfig with default settings.
+    #[new]
+    pub fn new() -> Self {
+        Self(ecore::HttpConfig::new())
+    }
+
+    /// Adds a header to every request. Adding a header that was already added replaces its value.
+    /// The headers are only sent to the origin of the requested url.
+    pub fn add_header(&self, name: &str, value: &str) -> PyResult<Self> {
+        let inner = self.0.clone().add_header(name, value);
+        Ok(Self(inner))
+    }
+
+    /// Authenticates the requests with a bearer token, sent in the `Authorization` header.
+    /// The credentials are only sent to the origin of the requested url.
+    pub fn set_bearer_auth(&self, token: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_bearer_auth(token);
+        Ok(Self(inner))
+    }
+
+    /// Authenticates the requests with a user name and password, sent in the `Authorization`
+    /// header. The credentials are only sent to the origin of the requested url.
+    pub fn set_basic_auth(&self, username: &str, password: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_basic_auth(username, password);
+        Ok(Self(inner))
+    }
+
+    /// Sends the requests through the HTTP proxy at the given host and port.
+    pub fn set_proxy(&self, host: &str, port: u16) -> PyResult<Self> {
+        let inner = self.0.clone().set_proxy(host, port);
+        Ok(Self(inner))
+    }
+
+    /// Sets what happens when the server answers with a redirect.
+    /// Default: Follow.
+    pub fn set_redirect_policy(&self, val: RedirectPolicy) -> PyResult<Self> {
+        let inner = self.0.clone().set_redirect_policy(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum number of redirects followed for a single url.
+    /// Default: 10.
+    pub fn set_max_redirects(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_redirects(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum time in seconds to wait for the connection to the server, 0 waits
+    /// indefinitely.
+    /// Default: 0.
+    pub fn set_connect_timeout_seconds(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_connect_timeout_seconds(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum time in seconds to wait for data from the server once connected, 0 waits
+    /// indefinitely.
+    /// Default: 0.
+    pub fn set_read_timeout_seconds(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_read_timeout_seconds(val);
+        Ok(Self(inner))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// Configuration of the streams returned by the extract functions
+#[pyclass]
+#[derive(Clone, PartialEq)]
+pub struct StreamConfig(ecore::StreamConfig);
+
+impl From<StreamConfig> for ecore::StreamConfig {
+    fn from(config: StreamConfig) -> Self {
+        config.0
+    }
+}
+
+#[pymethods]
+impl StreamConfig {
+    /// Creates a new instance of StreamConfig with default settings.
+    #[new]
+    pub fn new() -> Self {
+        Self(ecore::StreamConfig::new())
+    }
+
+    /// Sets the charset the text of the stream is encoded with. Characters the charset cannot
+    /// encode are replaced with `?`.
+    /// Default: CharSet.UTF_8.
+    pub fn set_charset(&self, val: CharSet) -> PyResult<Self> {
+        let inner = self.0.clone().set_charset(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Sets the size in bytes of the buffers between the parser and the reader of the stream.
+    /// Default: 32768.
+    pub fn set_buffer_size(&self, val: usize) -> PyResult<Self> {
+        let inner = self.0.clone().set_buffer_size(val);
+        Ok(Self(inner))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// Per-call configuration overrides
+///
+/// Each value that is not set inherits the value configured on the `Extractor`.
+#[pyclass]
+#[derive(Clone, PartialEq)]
+pub struct ExtractOverrides(ecore::ExtractOverrides);
+
+impl From<ExtractOverrides> for ecore::ExtractOverrides {
+    fn from(overrides: ExtractOverrides) -> Self {
+        overrides.0
+    }
+}
+
+#[pymethods]
+impl ExtractOverrides {
+    /// Creates a new instance of ExtractOverrides that overrides nothing.
+    #[new]
+    pub fn new() -> Self {
+        Self(ecore::ExtractOverrides::new())
+    }
+
+    /// Overrides the whole PDF parser configuration for a single call.
+    pub fn set_pdf_config(&self, val: PdfParserConfig) -> PyResult<Self> {
+        let inner = self.0.clone().set_pdf_config(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Overrides the whole Office parser configuration for a single call.
+    pub fn set_office_config(&self, val: OfficeParserConfig) -> PyResult<Self> {
+        let inner = self.0.clone().set_office_config(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Overrides the whole Tesseract OCR configuration for a single call.
+    pub fn set_ocr_config(&self, val: TesseractOcrConfig) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_config(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Overrides the PDF OCR strategy for a single call.
+    pub fn set_ocr_strategy(&self, val: PdfOcrStrategy) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_strategy(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Overrides the maximum length of the extracted text for a single call.
+    pub fn set_extract_string_max_length(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_extract_string_max_length(val);
+        Ok(Self(inner))
+    }
+
+    /// Overrides the maximum time in seconds that Tesseract should spend on OCR for a single call.
+    pub fn set_ocr_timeout_seconds(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_timeout_seconds(val);
+        Ok(Self(inner))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
@@ -1,10 +1,15 @@
-use crate::{ecore, OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
+use crate::{
+    ecore, ExtractOverrides, HttpConfig, OfficeParserConfig, PdfParserConfig, StreamConfig,
+    TesseractOcrConfig,
+};
 use pyo3::exceptions::PyTypeError;
 use pyo3::prelude::*;
 use pyo3::types::PyByteArray;
+use pyo3::types::PyBytes;
 use pyo3::types::PyDict;
 use std::collections::HashMap;
 use std::io::Read;
+use std::path::PathBuf;
 
 // PyO3 supports unit-only enums (which contain only unit variants)
 // These simple enums behave similarly to Python's enumerations (enum.Enum)
@@ -16,6 +21,9 @@ pub enum CharSet {
     UTF_8,
     US_ASCII,
     UTF_16BE,
+    UTF_16LE,
+    UTF_16,
+    ISO_8859_1,
 }
 
 impl From<CharSet> for ecore::CharSet {
@@ -24,6 +32,92 @@ impl From<CharSet> for ecore::CharSet {
             CharSet::UTF_8 => ecore::CharSet::UTF_8,
             CharSet::US_ASCII => ecore::CharSet::US_ASCII,
             CharSet::UTF_16BE => ecore::CharSet::UTF_16BE,
+            CharSet::UTF_16LE => ecore::CharSet::UTF_16LE,
+            CharSet::UTF_16 => ecore::CharSet::UTF_16,
+            CharSet::ISO_8859_1 => ecore::CharSet::ISO_8859_1,
+        }
+    }
+}
+
+/// Format of the extracted content
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Xhtml,
+    Html,
+    Markdown,
+    Ndjson,
+    Csv,
+}
+
+impl From<OutputFormat> for ecore::OutputFormat {
+    fn from(format: OutputFormat) -> Self {
+        match format {
+            OutputFormat::Text => ecore::OutputFormat::Text,
+            OutputFormat::Xhtml => ecore::OutputFormat::Xhtml,
+            OutputFormat::Html => ecore::OutputFormat::Html,
+            OutputFormat::Markdown => ecore::OutputFormat::Markdown,
+            OutputFormat::Ndjson => ecore::OutputFormat::Ndjson,
+            OutputFormat::Csv => ecore::OutputFormat::Csv,
+        }
+    }
+}
+
+/// How the charset of text based formats is resolved when the declared and the detected
+/// charset differ
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum CharsetPolicy {
+    PreferDeclared,
+    PreferDetected,
+    ForceUtf8,
+}
+
+impl From<CharsetPolicy> for ecore::CharsetPolicy {
+    fn from(policy: CharsetPolicy) -> Self {
+        match policy {
+            CharsetPolicy::PreferDeclared => ecore::CharsetPolicy::PreferDeclared,
+            CharsetPolicy::PreferDetected => ecore::CharsetPolicy::PreferDetected,
+            CharsetPolicy::ForceUtf8 => ecore::CharsetPolicy::ForceUtf8,
+        }
+    }
+}
+
+/// What the detection of the media type of an input relies on
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum DetectionMode {
+    ContentAndName,
+    ContentOnly,
+    NameOnly,
+}
+
+impl From<DetectionMode> for ecore::DetectionMode {
+    fn from(mode: DetectionMode) -> Self {
+        match mode {
+            DetectionMode::ContentAndName => ecore::DetectionMode::ContentAndName,
+            DetectionMode::ContentOnly => ecore::DetectionMode::ContentOnly,
+            DetectionMode::NameOnly => ecore::DetectionMode::NameOnly,
+        }
+    }
+}
+
+/// What a line break of the markup becomes in plain text output
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum LineBreak {
+    Newline,
+    DoubleNewline,
+    Space,
+}
+
+impl From<LineBreak> for ecore::LineBreak {
+    fn from(line_break: LineBreak) -> Self {
+        match line_break {
+            LineBreak::Newline => ecore::LineBreak::Newline,
+            LineBreak::DoubleNewline => ecore::LineBreak::DoubleNewline,
+            LineBreak::Space => ecore::LineBreak::Space,
         }
     }
 }
@@ -33,7 +127,7 @@ impl From<CharSet> for ecore::CharSet {
 /// Can be used to perform buffered reading.
 #[pyclass]
 pub struct StreamReader {
-    pub(crate) reader: ecore::StreamReader,
+    pub(crate) reader: ecore::StreamReader<'static>,
     pub(crate) buffer: Vec<u8>,
     pub(crate) py_bytes: Option<Py<PyByteArray>>,
 }
@@ -88,6 +182,11 @@ impl StreamReader {
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))?;
         Ok(bytes_read)
     }
+
+    /// Returns the number of bytes read so far from this stream
+    pub fn bytes_read(&self) -> u64 {
+        self.reader.bytes_read()
+    }
 }
 
 /// `Extractor` is the entry for all extract APIs
@@ -118,6 +217,13 @@ impl Extractor {
         Ok(Self(inner))
     }
 
+    /// Set the configuration of the streams returned by the extract functions: the charset the
+    /// text is encoded with and the size of the buffers between the parser and the reader
+    pub fn set_stream_config(&self, config: StreamConfig) -> PyResult<Self> {
+        let inner = self.0.clone().set_stream_config(config.into());
+        Ok(Self(inner))
+    }
+
     /// Set the configuration for the PDF parser
     pub fn set_pdf_config(&self, config: PdfParserConfig) -> PyResult<Self> {
         let inner = self.0.clone().set_pdf_config(config.into());
@@ -130,18 +236,123 @@ impl Extractor {
         Ok(Self(inner))
     }
 
+    /// Set the HTTP configuration of the url extractions
+    pub fn set_http_config(&self, config: HttpConfig) -> PyResult<Self> {
+        let inner = self.0.clone().set_http_config(config.into());
+        Ok(Self(inner))
+    }
+
+    /// Set a Tika XML configuration, given either as the XML itself or as the path of its file
+    pub fn set_tika_config(&self, config: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_tika_config(config);
+        Ok(Self(inner))
+    }
+
     /// Set the configuration for the Tesseract OCR
     pub fn set_ocr_config(&self, config: TesseractOcrConfig) -> PyResult<Self> {
         let inner = self.0.clone().set_ocr_config(config.into());
         Ok(Self(inner))
     }
 
+    /// Set whether the OCR config is passed to Tika once and reused by the following
+    /// extractions. Tesseract still runs once per image.
+    /// Default: False
+    pub fn set_ocr_keep_warm(&self, keep_warm: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_keep_warm(keep_warm);
+        Ok(Self(inner))
+    }
+
+    /// Set the locale, as a BCP-47 language tag such as "de-DE", used by the parsers to format
+    /// values such as spreadsheet dates and numbers.
+    /// Default: "en-US"
+    pub fn set_locale(&self, locale: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_locale(locale);
+        Ok(Self(inner))
+    }
+
     /// Set the configuration for the parse as xml
     pub fn set_xml_output(&self, xml_output: bool) -> PyResult<Self> {
         let inner = self.0.clone().set_xml_output(xml_output);
         Ok(Self(inner))
     }
 
+    /// Set how the charset of text based formats is resolved when the declared and the detected
+    /// charset differ
+    /// Default: CharsetPolicy.PreferDeclared
+    pub fn set_charset_policy(&self, policy: CharsetPolicy) -> PyResult<Self> {
+        let inner = self.0.clone().set_charset_policy(policy.into());
+        Ok(Self(inner))
+    }
+
+    /// Set what the detection of the media type relies on, the content, the name of the input
+    /// or both
+    /// Default: DetectionMode.ContentAndName
+    pub fn set_detection_mode(&self, mode: DetectionMode) -> PyResult<Self> {
+        let inner = self.0.clone().set_detection_mode(mode.into());
+        Ok(Self(inner))
+    }
+
+    /// Set what the end of block elements and `<br>` elements become in plain text output
+    /// Default: LineBreak.Newline for both
+    pub fn set_line_break_policy(
+        &self,
+        block_break: LineBreak,
+        br_break: LineBreak,
+    ) -> PyResult<Self> {
+        let policy = ecore::LineBreakPolicy::new()
+            .set_block_break(block_break.into())
+            .set_br_break(br_break.into());
+        let inner = self.0.clone().set_line_break_policy(policy);
+        Ok(Self(inner))
+    }
+
+    /// Only return the metadata whose keys are in one of the given namespaces, e.g. "dc" for
+    /// "dc:title". The empty string selects the keys without a namespace.
+    pub fn set_metadata_namespaces(&self, prefixes: Vec<String>) -> PyResult<Self> {
+        let inner = self.0.clone().set_metadata_namespaces(prefixes);
+        Ok(Self(inner))
+    }
+
+    /// Only parse inputs detected as one of the given media types, e.g. "application/pdf".
+    /// Other inputs are rejected with an error before parsing.
+    /// Default: empty, all media types are accepted
+    pub fn set_accepted_media_types(&self, types: Vec<String>) -> PyResult<Self> {
+        let inner = self.0.clone().set_accepted_media_types(types);
+        Ok(Self(inner))
+    }
+
+    /// Disable parsers by their fully qualified java class names, e.g.
+    /// "org.apache.tika.parser.ocr.TesseractOCRParser". The types only they support fail with
+    /// an error.
+    /// Default: empty, no parser is disabled
+    pub fn set_disabled_parsers(&self, parsers: Vec<String>) -> PyResult<Self> {
+        let inner = self.0.clone().set_disabled_parsers(parsers);
+        Ok(Self(inner))
+    }
+
+    /// Refuse to parse documents containing macros, which are rejected with an error before
+    /// parsing, see has_macros.
+    /// Default: False
+    pub fn set_reject_macro_documents(&self, reject: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_reject_macro_documents(reject);
+        Ok(Self(inner))
+    }
+
+    /// Set the format of the extracted content
+    /// Default: OutputFormat.Text
+    pub fn set_output_format(&self, output_format: OutputFormat) -> PyResult<Self> {
+        let inner = self.0.clone().set_output_format(output_format.into());
+        Ok(Self(inner))
+    }
+
+    /// Set the character separating the cells of the OutputFormat.Csv output. The double quote
+    /// and the line breaks \r and \n cannot be used, extracting then fails
+    /// Default: ','
+    pub fn set_csv_delimiter(&self, delimiter: char) -> PyResult<Self> {
+        let inner = self.0.clone().set_csv_delimiter(delimiter);
+        Ok(Self(inner))
+    }
+
     /// Extracts text from a file path. Returns a tuple with stream of the extracted text
     /// the stream is decoded using the extractor's `encoding` and tika metadata.
     pub fn extract_file<'py>(
@@ -173,10 +384,9 @@ impl Extractor {
         buffer: &Bound<'_, PyByteArray>,
         py: Python<'py>,
     ) -> PyResult<(StreamReader, PyObject)> {
-        let slice = buffer.to_vec();
         let (reader, metadata) = self
             .0
-            .extract_bytes(&slice)
+            .extract_bytes_owned(buffer.to_vec())
             .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
 
         // Create a new `StreamReader` with initial buffer capacity of ecore::DEFAULT_BUF_SIZE bytes
@@ -191,6 +401,31 @@ impl Extractor {
         ))
     }
 
+    /// Extracts text from a bytearray, seeding the metadata with the given hints, such as
+    /// resourceName or Content-Type, before parsing. Returns a tuple with stream of the extracted
+    /// text and the metadata as dict, the values found by the parsers taking precedence.
+    pub fn extract_bytes_with_input_metadata<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        hints: HashMap<String, String>,
+        py: Python<'py>,
+    ) -> PyResult<(StreamReader, PyObject)> {
+        let (reader, metadata) = self
+            .0
+            .extract_bytes_owned_with_input_metadata(buffer.to_vec(), hints)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((
+            StreamReader {
+                reader,
+                buffer: Vec::with_capacity(ecore::DEFAULT_BUF_SIZE),
+                py_bytes: None,
+            },
+            py_metadata.into(),
+        ))
+    }
+
     /// Extracts text from a url. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and tika metdata.
     pub fn extract_url<'py>(
@@ -248,6 +483,23 @@ impl Extractor {
         Ok((content, py_metadata.into()))
     }
 
+    /// Extracts text from a bytearray into a new temporary file, without holding the text in
+    /// memory. Returns a tuple with the path of the file and the metadata as dict. The caller is
+    /// responsible for deleting the file.
+    pub fn extract_to_temp_file<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        py: Python<'py>,
+    ) -> PyResult<(PathBuf, PyObject)> {
+        let (path, metadata) = self
+            .0
+            .extract_to_temp_file(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((path, py_metadata.into()))
+    }
+
     /// Extracts text from a URL. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and the metadata as dict.
     pub fn extract_url_to_string<'py>(
@@ -264,11 +516,251 @@ impl Extractor {
         Ok((content, py_metadata.into()))
     }
 
+    /// Decodes a base64 string and extracts text from the decoded bytes.
+    /// Returns a tuple with string that is of maximum length of the extractor's
+    /// `extract_string_max_length` and the metadata as dict.
+    pub fn extract_base64<'py>(&self, b64: &str, py: Python<'py>) -> PyResult<(String, PyObject)> {
+        let (content, metadata) = self
+            .0
+            .extract_base64(b64)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((content, py_metadata.into()))
+    }
+
+    /// Decodes a `data:` URI and extracts text from its data, using the declared media type
+    /// as a hint. Returns a tuple with string that is of maximum length of the extractor's
+    /// `extract_string_max_length` and the metadata as dict.
+    pub fn extract_data_uri<'py>(
+        &self,
+        uri: &str,
+        py: Python<'py>,
+    ) -> PyResult<(String, PyObject)> {
+        let (content, metadata) = self
+            .0
+            .extract_data_uri(uri)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((content, py_metadata.into()))
+    }
+
+    /// Extracts text from a file path using per-call overrides layered over the extractor's
+    /// configuration. Returns a tuple with string that is of maximum length of the overridden or
+    /// the extractor's `extract_string_max_length` and the metadata as dict.
+    pub fn extract_file_to_string_with_config<'py>(
+        &self,
+        filename: &str,
+        overrides: ExtractOverrides,
+        py: Python<'py>,
+    ) -> PyResult<(String, PyObject)> {
+        let (content, metadata) = self
+            .0
+            .extract_file_to_string_with_config(filename, overrides.into())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((content, py_metadata.into()))
+    }
+
+    /// Extracts text from a bytearray using per-call overrides layered over the extractor's
+    /// configuration. Returns a tuple with string that is of maximum length of the overridden or
+    /// the extractor's `extract_string_max_length` and the metadata as dict.
+    pub fn extract_bytes_to_string_with_config<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        overrides: ExtractOverrides,
+        py: Python<'py>,
+    ) -> PyResult<(String, PyObject)> {
+        let (content, metadata) = self
+            .0
+            .extract_bytes_to_string_with_config(&buffer.to_vec(), overrides.into())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok((content, py_metadata.into()))
+    }
+
+    /// Extracts the tables of a bytearray as a list of tuples of the header row of the table,
+    /// empty if it has none, and of its other rows, each a list of the text of its cells.
+    /// Merged cells have their text repeated in every column and row they span.
+    pub fn extract_tables(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+    ) -> PyResult<Vec<(Vec<String>, Vec<Vec<String>>)>> {
+        let tables = self
+            .0
+            .extract_tables(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(tables
+            .into_iter()
+            .map(|table| (table.headers, table.rows))
+            .collect())
+    }
+
+    /// Extracts the sheets of a spreadsheet bytearray as a list of tuples of the name of the
+    /// sheet and of its rows, each a list of the text of its cells.
+    pub fn extract_sheets(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+    ) -> PyResult<Vec<(String, Vec<Vec<String>>)>> {
+        let sheets = self
+            .0
+            .extract_sheets(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(sheets
+            .into_iter()
+            .map(|sheet| (sheet.name, sheet.rows))
+            .collect())
+    }
+
+    /// Extracts the text of a PDF file page by page, as a list of tuples of the page number,
+    /// starting at 1, and of the text of the page.
+    pub fn extract_pdf_pages(&self, filename: &str) -> PyResult<Vec<(u32, String)>> {
+        self.0
+            .extract_pdf_pages(filename)
+            .map(|page| page.map(|page| (page.page_number, page.text)))
+            .collect::<Result<_, _>>()
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// Extracts the slides of a PPTX bytearray as a list of tuples of the index of the slide,
+    /// starting at 0, its title or None, the text of its other shapes and its notes.
+    pub fn extract_slides(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+    ) -> PyResult<Vec<(usize, Option<String>, String, String)>> {
+        let slides = self
+            .0
+            .extract_slides(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(slides
+            .into_iter()
+            .map(|slide| (slide.index, slide.title, slide.body, slide.notes))
+            .collect())
+    }
+
+    /// Checks the container structure of a bytearray without parsing its content.
+    /// Raises an error if the input is truncated or corrupt.
+    pub fn validate(&self, buffer: &Bound<'_, PyByteArray>) -> PyResult<()> {
+        self.0
+            .validate(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// Starts the embedded Tika and loads its parsers ahead of the first extraction, for example
+    /// at service startup, then extracts a few tiny documents with this extractor's
+    /// configuration. Tika is only started once per process.
+    pub fn warmup(&self) -> PyResult<()> {
+        self.0
+            .warmup()
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// Detects the media type of a bytearray and returns its type hierarchy, ordered from the
+    /// most specific type to the most generic one.
+    pub fn media_type_hierarchy(&self, buffer: &Bound<'_, PyByteArray>) -> PyResult<Vec<String>> {
+        self.0
+            .media_type_hierarchy(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// Detects the media type of a file without parsing it. Returns a tuple of the media type
+    /// and of the confidence of the detection, one of "HIGH", "MEDIUM", "LOW" or "NONE".
+    pub fn detect_file(&self, filename: &str) -> PyResult<(String, String)> {
+        let detection = self
+            .0
+            .detect_file(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok((detection.mime_type, detection.confidence.to_string()))
+    }
+
+    /// Detects the media type of a bytearray without parsing it, see detect_file.
+    pub fn detect_bytes(&self, buffer: &Bound<'_, PyByteArray>) -> PyResult<(String, String)> {
+        let detection = self
+            .0
+            .detect_bytes(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok((detection.mime_type, detection.confidence.to_string()))
+    }
+
+    /// Detects the language of a text. Returns a tuple of the ISO 639-1 code of the language,
+    /// e.g. "en", and of its probability from 0 to 1, or None if the language cannot be told.
+    pub fn detect_language(&self, text: &str) -> PyResult<Option<(String, f32)>> {
+        let detected = self
+            .0
+            .detect_language(text)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(detected.map(|detected| (detected.language, detected.confidence)))
+    }
+
+    /// Renders a page, numbered from 1, of a PDF bytearray to a PNG image at the given dpi.
+    pub fn render_pdf_page<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        page: u32,
+        dpi: u32,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let image = self
+            .0
+            .render_pdf_page(&buffer.to_vec(), page, dpi)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(PyBytes::new_bound(py, &image))
+    }
+
+    /// Checks whether a bytearray holds a document containing macros, such as a VBA project,
+    /// without extracting it.
+    pub fn has_macros(&self, buffer: &Bound<'_, PyByteArray>) -> PyResult<bool> {
+        self.0
+            .has_macros(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// Checks the digital signatures of a PDF or OOXML bytearray. Returns a tuple of the status,
+    /// one of "UNSIGNED", "VALID" or "INVALID", and of the signer when valid or the reason when
+    /// invalid. Only the integrity of the signatures is checked, not the trust of the certificates.
+    pub fn verify_signature(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+    ) -> PyResult<(String, Option<String>)> {
+        let status = self
+            .0
+            .verify_signature(&buffer.to_vec())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(match status {
+            ecore::SignatureStatus::Unsigned => ("UNSIGNED".to_string(), None),
+            ecore::SignatureStatus::Valid { signer } => ("VALID".to_string(), Some(signer)),
+            ecore::SignatureStatus::Invalid { reason } => ("INVALID".to_string(), Some(reason)),
+        })
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
 }
 
+/// Returns the sorted list of media types that extractous can parse.
+#[pyfunction]
+pub fn supported_media_types() -> PyResult<Vec<String>> {
+    ecore::supported_media_types().map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+}
+
+/// Returns the sorted list of languages installed for Tesseract, empty when Tesseract is not
+/// installed.
+#[pyfunction]
+pub fn installed_ocr_languages() -> PyResult<Vec<String>> {
+    ecore::installed_ocr_languages().map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+}
+
+/// Verifies that the whole extraction stack works, raising an error if it does not.
+/// Useful for startup and readiness checks.
+#[pyfunction]
+pub fn self_test() -> PyResult<()> {
+    ecore::self_test().map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+}
+
 /// Converts HashMap<String, Vec<String> to PyDict
 fn metadata_hashmap_to_pydict<'py>(
     py: Python<'py>,
@@ -54,14 +54,27 @@ pub use config::*;
 #[pymodule]
 fn _extractous(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<CharSet>()?;
+    m.add_class::<OutputFormat>()?;
+    m.add_class::<CharsetPolicy>()?;
+    m.add_class::<DetectionMode>()?;
+    m.add_class::<LineBreak>()?;
     m.add_class::<StreamReader>()?;
     m.add_class::<Extractor>()?;
+    m.add_function(wrap_pyfunction!(supported_media_types, m)?)?;
+    m.add_function(wrap_pyfunction!(installed_ocr_languages, m)?)?;
+    m.add_function(wrap_pyfunction!(self_test, m)?)?;
 
     // Config
     m.add_class::<PdfOcrStrategy>()?;
+    m.add_class::<PdfOcrImageType>()?;
     m.add_class::<PdfParserConfig>()?;
     m.add_class::<OfficeParserConfig>()?;
+    m.add_class::<FormulaOutput>()?;
     m.add_class::<TesseractOcrConfig>()?;
+    m.add_class::<ExtractOverrides>()?;
+    m.add_class::<RedirectPolicy>()?;
+    m.add_class::<HttpConfig>()?;
+    m.add_class::<StreamConfig>()?;
 
     Ok(())
 }
@@ -5,6 +5,9 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use walkdir::WalkDir;
 
+/// Marker file in the libs directory of a tika_native build without the OCR parser
+const NO_OCR_MARKER: &str = "no-ocr";
+
 fn main() {
     // Exit early when building docs or when running clippy
     if env::var("DOCS_RS").is_ok() {
@@ -22,6 +25,9 @@ fn main() {
     let out_dir = env::var("OUT_DIR").map(PathBuf::from).unwrap();
     let libs_out_dir = out_dir.join("libs"); // out_dir subdir to copy the built libs to
 
+    // Without the ocr feature tika_native is built without the OCR parser
+    let ocr = env::var("CARGO_FEATURE_OCR").is_ok();
+
     // Just for debugging
     // let graal_home = env::var("GRAALVM_HOME");
     // let java_home = env::var("JAVA_HOME");
@@ -43,7 +49,7 @@ fn main() {
     }
 
     // Try to find already built libs
-    match find_already_built_libs(&out_dir) {
+    match find_already_built_libs(&out_dir, ocr) {
         Some(libs_dir) => {
             // ignore if libs_dir/.. is the same as out_dir
             if out_dir.join("libs") != libs_dir {
@@ -61,6 +67,7 @@ fn main() {
             &out_dir,
             &libs_out_dir,
             &python_bind_dir,
+            ocr,
         );
     }
 
@@ -77,8 +84,9 @@ fn main() {
 }
 
 /// Searches for directories two levels up from `out_dir` and checks if any of them
-/// have two subdirectories: "libs" and "tika-native".
-fn find_already_built_libs(out_dir: &Path) -> Option<PathBuf> {
+/// have two subdirectories: "libs" and "tika-native", with libs built with or without OCR
+/// as requested.
+fn find_already_built_libs(out_dir: &Path, ocr: bool) -> Option<PathBuf> {
     // Traverse two levels up going to the build dir (target/debug/build)
     if let Some(parent_dir) = out_dir.parent().and_then(|p| p.parent()) {
         // Iterate over the entries in (target/debug/build)
@@ -94,7 +102,10 @@ fn find_already_built_libs(out_dir: &Path) -> Option<PathBuf> {
                             let libs_dir = path.join("out").join("libs");
                             let tika_native_dir = path.join("out").join("tika-native");
 
-                            if libs_dir.is_dir() && tika_native_dir.is_dir() {
+                            if libs_dir.is_dir()
+                                && tika_native_dir.is_dir()
+                                && libs_dir.join(NO_OCR_MARKER).exists() != ocr
+                            {
                                 return Some(libs_dir);
                             }
                         }
@@ -144,6 +155,7 @@ fn gradle_build(
     out_dir: &PathBuf,
     libs_out_dir: &PathBuf,
     python_bind_dir: &PathBuf,
+    ocr: bool,
 ) {
     let jdk_install_dir = out_dir.join("graalvm-jdk"); // out_dir subdir where jdk is downloaded
     let tika_native_dir = out_dir.join("tika-native"); // out_dir subdir where the gradle build is run
@@ -180,13 +192,15 @@ fn gradle_build(
         .current_dir(&tika_native_dir)
         .arg("--no-daemon")
         .arg("nativeCompile")
+        .arg(format!("-Pocr={}", ocr))
         .env("JAVA_HOME", graalvm_home)
         .status()
         .expect("Failed to build tika-native");
 
     // Decide where to copy the graalvm build artifacts
     let mut copy_to_dirs = vec![libs_out_dir];
-    if python_bind_dir.is_dir() {
+    // The python bindings always use OCR, so they only get the libs built with it
+    if ocr && python_bind_dir.is_dir() {
         // If python binding directory exists, copy the build artifacts to it
         // When running cargo publish the CARGO_MANIFEST_DIR points to a different directory
         // than the root dir.
@@ -196,6 +210,10 @@ fn gradle_build(
     // Copy the build artifacts to the specified directories
     let build_path = tika_native_dir.join("build/native/nativeCompile");
     copy_build_artifacts(&build_path, copy_to_dirs, true);
+    if !ocr {
+        fs::write(libs_out_dir.join(NO_OCR_MARKER), "")
+            .expect("Failed to mark the libs built without OCR");
+    }
 
     println!("Successfully built libs 🚀");
 }
@@ -0,0 +1,24 @@
+/// How much text each anchor of [`crate::Extractor::extract_pdf_with_anchors`] covers
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnchorGranularity {
+    /// One anchor per word, for precise highlights
+    #[default]
+    Word,
+    /// One anchor per line, fewer anchors for large documents
+    Line,
+}
+
+/// Maps a range of the extracted text back to where it is drawn in the original document, as
+/// returned by [`crate::Extractor::extract_pdf_with_anchors`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextAnchor {
+    /// The character offset where the range starts in the extracted text
+    pub offset: usize,
+    /// The length of the range in characters
+    pub len: usize,
+    /// The page the range is drawn on, starting at 1
+    pub page: u32,
+    /// The `(x, y, width, height)` of the range on its page, in points from the top left corner
+    /// of the page
+    pub bbox: (f32, f32, f32, f32),
+}
@@ -0,0 +1,30 @@
+/// An annotation of a PDF page, such as a highlight, a note or a stamp, as returned by
+/// [`crate::Extractor::extract_pdf_annotations`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// The PDF subtype of the annotation, e.g. `Highlight`, `Text` for a note, `FreeText`,
+    /// `Stamp` or `Ink`
+    pub annotation_type: String,
+    /// The author of the annotation, if set
+    pub author: Option<String>,
+    /// The text of the annotation, e.g. the comment of a note or of a highlight, if set
+    pub text: Option<String>,
+    /// The page of the annotation, starting at 1
+    pub page: u32,
+    /// The `(x, y, width, height)` of the annotation on its page, in points from the top left
+    /// corner of the page
+    pub bbox: (f32, f32, f32, f32),
+}
+
+impl Annotation {
+    /// The kind of the annotation, its PDF subtype, same as [`Annotation::annotation_type`]
+    pub fn kind(&self) -> &str {
+        &self.annotation_type
+    }
+
+    /// The `(x, y, width, height)` rectangle of the annotation on its page, same as
+    /// [`Annotation::bbox`]
+    pub fn rect(&self) -> (f32, f32, f32, f32) {
+        self.bbox
+    }
+}
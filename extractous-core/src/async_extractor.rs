@@ -0,0 +1,226 @@
+use crate::errors::{Error, ExtractResult};
+use crate::{Extractor, Metadata, StreamReader, DEFAULT_BUF_SIZE};
+use std::io::{self, Read};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::{mpsc, oneshot};
+
+/// Number of chunks of extracted text buffered ahead of the reader of an [`AsyncStreamReader`]
+const CHANNEL_CAPACITY: usize = 4;
+
+/// Async interface of an [`Extractor`] for tokio runtimes. The blocking work of the
+/// extractions, the calls into the embedded Tika and the reads of their streams, runs on the
+/// blocking thread pool of the runtime with `spawn_blocking`, so the executor threads are never
+/// blocked, whatever the size of the documents.
+///
+/// Must be used from within a tokio runtime. Cloning is cheap, the extractor is shared.
+///
+/// ```no_run
+/// # async fn run() -> extractous::ExtractResult<()> {
+/// use extractous::{AsyncExtractor, Extractor};
+/// use tokio::io::AsyncReadExt;
+///
+/// let extractor = AsyncExtractor::new(Extractor::new());
+/// let (mut reader, metadata) = extractor.extract_file("README.md").await?;
+/// let mut text = String::new();
+/// reader.read_to_string(&mut text).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsyncExtractor {
+    inner: Arc<Extractor>,
+}
+
+impl AsyncExtractor {
+    pub fn new(extractor: Extractor) -> Self {
+        Self {
+            inner: Arc::new(extractor),
+        }
+    }
+
+    /// Returns the extractor doing the extractions
+    pub fn extractor(&self) -> &Extractor {
+        &self.inner
+    }
+
+    /// Same as [`Extractor::extract_file`], returning a stream implementing
+    /// [`tokio::io::AsyncRead`]
+    pub async fn extract_file(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(AsyncStreamReader, Metadata)> {
+        let extractor = self.inner.clone();
+        let file_path = file_path.to_string();
+        AsyncStreamReader::spawn(file_path, move |file_path| {
+            extractor.extract_file(file_path)
+        })
+        .await
+    }
+
+    /// Same as [`Extractor::extract_bytes`], returning a stream implementing
+    /// [`tokio::io::AsyncRead`]. The buffer is owned by the extraction, as the text is extracted
+    /// from it while the stream is read
+    pub async fn extract_bytes(
+        &self,
+        buffer: Vec<u8>,
+    ) -> ExtractResult<(AsyncStreamReader, Metadata)> {
+        let extractor = self.inner.clone();
+        AsyncStreamReader::spawn(buffer, move |buffer| extractor.extract_bytes(buffer)).await
+    }
+
+    /// Same as [`Extractor::extract_url`], returning a stream implementing
+    /// [`tokio::io::AsyncRead`]
+    pub async fn extract_url(&self, url: &str) -> ExtractResult<(AsyncStreamReader, Metadata)> {
+        let extractor = self.inner.clone();
+        let url = url.to_string();
+        AsyncStreamReader::spawn(url, move |url| extractor.extract_url(url)).await
+    }
+
+    /// Same as [`Extractor::extract_file_to_string`]
+    pub async fn extract_file_to_string(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(String, Metadata)> {
+        let extractor = self.inner.clone();
+        let file_path = file_path.to_string();
+        spawn_blocking(move || extractor.extract_file_to_string(&file_path)).await
+    }
+
+    /// Same as [`Extractor::extract_bytes_to_string`]
+    pub async fn extract_bytes_to_string(
+        &self,
+        buffer: Vec<u8>,
+    ) -> ExtractResult<(String, Metadata)> {
+        let extractor = self.inner.clone();
+        spawn_blocking(move || extractor.extract_bytes_to_string(&buffer)).await
+    }
+
+    /// Same as [`Extractor::extract_url_to_string`]
+    pub async fn extract_url_to_string(&self, url: &str) -> ExtractResult<(String, Metadata)> {
+        let extractor = self.inner.clone();
+        let url = url.to_string();
+        spawn_blocking(move || extractor.extract_url_to_string(&url)).await
+    }
+}
+
+impl From<Extractor> for AsyncExtractor {
+    fn from(extractor: Extractor) -> Self {
+        Self::new(extractor)
+    }
+}
+
+/// Runs a blocking extraction on the blocking thread pool of the runtime
+async fn spawn_blocking<T, F>(f: F) -> ExtractResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> ExtractResult<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Error::Unknown(format!("The extraction task failed: {}", e)))?
+}
+
+/// Async stream of the text extracted by an [`AsyncExtractor`], implementing
+/// [`tokio::io::AsyncRead`]. The text is read from the underlying [`StreamReader`] by a task
+/// of the blocking thread pool, a few chunks ahead of the reader. Dropping the stream stops the
+/// extraction.
+#[derive(Debug)]
+pub struct AsyncStreamReader {
+    receiver: mpsc::Receiver<io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    pos: usize,
+    bytes_read: u64,
+}
+
+impl AsyncStreamReader {
+    /// Opens a stream with `open` on the blocking thread pool, and keeps reading it there into
+    /// the channel of the returned reader. The input outlives the stream, so the stream can
+    /// borrow it
+    async fn spawn<I, F>(input: I, open: F) -> ExtractResult<(Self, Metadata)>
+    where
+        I: Send + 'static,
+        F: FnOnce(&I) -> ExtractResult<(StreamReader<'_>, Metadata)> + Send + 'static,
+    {
+        let (metadata_tx, metadata_rx) = oneshot::channel();
+        let (chunk_tx, chunk_rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+        tokio::task::spawn_blocking(move || {
+            // The input is captured by the task, so it is dropped after the reader
+            let mut reader = match open(&input) {
+                Ok((reader, metadata)) => {
+                    if metadata_tx.send(Ok(metadata)).is_err() {
+                        return;
+                    }
+                    reader
+                }
+                Err(e) => {
+                    metadata_tx.send(Err(e)).ok();
+                    return;
+                }
+            };
+            loop {
+                let mut chunk = vec![0u8; DEFAULT_BUF_SIZE];
+                match reader.read(&mut chunk) {
+                    Ok(0) => return,
+                    Ok(num_read) => {
+                        chunk.truncate(num_read);
+                        // A dropped reader stops the extraction
+                        if chunk_tx.blocking_send(Ok(chunk)).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => {
+                        chunk_tx.blocking_send(Err(e)).ok();
+                        return;
+                    }
+                }
+            }
+        });
+
+        let metadata = metadata_rx
+            .await
+            .map_err(|_e| Error::Unknown("The extraction task failed".to_string()))??;
+        let reader = Self {
+            receiver: chunk_rx,
+            chunk: Vec::new(),
+            pos: 0,
+            bytes_read: 0,
+        };
+        Ok((reader, metadata))
+    }
+
+    /// Returns the number of bytes read so far from this stream
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+}
+
+impl AsyncRead for AsyncStreamReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        while this.pos == this.chunk.len() {
+            match ready!(this.receiver.poll_recv(cx)) {
+                Some(Ok(chunk)) => {
+                    this.chunk = chunk;
+                    this.pos = 0;
+                }
+                Some(Err(e)) => return Poll::Ready(Err(e)),
+                // The end of the stream
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+
+        let num_read = buf.remaining().min(this.chunk.len() - this.pos);
+        buf.put_slice(&this.chunk[this.pos..this.pos + num_read]);
+        this.pos += num_read;
+        this.bytes_read += num_read as u64;
+        Poll::Ready(Ok(()))
+    }
+}
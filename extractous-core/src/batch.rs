@@ -0,0 +1,219 @@
+//! Extraction of document collections, such as the directories of an e-discovery or indexing job
+
+use crate::errors::{Error, ExtractResult};
+use crate::extractor::path_to_str;
+use crate::{ExtractionResult, Extractor};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Recursively walks the directory at `root` and extracts every file in it with the given
+/// extractor, as [`Extractor::extract_file_full`] does. Returns a [`Walker`] that can be
+/// narrowed down to some file names or media types before iterating it.
+///
+/// ```no_run
+/// use extractous::batch::walk_dir;
+/// use extractous::Extractor;
+///
+/// let extractor = Extractor::new();
+/// for (path, result) in walk_dir("documents", &extractor).add_glob("*.pdf") {
+///     match result {
+///         Ok(result) => println!("{}: {} characters", path.display(), result.content.len()),
+///         Err(e) => eprintln!("{}: {}", path.display(), e),
+///     }
+/// }
+/// ```
+pub fn walk_dir(root: impl AsRef<Path>, extractor: &Extractor) -> Walker {
+    Walker {
+        extractor: extractor.clone(),
+        root: root.as_ref().to_path_buf(),
+        globs: Vec::new(),
+        filter_media_types: false,
+        pending: vec![root.as_ref().to_path_buf()],
+    }
+}
+
+/// Iterator over the files of a directory tree and their extraction results, created with
+/// [`walk_dir`]
+///
+/// The files are extracted one at a time, as the iterator advances, in a depth-first order
+/// where the entries of each directory are sorted by name. Each item is the path of a file, the
+/// root joined with its relative path, and its [`ExtractionResult`] or the error its extraction
+/// failed with. A directory that cannot be read is yielded with its error and skipped.
+/// Symbolic links to files are extracted, symbolic links to directories are not followed.
+#[derive(Debug, Clone)]
+pub struct Walker {
+    extractor: Extractor,
+    root: PathBuf,
+    globs: Vec<Vec<char>>,
+    filter_media_types: bool,
+    /// Entries left to visit, the next one last
+    pending: Vec<PathBuf>,
+}
+
+impl Walker {
+    /// Only extract the files matching the glob pattern, or one of the patterns when called
+    /// several times. A pattern with a `/` matches the path relative to the root, e.g.
+    /// `reports/**/*.pdf`, one without matches the file name, e.g. `*.docx`. `*` matches any
+    /// characters but `/`, `**` any characters including `/` and `?` any single character but
+    /// `/`. Matching is case sensitive.
+    /// Default: no patterns, all the files are extracted
+    pub fn add_glob(mut self, pattern: &str) -> Self {
+        self.globs.push(pattern.chars().collect());
+        self
+    }
+
+    /// Only extract the files detected as one of the given media types, e.g. `application/pdf`.
+    /// The media type is detected as with [`Extractor::set_accepted_media_types`], before
+    /// parsing, and the files of the other types are skipped.
+    /// Default: empty, all the media types are extracted
+    pub fn set_media_types(mut self, types: Vec<String>) -> Self {
+        self.filter_media_types = !types.is_empty();
+        self.extractor.set_accepted_media_types_mut(types);
+        self
+    }
+
+    /// Returns whether the file at the given path, relative to the root, matches the globs
+    fn matches(&self, relative: &Path) -> bool {
+        if self.globs.is_empty() {
+            return true;
+        }
+        let relative: Vec<char> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+            .chars()
+            .collect();
+        let name_start = relative
+            .iter()
+            .rposition(|c| *c == '/')
+            .map_or(0, |slash| slash + 1);
+        self.globs.iter().any(|glob| {
+            let target = if glob.contains(&'/') {
+                &relative[..]
+            } else {
+                &relative[name_start..]
+            };
+            glob_match(glob, target)
+        })
+    }
+
+    /// Pushes the entries of the directory on the pending stack, so they are visited in the
+    /// order of their names
+    fn push_dir(&mut self, dir: &Path) -> ExtractResult<()> {
+        let mut entries = fs::read_dir(dir)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|e| Error::IoError(format!("Could not read {}: {}", dir.display(), e)))?;
+        entries.sort();
+        self.pending.extend(entries.into_iter().rev());
+        Ok(())
+    }
+}
+
+impl Iterator for Walker {
+    type Item = (PathBuf, ExtractResult<ExtractionResult>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(path) = self.pending.pop() {
+            // The root is followed if it is a link, and must exist
+            let file_type = if path == self.root {
+                match fs::metadata(&path) {
+                    Ok(metadata) => metadata.file_type(),
+                    Err(e) => {
+                        let msg = format!("Could not read {}: {}", path.display(), e);
+                        return Some((path, Err(Error::IoError(msg))));
+                    }
+                }
+            } else {
+                match fs::symlink_metadata(&path) {
+                    Ok(metadata) => metadata.file_type(),
+                    // Removed since its directory was read
+                    Err(_) => continue,
+                }
+            };
+            if file_type.is_dir() {
+                if let Err(e) = self.push_dir(&path) {
+                    return Some((path, Err(e)));
+                }
+                continue;
+            }
+            // Links are only followed to files
+            if file_type.is_symlink() && !path.is_file() {
+                continue;
+            }
+
+            // A root that is a file is matched on its name
+            let relative = match path.strip_prefix(&self.root) {
+                Ok(relative) if !relative.as_os_str().is_empty() => relative,
+                _ => Path::new(path.file_name().unwrap_or(path.as_os_str())),
+            };
+            if !self.matches(relative) {
+                continue;
+            }
+            let result = path_to_str(&path).and_then(|path| self.extractor.extract_file_full(path));
+            if self.filter_media_types && matches!(result, Err(Error::Rejected)) {
+                continue;
+            }
+            return Some((path, result));
+        }
+        None
+    }
+}
+
+/// Returns whether the path matches the glob pattern, where `*` matches any characters but `/`,
+/// `**` any characters including `/`, and `?` any single character but `/`
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // `**/` also matches no directory at all
+            if let ['/', after @ ..] = rest {
+                if glob_match(after, path) {
+                    return true;
+                }
+            }
+            (0..=path.len()).any(|start| glob_match(rest, &path[start..]))
+        }
+        ['*', rest @ ..] => {
+            let end = path.iter().position(|c| *c == '/').unwrap_or(path.len());
+            (0..=end).any(|start| glob_match(rest, &path[start..]))
+        }
+        ['?', rest @ ..] => match path {
+            [c, path @ ..] => *c != '/' && glob_match(rest, path),
+            [] => false,
+        },
+        [p, rest @ ..] => match path {
+            [c, path @ ..] => c == p && glob_match(rest, path),
+            [] => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_match;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let path: Vec<char> = path.chars().collect();
+        glob_match(&pattern, &path)
+    }
+
+    #[test]
+    fn glob_match_test() {
+        assert!(matches("*.pdf", "report.pdf"));
+        assert!(!matches("*.pdf", "report.pdf.txt"));
+        assert!(!matches("*.pdf", "dir/report.pdf"));
+        assert!(matches("simple.od?", "simple.odt"));
+        assert!(!matches("simple.od?", "simple.od"));
+        assert!(matches("reports/**/*.pdf", "reports/2024/q1/report.pdf"));
+        assert!(matches("reports/**/*.pdf", "reports/report.pdf"));
+        assert!(!matches("reports/**/*.pdf", "other/report.pdf"));
+        assert!(matches("**", "any/path/at/all"));
+        assert!(!matches("?", "/"));
+    }
+}
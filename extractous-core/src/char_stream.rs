@@ -0,0 +1,236 @@
+use std::io::Read;
+
+use crate::errors::{Error, ExtractResult};
+use crate::{StreamReader, DEFAULT_BUF_SIZE};
+
+/// How a [`CharStream`] handles bytes that are not valid UTF-8
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DecodeErrorPolicy {
+    /// Replace every invalid sequence with U+FFFD, like `String::from_utf8_lossy`
+    #[default]
+    Replace,
+    /// Drop the invalid sequences
+    Skip,
+    /// Stop at the first invalid sequence with [`Error::InvalidEncoding`], which holds its byte
+    /// offset in the stream
+    Error,
+}
+
+/// Decodes the UTF-8 bytes of a reader, such as a [`StreamReader`], incrementally, handling
+/// invalid bytes according to a [`DecodeErrorPolicy`] instead of silently replacing them.
+///
+/// Iterate over it to get the text `char` by `char`, or call [`CharStream::next_chunk`] to get
+/// it a `&str` chunk at a time. Characters split between two reads are decoded as a whole.
+/// With [`DecodeErrorPolicy::Error`] the text before the invalid bytes is returned first, then
+/// the error, after which the stream ends.
+///
+/// ```rust
+/// use extractous::{CharStream, DecodeErrorPolicy, Extractor};
+///
+/// let (reader, _metadata) = Extractor::new().extract_file("README.md").unwrap();
+/// let mut chars = CharStream::new(reader, DecodeErrorPolicy::Error);
+/// let mut content = String::new();
+/// while let Some(chunk) = chars.next_chunk() {
+///     content.push_str(chunk.unwrap());
+/// }
+/// ```
+pub struct CharStream<R: Read = StreamReader<'static>> {
+    reader: R,
+    policy: DecodeErrorPolicy,
+    /// Bytes read but not decoded yet, the trailing bytes of an incomplete character
+    pending: Vec<u8>,
+    /// Byte offset in the stream of the first pending byte
+    offset: u64,
+    /// The decoded chunk, and the position of the next char to return from it
+    chunk: String,
+    position: usize,
+    /// Offset of an invalid sequence, reported once the text before it has been returned
+    error: Option<u64>,
+    done: bool,
+}
+
+impl<R: Read> CharStream<R> {
+    /// Creates a stream decoding the bytes of the reader with the given policy
+    pub fn new(reader: R, policy: DecodeErrorPolicy) -> Self {
+        Self {
+            reader,
+            policy,
+            pending: Vec::new(),
+            offset: 0,
+            chunk: String::new(),
+            position: 0,
+            error: None,
+            done: false,
+        }
+    }
+
+    /// Returns the next chunk of decoded text, or None at the end of the stream. The chunk is
+    /// never empty. Characters of the current chunk not yet returned by the iterator are part of
+    /// the chunk.
+    pub fn next_chunk(&mut self) -> Option<ExtractResult<&str>> {
+        if self.position >= self.chunk.len() {
+            match self.fill_chunk() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        let start = self.position;
+        self.position = self.chunk.len();
+        Some(Ok(&self.chunk[start..]))
+    }
+
+    /// Returns the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Decodes the next non empty chunk. Returns false at the end of the stream
+    fn fill_chunk(&mut self) -> ExtractResult<bool> {
+        self.chunk.clear();
+        self.position = 0;
+        let mut buf = vec![0u8; DEFAULT_BUF_SIZE];
+        while self.chunk.is_empty() {
+            if let Some(offset) = self.error.take() {
+                self.done = true;
+                return Err(Error::InvalidEncoding(offset));
+            }
+            if self.done {
+                return Ok(false);
+            }
+
+            let num_read = self.reader.read(&mut buf).map_err(|e| {
+                self.done = true;
+                Error::IoError(e.to_string())
+            })?;
+            self.pending.extend_from_slice(&buf[..num_read]);
+            self.decode_pending(num_read == 0);
+            if num_read == 0 && self.error.is_none() {
+                self.done = true;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Decodes the pending bytes into the chunk. Unless the end of the stream is reached, the
+    /// bytes of a character cut by the end of the read are kept for the next one
+    fn decode_pending(&mut self, eof: bool) {
+        let mut start = 0;
+        while start < self.pending.len() {
+            let (valid_up_to, invalid_len) = match std::str::from_utf8(&self.pending[start..]) {
+                Ok(_) => (self.pending.len() - start, 0),
+                Err(e) => match e.error_len() {
+                    Some(len) => (e.valid_up_to(), len),
+                    // An incomplete character, only invalid if no more bytes follow
+                    None if eof => (
+                        e.valid_up_to(),
+                        self.pending.len() - start - e.valid_up_to(),
+                    ),
+                    None => (e.valid_up_to(), 0),
+                },
+            };
+            let valid = &self.pending[start..start + valid_up_to];
+            // The bytes were validated above
+            self.chunk
+                .push_str(std::str::from_utf8(valid).unwrap_or_default());
+            start += valid_up_to;
+            if invalid_len == 0 {
+                break;
+            }
+
+            match self.policy {
+                DecodeErrorPolicy::Replace => self.chunk.push(char::REPLACEMENT_CHARACTER),
+                DecodeErrorPolicy::Skip => {}
+                DecodeErrorPolicy::Error => {
+                    self.error = Some(self.offset + start as u64);
+                    self.pending.clear();
+                    return;
+                }
+            }
+            start += invalid_len;
+        }
+        self.pending.drain(..start);
+        self.offset += start as u64;
+    }
+}
+
+impl<R: Read> Iterator for CharStream<R> {
+    type Item = ExtractResult<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.chunk.len() {
+            match self.fill_chunk() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        let c = self.chunk[self.position..].chars().next()?;
+        self.position += c.len_utf8();
+        Some(Ok(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CharStream, DecodeErrorPolicy};
+    use crate::Error;
+    use std::io::Read;
+
+    /// Reader returning at most one byte per read, to split every character between reads
+    struct ByteByByte<'a>(&'a [u8]);
+
+    impl Read for ByteByByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(1);
+            self.0.read(&mut buf[..n])
+        }
+    }
+
+    fn decode(bytes: &[u8], policy: DecodeErrorPolicy) -> (String, Option<Error>) {
+        let mut text = String::new();
+        for c in CharStream::new(ByteByByte(bytes), policy) {
+            match c {
+                Ok(c) => text.push(c),
+                Err(e) => return (text, Some(e)),
+            }
+        }
+        (text, None)
+    }
+
+    #[test]
+    fn decode_valid_split_characters() {
+        let (text, error) = decode("Grüße, 世界 🎉".as_bytes(), DecodeErrorPolicy::Error);
+        assert_eq!(text, "Grüße, 世界 🎉");
+        assert!(error.is_none());
+    }
+
+    #[test]
+    fn decode_invalid_with_each_policy() {
+        let bytes = b"ab\xffc\xe4\xb8";
+
+        let (text, error) = decode(bytes, DecodeErrorPolicy::Replace);
+        assert_eq!(text, "ab\u{fffd}c\u{fffd}");
+        assert!(error.is_none());
+
+        let (text, error) = decode(bytes, DecodeErrorPolicy::Skip);
+        assert_eq!(text, "abc");
+        assert!(error.is_none());
+
+        // The text before the invalid byte is returned, then the error with its offset
+        let (text, error) = decode(bytes, DecodeErrorPolicy::Error);
+        assert_eq!(text, "ab");
+        assert!(matches!(error, Some(Error::InvalidEncoding(2))));
+    }
+
+    #[test]
+    fn decode_chunks_and_offset_of_truncated_end() {
+        let mut chars = CharStream::new(&b"abc\xe4\xb8"[..], DecodeErrorPolicy::Error);
+        assert_eq!(chars.next_chunk().unwrap().unwrap(), "abc");
+        assert!(matches!(
+            chars.next_chunk(),
+            Some(Err(Error::InvalidEncoding(3)))
+        ));
+        assert!(chars.next_chunk().is_none());
+    }
+}
@@ -0,0 +1,18 @@
+/// Identifier of a [`Comment`], unique within its document
+pub type CommentId = String;
+
+/// A comment of an Office document, as returned by [`crate::Extractor::extract_comments`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    /// The identifier of the comment, as written in the document
+    pub id: CommentId,
+    /// The display name of the author of the comment, if set
+    pub author: Option<String>,
+    /// The date and time of the comment as written in the document, in ISO 8601 format, e.g.
+    /// `2024-03-01T09:30:00Z`, if set
+    pub timestamp: Option<String>,
+    /// The text of the comment, its paragraphs separated by new lines
+    pub text: String,
+    /// The comment this one replies to, or None for the first comment of a thread
+    pub parent: Option<CommentId>,
+}
@@ -1,7 +1,12 @@
+use crate::{CharSet, DEFAULT_BUF_SIZE};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use std::fmt;
+use std::ops::RangeInclusive;
 use strum_macros::{Display, EnumString};
 
 /// OCR Strategy for PDF parsing
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum PdfOcrStrategy {
     NO_OCR,
@@ -11,16 +16,63 @@ pub enum PdfOcrStrategy {
     AUTO,
 }
 
+/// What the formula cells of spreadsheets become in the extracted content, see
+/// [`OfficeParserConfig::set_formula_output`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum FormulaOutput {
+    /// The value computed by the spreadsheet application, e.g. `2000`
+    #[default]
+    ComputedValue,
+    /// The formula, e.g. `=SUM(B2:B3)`
+    Formula,
+    /// The formula followed by its computed value, e.g. `=SUM(B2:B3) = 2000`
+    Both,
+}
+
+/// Color type of the images PDF pages are rendered to before they are OCR'd, see
+/// [`PdfParserConfig::set_ocr_image_type`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum PdfOcrImageType {
+    /// Black and white, one bit per pixel
+    Binary,
+    /// Grayscale, 8 bits per pixel
+    #[default]
+    Gray,
+    /// Color, 8 bits per channel
+    Rgb,
+    /// Color with transparency, 8 bits per channel
+    Argb,
+}
+
 /// PDF parsing configuration settings
 ///
 /// These settings are used to configure the behavior of the PDF parsing.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PdfParserConfig {
     pub(crate) ocr_strategy: PdfOcrStrategy,
     pub(crate) extract_inline_images: bool,
     pub(crate) extract_unique_inline_images_only: bool,
     pub(crate) extract_marked_content: bool,
     pub(crate) extract_annotation_text: bool,
+    pub(crate) sort_by_position: bool,
+    pub(crate) average_char_tolerance: Option<f32>,
+    pub(crate) spacing_tolerance: Option<f32>,
+    pub(crate) suppress_duplicate_overlapping_text: bool,
+    pub(crate) detect_angles: bool,
+    pub(crate) ocr_render_dpi: Option<i32>,
+    pub(crate) ocr_image_type: PdfOcrImageType,
+    pub(crate) ocr_image_quality: Option<f32>,
+    pub(crate) ocr_inline_images: bool,
+    pub(crate) password: Option<String>,
+    pub(crate) page_range: Option<(u32, u32)>,
 }
 
 impl Default for PdfParserConfig {
@@ -31,6 +83,17 @@ impl Default for PdfParserConfig {
             extract_unique_inline_images_only: false,
             extract_marked_content: false,
             extract_annotation_text: true,
+            sort_by_position: false,
+            average_char_tolerance: None,
+            spacing_tolerance: None,
+            suppress_duplicate_overlapping_text: false,
+            detect_angles: false,
+            ocr_render_dpi: None,
+            ocr_image_type: PdfOcrImageType::Gray,
+            ocr_image_quality: None,
+            ocr_inline_images: true,
+            password: None,
+            page_range: None,
         }
     }
 }
@@ -41,6 +104,24 @@ impl PdfParserConfig {
         Self::default()
     }
 
+    /// Preset for scanned PDFs, whose pages are images without a text layer: every page is
+    /// OCR'd, ignoring any text layer, and rendered at 400 DPI so that small print is still
+    /// recognized. Slower than the defaults, and pointless for born-digital PDFs.
+    pub fn for_scanned_documents() -> Self {
+        Self::new()
+            .set_ocr_strategy(PdfOcrStrategy::OCR_ONLY)
+            .set_ocr_render_dpi(400)
+    }
+
+    /// Preset for born-digital PDFs, such as reports exported from an office suite: OCR is
+    /// disabled, so images with text are not recognized, and the text is extracted along with
+    /// its marked structure, such as paragraphs and headings, when the PDF is tagged.
+    pub fn for_born_digital() -> Self {
+        Self::new()
+            .set_ocr_strategy(PdfOcrStrategy::NO_OCR)
+            .set_extract_marked_content(true)
+    }
+
     /// Sets the OCR strategy for PDF parsing.
     /// Default: AUTO.
     pub fn set_ocr_strategy(mut self, val: PdfOcrStrategy) -> Self {
@@ -85,17 +166,161 @@ impl PdfParserConfig {
     }
 
     /// If the PDF contains annotations, try to extract the text of the annotations.
+    /// The text is mixed into the text of the pages; use
+    /// [`crate::Extractor::extract_pdf_annotations`] to get each annotation with its kind,
+    /// author, text, page and rectangle instead.
     /// Default: true.
     pub fn set_extract_annotation_text(mut self, val: bool) -> Self {
         self.extract_annotation_text = val;
         self
     }
+
+    /// If true, the text of each page is sorted by its position, top to bottom and left to
+    /// right, instead of following the order it is drawn in. This fixes a jumbled reading
+    /// order in PDFs whose generator draws the text out of order, but interleaves the lines of
+    /// side by side columns.
+    /// Default: false.
+    pub fn set_sort_by_position(mut self, val: bool) -> Self {
+        self.sort_by_position = val;
+        self
+    }
+
+    /// Sets the tolerance, as a fraction of the average character width, within which
+    /// consecutive characters are considered part of the same word. Raise it when words of
+    /// tightly set text are split apart.
+    /// Default: None, which keeps PDFBox's default of 0.3.
+    pub fn set_average_char_tolerance(mut self, val: f32) -> Self {
+        self.average_char_tolerance = Some(val);
+        self
+    }
+
+    /// Sets the tolerance, as a fraction of the width of a space, from which the gap between
+    /// two characters is considered a space. Lower it when words of loosely set text run
+    /// together.
+    /// Default: None, which keeps PDFBox's default of 0.5.
+    pub fn set_spacing_tolerance(mut self, val: f32) -> Self {
+        self.spacing_tolerance = Some(val);
+        self
+    }
+
+    /// If true, characters drawn on top of an identical character, such as the overlapping
+    /// copies some generators draw to fake bold text or the text layers stacked by some
+    /// scanners, are only extracted once. Slows down the extraction of large pages.
+    /// Default: false.
+    pub fn set_suppress_duplicate_overlapping_text(mut self, val: bool) -> Self {
+        self.suppress_duplicate_overlapping_text = val;
+        self
+    }
+
+    /// If true, text drawn at an angle, such as a rotated stamp or watermark, is extracted as
+    /// lines of its own instead of being broken into single characters mixed into the text of
+    /// the page. Each page is parsed once per angle it has text at, which slows down pages with
+    /// rotated text.
+    /// Default: false.
+    pub fn set_detect_angles(mut self, val: bool) -> Self {
+        self.detect_angles = val;
+        self
+    }
+
+    /// Sets whether inline images, such as a logo repeated on every page, are OCR'd when they
+    /// are extracted with `extract_inline_images`. This is independent of the OCR strategy,
+    /// which controls the OCR of whole pages, so scanned pages can be OCR'd while decorative
+    /// images are skipped.
+    /// Default: true.
+    pub fn set_ocr_inline_images(mut self, val: bool) -> Self {
+        self.ocr_inline_images = val;
+        self
+    }
+
+    /// Sets the resolution, in dots per inch, at which PDF pages are rendered to images before
+    /// they are OCR'd. This is independent of the Tesseract density setting. A higher DPI helps
+    /// with small fonts at the cost of speed and memory. Values below 1 are raised to 1.
+    /// Default: None, which keeps Tika's default of 300.
+    pub fn set_ocr_render_dpi(mut self, val: i32) -> Self {
+        self.ocr_render_dpi = Some(val.max(1));
+        self
+    }
+
+    /// Sets the color type of the images PDF pages are rendered to before they are OCR'd.
+    /// Grayscale suits most scans, color helps with colored text on colored backgrounds, and
+    /// binary images are the smallest but lose the anti-aliasing of small fonts.
+    /// Default: Gray.
+    pub fn set_ocr_image_type(mut self, val: PdfOcrImageType) -> Self {
+        self.ocr_image_type = val;
+        self
+    }
+
+    /// Sets the quality, between 0.0 and 1.0, at which the rendered PDF pages are encoded
+    /// before they are OCR'd. Lower values make smaller images at the cost of detail.
+    /// Default: None, which keeps Tika's default of 1.0.
+    pub fn set_ocr_image_quality(mut self, val: f32) -> Self {
+        self.ocr_image_quality = Some(val.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Sets the password used to decrypt password protected PDFs, including the PDFs embedded
+    /// in other documents. PDFs encrypted with an empty user password, which only restricts
+    /// permissions, are parsed without it. A missing or wrong password fails the extraction
+    /// with [`crate::Error::EncryptedDocument`].
+    /// Default: None.
+    pub fn set_password(mut self, secret: &str) -> Self {
+        self.password = Some(secret.to_string());
+        self
+    }
+
+    /// Sets the pages parsed, counted from 1, e.g. `1..=20` for the first 20 pages of a long
+    /// filing. The other pages are neither parsed nor OCR'd, which saves most of the time of
+    /// OCR'ing long documents. Pages past the end of the document are ignored and an empty
+    /// range parses no pages. The page count in the metadata remains the one of the whole
+    /// document, while the pages of [`crate::Extractor::extract_pdf_pages`] and similar
+    /// functions are numbered from the first page of the range. Only applies to the PDF being
+    /// extracted, not to the PDFs embedded in other documents.
+    /// Default: None, which parses all pages.
+    pub fn set_page_range(mut self, range: RangeInclusive<u32>) -> Self {
+        self.page_range = Some((*range.start(), *range.end()));
+        self
+    }
+}
+
+impl fmt::Debug for PdfParserConfig {
+    // The password is not printed
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PdfParserConfig")
+            .field("ocr_strategy", &self.ocr_strategy)
+            .field("extract_inline_images", &self.extract_inline_images)
+            .field(
+                "extract_unique_inline_images_only",
+                &self.extract_unique_inline_images_only,
+            )
+            .field("extract_marked_content", &self.extract_marked_content)
+            .field("extract_annotation_text", &self.extract_annotation_text)
+            .field("sort_by_position", &self.sort_by_position)
+            .field("average_char_tolerance", &self.average_char_tolerance)
+            .field("spacing_tolerance", &self.spacing_tolerance)
+            .field(
+                "suppress_duplicate_overlapping_text",
+                &self.suppress_duplicate_overlapping_text,
+            )
+            .field("detect_angles", &self.detect_angles)
+            .field("ocr_render_dpi", &self.ocr_render_dpi)
+            .field("ocr_image_type", &self.ocr_image_type)
+            .field("ocr_image_quality", &self.ocr_image_quality)
+            .field("ocr_inline_images", &self.ocr_inline_images)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("page_range", &self.page_range)
+            .finish()
+    }
 }
 
 /// Microsoft Office parser configuration settings
 ///
-/// These settings are used to configure the behavior of the MSOffice parsing.
+/// These settings are used to configure the behavior of the MSOffice parsing. OpenDocument
+/// files follow `include_slide_notes` for ODP presentations, `extract_custom_properties` for
+/// their user defined properties and `formula_output` for ODS spreadsheets, the other settings
+/// only apply to Microsoft formats.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OfficeParserConfig {
     pub(crate) extract_macros: bool,
     pub(crate) include_deleted_content: bool,
@@ -107,6 +332,10 @@ pub struct OfficeParserConfig {
     pub(crate) include_slide_master_content: bool,
     pub(crate) concatenate_phonetic_runs: bool,
     pub(crate) extract_all_alternatives_from_msg: bool,
+    pub(crate) extract_custom_properties: bool,
+    pub(crate) include_chart_content: bool,
+    pub(crate) include_smartart_content: bool,
+    pub(crate) formula_output: FormulaOutput,
 }
 
 impl Default for OfficeParserConfig {
@@ -122,6 +351,10 @@ impl Default for OfficeParserConfig {
             include_slide_master_content: true,
             concatenate_phonetic_runs: true,
             extract_all_alternatives_from_msg: false,
+            extract_custom_properties: true,
+            include_chart_content: true,
+            include_smartart_content: true,
+            formula_output: FormulaOutput::ComputedValue,
         }
     }
 }
@@ -132,6 +365,23 @@ impl OfficeParserConfig {
         Self::default()
     }
 
+    /// Preset for reviewing documents, where nothing the author may have hidden should be
+    /// missed: the deleted and moved text of tracked changes, headers and footers, slide notes
+    /// and master slides, text in shapes and the missing rows of sparse tables are all included,
+    /// and spreadsheet formulas are written along with their values. Comments are always
+    /// included.
+    pub fn for_legal_review() -> Self {
+        Self::new()
+            .set_include_deleted_content(true)
+            .set_include_move_from_content(true)
+            .set_include_headers_and_footers(true)
+            .set_include_missing_rows(true)
+            .set_include_slide_notes(true)
+            .set_include_slide_master_content(true)
+            .set_include_shape_based_content(true)
+            .set_formula_output(FormulaOutput::Both)
+    }
+
     /// Sets whether MSOffice parsers should extract macros.
     /// Default: false.
     pub fn set_extract_macros(mut self, val: bool) -> Self {
@@ -182,7 +432,8 @@ impl OfficeParserConfig {
     }
 
     /// Whether to process slide notes content. If set to false, the parser will skip the text
-    /// content and all embedded objects from the slide notes in ppt and pptxm.
+    /// content and all embedded objects from the slide notes in ppt and pptxm. The notes of odp
+    /// presentations are skipped as well.
     /// Default: true
     pub fn set_include_slide_notes(mut self, val: bool) -> Self {
         self.include_slide_notes = val;
@@ -215,12 +466,54 @@ impl OfficeParserConfig {
         self.extract_all_alternatives_from_msg = val;
         self
     }
+
+    /// Office documents can carry custom (user defined) properties besides the standard ones
+    /// such as author or title. Custom properties are returned in the metadata under the
+    /// `custom:` prefix, e.g. `custom:DocumentClassification`, so they can be told apart from
+    /// the standard ones. Set this to false to drop all custom properties from the metadata.
+    /// Default: true
+    pub fn set_extract_custom_properties(mut self, val: bool) -> Self {
+        self.extract_custom_properties = val;
+        self
+    }
+
+    /// Whether to include the text of the charts of docx, pptx and xlsx files, such as titles,
+    /// axis labels and data labels. Independent of `include_shape_based_content`, so chart text
+    /// can be kept while the text of decorative shapes is skipped.
+    /// Default: true
+    pub fn set_include_chart_content(mut self, val: bool) -> Self {
+        self.include_chart_content = val;
+        self
+    }
+
+    /// Whether to include the text of the SmartArt diagrams of docx and pptx files. Independent
+    /// of `include_shape_based_content`.
+    /// Default: true
+    pub fn set_include_smartart_content(mut self, val: bool) -> Self {
+        self.include_smartart_content = val;
+        self
+    }
+
+    /// Sets what the formula cells of xls, xlsx and ods spreadsheets become in the extracted
+    /// content: the computed value, the formula, or both, e.g. to audit the calculations of a
+    /// financial model. Formulas of xls and xlsx files use the Excel syntax, e.g. `=SUM(B2:B3)`,
+    /// and formulas of ods files the OpenFormula syntax, e.g. `=SUM([.B2:.B3])`. Other settings
+    /// than the computed value load the whole spreadsheet in memory before parsing it.
+    /// Default: FormulaOutput::ComputedValue
+    pub fn set_formula_output(mut self, val: FormulaOutput) -> Self {
+        self.formula_output = val;
+        self
+    }
 }
 
 /// Tesseract OCR configuration settings
 ///
 /// These settings are used to configure the behavior of the optical image recognition.
+/// Only available with the `ocr` feature.
+#[cfg(feature = "ocr")]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct TesseractOcrConfig {
     pub(crate) density: i32,
     pub(crate) depth: i32,
@@ -228,8 +521,13 @@ pub struct TesseractOcrConfig {
     pub(crate) enable_image_preprocessing: bool,
     pub(crate) apply_rotation: bool,
     pub(crate) language: String,
+    pub(crate) language_fallback: Vec<String>,
+    pub(crate) language_fallback_max_attempts: i32,
+    pub(crate) language_fallback_min_confidence: f32,
+    pub(crate) fallback_on_missing_language: bool,
 }
 
+#[cfg(feature = "ocr")]
 impl Default for TesseractOcrConfig {
     fn default() -> Self {
         Self {
@@ -239,10 +537,15 @@ impl Default for TesseractOcrConfig {
             enable_image_preprocessing: false,
             apply_rotation: false,
             language: "eng".to_string(),
+            language_fallback: Vec::new(),
+            language_fallback_max_attempts: 3,
+            language_fallback_min_confidence: 0.9,
+            fallback_on_missing_language: true,
         }
     }
 }
 
+#[cfg(feature = "ocr")]
 impl TesseractOcrConfig {
     /// Creates a new instance of TesseractOcrConfig with default settings.
     pub fn new() -> Self {
@@ -287,10 +590,333 @@ impl TesseractOcrConfig {
         self
     }
 
+    /// Sets a prioritized list of languages to try when the language of the documents is not
+    /// known, each a language or a combination as in [`TesseractOcrConfig::set_language`], e.g.
+    /// `vec!["eng", "deu", "fra+eng"]`. When set, every image is OCR'd with the languages in
+    /// order and the text with the highest mean Tesseract word confidence is kept, instead of
+    /// OCR'ing with the language set by [`TesseractOcrConfig::set_language`].
+    /// The language and the confidence, from 0 to 1, of each OCR'd image or PDF page are listed
+    /// in the `X-OCR-Language` and `X-OCR-Confidence` metadata. Not used with an OCR backend set
+    /// with [`crate::Extractor::set_ocr_backend`].
+    /// Default: empty, only the language set by [`TesseractOcrConfig::set_language`] is used.
+    pub fn set_language_fallback(mut self, val: Vec<String>) -> Self {
+        self.language_fallback = val;
+        self
+    }
+
+    /// Sets the maximum number of languages of the fallback list tried for each image, to bound
+    /// the cost of OCR'ing the same image several times. The languages after it are never tried.
+    /// Default: 3.
+    pub fn set_language_fallback_max_attempts(mut self, val: i32) -> Self {
+        self.language_fallback_max_attempts = val;
+        self
+    }
+
+    /// Sets the mean word confidence, from 0 to 1, at which the text of a fallback language is
+    /// kept right away, without trying the next languages.
+    /// Default: 0.9.
+    pub fn set_language_fallback_min_confidence(mut self, val: f32) -> Self {
+        self.language_fallback_min_confidence = val;
+        self
+    }
+
+    /// Sets what happens when a language set by [`TesseractOcrConfig::set_language`] is not
+    /// installed, see [`crate::installed_ocr_languages`]. The languages are checked when an
+    /// extraction starts, whatever the type of the input. If true, the missing languages are
+    /// dropped, falling back to `eng`, or another installed language, when none is left, and a
+    /// warning is added to the `X-OCR-Warning` metadata. If false, the extraction fails with
+    /// [`crate::Error::OcrLanguageMissing`]. Nothing is checked when Tesseract is not installed
+    /// or with an OCR backend set with [`crate::Extractor::set_ocr_backend`].
+    /// Default: true.
+    pub fn set_fallback_on_missing_language(mut self, val: bool) -> Self {
+        self.fallback_on_missing_language = val;
+        self
+    }
+
     /// Sets the maximum time in seconds that Tesseract should spend on OCR.
+    /// The limit applies to each OCR invocation. When the OCR of a rendered PDF page times out,
+    /// that page is left without text and the extraction continues with the next pages. The
+    /// numbers of the skipped pages are listed in the `X-OCR-Skipped-Pages` metadata, which is
+    /// only complete in the metadata of the extract_to_string functions.
     /// Default: 120.
     pub fn set_timeout_seconds(mut self, val: i32) -> Self {
         self.timeout_seconds = val;
         self
     }
 }
+
+/// What the url extractions do when the server answers with a redirect, see
+/// [`HttpConfig::set_redirect_policy`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum RedirectPolicy {
+    /// Follow redirects to any host, as long as the scheme stays the same: redirects between
+    /// http and https fail, as they do without an http config
+    #[default]
+    Follow,
+    /// Follow redirects to the origin of the requested url only, the same scheme, host and
+    /// port, and fail on the others
+    SameOrigin,
+    /// Do not follow redirects, a redirect fails the extraction
+    NoFollow,
+}
+
+/// HTTP configuration settings of the url extractions
+///
+/// These settings configure the requests of [`crate::Extractor::extract_url`] and
+/// [`crate::Extractor::extract_url_to_string`], e.g. to pull documents from endpoints that
+/// require authentication. They only apply to http and https urls.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct HttpConfig {
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) authorization: Option<String>,
+    pub(crate) proxy: Option<(String, u16)>,
+    pub(crate) redirect_policy: RedirectPolicy,
+    pub(crate) max_redirects: i32,
+    pub(crate) connect_timeout_seconds: i32,
+    pub(crate) read_timeout_seconds: i32,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            headers: Vec::new(),
+            authorization: None,
+            proxy: None,
+            redirect_policy: RedirectPolicy::Follow,
+            max_redirects: 10,
+            connect_timeout_seconds: 0,
+            read_timeout_seconds: 0,
+        }
+    }
+}
+
+impl HttpConfig {
+    /// Creates a new instance of HttpConfig with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a header to every request, e.g. `X-Api-Key`. Adding a header that was already added
+    /// replaces its value. As they may hold credentials, the headers are only sent to the
+    /// origin of the requested url, its scheme, host and port, not to the other origins it
+    /// redirects to.
+    /// Default: no headers.
+    pub fn add_header(mut self, name: &str, value: &str) -> Self {
+        self.headers
+            .retain(|(header, _)| !header.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Authenticates the requests with a bearer token, sent in the `Authorization` header.
+    /// Replaces the basic authentication, if any. The credentials are only sent to the origin of
+    /// the requested url, its scheme, host and port, not to the other origins it redirects to.
+    /// Default: None.
+    pub fn set_bearer_auth(mut self, token: &str) -> Self {
+        self.authorization = Some(format!("Bearer {}", token));
+        self
+    }
+
+    /// Authenticates the requests with a user name and password, sent in the `Authorization`
+    /// header. Replaces the bearer token, if any. The credentials are only sent to the origin of
+    /// the requested url, its scheme, host and port, not to the other origins it redirects to.
+    /// Default: None.
+    pub fn set_basic_auth(mut self, username: &str, password: &str) -> Self {
+        let credentials = BASE64_STANDARD.encode(format!("{}:{}", username, password));
+        self.authorization = Some(format!("Basic {}", credentials));
+        self
+    }
+
+    /// Sends the requests through the HTTP proxy at the given host and port.
+    /// Default: None, the proxy of the `http.proxyHost` and `https.proxyHost` system properties
+    /// of the embedded JVM, if any, is used.
+    pub fn set_proxy(mut self, host: &str, port: u16) -> Self {
+        self.proxy = Some((host.to_string(), port));
+        self
+    }
+
+    /// Sets what happens when the server answers with a redirect.
+    /// Default: RedirectPolicy::Follow.
+    pub fn set_redirect_policy(mut self, val: RedirectPolicy) -> Self {
+        self.redirect_policy = val;
+        self
+    }
+
+    /// Sets the maximum number of redirects followed for a single url, the extraction fails
+    /// after that.
+    /// Default: 10.
+    pub fn set_max_redirects(mut self, val: i32) -> Self {
+        self.max_redirects = val;
+        self
+    }
+
+    /// Sets the maximum time in seconds to wait for the connection to the server, 0 waits
+    /// indefinitely.
+    /// Default: 0.
+    pub fn set_connect_timeout_seconds(mut self, val: i32) -> Self {
+        self.connect_timeout_seconds = val;
+        self
+    }
+
+    /// Sets the maximum time in seconds to wait for data from the server once connected, 0 waits
+    /// indefinitely. Applies to every read of the response, not to the whole download.
+    /// Default: 0.
+    pub fn set_read_timeout_seconds(mut self, val: i32) -> Self {
+        self.read_timeout_seconds = val;
+        self
+    }
+}
+
+impl fmt::Debug for HttpConfig {
+    // The credentials and the header values, which may hold credentials too, are not printed
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let headers: Vec<_> = self
+            .headers
+            .iter()
+            .map(|(name, _value)| (name, "<redacted>"))
+            .collect();
+        f.debug_struct("HttpConfig")
+            .field("headers", &headers)
+            .field(
+                "authorization",
+                &self.authorization.as_ref().map(|_| "<redacted>"),
+            )
+            .field("proxy", &self.proxy)
+            .field("redirect_policy", &self.redirect_policy)
+            .field("max_redirects", &self.max_redirects)
+            .field("connect_timeout_seconds", &self.connect_timeout_seconds)
+            .field("read_timeout_seconds", &self.read_timeout_seconds)
+            .finish()
+    }
+}
+
+/// Configuration of the streams returned by the `extract_*` functions of [`crate::Extractor`]
+///
+/// These settings configure how the extracted text is handed over to the reader, e.g. to feed
+/// legacy systems that expect Latin-1 or UTF-16 without transcoding the stream.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct StreamConfig {
+    pub(crate) charset: CharSet,
+    pub(crate) buffer_size: usize,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            charset: CharSet::UTF_8,
+            buffer_size: DEFAULT_BUF_SIZE,
+        }
+    }
+}
+
+impl StreamConfig {
+    /// Creates a new instance of StreamConfig with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the charset the text of the stream is encoded with. Characters the charset cannot
+    /// encode, e.g. non latin characters in ISO-8859-1, are replaced with `?`.
+    /// Default: CharSet::UTF_8.
+    pub fn set_charset(mut self, val: CharSet) -> Self {
+        self.charset = val;
+        self
+    }
+
+    /// Sets the size in bytes of the buffers between the parser and the reader of the stream.
+    /// Larger buffers make fewer calls into the JVM for large documents, smaller ones use less
+    /// memory per stream. Sizes below 1 are raised to 1.
+    /// Default: [`crate::DEFAULT_BUF_SIZE`].
+    pub fn set_buffer_size(mut self, val: usize) -> Self {
+        self.buffer_size = val.max(1);
+        self
+    }
+}
+
+/// Per-call configuration overrides
+///
+/// Each field left as `None` inherits the value configured on the [`crate::Extractor`]. The
+/// overrides only apply to the call they are passed to and never modify the extractor itself.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ExtractOverrides {
+    pub(crate) pdf_config: Option<PdfParserConfig>,
+    pub(crate) office_config: Option<OfficeParserConfig>,
+    #[cfg(feature = "ocr")]
+    pub(crate) ocr_config: Option<TesseractOcrConfig>,
+    pub(crate) ocr_strategy: Option<PdfOcrStrategy>,
+    pub(crate) extract_string_max_length: Option<i32>,
+    #[cfg(feature = "ocr")]
+    pub(crate) ocr_timeout_seconds: Option<i32>,
+}
+
+impl ExtractOverrides {
+    /// Creates a new instance of ExtractOverrides that overrides nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the whole PDF parser configuration for a single call. The OCR strategy set with
+    /// [`ExtractOverrides::set_ocr_strategy`], if any, still applies over it.
+    pub fn set_pdf_config(mut self, val: PdfParserConfig) -> Self {
+        self.pdf_config = Some(val);
+        self
+    }
+
+    /// Overrides the whole Office parser configuration for a single call.
+    pub fn set_office_config(mut self, val: OfficeParserConfig) -> Self {
+        self.office_config = Some(val);
+        self
+    }
+
+    /// Overrides the whole Tesseract OCR configuration for a single call. The timeout set with
+    /// [`ExtractOverrides::set_ocr_timeout_seconds`], if any, still applies over it.
+    #[cfg(feature = "ocr")]
+    pub fn set_ocr_config(mut self, val: TesseractOcrConfig) -> Self {
+        self.ocr_config = Some(val);
+        self
+    }
+
+    /// Overrides the PDF OCR strategy for a single call.
+    pub fn set_ocr_strategy(mut self, val: PdfOcrStrategy) -> Self {
+        self.ocr_strategy = Some(val);
+        self
+    }
+
+    /// Overrides the maximum length of the extracted text for a single call.
+    /// Used only for extract_to_string functions.
+    pub fn set_extract_string_max_length(mut self, val: i32) -> Self {
+        self.extract_string_max_length = Some(val);
+        self
+    }
+
+    /// Overrides the maximum time in seconds that Tesseract should spend on OCR for a single call.
+    #[cfg(feature = "ocr")]
+    pub fn set_ocr_timeout_seconds(mut self, val: i32) -> Self {
+        self.ocr_timeout_seconds = Some(val);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_config_debug_redacts_credentials() {
+        let config = HttpConfig::new()
+            .add_header("X-Api-Key", "secret-key")
+            .set_bearer_auth("secret-token");
+        let debug = format!("{:?}", config);
+        assert!(debug.contains("X-Api-Key"), "debug: {}", debug);
+        assert!(!debug.contains("secret"), "debug: {}", debug);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::errors::{Error, ExtractResult};
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+
+/// Standard base64 that accepts input with or without padding
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Decodes a base64 string. Whitespace, such as the line breaks of wrapped base64, is ignored.
+pub(crate) fn decode_base64(b64: &str) -> ExtractResult<Vec<u8>> {
+    let compact: String = b64.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+    BASE64
+        .decode(compact)
+        .map_err(|e| Error::ParseError(format!("Invalid base64 input: {}", e)))
+}
+
+/// Decodes a `data:[<media type>][;base64],<data>` URI into its media type, if declared, and
+/// its bytes
+pub(crate) fn decode_data_uri(uri: &str) -> ExtractResult<(Option<String>, Vec<u8>)> {
+    let invalid = |reason: &str| Error::ParseError(format!("Invalid data URI: {}", reason));
+
+    let rest = uri
+        .trim_start()
+        .strip_prefix("data:")
+        .ok_or_else(|| invalid("missing data: scheme"))?;
+    let (header, data) = rest
+        .split_once(',')
+        .ok_or_else(|| invalid("missing ',' before the data"))?;
+
+    let (header, is_base64) = match header.strip_suffix(";base64") {
+        Some(header) => (header, true),
+        None => (header, false),
+    };
+    // The media type is kept with its parameters, e.g. text/plain;charset=utf-8
+    let media_type = Some(header.trim())
+        .filter(|media_type| !media_type.is_empty() && !media_type.starts_with(';'))
+        .map(str::to_string);
+
+    let data = percent_decode(data.as_bytes())?;
+    let bytes = if is_base64 {
+        std::str::from_utf8(&data)
+            .map_err(|_| invalid("the data is not valid base64"))
+            .and_then(|b64| {
+                decode_base64(b64).map_err(|_| invalid("the data is not valid base64"))
+            })?
+    } else {
+        data
+    };
+    Ok((media_type, bytes))
+}
+
+/// Decodes the %XX escapes of a URI component
+fn percent_decode(data: &[u8]) -> ExtractResult<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        if data[i] == b'%' {
+            let hex = data
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| {
+                    Error::ParseError("Invalid data URI: malformed % escape".to_string())
+                })?;
+            decoded.push(hex);
+            i += 3;
+        } else {
+            decoded.push(data[i]);
+            i += 1;
+        }
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_base64_test() {
+        assert_eq!(decode_base64("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode_base64("aGVs\nbG8").unwrap(), b"hello");
+        assert!(decode_base64("not base64!").is_err());
+    }
+
+    #[test]
+    fn decode_data_uri_test() {
+        let (media_type, bytes) = decode_data_uri("data:text/plain;base64,aGVsbG8=").unwrap();
+        assert_eq!(media_type.as_deref(), Some("text/plain"));
+        assert_eq!(bytes, b"hello");
+
+        let (media_type, bytes) =
+            decode_data_uri("data:text/plain;charset=utf-8,hello%20world").unwrap();
+        assert_eq!(media_type.as_deref(), Some("text/plain;charset=utf-8"));
+        assert_eq!(bytes, b"hello world");
+
+        let (media_type, bytes) = decode_data_uri("data:,hello").unwrap();
+        assert_eq!(media_type, None);
+        assert_eq!(bytes, b"hello");
+    }
+
+    #[test]
+    fn decode_invalid_data_uri_test() {
+        assert!(decode_data_uri("http://example.com").is_err());
+        assert!(decode_data_uri("data:text/plain").is_err());
+        assert!(decode_data_uri("data:text/plain;base64,@@@").is_err());
+        assert!(decode_data_uri("data:,%zz").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::Metadata;
+
+/// Metadata key holding the content of a document in a recursive parse
+const CONTENT_KEY: &str = "X-TIKA:content";
+/// Metadata key holding the path of an embedded document within its container
+const EMBEDDED_RESOURCE_PATH_KEY: &str = "X-TIKA:embedded_resource_path";
+/// Metadata key holding the name of a document, such as the name of an archive entry
+const RESOURCE_NAME_KEY: &str = "resourceName";
+
+/// Metadata key set to `true` on the container of a recursive extraction that stopped at the
+/// limit of [`crate::Extractor::set_max_embedded_resources`]
+pub const EMBEDDED_RESOURCE_LIMIT_REACHED: &str =
+    "X-TIKA:EXCEPTION:embedded_resource_limit_reached";
+
+/// A document extracted by a recursive extraction, either the container itself or a document
+/// embedded in it, such as an attachment of an email or a file inside a zip
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedDocument {
+    /// The path of the document within the container, e.g. `/report.zip/scan.pdf`.
+    /// Empty for the container itself
+    pub path: String,
+    /// The extracted text of the document, without the text of the documents embedded in it
+    pub content: String,
+    /// The metadata of the document
+    pub metadata: Metadata,
+}
+
+impl EmbeddedDocument {
+    /// Builds a document from the metadata tika returns for it, which carries the content
+    pub(crate) fn from_metadata(mut metadata: Metadata) -> Self {
+        let mut take = |key: &str| {
+            metadata
+                .remove(key)
+                .and_then(|values| values.into_iter().next())
+                .unwrap_or_default()
+        };
+        let content = take(CONTENT_KEY);
+        let path = take(EMBEDDED_RESOURCE_PATH_KEY);
+        Self {
+            path,
+            content,
+            metadata,
+        }
+    }
+
+    /// Returns true if this is the container document
+    pub fn is_container(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    /// Returns the name of the document within its direct parent, if known. For the entries of
+    /// a zip or tar archive this is the entry name, including its directories, e.g.
+    /// `docs/report.pdf`
+    pub fn name(&self) -> Option<&str> {
+        self.metadata
+            .get(RESOURCE_NAME_KEY)
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    }
+}
+
+/// What is known about an embedded document when it is discovered, before it is extracted.
+/// Passed to the filter of [`crate::Extractor::extract_embedded_filtered`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedMeta {
+    /// The file name of the embedded document, if known
+    pub name: Option<String>,
+    /// The declared media type of the embedded document, if known
+    pub media_type: Option<String>,
+    /// The size in bytes of the embedded document, if known
+    pub size: Option<u64>,
+}
+
+/// The raw bytes of a document embedded in another, such as an attachment, an archive entry or
+/// an image, as passed to the callback of [`crate::Extractor::unpack_embedded`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedFile {
+    /// The path of the document within the container, e.g. `/report.zip/scan.pdf`. Documents
+    /// without a name are named `embedded-1`, `embedded-2`, ... in their path
+    pub path: String,
+    /// The file name of the document, if known
+    pub name: Option<String>,
+    /// The detected media type of the document, e.g. `image/png`
+    pub mime_type: String,
+    /// The bytes of the document
+    pub data: Vec<u8>,
+}
+
+impl EmbeddedFile {
+    /// Returns a name the document can be safely written under in a directory: the last
+    /// segment of its path, without the characters that are not allowed in file names
+    pub(crate) fn file_name(&self) -> String {
+        let last = self.path.rsplit(['/', '\\']).next().unwrap_or_default();
+        let name: String = last
+            .chars()
+            .map(|c| match c {
+                '<' | '>' | ':' | '"' | '|' | '?' | '*' => '_',
+                c if c.is_control() => '_',
+                c => c,
+            })
+            .collect();
+        match name.trim() {
+            "" | "." | ".." => "embedded".to_string(),
+            _ => name,
+        }
+    }
+}
+
+/// A file attached to a PDF through the `EmbeddedFiles` name tree of its catalog, as returned
+/// by [`crate::Extractor::extract_pdf_attachments`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdfAttachment {
+    /// The key of the attachment in the name tree, which PDF viewers commonly show
+    pub name: String,
+    /// The original file name of the attachment, if set, e.g. `invoice-data.csv`
+    pub file_name: Option<String>,
+    /// The description of the attachment, if set
+    pub description: Option<String>,
+    /// The relationship of the attachment to the PDF, its `AFRelationship`, if set: `Source`,
+    /// `Data`, `Alternative`, `Supplement`, `EncryptedPayload`, `FormData`, `Schema` or
+    /// `Unspecified`. PDF/A-3 and e-invoices such as ZUGFeRD use it to tell the machine
+    /// readable data apart from the other files
+    pub relationship: Option<String>,
+    /// The media type declared for the attachment, if set, e.g. `text/csv`
+    pub mime_type: Option<String>,
+    /// The creation date of the attachment in ISO 8601, if set
+    pub creation_date: Option<String>,
+    /// The modification date of the attachment in ISO 8601, if set
+    pub modification_date: Option<String>,
+    /// The bytes of the attachment
+    pub data: Vec<u8>,
+}
+
+/// An image found in a document, as returned by [`crate::Extractor::extract_images`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageRef {
+    /// The bytes of the image
+    pub data: Vec<u8>,
+    /// The media type of the image, e.g. `image/png`
+    pub mime_type: String,
+    /// The page the image is drawn on, starting at 1. Only known for PDFs
+    pub page: Option<u32>,
+    /// The `(x, y, width, height)` of the image on its page, in points from the top left corner
+    /// of the page. Only known for PDFs
+    pub bbox: Option<(f32, f32, f32, f32)>,
+}
@@ -0,0 +1,32 @@
+use crate::Metadata;
+
+/// Metadata keys under which the entities are passed from Java
+const EMAIL_KEY: &str = "email";
+const PHONE_KEY: &str = "phone";
+const URL_KEY: &str = "url";
+
+/// The entities found in a document, as returned by [`crate::Extractor::extract_entities`].
+/// Each list is de-duplicated and keeps the order in which the entities first occur.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Entities {
+    /// The e-mail addresses, lower cased, from the text and from `mailto:` links
+    pub emails: Vec<String>,
+    /// The phone numbers, as found by Tika's `PhoneExtractingContentHandler`, reduced to their
+    /// digits, e.g. `8015961887` for `(801) 596-1887`
+    pub phones: Vec<String>,
+    /// The `http` and `https` URLs, from the text and from links. URLs written without a
+    /// scheme, such as `www.example.com`, get `http://`
+    pub urls: Vec<String>,
+}
+
+impl Entities {
+    /// Builds the entities from the metadata java returns them in
+    pub(crate) fn from_metadata(mut metadata: Metadata) -> Self {
+        let mut take = |key: &str| metadata.remove(key).unwrap_or_default();
+        Self {
+            emails: take(EMAIL_KEY),
+            phones: take(PHONE_KEY),
+            urls: take(URL_KEY),
+        }
+    }
+}
@@ -13,6 +13,42 @@ pub enum Error {
     #[error("{0}")]
     ParseError(String),
 
+    /// The media type of the input was detected, but no parser is available for it.
+    /// Holds the detected media type
+    #[error("No parser available for media type {0}")]
+    NoParser(String),
+
+    /// A language of the OCR config is not installed, and falling back to the installed ones is
+    /// disabled with [`crate::TesseractOcrConfig::set_fallback_on_missing_language`]. Holds the
+    /// language
+    #[error("OCR language {0} is not installed")]
+    OcrLanguageMissing(String),
+
+    /// The detected media type of the input is not one of the accepted media types set with
+    /// [`crate::Extractor::set_accepted_media_types`], or the input contains macros and macro
+    /// documents are rejected with [`crate::Extractor::set_reject_macro_documents`]. Carries no
+    /// message, the detected media type is returned by [`crate::Extractor::media_type_hierarchy`]
+    #[error("Media type is not accepted")]
+    Rejected,
+
+    /// The input ended before the parser expected it to, e.g. a truncated file. Carries no message
+    #[error("Unexpected end of input")]
+    UnexpectedEof,
+
+    /// The parse, or the OCR run by it, timed out. Carries no message
+    #[error("Timed out")]
+    Timeout,
+
+    /// The document is encrypted and cannot be decrypted, because no password was set with
+    /// [`crate::PdfParserConfig::set_password`] or the password is wrong. Carries no message
+    #[error("Document is encrypted")]
+    EncryptedDocument,
+
+    /// The extracted text is not valid UTF-8, as reported by a [`crate::CharStream`] with the
+    /// [`crate::DecodeErrorPolicy::Error`] policy. Holds the byte offset of the invalid sequence
+    #[error("Invalid UTF-8 sequence at byte offset {0}")]
+    InvalidEncoding(u64),
+
     #[error("{0}")]
     Utf8Error(#[from] Utf8Error),
 
@@ -34,6 +70,24 @@ impl From<Error> for io::Error {
             Error::ParseError(msg) => {
                 io::Error::new(io::ErrorKind::Other, format!("Parse error: {}", msg))
             }
+            Error::NoParser(media_type) => {
+                io::Error::other(format!("No parser available for media type {}", media_type))
+            }
+            Error::OcrLanguageMissing(language) => {
+                io::Error::other(format!("OCR language {} is not installed", language))
+            }
+            Error::Rejected => io::Error::other("Media type is not accepted"),
+            Error::UnexpectedEof => {
+                io::Error::new(io::ErrorKind::UnexpectedEof, "Unexpected end of input")
+            }
+            Error::Timeout => io::Error::new(io::ErrorKind::TimedOut, "Timed out"),
+            Error::EncryptedDocument => {
+                io::Error::new(io::ErrorKind::PermissionDenied, "Document is encrypted")
+            }
+            Error::InvalidEncoding(offset) => io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid UTF-8 sequence at byte offset {}", offset),
+            ),
             Error::Utf8Error(e) => {
                 io::Error::new(io::ErrorKind::Other, format!("UTF8 error: {}", e))
             }
@@ -1,21 +1,189 @@
-use crate::errors::ExtractResult;
+use crate::data_uri;
+use crate::errors::{Error, ExtractResult};
+#[cfg(feature = "ocr")]
+use crate::ocr::{OcrBackendRef, WarmOcrConfig};
+use crate::segment;
+use crate::structure;
+use crate::tables;
 use crate::tika;
-use crate::tika::JReaderInputStream;
-use crate::{OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
+use crate::tika::callbacks::{PasswordProviderRef, ProgressCallbackRef};
+use crate::tika::{CallbackGuard, JReaderInputStream};
+use crate::validate;
+use crate::{
+    AnchorGranularity, Annotation, CharStream, Comment, Cursor, DecodeErrorPolicy,
+    DetectedLanguage, DocumentInfo, EmbeddedDocument, EmbeddedFile, EmbeddedMeta, Entities,
+    ExtractOverrides, ExtractionResult, FontInfo, FormField, Heading, HttpConfig, ImageRef,
+    OfficeParserConfig, Outline, Page, PageMeta, Paragraph, PdfAttachment, PdfOcrStrategy,
+    PdfPages, PdfParserConfig, QualityScore, SaxEvent, Sheet, Slide, StreamConfig, Table,
+    TextAnchor, DEFAULT_BUF_SIZE,
+};
+#[cfg(feature = "ocr")]
+use crate::{OcrBackend, TesseractOcrConfig};
 use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::iter;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 use strum_macros::{Display, EnumString};
 
+/// Small documents extracted by [`Extractor::warmup`] to load the parsers of the hot paths
+const WARMUP_FIXTURES: [&[u8]; 3] = [
+    b"extractous warmup",
+    b"<html><body><p>extractous warmup</p></body></html>",
+    include_bytes!("../resources/warmup.pdf"),
+];
+
+/// Namespace passed to [`Extractor::set_metadata_namespaces`] to select the metadata keys
+/// without a namespace prefix, such as `Content-Type`
+pub const METADATA_NO_NAMESPACE: &str = "";
+
 /// Metadata type alias
 pub type Metadata = HashMap<String, Vec<String>>;
 
-/// CharSet enum of all supported encodings
+/// CharSet enum of all supported encodings. Displayed as the canonical java name of the charset,
+/// e.g. `UTF-8`
 #[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum CharSet {
     #[default]
+    #[strum(to_string = "UTF-8", serialize = "UTF_8")]
     UTF_8,
+    #[strum(to_string = "US-ASCII", serialize = "US_ASCII")]
     US_ASCII,
+    #[strum(to_string = "UTF-16BE", serialize = "UTF_16BE")]
     UTF_16BE,
+    #[strum(to_string = "UTF-16LE", serialize = "UTF_16LE")]
+    UTF_16LE,
+    /// UTF-16 with a byte order mark, big endian
+    #[strum(to_string = "UTF-16", serialize = "UTF_16")]
+    UTF_16,
+    /// Latin-1
+    #[strum(to_string = "ISO-8859-1", serialize = "ISO_8859_1")]
+    ISO_8859_1,
+}
+
+/// Format of the extracted content
+#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum OutputFormat {
+    /// Plain text
+    #[default]
+    Text,
+    /// The XHTML produced by the parsers
+    Xhtml,
+    /// The XHTML produced by the parsers serialized as HTML, for rendering: no XML declaration
+    /// and void elements such as `<br>` or `<img>` are not closed. Keeps the structural markup,
+    /// such as headings, tables and lists.
+    Html,
+    /// GitHub flavored Markdown keeping bold, italic, headings, lists, tables, links and
+    /// preformatted text as fenced code blocks. Formatting that cannot be expressed, such as
+    /// malformed or overlapping markup, degrades to plain text.
+    Markdown,
+    /// Newline delimited JSON, one self-contained record per paragraph, heading, list item,
+    /// table row or image, e.g.
+    /// `{"type":"heading","index":0,"page":1,"text":"Results","level":2}`. Every record has
+    /// the `type` of the element, its `text`, its `index` among the records of the document
+    /// and the `page` it is on, counted from 1 over the pages of PDFs, the sheets of
+    /// spreadsheets and the slides of presentations, or `null` for documents without pages.
+    /// Headings also have their `level`, table rows their `cells`, their `table` and their
+    /// `row` index in it, and images their `src` and `alt`.
+    Ndjson,
+    /// RFC 4180 CSV of the tables of the document, such as the sheets of a spreadsheet: one
+    /// CRLF terminated record per row, the cells separated by the delimiter set with
+    /// [`Extractor::set_csv_delimiter`] and quoted when they contain the delimiter, a double
+    /// quote or a line break. The tables are separated by an empty line and the text outside
+    /// of them, such as the names of the sheets, is dropped.
+    Csv,
+}
+
+/// How the charset of text based formats, such as HTML, XML or plain text, is resolved when the
+/// charset declared by the document differs from the one detected from its bytes
+#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum CharsetPolicy {
+    /// Use the declared charset, e.g. of a `<meta>` tag, and detect it only if none is declared
+    #[default]
+    PreferDeclared,
+    /// Detect the charset from the bytes, and use the declared one only if detection fails
+    PreferDetected,
+    /// Always decode as UTF-8
+    ForceUtf8,
+}
+
+/// What the detection of the media type of an input relies on. Tika combines the magic bytes
+/// of the content with the name of the input, such as the file name of [`Extractor::extract_file`]
+/// or a `resourceName` hint.
+#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum DetectionMode {
+    /// Use both the content and the name, as Tika does
+    #[default]
+    ContentAndName,
+    /// Use the content only and ignore the name, e.g. for files with meaningless names
+    ContentOnly,
+    /// Use the name only and ignore the content, e.g. for generic containers with reliable
+    /// extensions. An input without name is detected as `application/octet-stream`, unless a
+    /// `Content-Type` hint is given
+    NameOnly,
+}
+
+/// What a line break of the markup becomes in plain text output, see [`LineBreakPolicy`]
+#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum LineBreak {
+    /// A single newline
+    #[default]
+    Newline,
+    /// Two newlines, leaving an empty line
+    DoubleNewline,
+    /// A space, joining the text before and after the break on one line
+    Space,
+}
+
+/// How the boundaries of block elements, such as paragraphs, headings, list items or table rows,
+/// and `<br>` elements, which most formats use for soft line breaks, are written in plain text
+/// output. Only applies to [`OutputFormat::Text`].
+#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LineBreakPolicy {
+    pub(crate) block_break: LineBreak,
+    pub(crate) br_break: LineBreak,
+}
+
+impl LineBreakPolicy {
+    /// Creates a new instance of LineBreakPolicy writing single newlines, as Tika does.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set what the end of a block element becomes.
+    /// Default: LineBreak::Newline
+    pub fn set_block_break(mut self, val: LineBreak) -> Self {
+        self.block_break = val;
+        self
+    }
+
+    /// Set what a `<br>` element becomes.
+    /// Default: LineBreak::Newline
+    pub fn set_br_break(mut self, val: LineBreak) -> Self {
+        self.br_break = val;
+        self
+    }
 }
 
 /// StreamReader implements std::io::Read
@@ -35,16 +203,131 @@ pub enum CharSet {
 /// println!("{}", content);
 /// ```
 ///
-pub struct StreamReader {
+/// The total size of the extracted text is only known once the end of the stream is reached.
+/// Use [`StreamReader::bytes_read`] to report progress while reading. Functions that fully
+/// materialize the content, such as `extract_bytes_to_string`, return a `String` whose
+/// `len()` is the total size in bytes.
+///
+/// The lifetime is the one of the input when Tika reads it in place, as with
+/// [`Extractor::extract_bytes`], and `'static` otherwise.
+pub struct StreamReader<'a> {
     pub(crate) inner: JReaderInputStream,
+    pub(crate) bytes_read: u64,
+    pub(crate) ocr_backend: Option<CallbackGuard>,
+    pub(crate) progress: Option<CallbackGuard>,
+    pub(crate) password: Option<CallbackGuard>,
+    pub(crate) input: Option<CallbackGuard>,
+    pub(crate) bytes: Option<Arc<[u8]>>,
+    pub(crate) borrowed: PhantomData<&'a [u8]>,
+}
+
+impl<'a> StreamReader<'a> {
+    pub(crate) fn new(inner: JReaderInputStream) -> Self {
+        Self {
+            inner,
+            bytes_read: 0,
+            ocr_backend: None,
+            progress: None,
+            password: None,
+            input: None,
+            bytes: None,
+            borrowed: PhantomData,
+        }
+    }
+
+    /// Returns the number of bytes read so far from this stream
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Wraps this stream into a [`CharStream`] decoding its bytes with the given policy
+    pub fn into_chars(self, policy: DecodeErrorPolicy) -> CharStream<Self> {
+        CharStream::new(self, policy)
+    }
 }
 
-impl std::io::Read for StreamReader {
+impl std::io::Read for StreamReader<'_> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.inner.read(buf)
+        let num_read_bytes = self.inner.read(buf)?;
+        self.bytes_read += num_read_bytes as u64;
+        Ok(num_read_bytes)
     }
 }
 
+/// What an [`Extractor`] would do with an input, as returned by [`Extractor::explain`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractionPlan {
+    /// The detected media type, e.g. `application/pdf`
+    pub detected_type: String,
+    /// The fully qualified java class name of the Tika parser that would handle the input,
+    /// e.g. `org.apache.tika.parser.pdf.PDFParser`, or None if no parser supports it
+    pub selected_parser: Option<String>,
+    /// Whether OCR would run. For PDFs with the AUTO strategy OCR only runs on pages with too
+    /// little text, which cannot be known without parsing, so this reports whether it may run.
+    pub ocr_would_run: bool,
+    /// The effective PDF parser configuration
+    pub pdf_config: PdfParserConfig,
+    /// The effective Office parser configuration
+    pub office_config: OfficeParserConfig,
+    /// The effective Tesseract OCR configuration
+    #[cfg(feature = "ocr")]
+    pub ocr_config: TesseractOcrConfig,
+}
+
+/// How much the detected media type of a [`Detection`] can be trusted, from the least to the
+/// most confident
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display, EnumString)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum DetectionConfidence {
+    /// Nothing was detected, the type is `application/octet-stream`
+    None,
+    /// The type was detected from the name or a `Content-Type` hint only, the content does not
+    /// back it
+    Low,
+    /// The content gives the type but the name disagrees with it, or the name refines the type
+    /// given by the content, e.g. `text/csv` for plain text
+    Medium,
+    /// The content gives the type and the name, if any, agrees with it
+    High,
+}
+
+/// The media type of an input, as returned by [`Extractor::detect_file`] and
+/// [`Extractor::detect_bytes`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detection {
+    /// The detected media type, without parameters, e.g. `application/pdf`
+    pub mime_type: String,
+    /// How much the detected media type can be trusted
+    pub confidence: DetectionConfidence,
+}
+
+/// The digital signature status of a document, as returned by [`Extractor::verify_signature`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// The document carries no digital signature
+    Unsigned,
+    /// Every signature matches the signed content. Holds the subject of the certificate of the
+    /// first signature, e.g. `CN=John Doe,O=Example`
+    Valid { signer: String },
+    /// A signature does not match the signed content, cannot be verified, or the document was
+    /// modified after it was signed
+    Invalid { reason: String },
+}
+
+/// Returns the sorted list of media types, e.g. `application/pdf`, that extractous can parse.
+/// Useful to validate inputs up front or to notice formats added or removed by an upgrade.
+pub fn supported_media_types() -> ExtractResult<Vec<String>> {
+    tika::supported_media_types()
+}
+
+/// Returns the sorted list of languages installed for Tesseract, e.g. `eng` or `deu`, from the
+/// traineddata files it finds. Empty when Tesseract is not installed. Useful to validate a
+/// deployment, see [`TesseractOcrConfig::set_fallback_on_missing_language`].
+#[cfg(feature = "ocr")]
+pub fn installed_ocr_languages() -> ExtractResult<Vec<String>> {
+    tika::installed_ocr_languages()
+}
+
 /// Extractor for extracting text from different file formats
 ///
 /// The Extractor uses the builder pattern to set configurations. This allows configuring and
@@ -58,25 +341,93 @@ impl std::io::Read for StreamReader {
 /// println!("{}", text);
 /// ```
 ///
+/// Every setter also has a `_mut` variant that changes an owned extractor in place, e.g. when it
+/// is a field of a struct. Changes only apply to the extractions started after them: the
+/// settings are passed to the parser when an extraction starts, so ongoing extractions and the
+/// streams returned earlier keep the settings they started with.
+/// ```rust
+/// use extractous::Extractor;
+/// let mut extractor = Extractor::new();
+/// extractor
+///     .set_extract_string_max_length_mut(1000)
+///     .set_locale_mut("de-DE");
+/// ```
+///
+/// With the `serde` feature, the settings of an extractor and of its configs can be serialized,
+/// e.g. to load extraction profiles from configuration files. Missing settings keep their
+/// default values. The OCR backend, the progress callback, the password provider and the warm
+/// OCR setup are runtime state and are not serialized.
+///
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Extractor {
-    extract_string_max_length: i32,
-    encoding: CharSet,
-    pdf_config: PdfParserConfig,
-    office_config: OfficeParserConfig,
-    ocr_config: TesseractOcrConfig,
-    xml_output: bool,
+    pub(crate) extract_string_max_length: i32,
+    pub(crate) stream_config: StreamConfig,
+    pub(crate) pdf_config: PdfParserConfig,
+    pub(crate) office_config: OfficeParserConfig,
+    #[cfg(feature = "ocr")]
+    pub(crate) ocr_config: TesseractOcrConfig,
+    pub(crate) http_config: HttpConfig,
+    pub(crate) tika_config: Option<String>,
+    pub(crate) output_format: OutputFormat,
+    pub(crate) csv_delimiter: char,
+    pub(crate) charset_policy: CharsetPolicy,
+    pub(crate) detection_mode: DetectionMode,
+    pub(crate) line_break_policy: LineBreakPolicy,
+    pub(crate) metadata_namespaces: Option<Vec<String>>,
+    #[cfg(feature = "ocr")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) ocr_backend: Option<OcrBackendRef>,
+    #[cfg(feature = "ocr")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) warm_ocr_config: Option<WarmOcrConfig>,
+    pub(crate) locale: String,
+    pub(crate) content_type_hint: Option<String>,
+    pub(crate) metadata_hints: HashMap<String, String>,
+    pub(crate) accepted_media_types: Vec<String>,
+    pub(crate) disabled_parsers: Vec<String>,
+    pub(crate) reject_macro_documents: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) detect_language: bool,
+    pub(crate) max_embedded_resources: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) progress_callback: Option<ProgressCallbackRef>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) password_provider: Option<PasswordProviderRef>,
 }
 
 impl Default for Extractor {
     fn default() -> Self {
         Self {
             extract_string_max_length: 500_000, // 500KB
-            encoding: CharSet::UTF_8,
+            stream_config: StreamConfig::default(),
             pdf_config: PdfParserConfig::default(),
             office_config: OfficeParserConfig::default(),
+            #[cfg(feature = "ocr")]
             ocr_config: TesseractOcrConfig::default(),
-            xml_output: false,
+            http_config: HttpConfig::default(),
+            tika_config: None,
+            output_format: OutputFormat::Text,
+            csv_delimiter: ',',
+            charset_policy: CharsetPolicy::PreferDeclared,
+            detection_mode: DetectionMode::ContentAndName,
+            line_break_policy: LineBreakPolicy::default(),
+            metadata_namespaces: None,
+            #[cfg(feature = "ocr")]
+            ocr_backend: None,
+            #[cfg(feature = "ocr")]
+            warm_ocr_config: None,
+            locale: "en-US".to_string(),
+            content_type_hint: None,
+            metadata_hints: HashMap::new(),
+            accepted_media_types: Vec::new(),
+            disabled_parsers: Vec::new(),
+            reject_macro_documents: false,
+            detect_language: false,
+            max_embedded_resources: None,
+            progress_callback: None,
+            password_provider: None,
         }
     }
 }
@@ -89,130 +440,1397 @@ impl Extractor {
     /// Set the maximum length of the extracted text. Used only for extract_to_string functions
     /// Default: 500_000
     pub fn set_extract_string_max_length(mut self, max_length: i32) -> Self {
+        self.set_extract_string_max_length_mut(max_length);
+        self
+    }
+
+    /// Same as [`Extractor::set_extract_string_max_length`], but changes this extractor in place
+    pub fn set_extract_string_max_length_mut(&mut self, max_length: i32) -> &mut Self {
         self.extract_string_max_length = max_length;
         self
     }
 
     /// Set the encoding to use for when extracting text to a stream.
-    /// Not used for extract_to_string functions.
+    /// Not used for extract_to_string functions. Shorthand for the charset of
+    /// [`Extractor::set_stream_config`].
     /// Default: CharSet::UTF_8
     pub fn set_encoding(mut self, encoding: CharSet) -> Self {
-        self.encoding = encoding;
+        self.set_encoding_mut(encoding);
+        self
+    }
+
+    /// Same as [`Extractor::set_encoding`], but changes this extractor in place
+    pub fn set_encoding_mut(&mut self, encoding: CharSet) -> &mut Self {
+        self.stream_config.charset = encoding;
+        self
+    }
+
+    /// Set the configuration of the streams returned by the `extract_*` functions: the charset
+    /// the text is encoded with and the size of the buffers between the parser and the reader.
+    /// Default: StreamConfig::default()
+    pub fn set_stream_config(mut self, config: StreamConfig) -> Self {
+        self.set_stream_config_mut(config);
+        self
+    }
+
+    /// Same as [`Extractor::set_stream_config`], but changes this extractor in place
+    pub fn set_stream_config_mut(&mut self, config: StreamConfig) -> &mut Self {
+        self.stream_config = config;
         self
     }
 
     /// Set the configuration for the PDF parser
     pub fn set_pdf_config(mut self, config: PdfParserConfig) -> Self {
+        self.set_pdf_config_mut(config);
+        self
+    }
+
+    /// Same as [`Extractor::set_pdf_config`], but changes this extractor in place
+    pub fn set_pdf_config_mut(&mut self, config: PdfParserConfig) -> &mut Self {
         self.pdf_config = config;
         self
     }
 
     /// Set the configuration for the Office parser
     pub fn set_office_config(mut self, config: OfficeParserConfig) -> Self {
+        self.set_office_config_mut(config);
+        self
+    }
+
+    /// Same as [`Extractor::set_office_config`], but changes this extractor in place
+    pub fn set_office_config_mut(&mut self, config: OfficeParserConfig) -> &mut Self {
         self.office_config = config;
         self
     }
 
     /// Set the configuration for the Tesseract OCR
+    #[cfg(feature = "ocr")]
     pub fn set_ocr_config(mut self, config: TesseractOcrConfig) -> Self {
+        self.set_ocr_config_mut(config);
+        self
+    }
+
+    /// Same as [`Extractor::set_ocr_config`], but changes this extractor in place
+    #[cfg(feature = "ocr")]
+    pub fn set_ocr_config_mut(&mut self, config: TesseractOcrConfig) -> &mut Self {
         self.ocr_config = config;
         self
     }
 
-    /// Set the configuration for the parse as xml
-    pub fn set_xml_output(mut self, xml_output: bool) -> Self {
-        self.xml_output = xml_output;
+    /// Set the HTTP configuration of the url extractions, such as headers, authentication,
+    /// proxy, redirects and timeouts
+    pub fn set_http_config(mut self, config: HttpConfig) -> Self {
+        self.set_http_config_mut(config);
+        self
+    }
+
+    /// Same as [`Extractor::set_http_config`], but changes this extractor in place
+    pub fn set_http_config_mut(&mut self, config: HttpConfig) -> &mut Self {
+        self.http_config = config;
+        self
+    }
+
+    /// Set a Tika XML configuration, e.g. to exclude parsers or to set the parameters of a
+    /// parser, given either as the XML itself or as the path of its file. The configuration
+    /// replaces Tika's default one for the detection and the parsers, the settings of the
+    /// extractor and of its configs still apply over it. It is loaded the first time it is used
+    /// and then reused, so changes to its file are not picked up. Only the parser classes
+    /// compiled into the native library can be referenced. An invalid configuration makes the
+    /// extractions fail with [`crate::Error::IoError`].
+    /// Default: None, Tika's default configuration is used
+    pub fn set_tika_config(mut self, config: &str) -> Self {
+        self.set_tika_config_mut(config);
+        self
+    }
+
+    /// Same as [`Extractor::set_tika_config`], but changes this extractor in place
+    pub fn set_tika_config_mut(&mut self, config: &str) -> &mut Self {
+        self.tika_config = Some(config.to_string());
+        self
+    }
+
+    /// Set an OCR backend to use instead of Tesseract. All images that would be OCR'd, including
+    /// the rendered pages of a PDF, are passed to the backend. See [`OcrBackend`].
+    /// Default: None, Tesseract is used
+    #[cfg(feature = "ocr")]
+    pub fn set_ocr_backend(mut self, backend: Box<dyn OcrBackend>) -> Self {
+        self.set_ocr_backend_mut(backend);
+        self
+    }
+
+    /// Same as [`Extractor::set_ocr_backend`], but changes this extractor in place
+    #[cfg(feature = "ocr")]
+    pub fn set_ocr_backend_mut(&mut self, backend: Box<dyn OcrBackend>) -> &mut Self {
+        self.ocr_backend = Some(OcrBackendRef(Arc::from(backend)));
+        self
+    }
+
+    /// Set whether the OCR setup is kept between extractions: the OCR config is then passed to
+    /// Tika once and reused by every following extraction of this extractor and of its clones.
+    /// Tika runs the Tesseract executable once per image, which loads the language models each
+    /// time, and cannot keep a Tesseract process running, so only the per extraction setup is
+    /// saved. To keep a model loaded across documents, use an [`OcrBackend`] that holds it.
+    /// Has no effect when an OCR backend is set.
+    /// Default: false
+    #[cfg(feature = "ocr")]
+    pub fn set_ocr_keep_warm(mut self, keep_warm: bool) -> Self {
+        self.set_ocr_keep_warm_mut(keep_warm);
+        self
+    }
+
+    /// Same as [`Extractor::set_ocr_keep_warm`], but changes this extractor in place
+    #[cfg(feature = "ocr")]
+    pub fn set_ocr_keep_warm_mut(&mut self, keep_warm: bool) -> &mut Self {
+        self.warm_ocr_config = keep_warm.then(WarmOcrConfig::default);
+        self
+    }
+
+    /// Set the locale, as a BCP-47 language tag such as "de-DE", used by the parsers to format
+    /// values such as spreadsheet dates and numbers. Pinning it makes the output independent of
+    /// the locale of the host.
+    /// Default: "en-US"
+    pub fn set_locale(mut self, locale: &str) -> Self {
+        self.set_locale_mut(locale);
+        self
+    }
+
+    /// Same as [`Extractor::set_locale`], but changes this extractor in place
+    pub fn set_locale_mut(&mut self, locale: &str) -> &mut Self {
+        self.locale = locale.to_string();
+        self
+    }
+
+    /// Set the configuration for the parse as xml. Shorthand for
+    /// `set_output_format(OutputFormat::Xhtml)` or `set_output_format(OutputFormat::Text)`
+    pub fn set_xml_output(self, xml_output: bool) -> Self {
+        self.set_output_format(if xml_output {
+            OutputFormat::Xhtml
+        } else {
+            OutputFormat::Text
+        })
+    }
+
+    /// Same as [`Extractor::set_xml_output`], but changes this extractor in place
+    pub fn set_xml_output_mut(&mut self, xml_output: bool) -> &mut Self {
+        self.set_output_format_mut(if xml_output {
+            OutputFormat::Xhtml
+        } else {
+            OutputFormat::Text
+        })
+    }
+
+    /// Set the format of the extracted content
+    /// Default: OutputFormat::Text
+    pub fn set_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.set_output_format_mut(output_format);
+        self
+    }
+
+    /// Same as [`Extractor::set_output_format`], but changes this extractor in place
+    pub fn set_output_format_mut(&mut self, output_format: OutputFormat) -> &mut Self {
+        self.output_format = output_format;
+        self
+    }
+
+    /// Set the character separating the cells of the [`OutputFormat::Csv`] output, e.g. `;`
+    /// for the locales using the comma as decimal separator, or `\t`. The double quote and the
+    /// line breaks `\r` and `\n` cannot be used, extracting then fails with an
+    /// [`Error::ParseError`]
+    /// Default: ','
+    pub fn set_csv_delimiter(mut self, delimiter: char) -> Self {
+        self.set_csv_delimiter_mut(delimiter);
+        self
+    }
+
+    /// Same as [`Extractor::set_csv_delimiter`], but changes this extractor in place
+    pub fn set_csv_delimiter_mut(&mut self, delimiter: char) -> &mut Self {
+        self.csv_delimiter = delimiter;
+        self
+    }
+
+    /// Set how the charset of text based formats is resolved when the declared and the detected
+    /// charset differ, e.g. for scraped HTML with wrong `<meta>` declarations
+    /// Default: CharsetPolicy::PreferDeclared
+    pub fn set_charset_policy(mut self, policy: CharsetPolicy) -> Self {
+        self.set_charset_policy_mut(policy);
+        self
+    }
+
+    /// Same as [`Extractor::set_charset_policy`], but changes this extractor in place
+    pub fn set_charset_policy_mut(&mut self, policy: CharsetPolicy) -> &mut Self {
+        self.charset_policy = policy;
+        self
+    }
+
+    /// Set what the detection of the media type relies on, both when extracting and in
+    /// [`Extractor::explain`] and [`Extractor::media_type_hierarchy`]. Use
+    /// [`DetectionMode::ContentOnly`] to ignore unreliable file names.
+    /// Default: DetectionMode::ContentAndName
+    pub fn set_detection_mode(mut self, mode: DetectionMode) -> Self {
+        self.set_detection_mode_mut(mode);
+        self
+    }
+
+    /// Same as [`Extractor::set_detection_mode`], but changes this extractor in place
+    pub fn set_detection_mode_mut(&mut self, mode: DetectionMode) -> &mut Self {
+        self.detection_mode = mode;
+        self
+    }
+
+    /// Set how block boundaries and `<br>` elements are written in plain text output, e.g. to
+    /// get one line per paragraph by writing `<br>` as a space. Has no effect on the other
+    /// output formats.
+    /// Default: LineBreakPolicy::default(), single newlines
+    pub fn set_line_break_policy(mut self, policy: LineBreakPolicy) -> Self {
+        self.set_line_break_policy_mut(policy);
+        self
+    }
+
+    /// Same as [`Extractor::set_line_break_policy`], but changes this extractor in place
+    pub fn set_line_break_policy_mut(&mut self, policy: LineBreakPolicy) -> &mut Self {
+        self.line_break_policy = policy;
+        self
+    }
+
+    /// Only return the metadata whose keys are in one of the given namespaces, the namespace of
+    /// a key being the part before its first colon, e.g. `dc` for `dc:title` or `pdf` for
+    /// `pdf:docinfo:created`. Use [`METADATA_NO_NAMESPACE`] to select the keys without a
+    /// namespace. The metadata is filtered before it is passed from Java to Rust.
+    /// Not applied to the metadata of [`Extractor::extract_embedded`].
+    /// Default: None, all metadata is returned
+    pub fn set_metadata_namespaces(mut self, prefixes: Vec<String>) -> Self {
+        self.set_metadata_namespaces_mut(prefixes);
+        self
+    }
+
+    /// Same as [`Extractor::set_metadata_namespaces`], but changes this extractor in place
+    pub fn set_metadata_namespaces_mut(&mut self, prefixes: Vec<String>) -> &mut Self {
+        self.metadata_namespaces = Some(prefixes);
+        self
+    }
+
+    /// Only parse inputs detected as one of the given media types, e.g. `application/pdf`.
+    /// The media type is detected before parsing and any other type makes the extraction fail
+    /// with [`crate::Error::Rejected`] without running a parser. Types are compared without
+    /// their parameters and there is no matching of subtypes, so list every accepted type.
+    /// Only the input itself is checked, not the documents embedded in it.
+    /// Default: empty, all media types are accepted
+    pub fn set_accepted_media_types(mut self, types: Vec<String>) -> Self {
+        self.set_accepted_media_types_mut(types);
+        self
+    }
+
+    /// Same as [`Extractor::set_accepted_media_types`], but changes this extractor in place
+    pub fn set_accepted_media_types_mut(&mut self, types: Vec<String>) -> &mut Self {
+        self.accepted_media_types = types;
+        self
+    }
+
+    /// Disable parsers by their fully qualified java class names, e.g.
+    /// `org.apache.tika.parser.ocr.TesseractOCRParser` to never run Tesseract, to keep expensive
+    /// or risky parsers out of a deployment. The disabled parsers are removed from the parsers
+    /// Tika selects from, including the documents embedded in the input, so the types only they
+    /// support fail with [`crate::Error::NoParser`], or are handled by a more generic parser.
+    /// The parser classes are listed in the `X-Parsed-By` metadata of the extractions.
+    /// Default: empty, no parser is disabled
+    pub fn set_disabled_parsers(mut self, parsers: Vec<String>) -> Self {
+        self.set_disabled_parsers_mut(parsers);
+        self
+    }
+
+    /// Same as [`Extractor::set_disabled_parsers`], but changes this extractor in place
+    pub fn set_disabled_parsers_mut(&mut self, parsers: Vec<String>) -> &mut Self {
+        self.disabled_parsers = parsers;
+        self
+    }
+
+    /// Refuse to parse documents containing macros, a common malware vector: their extraction
+    /// fails with [`crate::Error::Rejected`] without running a parser. The check is the one of
+    /// [`Extractor::has_macros`], which is independent of
+    /// [`OfficeParserConfig::set_extract_macros`]. Only the input itself is checked, not the
+    /// documents embedded in it.
+    /// Default: false
+    pub fn set_reject_macro_documents(mut self, reject: bool) -> Self {
+        self.set_reject_macro_documents_mut(reject);
+        self
+    }
+
+    /// Same as [`Extractor::set_reject_macro_documents`], but changes this extractor in place
+    pub fn set_reject_macro_documents_mut(&mut self, reject: bool) -> &mut Self {
+        self.reject_macro_documents = reject;
+        self
+    }
+
+    /// Set the maximum number of embedded documents extracted by [`Extractor::extract_embedded`],
+    /// across all levels of nesting, to bound the work on archives with huge numbers of entries.
+    /// Once the limit is reached the remaining documents are skipped, the documents gathered so
+    /// far are still returned and the metadata of the container gets the
+    /// [`crate::EMBEDDED_RESOURCE_LIMIT_REACHED`] key set to `true`.
+    /// Default: None, no limit
+    pub fn set_max_embedded_resources(mut self, max_resources: usize) -> Self {
+        self.set_max_embedded_resources_mut(max_resources);
+        self
+    }
+
+    /// Same as [`Extractor::set_max_embedded_resources`], but changes this extractor in place
+    pub fn set_max_embedded_resources_mut(&mut self, max_resources: usize) -> &mut Self {
+        self.max_embedded_resources = Some(max_resources);
+        self
+    }
+
+    /// Set a callback receiving an estimate of the progress of each extraction, as a fraction
+    /// between 0.0 and 1.0, e.g. to update a progress bar during a long OCR job. The estimate is
+    /// best-effort: for paged documents such as PDFs it is the share of the pages already
+    /// parsed, OCR'd pages included, for other documents the share of the input read so far.
+    /// It is reported with 0.0 when the parse starts, whenever it grows by at least 1%, and
+    /// with 1.0 when the parse ends. For the streaming functions the parse, and so the callback,
+    /// runs on a background thread while the stream is read. A panic of the callback is ignored.
+    /// Default: None
+    pub fn set_progress_callback(mut self, callback: impl Fn(f32) + Send + Sync + 'static) -> Self {
+        self.set_progress_callback_mut(callback);
+        self
+    }
+
+    /// Same as [`Extractor::set_progress_callback`], but changes this extractor in place
+    pub fn set_progress_callback_mut(
+        &mut self,
+        callback: impl Fn(f32) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.progress_callback = Some(ProgressCallbackRef(Arc::new(callback)));
+        self
+    }
+
+    /// Set a provider resolving the passwords of encrypted documents, e.g. from a vault, instead
+    /// of a fixed password. It is called with the name and media type of each encrypted document
+    /// Tika can decrypt, embedded documents included: PDFs, encrypted Office documents and 7z
+    /// archives. Returning `None` falls back to the password of
+    /// [`crate::PdfParserConfig::set_password`] for PDFs. A document that cannot be decrypted
+    /// fails with [`crate::Error::EncryptedDocument`], which is also the case of encrypted ZIP
+    /// archives, which Tika cannot decrypt. For the streaming functions the parse, and so the
+    /// provider, runs on a background thread while the stream is read.
+    /// Default: None
+    pub fn set_password_provider(
+        mut self,
+        provider: impl Fn(&DocumentInfo) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.set_password_provider_mut(provider);
+        self
+    }
+
+    /// Same as [`Extractor::set_password_provider`], but changes this extractor in place
+    pub fn set_password_provider_mut(
+        &mut self,
+        provider: impl Fn(&DocumentInfo) -> Option<String> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.password_provider = Some(PasswordProviderRef(Arc::new(provider)));
         self
     }
 
     /// Extracts text from a file path. Returns a tuple with stream of the extracted text and metadata.
     /// the stream is decoded using the extractor's `encoding`
-    pub fn extract_file(&self, file_path: &str) -> ExtractResult<(StreamReader, Metadata)> {
-        tika::parse_file(
-            file_path,
-            &self.encoding,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+    pub fn extract_file(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(StreamReader<'static>, Metadata)> {
+        tika::parse_file(file_path, self)
     }
 
     /// Extracts text from a byte buffer. Returns a tuple with stream of the extracted text and metadata.
     /// the stream is decoded using the extractor's `encoding`
-    pub fn extract_bytes(&self, buffer: &[u8]) -> ExtractResult<(StreamReader, Metadata)> {
-        tika::parse_bytes(
-            buffer,
-            &self.encoding,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+    ///
+    /// The buffer is wrapped in a direct `ByteBuffer` and read in place, it is never copied to
+    /// the heap or to a JVM array. It can be a memory mapped file, such as a `memmap2::Mmap`
+    /// dereferenced to `&[u8]`. Tika reads the buffer while the stream is read, so the stream
+    /// borrows it. Use [`Extractor::extract_bytes_owned`] for a stream that outlives the buffer.
+    ///
+    /// ```compile_fail
+    /// let bytes = std::fs::read("README.md").unwrap();
+    /// let (mut reader, _metadata) = extractous::Extractor::new().extract_bytes(&bytes).unwrap();
+    /// drop(bytes); // the buffer must outlive the stream
+    /// std::io::copy(&mut reader, &mut std::io::sink()).unwrap();
+    /// ```
+    pub fn extract_bytes<'a>(
+        &self,
+        buffer: &'a [u8],
+    ) -> ExtractResult<(StreamReader<'a>, Metadata)> {
+        tika::parse_bytes(buffer, self)
+    }
+
+    /// Same as [`Extractor::extract_bytes`], but the returned stream keeps the buffer alive, so
+    /// it can outlive the caller, e.g. be stored or sent to another thread. The buffer is still
+    /// read in place, though converting a `Vec<u8>` into an `Arc<[u8]>` copies it once.
+    pub fn extract_bytes_owned(
+        &self,
+        buffer: impl Into<Arc<[u8]>>,
+    ) -> ExtractResult<(StreamReader<'static>, Metadata)> {
+        tika::parse_shared_bytes(buffer.into(), self)
+    }
+
+    /// Extracts text from a byte buffer like [`Extractor::extract_bytes`], seeding the metadata
+    /// with the given hints before parsing, as Tika's `Metadata` API allows. Typical hints are
+    /// `resourceName`, `Content-Type`, `Content-Length` and `Content-Encoding`, which guide the
+    /// detection of the media type and charset. The returned metadata holds both the hints and
+    /// what the parsers found, the values found by the parsers taking precedence.
+    pub fn extract_bytes_with_input_metadata<'a>(
+        &self,
+        buffer: &'a [u8],
+        hints: HashMap<String, String>,
+    ) -> ExtractResult<(StreamReader<'a>, Metadata)> {
+        let mut extractor = self.clone();
+        extractor.metadata_hints = hints;
+        tika::parse_bytes(buffer, &extractor)
+    }
+
+    /// Same as [`Extractor::extract_bytes_with_input_metadata`], but the returned stream keeps
+    /// the buffer alive, like [`Extractor::extract_bytes_owned`]
+    pub fn extract_bytes_owned_with_input_metadata(
+        &self,
+        buffer: impl Into<Arc<[u8]>>,
+        hints: HashMap<String, String>,
+    ) -> ExtractResult<(StreamReader<'static>, Metadata)> {
+        let mut extractor = self.clone();
+        extractor.metadata_hints = hints;
+        tika::parse_shared_bytes(buffer.into(), &extractor)
+    }
+
+    /// Extracts text from a reader, such as a large file or a network stream, without reading it
+    /// into memory first. Returns a tuple with stream of the extracted text and metadata. The
+    /// stream is decoded using the extractor's `encoding`.
+    ///
+    /// Tika reads the input chunk by chunk while the returned stream is read, so the reader is
+    /// kept alive by the stream and read from another thread. Formats that need random access,
+    /// such as zip based formats or PDF, are spooled to a temporary file by Tika first.
+    pub fn extract_reader(
+        &self,
+        reader: impl Read + Send + 'static,
+    ) -> ExtractResult<(StreamReader<'static>, Metadata)> {
+        tika::parse_reader(Box::new(reader), self)
     }
 
     /// Extracts text from an url. Returns a tuple with stream of the extracted text and metadata.
-    /// the stream is decoded using the extractor's `encoding`
-    pub fn extract_url(&self, url: &str) -> ExtractResult<(StreamReader, Metadata)> {
-        tika::parse_url(
-            url,
-            &self.encoding,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+    /// the stream is decoded using the extractor's `encoding`. Http and https urls are fetched
+    /// with the extractor's `http_config`
+    pub fn extract_url(&self, url: &str) -> ExtractResult<(StreamReader<'static>, Metadata)> {
+        tika::parse_url(url, self)
     }
 
     /// Extracts text from a file path. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and metadata.
     pub fn extract_file_to_string(&self, file_path: &str) -> ExtractResult<(String, Metadata)> {
-        tika::parse_file_to_string(
-            file_path,
-            self.extract_string_max_length,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+        tika::parse_file_to_string(file_path, self)
+    }
+
+    /// Extracts text from a file path and writes it to `writer` as it is produced, so the text
+    /// is never held in memory whatever its size. The text is read in chunks the size of the
+    /// buffer of the underlying stream, with a single buffer reused for all of them. Returns a
+    /// tuple with the number of bytes written and metadata. The text is encoded using the
+    /// extractor's `encoding`. The writer is not flushed.
+    pub fn extract_file_to_writer(
+        &self,
+        file_path: &str,
+        writer: &mut impl Write,
+    ) -> ExtractResult<(u64, Metadata)> {
+        let (reader, metadata) = self.extract_file(file_path)?;
+        let written = copy_to_writer(reader, writer).map_err(|e| Error::IoError(e.to_string()))?;
+        Ok((written, metadata))
     }
 
     /// Extracts text from a byte buffer. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and metadata.
+    ///
+    /// The buffer is wrapped in a direct `ByteBuffer` and read in place, it is never copied to
+    /// the heap or to a JVM array. It can be a memory mapped file, such as a `memmap2::Mmap`
+    /// dereferenced to `&[u8]`. The text is fully extracted before returning, so the mapping
+    /// only has to outlive this call.
     pub fn extract_bytes_to_string(&self, buffer: &[u8]) -> ExtractResult<(String, Metadata)> {
-        tika::parse_bytes_to_string(
-            buffer,
-            self.extract_string_max_length,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+        tika::parse_bytes_to_string(buffer, self)
+    }
+
+    /// Extracts everything about a file in a single parse: the text, of maximum length of the
+    /// extractor's `extract_string_max_length`, the metadata, the media type and the language
+    /// of the text, detected with Tika's Optimaize language detector.
+    pub fn extract_file_full(&self, file_path: &str) -> ExtractResult<ExtractionResult> {
+        let (content, metadata) =
+            tika::parse_file_to_string(file_path, &self.detecting_language())?;
+        Ok(ExtractionResult::from_parts(content, metadata))
+    }
+
+    /// Same as [`Extractor::extract_file_full`], but extracts from a byte buffer
+    pub fn extract_bytes_full(&self, buffer: &[u8]) -> ExtractResult<ExtractionResult> {
+        let (content, metadata) = tika::parse_bytes_to_string(buffer, &self.detecting_language())?;
+        Ok(ExtractionResult::from_parts(content, metadata))
+    }
+
+    /// Same as [`Extractor::extract_file_full`], but extracts from a URL
+    pub fn extract_url_full(&self, url: &str) -> ExtractResult<ExtractionResult> {
+        let (content, metadata) = tika::parse_url_to_string(url, &self.detecting_language())?;
+        Ok(ExtractionResult::from_parts(content, metadata))
+    }
+
+    /// Detects the language of a text with Tika's Optimaize language detector, e.g. to route the
+    /// documents of a multilingual corpus to the right analyzers. Only the first 10000
+    /// characters are looked at. Returns None if the text is blank or its language cannot be
+    /// told with reasonable certainty.
+    pub fn detect_language(&self, text: &str) -> ExtractResult<Option<DetectedLanguage>> {
+        tika::detect_language(text)
+    }
+
+    /// Returns a copy of this extractor that detects the language of the extracted text
+    fn detecting_language(&self) -> Self {
+        let mut extractor = self.clone();
+        extractor.detect_language = true;
+        extractor
+    }
+
+    /// Extracts text from a byte buffer like [`Extractor::extract_bytes_to_string`] and scores
+    /// the quality of the result. See [`QualityScore`]
+    pub fn extract_bytes_with_quality(
+        &self,
+        buffer: &[u8],
+    ) -> ExtractResult<(String, Metadata, QualityScore)> {
+        let (text, metadata) = self.extract_bytes_to_string(buffer)?;
+        let quality = QualityScore::compute(&text, &metadata, buffer.len());
+        Ok((text, metadata, quality))
+    }
+
+    /// Extracts text from a byte buffer into a new temporary file. The text is streamed to the
+    /// file, so it is never held in memory whatever its size. Returns a tuple with the path of
+    /// the file and metadata. The file is encoded using the extractor's `encoding`.
+    ///
+    /// The caller owns the file and is responsible for deleting it. If the extraction fails
+    /// while writing, the file is removed.
+    pub fn extract_to_temp_file(&self, bytes: &[u8]) -> ExtractResult<(PathBuf, Metadata)> {
+        let (reader, metadata) = self.extract_bytes(bytes)?;
+        let (mut file, path) = create_temp_file(match self.output_format {
+            OutputFormat::Text => "txt",
+            OutputFormat::Xhtml => "xhtml",
+            OutputFormat::Html => "html",
+            OutputFormat::Markdown => "md",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Csv => "csv",
+        })?;
+        if let Err(e) = copy_to_writer(reader, &mut file).and_then(|_| file.flush()) {
+            drop(file);
+            fs::remove_file(&path).ok();
+            return Err(Error::IoError(e.to_string()));
+        }
+        Ok((path, metadata))
+    }
+
+    /// Extracts text from a byte buffer as a lazy iterator over its lines, without their line
+    /// endings. The text is read from the parse as the iterator advances, so memory stays bounded
+    /// whatever the size of the document. The text is always decoded as UTF-8, whatever the
+    /// extractor's `encoding`.
+    /// A read error in the middle of the text is yielded as an `Err` item, which ends the
+    /// iteration. The parse reads `bytes` in place while the iterator advances, so the iterator
+    /// borrows them.
+    ///
+    /// ```compile_fail
+    /// let bytes = b"first\nsecond".to_vec();
+    /// let lines = extractous::Extractor::new().extract_lines(&bytes).unwrap();
+    /// drop(bytes); // the input must outlive the iterator
+    /// lines.for_each(drop);
+    /// ```
+    pub fn extract_lines<'a>(
+        &self,
+        bytes: &'a [u8],
+    ) -> ExtractResult<impl Iterator<Item = ExtractResult<String>> + 'a> {
+        let extractor = self.clone().set_encoding(CharSet::UTF_8);
+        let (reader, _metadata) = extractor.extract_bytes(bytes)?;
+        let mut lines = BufReader::new(reader).lines();
+        let mut failed = false;
+        Ok(iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let line = lines.next()?;
+            failed = line.is_err();
+            Some(line.map_err(|e| Error::IoError(e.to_string())))
+        }))
+    }
+
+    /// Extracts text from a byte buffer and sends it to `tx` in chunks as it is produced, so a
+    /// consumer on another thread can process the text while the extraction continues. Chunks
+    /// are at most [`crate::DEFAULT_BUF_SIZE`] bytes of UTF-8 text, whatever the extractor's
+    /// `encoding`, and never split a character. A read error in the middle of the text is sent
+    /// as a final `Err` chunk. The channel is closed by dropping `tx` when this returns.
+    ///
+    /// Blocks until the text is fully sent, or until the receiver is dropped, which stops the
+    /// extraction early. Returns an error, without sending anything, if the extraction cannot
+    /// start.
+    pub fn extract_to_channel(
+        &self,
+        bytes: &[u8],
+        tx: Sender<ExtractResult<String>>,
+    ) -> ExtractResult<()> {
+        let extractor = self.clone().set_encoding(CharSet::UTF_8);
+        let (mut reader, _metadata) = extractor.extract_bytes(bytes)?;
+
+        let mut buf = vec![0u8; DEFAULT_BUF_SIZE];
+        let mut incomplete = Vec::new();
+        loop {
+            let num_read = match reader.read(&mut buf) {
+                Ok(num_read) => num_read,
+                Err(e) => {
+                    tx.send(Err(Error::IoError(e.to_string()))).ok();
+                    return Ok(());
+                }
+            };
+            if num_read == 0 {
+                // Bytes left at the end are a truncated character
+                if let Err(e) = std::str::from_utf8(&incomplete) {
+                    tx.send(Err(e.into())).ok();
+                }
+                return Ok(());
+            }
+
+            incomplete.extend_from_slice(&buf[..num_read]);
+            let valid_up_to = match std::str::from_utf8(&incomplete) {
+                Ok(_) => incomplete.len(),
+                // An error_len means invalid bytes rather than a character cut by the buffer
+                Err(e) if e.error_len().is_some() => {
+                    tx.send(Err(e.into())).ok();
+                    return Ok(());
+                }
+                Err(e) => e.valid_up_to(),
+            };
+            if valid_up_to == 0 {
+                continue;
+            }
+            let valid: Vec<u8> = incomplete.drain(..valid_up_to).collect();
+            let chunk = String::from_utf8(valid).map_err(|e| Error::from(e.utf8_error()));
+            // A dropped receiver stops the extraction
+            if tx.send(chunk).is_err() {
+                return Ok(());
+            }
+        }
     }
 
     /// Extracts text from a URL. Returns a tuple with string that is of maximum length
-    /// of the extractor's `extract_string_max_length` and metadata.
+    /// of the extractor's `extract_string_max_length` and metadata. Http and https urls are
+    /// fetched with the extractor's `http_config`
     pub fn extract_url_to_string(&self, url: &str) -> ExtractResult<(String, Metadata)> {
-        tika::parse_url_to_string(
-            url,
-            self.extract_string_max_length,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+        tika::parse_url_to_string(url, self)
+    }
+
+    /// Extracts the text of many files, by up to `concurrency` worker threads sharing the same
+    /// VM, at least one. Returns an iterator over the results as the files complete, which is not
+    /// the order of `paths`: each file with its text, of maximum length
+    /// `extract_string_max_length`, and metadata, or the error it failed with. The workers are
+    /// attached to the VM once, for the whole batch, and only get ahead of the iterator by a few
+    /// results. Dropping the iterator stops the batch once the files being extracted complete.
+    pub fn extract_batch(
+        &self,
+        paths: Vec<PathBuf>,
+        concurrency: usize,
+    ) -> impl Iterator<Item = (PathBuf, ExtractResult<(String, Metadata)>)> {
+        let concurrency = concurrency.clamp(1, paths.len().max(1));
+        let paths = Arc::new(paths);
+        let next = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = mpsc::sync_channel(concurrency);
+        for _ in 0..concurrency {
+            let extractor = self.clone();
+            let paths = paths.clone();
+            let next = next.clone();
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                // The thread is detached when it exits. If attaching fails here, every call
+                // attaches the thread for itself
+                tika::vm().attach_current_thread_permanently().ok();
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(path) = paths.get(index) else {
+                        break;
+                    };
+                    let result =
+                        path_to_str(path).and_then(|path| extractor.extract_file_to_string(path));
+                    // A dropped iterator stops the batch
+                    if tx.send((path.clone(), result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        rx.into_iter()
+    }
+
+    /// Reads the metadata of many files, e.g. to catalog a document collection, without
+    /// extracting their text. The content is discarded as it is parsed, OCR is skipped and the
+    /// embedded documents are not parsed, which makes it much faster than a full extraction.
+    /// The files are parsed by up to `concurrency` threads sharing the same VM, at least one.
+    /// Returns the metadata of each file, or the error it failed with, in the order of `paths`.
+    /// The progress callback, if any, is not called.
+    pub fn catalog(
+        &self,
+        paths: Vec<PathBuf>,
+        concurrency: usize,
+    ) -> Vec<(PathBuf, ExtractResult<Metadata>)> {
+        let mut extractor = self.clone();
+        extractor.pdf_config = extractor
+            .pdf_config
+            .set_ocr_strategy(PdfOcrStrategy::NO_OCR)
+            .set_extract_inline_images(false);
+        extractor.progress_callback = None;
+
+        let next = AtomicUsize::new(0);
+        let results: Vec<Mutex<Option<ExtractResult<Metadata>>>> =
+            paths.iter().map(|_| Mutex::new(None)).collect();
+        std::thread::scope(|scope| {
+            for _ in 0..concurrency.clamp(1, paths.len().max(1)) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(path) = paths.get(index) else {
+                        break;
+                    };
+                    let result = path_to_str(path)
+                        .and_then(|path| tika::parse_file_to_metadata(path, &extractor));
+                    if let Ok(mut slot) = results[index].lock() {
+                        *slot = Some(result);
+                    }
+                });
+            }
+        });
+
+        paths
+            .into_iter()
+            .zip(results)
+            .map(|(path, result)| {
+                let result = result.into_inner().ok().flatten().unwrap_or_else(|| {
+                    Err(Error::Unknown("The file was not cataloged".to_string()))
+                });
+                (path, result)
+            })
+            .collect()
+    }
+
+    /// Extracts the text of a byte buffer in bounded segments of at most
+    /// `extract_string_max_length` characters. Pass `None` to get the first segment, then the
+    /// returned cursor to get the next one, until the returned cursor is `None` at the end of
+    /// the text. The parse is kept alive between calls, so the document is parsed only once.
+    /// A cursor re-created with [`Cursor::from_offset`], for example after a restart, works as
+    /// well but re-parses the document up to its offset. The text is always UTF-8.
+    pub fn extract_segment(
+        &self,
+        buffer: &[u8],
+        cursor: Option<Cursor>,
+    ) -> ExtractResult<(String, Option<Cursor>)> {
+        let extractor = self.clone().set_encoding(CharSet::UTF_8);
+        let max_chars = self.extract_string_max_length.max(1) as usize;
+        segment::extract_segment(&extractor, buffer, cursor, max_chars)
+    }
+
+    /// Extracts text from a byte buffer and, recursively, from all the documents embedded in it,
+    /// such as email attachments, files inside archives or images inside office documents.
+    /// Compressed inputs, such as a `tar.gz`, are decompressed and archive entries that are
+    /// archives themselves are extracted too.
+    /// Returns one [`EmbeddedDocument`] per document, the container first, each with its own
+    /// content and metadata. The maximum length of the extracted text applies to each document
+    /// separately: a document reaching it is truncated, and the documents after it still get
+    /// their own budget.
+    pub fn extract_embedded(&self, buffer: &[u8]) -> ExtractResult<Vec<EmbeddedDocument>> {
+        let metadata_list = tika::parse_bytes_recursive(buffer, self, None)?;
+        Ok(metadata_list
+            .into_iter()
+            .map(EmbeddedDocument::from_metadata)
+            .collect())
+    }
+
+    /// Same as [`Extractor::extract_embedded`], but extracts from a file. The container is
+    /// read from disk rather than from memory, which suits large archives and mailboxes.
+    pub fn extract_file_embedded(&self, file_path: &str) -> ExtractResult<Vec<EmbeddedDocument>> {
+        let metadata_list = tika::parse_file_recursive(file_path, self, None)?;
+        Ok(metadata_list
+            .into_iter()
+            .map(EmbeddedDocument::from_metadata)
+            .collect())
     }
 
+    /// Same as [`Extractor::extract_embedded`], but calls `filter` with what is known about each
+    /// embedded document as it is discovered: its name, media type and size, when available.
+    /// Returning `false` skips the document, along with everything embedded in it, before any
+    /// time is spent on it, and it is left out of the result.
+    /// The filter runs on the parsing thread, hence the `Send + 'static` requirement; share
+    /// state with it through an `Arc` if needed.
+    pub fn extract_embedded_filtered(
+        &self,
+        buffer: &[u8],
+        filter: impl FnMut(&EmbeddedMeta) -> bool + Send + 'static,
+    ) -> ExtractResult<Vec<EmbeddedDocument>> {
+        let metadata_list = tika::parse_bytes_recursive(buffer, self, Some(Box::new(filter)))?;
+        Ok(metadata_list
+            .into_iter()
+            .map(EmbeddedDocument::from_metadata)
+            .collect())
+    }
+
+    /// Lists the documents embedded directly in a file, such as PDF attachments, OLE objects,
+    /// email attachments or archive entries, with their name, media type and size when known,
+    /// to decide what to extract before paying for it. The embedded documents are discovered
+    /// while the container is parsed but are neither parsed nor extracted themselves, so the
+    /// documents nested in them are not listed.
+    pub fn list_embedded(&self, file_path: &str) -> ExtractResult<Vec<EmbeddedMeta>> {
+        let listed = Arc::new(Mutex::new(Vec::new()));
+        let listed_by_filter = Arc::clone(&listed);
+        let filter = move |meta: &EmbeddedMeta| {
+            if let Ok(mut listed) = listed_by_filter.lock() {
+                listed.push(meta.clone());
+            }
+            false
+        };
+
+        // The text of the container is not needed
+        let mut extractor = self.clone();
+        extractor.extract_string_max_length = 0;
+        tika::parse_file_recursive(file_path, &extractor, Some(Box::new(filter)))?;
+
+        let listed = listed
+            .lock()
+            .map(|listed| listed.clone())
+            .unwrap_or_default();
+        Ok(listed)
+    }
+
+    /// Unpacks the documents embedded in a byte buffer, like the unpacker resource of Tika
+    /// server: `callback` receives the raw bytes of each attachment, archive entry or image,
+    /// with its path, name and detected media type, see [`EmbeddedFile`]. The embedded documents
+    /// are parsed in turn, so the documents embedded in them are unpacked too, after their
+    /// container. Returns the metadata of the container.
+    /// The callback runs on the parsing thread, hence the `Send + 'static` requirement; share
+    /// state with it through an `Arc` if needed. An error returned by the callback, or a panic,
+    /// stops the unpacking and is returned as an [`Error::IoError`].
+    pub fn unpack_embedded(
+        &self,
+        buffer: &[u8],
+        callback: impl FnMut(EmbeddedFile) -> io::Result<()> + Send + 'static,
+    ) -> ExtractResult<Metadata> {
+        tika::parse_bytes_to_unpacker(buffer, self, Box::new(callback))
+    }
+
+    /// Same as [`Extractor::unpack_embedded`], but writes each embedded document to a file in
+    /// `dir`, which must exist. The files are named after the documents, without the characters
+    /// not allowed in file names, and never overwrite an existing file: a `-1`, `-2`, ... suffix
+    /// is added to the names already taken. Returns the paths of the written files, in the order
+    /// the documents were unpacked.
+    pub fn unpack_embedded_to_dir(
+        &self,
+        buffer: &[u8],
+        dir: impl AsRef<Path>,
+    ) -> ExtractResult<Vec<PathBuf>> {
+        let dir = dir.as_ref().to_path_buf();
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let written_by_callback = Arc::clone(&written);
+        let callback = move |file: EmbeddedFile| {
+            let path = write_new_file(&dir, &file.file_name(), &file.data)?;
+            if let Ok(mut written) = written_by_callback.lock() {
+                written.push(path);
+            }
+            Ok(())
+        };
+        tika::parse_bytes_to_unpacker(buffer, self, Box::new(callback))?;
+
+        let written = written
+            .lock()
+            .map(|written| written.clone())
+            .unwrap_or_default();
+        Ok(written)
+    }
+
+    /// Extracts the e-mail addresses, phone numbers and URLs of a document, in the same parse as
+    /// its text, with Tika's `PhoneExtractingContentHandler` and `LinkContentHandler` and
+    /// patterns for the addresses and URLs written in the text. The entities are normalized and
+    /// de-duplicated, see [`Entities`]. The maximum length of the extracted text does not apply.
+    pub fn extract_entities(&self, buffer: &[u8]) -> ExtractResult<Entities> {
+        let metadata = tika::parse_bytes_to_entities(buffer, self)?;
+        Ok(Entities::from_metadata(metadata))
+    }
+
+    /// Parses a byte buffer and passes the events of the XHTML document Tika produces to
+    /// `handler` as they happen, instead of building the text: elements starting, text and
+    /// elements ending, see [`SaxEvent`]. Useful to build custom output without going through an
+    /// intermediate string. Returns the metadata of the document.
+    /// The maximum length of the extracted text and the output format do not apply.
+    /// The handler runs on the parsing thread, hence the `Send + 'static` requirement; share
+    /// state with it through an `Arc` if needed. A panic of the handler stops the parse with a
+    /// [`Error::ParseError`].
+    pub fn extract_bytes_to_handler(
+        &self,
+        buffer: &[u8],
+        handler: impl FnMut(SaxEvent) + Send + 'static,
+    ) -> ExtractResult<Metadata> {
+        tika::parse_bytes_to_handler(buffer, self, Box::new(handler))
+    }
+
+    /// Returns the images of a document with, for PDFs, the page each image is drawn on and
+    /// its bounding box. The images of a PDF are found in the page content, independently of
+    /// the inline image settings of the PDF config, JPEG images are returned as stored and the
+    /// others as PNG. For other formats, such as Office documents, the embedded image files are
+    /// returned, recursively, without page or bounding box.
+    pub fn extract_images(&self, buffer: &[u8]) -> ExtractResult<Vec<ImageRef>> {
+        tika::extract_images(buffer)
+    }
+
+    /// Returns a copy of this extractor that outputs the whole XHTML representation, whatever
+    /// its `extract_string_max_length`, for the functions reading the structure of a document
+    fn reading_whole_xhtml(&self) -> Self {
+        self.clone()
+            .set_xml_output(true)
+            .set_extract_string_max_length(-1)
+    }
+
+    /// Extracts the outline of a document from the `<h1>`-`<h6>` headings of its XHTML
+    /// representation. Works uniformly for formats such as DOCX, HTML, ODT and EPUB.
+    /// Each heading carries the character offset where it occurs in the plain text body.
+    /// Documents without headings return an empty vec. The whole document is read, the
+    /// extractor's `extract_string_max_length` does not apply.
+    pub fn extract_outline(&self, buffer: &[u8]) -> ExtractResult<Vec<Heading>> {
+        let extractor = self.reading_whole_xhtml();
+        let (xhtml, _metadata) = tika::parse_bytes_to_string(buffer, &extractor)?;
+        Ok(structure::outline_from_xhtml(&xhtml))
+    }
+
+    /// Extracts the paragraphs of a document, classified by the block element of its XHTML
+    /// representation they belong to: headings, list items, quotations or normal paragraphs.
+    /// Works uniformly for formats such as DOCX, HTML, ODT and EPUB. Empty paragraphs are
+    /// dropped. The whole document is read, the extractor's `extract_string_max_length` does
+    /// not apply.
+    pub fn extract_paragraphs(&self, buffer: &[u8]) -> ExtractResult<Vec<Paragraph>> {
+        let extractor = self.reading_whole_xhtml();
+        let (xhtml, _metadata) = tika::parse_bytes_to_string(buffer, &extractor)?;
+        Ok(structure::paragraphs_from_xhtml(&xhtml))
+    }
+
+    /// Decodes a base64 string and extracts text from the decoded bytes. Whitespace, such as the
+    /// line breaks of wrapped base64, is ignored. Invalid base64 returns an error without
+    /// starting an extraction.
+    pub fn extract_base64(&self, b64: &str) -> ExtractResult<(String, Metadata)> {
+        let bytes = data_uri::decode_base64(b64)?;
+        tika::parse_bytes_to_string(&bytes, self)
+    }
+
+    /// Decodes a `data:` URI, e.g. `data:application/pdf;base64,JVBERi0...`, and extracts text
+    /// from its data. The media type declared by the URI is used as a hint to detect the type
+    /// of the data. An invalid URI returns an error without starting an extraction.
+    pub fn extract_data_uri(&self, uri: &str) -> ExtractResult<(String, Metadata)> {
+        let (media_type, bytes) = data_uri::decode_data_uri(uri)?;
+        let mut extractor = self.clone();
+        extractor.content_type_hint = media_type;
+        tika::parse_bytes_to_string(&bytes, &extractor)
+    }
+
+    /// Extracts the tables of a document as a header row and rows of cells, from the `<table>`
+    /// structure of its XHTML representation instead of the flattened text. Works for formats
+    /// such as DOCX, HTML, XLSX and PDF with marked content. The header row is only known for
+    /// the formats marking it, such as HTML. Merged cells have their text repeated in every
+    /// column and row they span, see [`Table`]. Documents without tables return an empty vec.
+    /// The whole document is read, the extractor's `extract_string_max_length` does not apply.
+    pub fn extract_tables(&self, buffer: &[u8]) -> ExtractResult<Vec<Table>> {
+        let extractor = self.reading_whole_xhtml();
+        let (xhtml, _metadata) = tika::parse_bytes_to_string(buffer, &extractor)?;
+        Ok(tables::tables_from_xhtml(&xhtml))
+    }
+
+    /// Extracts the sheets of a spreadsheet with their name and their rows of cells, from the
+    /// structure of its XHTML representation instead of the tab separated text, so that the
+    /// sheet names and the cell boundaries are kept. Works for formats such as XLSX, XLS and
+    /// ODS. The cells hold the text of the values as formatted in the sheet, see [`Sheet`].
+    /// Documents without sheets, such as a text document with tables, return their tables as
+    /// a single sheet without a name, or an empty vec if they have no tables either. The whole
+    /// document is read, the extractor's `extract_string_max_length` does not apply.
+    pub fn extract_sheets(&self, buffer: &[u8]) -> ExtractResult<Vec<Sheet>> {
+        let extractor = self.reading_whole_xhtml();
+        let (xhtml, _metadata) = tika::parse_bytes_to_string(buffer, &extractor)?;
+        Ok(structure::sheets_from_xhtml(&xhtml))
+    }
+
+    /// Extracts the text of the pages of a document that match the predicate, skipping the
+    /// others. The pages are those of a PDF, the sheets of a spreadsheet and the slides of a
+    /// presentation, read from the structure of its XHTML representation; other documents are a
+    /// single page. The predicate gets the index of each page and a preview of its text, see
+    /// [`PageMeta`], e.g. to keep the pages starting with a given header or the non-empty sheets.
+    /// The whole document is still parsed, whatever the extractor's `extract_string_max_length`,
+    /// only the pages returned are filtered.
+    pub fn extract_pages_where(
+        &self,
+        buffer: &[u8],
+        predicate: impl Fn(&PageMeta) -> bool,
+    ) -> ExtractResult<Vec<Page>> {
+        let extractor = self.reading_whole_xhtml();
+        let (xhtml, _metadata) = tika::parse_bytes_to_string(buffer, &extractor)?;
+        Ok(structure::pages_from_xhtml(&xhtml)
+            .into_iter()
+            .filter(|page| predicate(&page.meta()))
+            .collect())
+    }
+
+    /// Extracts the text of a PDF page by page, with the number of each page, e.g. to cite the
+    /// page a passage comes from. Returns an iterator yielding each page as soon as it is
+    /// parsed, see [`PdfPages`], instead of waiting for the whole document: the pages are read
+    /// from the page boundaries the PDF parser reports, which the flat text does not keep. The
+    /// text of the documents embedded in the PDF is not part of any page, and documents of
+    /// other types yield no pages. The maximum length of the extracted text and the output
+    /// format do not apply.
+    /// Errors, such as a missing file, are returned by the iterator.
+    ///
+    /// ```no_run
+    /// use extractous::Extractor;
+    ///
+    /// for page in Extractor::new().extract_pdf_pages("report.pdf") {
+    ///     let page = page.unwrap();
+    ///     println!("page {}: {} characters", page.page_number, page.text.len());
+    /// }
+    /// ```
+    pub fn extract_pdf_pages(&self, file_path: &str) -> PdfPages {
+        PdfPages::start(self, file_path)
+    }
+
+    /// Extracts text from a file path using per-call overrides layered over the extractor's
+    /// configuration, e.g. another PDF or OCR configuration for this file only. The extractor
+    /// itself is left untouched, so it is safe to use this while the same extractor is shared
+    /// between threads.
+    /// Returns a tuple with stream of the extracted text and metadata.
+    pub fn extract_file_with_config(
+        &self,
+        file_path: &str,
+        overrides: ExtractOverrides,
+    ) -> ExtractResult<(StreamReader<'static>, Metadata)> {
+        self.with_overrides(&overrides).extract_file(file_path)
+    }
+
+    /// Extracts text from a file path using per-call overrides layered over the extractor's
+    /// configuration. Returns a tuple with string that is of maximum length of the overridden or
+    /// the extractor's `extract_string_max_length` and metadata.
+    pub fn extract_file_to_string_with_config(
+        &self,
+        file_path: &str,
+        overrides: ExtractOverrides,
+    ) -> ExtractResult<(String, Metadata)> {
+        self.with_overrides(&overrides)
+            .extract_file_to_string(file_path)
+    }
+
+    /// Extracts text from a byte buffer using per-call overrides layered over the extractor's
+    /// configuration. The extractor itself is left untouched, so it is safe to use this while the
+    /// same extractor is shared between threads.
+    /// Returns a tuple with stream of the extracted text and metadata.
+    pub fn extract_bytes_with_config<'a>(
+        &self,
+        buffer: &'a [u8],
+        overrides: ExtractOverrides,
+    ) -> ExtractResult<(StreamReader<'a>, Metadata)> {
+        self.with_overrides(&overrides).extract_bytes(buffer)
+    }
+
+    /// Extracts text from a byte buffer using per-call overrides layered over the extractor's
+    /// configuration. Returns a tuple with string that is of maximum length of the overridden or
+    /// the extractor's `extract_string_max_length` and metadata.
+    pub fn extract_bytes_to_string_with_config(
+        &self,
+        buffer: &[u8],
+        overrides: ExtractOverrides,
+    ) -> ExtractResult<(String, Metadata)> {
+        self.with_overrides(&overrides)
+            .extract_bytes_to_string(buffer)
+    }
+
+    /// Returns a copy of this extractor with the given overrides applied
+    fn with_overrides(&self, overrides: &ExtractOverrides) -> Self {
+        let mut extractor = self.clone();
+        // The whole configs first, so the single settings apply over them
+        if let Some(pdf_config) = &overrides.pdf_config {
+            extractor.pdf_config = pdf_config.clone();
+        }
+        if let Some(office_config) = &overrides.office_config {
+            extractor.office_config = office_config.clone();
+        }
+        #[cfg(feature = "ocr")]
+        if let Some(ocr_config) = &overrides.ocr_config {
+            extractor.ocr_config = ocr_config.clone();
+        }
+        if let Some(ocr_strategy) = overrides.ocr_strategy {
+            extractor.pdf_config.ocr_strategy = ocr_strategy;
+        }
+        if let Some(max_length) = overrides.extract_string_max_length {
+            extractor.extract_string_max_length = max_length;
+        }
+        #[cfg(feature = "ocr")]
+        if let Some(timeout_seconds) = overrides.ocr_timeout_seconds {
+            extractor.ocr_config.timeout_seconds = timeout_seconds;
+        }
+        extractor
+    }
+
+    /// Reports what extracting a byte buffer would do, without extracting: the detected media
+    /// type, the parser Tika would select, whether OCR would run and the effective configuration.
+    /// Only detection and parser selection run, so this is fast and side effect free.
+    pub fn explain(&self, buffer: &[u8]) -> ExtractResult<ExtractionPlan> {
+        tika::explain(buffer, self)
+    }
+
+    /// Cheaply checks the container structure of a byte buffer before committing to a full parse:
+    /// the trailer of a PDF, the central directory of a zip based format such as docx or xlsx,
+    /// the chunks of a PNG and the markers of a JPEG. The content itself is not parsed, and
+    /// formats without a known structure are considered valid.
+    /// Returns [`crate::Error::ParseError`] describing the problem if the input is truncated
+    /// or corrupt.
+    pub fn validate(&self, buffer: &[u8]) -> ExtractResult<()> {
+        validate::validate_structure(buffer)
+    }
+
+    /// Detects the media type of a file from its content and its name, with the extractor's
+    /// detection mode, and rates the confidence of the detection. Only Tika's detector runs, the
+    /// content is not parsed, so this is cheap enough to route files before extracting them.
+    /// Returns [`crate::Error::IoError`] if the file cannot be read.
+    pub fn detect_file(&self, file_path: &str) -> ExtractResult<Detection> {
+        tika::detect_file(file_path, self)
+    }
+
+    /// Detects the media type of a byte buffer and rates the confidence of the detection, see
+    /// [`Extractor::detect_file`]. Bytes have no name, so the detection relies on the content
+    /// and the `Content-Type` hint, if any.
+    pub fn detect_bytes(&self, buffer: &[u8]) -> ExtractResult<Detection> {
+        tika::detect_bytes(buffer, self)
+    }
+
+    /// Detects the media type of a byte buffer and returns its type hierarchy, ordered from the
+    /// most specific type to the most generic one. For example a docx file returns
+    /// `application/vnd.openxmlformats-officedocument.wordprocessingml.document`,
+    /// `application/x-tika-ooxml`, `application/zip` and finally `application/octet-stream`.
+    pub fn media_type_hierarchy(&self, buffer: &[u8]) -> ExtractResult<Vec<String>> {
+        tika::media_type_hierarchy(buffer, self)
+    }
+
+    /// Renders a page of a PDF to a PNG image, for thumbnails or vision models, without OCR.
+    /// Pages are numbered from 1 and `dpi` sets the resolution of the image, 72 rendering the
+    /// page at its natural size.
+    /// Returns [`crate::Error::IoError`] if the page is out of range or the PDF cannot be loaded.
+    pub fn render_pdf_page(&self, bytes: &[u8], page: u32, dpi: u32) -> ExtractResult<Vec<u8>> {
+        tika::render_pdf_page(bytes, page, dpi)
+    }
+
+    /// Extracts the text of a PDF with anchors that map ranges of the text back to the page and
+    /// the box they are drawn in, e.g. to draw highlights over the matches of a search on the
+    /// rendered PDF. `granularity` sets whether each anchor covers a word or a line. The text is
+    /// extracted with PDFBox sorted by position, independently of the other settings of the
+    /// extractor, so it can differ from the text of the other extract functions.
+    /// Returns [`crate::Error::IoError`] if the PDF cannot be loaded.
+    pub fn extract_pdf_with_anchors(
+        &self,
+        bytes: &[u8],
+        granularity: AnchorGranularity,
+    ) -> ExtractResult<(String, Vec<TextAnchor>)> {
+        tika::extract_text_anchors(bytes, granularity)
+    }
+
+    /// Lists the annotations of a PDF, such as highlights, notes and stamps, with their type,
+    /// author, text, page and position, in page order. Unlike
+    /// [`crate::PdfParserConfig::set_extract_annotation_text`], which adds the annotation text to
+    /// the extracted text, this keeps the annotations apart from the body. Annotations without
+    /// text, such as a highlight without a comment, are listed too. Popups and form field
+    /// widgets are left out, and the annotations of a page that cannot be read are skipped.
+    /// Returns [`crate::Error::IoError`] if the PDF cannot be loaded.
+    pub fn extract_pdf_annotations(&self, bytes: &[u8]) -> ExtractResult<Vec<Annotation>> {
+        tika::extract_pdf_annotations(bytes)
+    }
+
+    /// Lists the files attached to a PDF through the `EmbeddedFiles` name tree of its catalog,
+    /// in name order, with their bytes and the properties of their file specification: the
+    /// original file name, the description and the `AFRelationship` telling, e.g. in PDF/A-3
+    /// and ZUGFeRD invoices, whether the file is the source or the data of the PDF. The text
+    /// of the attachments is extracted by [`Extractor::extract_embedded`], which does not
+    /// carry these properties. A PDF without attachments returns an empty vec, and the
+    /// attachments that cannot be read are skipped.
+    /// Returns [`crate::Error::IoError`] if the PDF cannot be loaded.
+    pub fn extract_pdf_attachments(&self, bytes: &[u8]) -> ExtractResult<Vec<PdfAttachment>> {
+        tika::extract_pdf_attachments(bytes)
+    }
+
+    /// Returns the outline of a PDF, the bookmark tree shown by PDF viewers, with the page
+    /// each bookmark points to, e.g. to chunk long reports by chapter. Unlike
+    /// [`Extractor::extract_outline`], which reads the headings of the text, it uses the
+    /// bookmarks set by the author of the PDF. A PDF without bookmarks returns an empty vec.
+    /// Returns [`crate::Error::IoError`] if the PDF cannot be loaded.
+    pub fn extract_pdf_outline(&self, bytes: &[u8]) -> ExtractResult<Vec<Outline>> {
+        tika::extract_pdf_outline(bytes)
+    }
+
+    /// Lists the fields of the AcroForm of a PDF with their fully qualified name, kind and
+    /// value, in the order of the field tree, so that the values filled in a form can be read
+    /// as structured data instead of being interleaved with the text of the pages. Only the
+    /// fields holding values are listed, not the fields grouping them, and the fields left
+    /// empty are listed without a value. XFA forms are not supported. A PDF without a form
+    /// returns an empty vec.
+    /// Returns [`crate::Error::IoError`] if the PDF cannot be loaded.
+    pub fn extract_pdf_forms(&self, bytes: &[u8]) -> ExtractResult<Vec<FormField>> {
+        tika::extract_pdf_forms(bytes)
+    }
+
+    /// Lists the comments of an Office document with their author, timestamp and the comment
+    /// they reply to, so that discussion threads can be rebuilt instead of being flattened into
+    /// the body text. Supports the comments of Word documents, the threaded comments of Excel
+    /// workbooks and the modern comments of PowerPoint presentations, in document order.
+    /// Documents without comments, including the legacy notes of Excel and PowerPoint and other
+    /// formats, return an empty vec. Returns [`crate::Error::IoError`] if the comments cannot be
+    /// read.
+    pub fn extract_comments(&self, bytes: &[u8]) -> ExtractResult<Vec<Comment>> {
+        tika::extract_comments(bytes)
+    }
+
+    /// Extracts the slides of a PowerPoint presentation one by one, each with its title, the
+    /// text of its other shapes and its speaker notes, where the flat text loses the slide
+    /// boundaries, e.g. to summarize a deck slide by slide. The title is the text of the title
+    /// placeholder of the slide, and the slide number, date and footer placeholders are left
+    /// out. Supports PPTX, including macro enabled presentations and slide shows; other
+    /// documents, including legacy PPT presentations, return an empty vec. Returns
+    /// [`crate::Error::IoError`] if the presentation cannot be read.
+    pub fn extract_slides(&self, bytes: &[u8]) -> ExtractResult<Vec<Slide>> {
+        tika::extract_slides(bytes)
+    }
+
+    /// Lists the fonts a PDF uses, with whether each is embedded and subset, e.g. to reject
+    /// documents with fonts that are not embedded before printing them. The fonts are collected
+    /// from the resources of the pages and of the forms drawn on them, each font once, in the
+    /// order it is first used. The fonts of a page whose resources cannot be read are skipped.
+    /// Returns [`crate::Error::IoError`] if the PDF cannot be loaded.
+    pub fn extract_pdf_fonts(&self, bytes: &[u8]) -> ExtractResult<Vec<FontInfo>> {
+        tika::extract_pdf_fonts(bytes)
+    }
+
+    /// Returns the page labels of a PDF, one per page in physical order, so that the page at
+    /// index `i` of the result is physical page `i + 1`. The labels are the logical page numbers
+    /// the PDF defines with its page label ranges, e.g. `iv` for front matter or `A-3` for an
+    /// appendix. A page without label, or every page of a PDF that defines none, gets its
+    /// physical page number. Returns [`crate::Error::IoError`] if the PDF cannot be loaded.
+    pub fn extract_pdf_page_labels(&self, bytes: &[u8]) -> ExtractResult<Vec<String>> {
+        tika::extract_pdf_page_labels(bytes)
+    }
+
+    /// Checks whether a document contains macros, without extracting it, e.g. to flag or
+    /// quarantine macro-enabled Office files. Looks for the VBA project of legacy Word, Excel and
+    /// PowerPoint documents and of OOXML documents, for the Excel 4 macro sheets of OOXML
+    /// workbooks and for the Basic and script folders of OpenDocument files. Other formats have
+    /// no macros. Only the document itself is checked, not the documents embedded in it.
+    /// Returns [`crate::Error::IoError`] if the document cannot be read.
+    pub fn has_macros(&self, bytes: &[u8]) -> ExtractResult<bool> {
+        tika::has_macros(bytes)
+    }
+
+    /// Checks whether a PDF or OOXML document (docx, xlsx, pptx...) is digitally signed, and
+    /// whether its signatures match the signed content and the certificates they embed.
+    /// Only the integrity of the signatures is checked: the certificates are not validated
+    /// against trusted roots, nor checked for expiry or revocation. Other formats, including the
+    /// legacy Office formats, are reported as [`SignatureStatus::Unsigned`].
+    /// Returns [`crate::Error::IoError`] if the document cannot be loaded.
+    pub fn verify_signature(&self, bytes: &[u8]) -> ExtractResult<SignatureStatus> {
+        tika::verify_signature(bytes)
+    }
+
+    /// Moves the one time cost of the first extraction to a controlled point, such as the
+    /// startup of a service: starts the embedded Tika, loads its configuration and parsers, and
+    /// extracts a few tiny documents with this extractor's configuration. Tika is compiled ahead
+    /// of time, so there is no JIT to warm up, but class initialization and parser loading still
+    /// make the first extraction slow.
+    /// Tika is started and its parsers loaded once per process, later calls skip that step. The
+    /// tiny documents are extracted on every call, so every configuration in use, e.g. one with
+    /// OCR and one without, can be warmed up. Safe to call from any thread.
+    pub fn warmup(&self) -> ExtractResult<()> {
+        static TIKA_LOADED: Mutex<bool> = Mutex::new(false);
+
+        {
+            let mut tika_loaded = TIKA_LOADED
+                .lock()
+                .map_err(|_e| Error::Unknown("Warmup lock is poisoned".to_string()))?;
+            if !*tika_loaded {
+                tika::supported_media_types()?;
+                *tika_loaded = true;
+            }
+        }
+        for fixture in WARMUP_FIXTURES {
+            self.extract_bytes_to_string(fixture)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the text of the stream to the writer, in chunks the size of the buffer of the stream.
+/// Returns the number of bytes written
+fn copy_to_writer(mut reader: StreamReader<'_>, writer: &mut impl Write) -> io::Result<u64> {
+    let mut buf = vec![0u8; DEFAULT_BUF_SIZE];
+    loop {
+        let num_read = reader.read(&mut buf)?;
+        if num_read == 0 {
+            return Ok(reader.bytes_read());
+        }
+        writer.write_all(&buf[..num_read])?;
+    }
+}
+
+/// Returns the path as a str, as Tika takes it, or an error if it is not valid UTF-8 rather
+/// than a lossy version of it that may name another file
+pub(crate) fn path_to_str(path: &Path) -> ExtractResult<&str> {
+    path.to_str()
+        .ok_or_else(|| Error::IoError(format!("Path is not valid UTF-8: {}", path.display())))
+}
+
+/// Creates a new file with a unique name and the given extension in the temporary directory
+fn create_temp_file(extension: &str) -> ExtractResult<(File, PathBuf)> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    loop {
+        let name = format!(
+            "extractous-{}-{}-{}.{}",
+            std::process::id(),
+            nanos,
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+            extension
+        );
+        let path = std::env::temp_dir().join(name);
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((file, path)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(Error::IoError(e.to_string())),
+        }
+    }
+}
+
+/// Writes the data to a new file named `name` in the directory, adding a `-1`, `-2`, ... suffix
+/// before the extension while the name is taken. Returns the path of the written file
+fn write_new_file(dir: &Path, name: &str, data: &[u8]) -> io::Result<PathBuf> {
+    let (stem, extension) = match name.rfind('.') {
+        Some(dot) if dot > 0 => name.split_at(dot),
+        _ => (name, ""),
+    };
+    for suffix in 0u64.. {
+        let candidate = if suffix == 0 {
+            name.to_string()
+        } else {
+            format!("{}-{}{}", stem, suffix, extension)
+        };
+        let path = dir.join(candidate);
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                file.write_all(data)?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!("the suffixes are exhausted")
 }
 
 #[cfg(test)]
 mod tests {
     use super::StreamReader;
-    use crate::Extractor;
+    use crate::{Extractor, HttpConfig, RedirectPolicy};
     use std::fs::File;
     use std::io::BufReader;
-    use std::io::{self, Read};
+    use std::io::{self, BufRead, Read, Write};
+    use std::net::TcpListener;
     use std::str;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
 
     const TEST_FILE: &str = "README.md";
 
@@ -241,7 +1859,7 @@ mod tests {
         );
     }
 
-    fn read_content_from_stream(stream: StreamReader) -> String {
+    fn read_content_from_stream(stream: StreamReader<'_>) -> String {
         let mut reader = BufReader::new(stream);
         let mut buffer = Vec::new();
         reader.read_to_end(&mut buffer).unwrap();
@@ -309,6 +1927,187 @@ mod tests {
         );
     }
 
+    /// A request received by a [`serve`] server, with its header names in lowercase
+    struct Request {
+        line: String,
+        headers: Vec<(String, String)>,
+    }
+
+    /// The requests received by a [`serve`] server so far
+    type Requests = Arc<Mutex<Vec<Request>>>;
+
+    impl Request {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// Serves http on a local port, answering each request with the response `respond` builds
+    /// from its request line. Returns the url of the server and the requests received so far
+    fn serve(respond: impl Fn(&str) -> String + Send + 'static) -> (String, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&requests);
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                let mut headers = Vec::new();
+                reader.read_line(&mut line).ok();
+                loop {
+                    let mut header = String::new();
+                    if reader.read_line(&mut header).unwrap_or(0) == 0 || header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+                    }
+                }
+                let line = line.trim().to_string();
+                let response = respond(&line);
+                // Recorded before answering, so the request is known once the extraction ends
+                received.lock().unwrap().push(Request { line, headers });
+                stream.write_all(response.as_bytes()).ok();
+            }
+        });
+        (url, requests)
+    }
+
+    fn text_response(body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    }
+
+    fn redirect_response(location: &str) -> String {
+        format!(
+            "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            location
+        )
+    }
+
+    /// Serves a document on another origin, and on the origin of the returned url:
+    /// `/local.txt`, a document, `/same`, a redirect to it, `/cross`, a redirect to the other
+    /// origin, and `/loop`, a redirect to itself
+    fn serve_redirects() -> (String, Requests, Requests) {
+        let (remote_url, remote_requests) = serve(|_line| text_response("remote document"));
+        let (url, requests) = serve(move |line| {
+            if line.starts_with("GET /local.txt ") {
+                text_response("local document")
+            } else if line.starts_with("GET /same ") {
+                redirect_response("/local.txt")
+            } else if line.starts_with("GET /cross ") {
+                redirect_response(&format!("{}/remote.txt", remote_url))
+            } else {
+                redirect_response("/loop")
+            }
+        });
+        (url, requests, remote_requests)
+    }
+
+    fn http_config() -> HttpConfig {
+        HttpConfig::new()
+            .add_header("X-Api-Key", "api-key")
+            .set_bearer_auth("secret-token")
+            .set_connect_timeout_seconds(30)
+            .set_read_timeout_seconds(30)
+    }
+
+    #[test]
+    fn extract_url_with_http_config_test() {
+        let (url, requests) = serve(|_line| text_response("served document"));
+        assert!(!format!("{:?}", http_config()).contains("secret-token"));
+
+        let extractor = Extractor::new().set_http_config(http_config());
+        let (content, _metadata) = extractor
+            .extract_url_to_string(&format!("{}/doc.txt", url))
+            .unwrap();
+        assert!(content.contains("served document"));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].line, "GET /doc.txt HTTP/1.1");
+        assert_eq!(
+            requests[0].header("authorization"),
+            Some("Bearer secret-token")
+        );
+        assert_eq!(requests[0].header("x-api-key"), Some("api-key"));
+    }
+
+    #[test]
+    fn extract_url_redirect_headers_test() {
+        let (url, requests, remote_requests) = serve_redirects();
+        let extractor = Extractor::new().set_http_config(http_config());
+
+        // The headers follow the redirects within the origin
+        let (content, _metadata) = extractor
+            .extract_url_to_string(&format!("{}/same", url))
+            .unwrap();
+        assert!(content.contains("local document"));
+        for request in requests.lock().unwrap().iter() {
+            assert_eq!(request.header("authorization"), Some("Bearer secret-token"));
+            assert_eq!(request.header("x-api-key"), Some("api-key"));
+        }
+
+        // But are not sent to another origin, here another port of the same host
+        let (content, _metadata) = extractor
+            .extract_url_to_string(&format!("{}/cross", url))
+            .unwrap();
+        assert!(content.contains("remote document"));
+        let remote_requests = remote_requests.lock().unwrap();
+        assert_eq!(remote_requests.len(), 1);
+        assert_eq!(remote_requests[0].line, "GET /remote.txt HTTP/1.1");
+        assert_eq!(remote_requests[0].header("authorization"), None);
+        assert_eq!(remote_requests[0].header("x-api-key"), None);
+    }
+
+    #[test]
+    fn extract_url_redirect_policy_test() {
+        let (url, requests, remote_requests) = serve_redirects();
+        let extract = |policy: RedirectPolicy, path: &str| {
+            Extractor::new()
+                .set_http_config(HttpConfig::new().set_redirect_policy(policy))
+                .extract_url_to_string(&format!("{}{}", url, path))
+        };
+
+        assert!(extract(RedirectPolicy::SameOrigin, "/same").is_ok());
+        assert!(extract(RedirectPolicy::SameOrigin, "/cross").is_err());
+        assert!(extract(RedirectPolicy::NoFollow, "/same").is_err());
+        assert!(remote_requests.lock().unwrap().is_empty());
+
+        // The first request and the 3 redirects followed, then the extraction fails
+        requests.lock().unwrap().clear();
+        let extractor = Extractor::new().set_http_config(HttpConfig::new().set_max_redirects(3));
+        assert!(extractor
+            .extract_url_to_string(&format!("{}/loop", url))
+            .is_err());
+        assert_eq!(requests.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn extract_url_with_proxy_test() {
+        let (proxy_url, requests) = serve(|_line| text_response("proxied document"));
+        let port = proxy_url.rsplit(':').next().unwrap().parse().unwrap();
+
+        // The host of the url does not exist, only the proxy can answer
+        let extractor =
+            Extractor::new().set_http_config(HttpConfig::new().set_proxy("127.0.0.1", port));
+        let (content, _metadata) = extractor
+            .extract_url_to_string("http://extractous.invalid/doc.txt")
+            .unwrap();
+        assert!(content.contains("proxied document"));
+        assert_eq!(
+            requests.lock().unwrap()[0].line,
+            "GET http://extractous.invalid/doc.txt HTTP/1.1"
+        );
+    }
+
     #[test]
     fn extract_file_to_xml_test() {
         // Parse the files using extractous
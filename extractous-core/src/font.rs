@@ -0,0 +1,14 @@
+/// A font used by a PDF, as returned by [`crate::Extractor::extract_pdf_fonts`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FontInfo {
+    /// The base font name, including the subset tag if any, e.g. `ABCDEF+Arial`
+    pub name: String,
+    /// Whether the font program is embedded in the document. Type3 fonts, whose glyphs are
+    /// drawn by the document itself, are always embedded
+    pub embedded: bool,
+    /// Whether only the glyphs used by the document are embedded, as told by the subset tag of
+    /// its name
+    pub subset: bool,
+    /// The font type, e.g. `Type1`, `TrueType`, `Type0` or `Type3`
+    pub font_type: String,
+}
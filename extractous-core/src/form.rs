@@ -0,0 +1,36 @@
+use strum_macros::{Display, EnumString};
+
+/// The kind of a field of a PDF form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum FormFieldKind {
+    /// A text field
+    Text,
+    /// A check box, whose value is the name of its checked state, e.g. `Yes`, or `Off`
+    Checkbox,
+    /// A group of radio buttons, whose value is the name of the selected button, or `Off`
+    RadioButton,
+    /// A push button, which has no value
+    PushButton,
+    /// A drop-down list, possibly editable
+    ComboBox,
+    /// A scrollable list of options
+    ListBox,
+    /// A signature field, whose value is the name of the signer when it is signed
+    Signature,
+    /// Any other kind of field
+    Other,
+}
+
+/// A field of the AcroForm of a PDF, as returned by [`crate::Extractor::extract_pdf_forms`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormField {
+    /// The fully qualified name of the field, the names of the fields grouping it and its own
+    /// separated by dots, e.g. `applicant.name`
+    pub name: String,
+    /// The kind of the field
+    pub kind: FormFieldKind,
+    /// The value of the field, if set. The options selected in a list box are separated by
+    /// line breaks
+    pub value: Option<String>,
+}
@@ -46,11 +46,28 @@
 //!
 //! ```
 //!
+//! ## Read the metadata
+//!
+//! The metadata is returned with the content, from the same parse. [`MetadataExt`] reads its
+//! common keys.
+//!
+//! ```no_run
+//! use extractous::{Extractor, MetadataExt};
+//!
+//! let (text, metadata) = Extractor::new().extract_file_to_string("README.md").unwrap();
+//! println!("{:?} by {:?}, {:?} pages", metadata.title(), metadata.author(), metadata.page_count());
+//!
+//! ```
+//!
 //! ## Extract text with OCR
 //! * Make sure Tesseract is installed with the corresponding language packs. For example on debian `sudo apt install tesseract-ocr tesseract-ocr-deu` to install tesseract with German language pack.
 //! * If you get `Parse error occurred : Unable to extract PDF content`, it is most likely that the OCR language pack is not installed
+//! * OCR requires the `ocr` feature, which is enabled by default. Building with
+//!   `default-features = false` removes the OCR configuration and never OCRs PDF pages.
 //!
 //! ```no_run
+//! # #[cfg(feature = "ocr")]
+//! # {
 //! use extractous::{Extractor, TesseractOcrConfig, PdfParserConfig, PdfOcrStrategy};
 //!
 //! let file_path = "../test_files/documents/deu-ocr.pdf";
@@ -63,12 +80,31 @@
 //! // extract file with extractor
 //! let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
 //! println!("{}", content);
-//!
+//! # }
 //! ```
 
 /// Default buffer size
 pub const DEFAULT_BUF_SIZE: usize = 32768;
 
+// async_extractor module holds the async interface for tokio runtimes
+#[cfg(feature = "tokio")]
+mod async_extractor;
+#[cfg(feature = "tokio")]
+pub use async_extractor::*;
+// anchor module holds the types mapping the extracted text to positions in the document
+mod anchor;
+pub use anchor::*;
+// annotation module holds the types of the annotations of a PDF
+mod annotation;
+pub use annotation::*;
+// batch module holds the extraction of document collections, exposed as `extractous::batch`
+pub mod batch;
+// char_stream module decodes the extracted text with a policy for invalid bytes
+mod char_stream;
+pub use char_stream::*;
+// comment module holds the types of the comments of an Office document
+mod comment;
+pub use comment::*;
 // errors module
 mod errors;
 pub use errors::*;
@@ -76,15 +112,103 @@ pub use errors::*;
 // extractor module is the config interface
 mod config;
 pub use config::*;
+// data_uri module decodes base64 and data URI inputs, not exposed outside this crate
+mod data_uri;
+// embedded module holds the types of the recursive extraction
+mod embedded;
+pub use embedded::*;
+// entity module holds the entities found in the text of a document
+mod entity;
+pub use entity::*;
 // extractor module is the main public api interface
 mod extractor;
 pub use extractor::*;
+// font module holds the types of the font table of a PDF
+mod font;
+pub use font::*;
+// form module holds the types of the fields of a PDF form
+mod form;
+pub use form::*;
+// metadata module holds the typed accessors of the metadata
+mod metadata;
+pub use metadata::MetadataExt;
+// ocr module holds the pluggable OCR backend
+#[cfg(feature = "ocr")]
+mod ocr;
+#[cfg(feature = "ocr")]
+pub use ocr::*;
+// outline module holds the bookmark tree of a PDF
+mod outline;
+pub use outline::*;
+// pages module iterates over the text of the pages of a PDF
+mod pages;
+pub use pages::{PageText, PdfPages};
+// password module holds the types of the password provider
+mod password;
+pub use password::*;
+// quality module holds the heuristic quality score of an extraction
+mod quality;
+pub use quality::QualityScore;
+// result module holds the unified result of an extraction
+mod result;
+pub use result::{DetectedLanguage, ExtractionResult};
+// sax module holds the events passed to custom content handlers
+mod sax;
+pub use sax::SaxEvent;
+// segment module implements the resumable segmented extraction
+mod segment;
+pub use segment::Cursor;
+// self_test module verifies the extraction stack works
+mod self_test;
+pub use self_test::self_test;
+mod slide;
+pub use slide::*;
+// structure module holds the types describing the structure of a document
+mod structure;
+pub use structure::*;
+// tables module rebuilds the tables of a document from its xhtml output
+mod tables;
+pub use tables::*;
+// validate module checks the container structure of inputs, not exposed outside this crate
+mod validate;
+// xhtml module walks the xhtml output of tika, not exposed outside this crate
+mod xhtml;
 
 // tika module, not exposed outside this crate
 mod tika {
+    mod anchors;
+    mod annotations;
+    mod attachments;
+    pub(crate) mod callbacks;
+    mod comments;
+    mod detect;
+    mod fonts;
+    mod forms;
+    mod images;
     mod jni_utils;
+    mod macros;
+    mod outline;
+    mod page_labels;
     mod parse;
+    mod render;
+    mod signature;
+    mod slides;
     mod wrappers;
+    pub use anchors::*;
+    pub use annotations::*;
+    pub use attachments::*;
+    pub(crate) use callbacks::CallbackGuard;
+    pub use comments::*;
+    pub use detect::*;
+    pub use fonts::*;
+    pub use forms::*;
+    pub use images::*;
+    pub use macros::*;
+    pub use outline::*;
+    pub use page_labels::*;
     pub use parse::*;
+    pub use render::*;
+    pub use signature::*;
+    pub use slides::*;
     pub use wrappers::JReaderInputStream;
 }
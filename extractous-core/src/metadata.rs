@@ -0,0 +1,50 @@
+use crate::Metadata;
+
+/// Typed accessors for the common [`Metadata`] keys, so the title, author or page count of a
+/// document can be read from the metadata returned with its content, without knowing the Tika
+/// key of each property. Every accessor returns the first value of its key, or `None` if the
+/// parsers did not find it.
+pub trait MetadataExt {
+    /// The first value of the given key
+    fn first(&self, key: &str) -> Option<&str>;
+
+    /// The title, `dc:title`
+    fn title(&self) -> Option<&str> {
+        self.first("dc:title")
+    }
+
+    /// The author, `dc:creator`
+    fn author(&self) -> Option<&str> {
+        self.first("dc:creator")
+    }
+
+    /// The detected media type, `Content-Type`, with its parameters such as the charset of text
+    /// formats, e.g. `text/plain; charset=UTF-8`
+    fn content_type(&self) -> Option<&str> {
+        self.first("Content-Type")
+    }
+
+    /// The creation date, `dcterms:created`, in ISO 8601 format, e.g. `2022-07-29T10:03:21Z`
+    fn created(&self) -> Option<&str> {
+        self.first("dcterms:created")
+    }
+
+    /// The last modification date, `dcterms:modified`, in ISO 8601 format
+    fn modified(&self) -> Option<&str> {
+        self.first("dcterms:modified")
+    }
+
+    /// The number of pages, `xmpTPg:NPages`, or the page or slide count of the document
+    /// statistics, `meta:page-count` or `meta:slide-count`, for the formats that only have those
+    fn page_count(&self) -> Option<u32> {
+        ["xmpTPg:NPages", "meta:page-count", "meta:slide-count"]
+            .iter()
+            .find_map(|key| self.first(key)?.trim().parse().ok())
+    }
+}
+
+impl MetadataExt for Metadata {
+    fn first(&self, key: &str) -> Option<&str> {
+        self.get(key)?.first().map(String::as_str)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::errors::ExtractResult;
+use crate::TesseractOcrConfig;
+use jni::objects::GlobalRef;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// OcrBackend allows plugging in an OCR engine other than Tesseract
+///
+/// When a backend is set on the [`crate::Extractor`], every image that would be OCR'd by
+/// Tesseract, including the rendered pages of a PDF, is passed to the backend instead and the
+/// returned text is assembled into the extracted content. For example:
+/// ```no_run
+/// use extractous::{ExtractResult, Extractor, OcrBackend};
+///
+/// struct MyCloudOcr;
+///
+/// impl OcrBackend for MyCloudOcr {
+///     fn ocr(&self, image: &[u8]) -> ExtractResult<String> {
+///         // send the image bytes to the OCR service
+///         Ok(String::new())
+///     }
+/// }
+///
+/// let extractor = Extractor::new().set_ocr_backend(Box::new(MyCloudOcr));
+/// ```
+///
+/// The backend is called from the thread that runs the parse, which for the streaming
+/// functions is a background thread, hence the `Send + Sync` requirement.
+pub trait OcrBackend: Send + Sync {
+    /// Returns the text recognized in the given image bytes
+    fn ocr(&self, image: &[u8]) -> ExtractResult<String>;
+}
+
+/// Shared reference to an [`OcrBackend`] that can be cloned along with the extractor
+#[derive(Clone)]
+pub(crate) struct OcrBackendRef(pub(crate) Arc<dyn OcrBackend>);
+
+impl fmt::Debug for OcrBackendRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OcrBackend")
+    }
+}
+
+/// The java OCR config built by the first extraction of an extractor with
+/// [`crate::Extractor::set_ocr_keep_warm`], reused by its later extractions and by its clones
+/// as long as their OCR config is the same
+#[derive(Clone, Default)]
+pub(crate) struct WarmOcrConfig(pub(crate) Arc<Mutex<Option<(TesseractOcrConfig, GlobalRef)>>>);
+
+impl fmt::Debug for WarmOcrConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WarmOcrConfig")
+    }
+}
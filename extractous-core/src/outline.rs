@@ -0,0 +1,11 @@
+/// An item of the outline of a PDF, the bookmark tree shown by PDF viewers, as returned by
+/// [`crate::Extractor::extract_pdf_outline`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outline {
+    /// The title of the item, e.g. the title of a chapter
+    pub title: String,
+    /// The page the item points to, starting at 1, if it points to a page of the document
+    pub page: Option<u32>,
+    /// The items nested in this item, e.g. the sections of a chapter, in outline order
+    pub children: Vec<Outline>,
+}
@@ -0,0 +1,52 @@
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use crate::errors::ExtractResult;
+use crate::tika;
+use crate::Extractor;
+
+/// The text of a page of a PDF, as yielded by [`crate::Extractor::extract_pdf_pages`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageText {
+    /// The number of the page in the document, starting at 1
+    pub page_number: u32,
+    /// The text of the page
+    pub text: String,
+}
+
+/// Iterator over the pages of a PDF, created with [`crate::Extractor::extract_pdf_pages`]
+///
+/// The document is parsed on a background thread, which stays at most one page ahead of the
+/// iterator: each page is yielded as soon as it is parsed, without waiting for the rest of the
+/// document. An error ends the iteration, after the pages parsed before it. Dropping the
+/// iterator stops the parse at the next page.
+#[derive(Debug)]
+pub struct PdfPages {
+    receiver: Receiver<ExtractResult<PageText>>,
+}
+
+impl PdfPages {
+    /// Starts parsing the file on a background thread
+    pub(crate) fn start(extractor: &Extractor, file_path: &str) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let extractor = extractor.clone();
+        let file_path = file_path.to_string();
+        thread::spawn(move || {
+            let page_sender = sender.clone();
+            let handler = move |page: PageText| page_sender.send(Ok(page)).is_ok();
+            if let Err(e) = tika::parse_file_to_pages(&file_path, &extractor, Box::new(handler)) {
+                // Nobody is left to report to if the iterator was dropped
+                sender.send(Err(e)).ok();
+            }
+        });
+        Self { receiver }
+    }
+}
+
+impl Iterator for PdfPages {
+    type Item = ExtractResult<PageText>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
@@ -0,0 +1,9 @@
+/// What is known about an encrypted document when its password is needed. Passed to the
+/// provider of [`crate::Extractor::set_password_provider`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentInfo {
+    /// The file name of the document, if known, e.g. the name of an archive entry
+    pub name: Option<String>,
+    /// The media type of the document, if known, e.g. `application/pdf`
+    pub media_type: Option<String>,
+}
@@ -0,0 +1,128 @@
+use crate::Metadata;
+
+/// Metadata key holding the mean confidence of the OCR, from 0 to 1 or as a percentage
+const OCR_CONFIDENCE_KEY: &str = "X-OCR-Confidence";
+/// Inputs smaller than this are not expected to yield a lot of text, so a short body is not
+/// suspicious for them
+const MIN_SIZE_FOR_DENSITY: usize = 4096;
+/// Ratio of text bytes to input bytes under which the body is considered suspiciously short
+const MIN_TEXT_DENSITY: f64 = 0.01;
+/// Ratio of alphanumeric characters expected at least in natural language text
+const MIN_ALPHANUMERIC_RATIO: f64 = 0.6;
+/// Ratio of replacement characters at which the text is considered unusable
+const MAX_REPLACEMENT_RATIO: f64 = 0.1;
+
+/// Heuristic quality of an extraction, see [`crate::Extractor::extract_bytes_with_quality`]
+///
+/// Low scores point at garbled OCR, mojibake from a wrong charset or content that was not
+/// extracted at all, such as a scanned document parsed without OCR.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QualityScore {
+    /// Ratio of alphanumeric characters to all non whitespace characters
+    pub alphanumeric_ratio: f64,
+    /// Ratio of unicode replacement characters (U+FFFD) to all non whitespace characters
+    pub replacement_char_ratio: f64,
+    /// Mean confidence of the OCR from 0 to 1, when the input was OCR'd and the OCR reported a
+    /// confidence
+    pub ocr_confidence: Option<f64>,
+    /// Ratio of the size of the text to the size of the input, both in bytes
+    pub text_density: f64,
+    /// Overall score from 0, unusable, to 1, no sign of a problem
+    pub score: f64,
+}
+
+impl QualityScore {
+    /// Computes the quality of the given text extracted from an input of `input_size` bytes
+    pub(crate) fn compute(text: &str, metadata: &Metadata, input_size: usize) -> Self {
+        let (mut total, mut alphanumeric, mut replacement) = (0usize, 0usize, 0usize);
+        for c in text.chars().filter(|c| !c.is_whitespace()) {
+            total += 1;
+            if c.is_alphanumeric() {
+                alphanumeric += 1;
+            } else if c == char::REPLACEMENT_CHARACTER {
+                replacement += 1;
+            }
+        }
+        let ratio = |count: usize| {
+            if total == 0 {
+                0.0
+            } else {
+                count as f64 / total as f64
+            }
+        };
+        let alphanumeric_ratio = ratio(alphanumeric);
+        let replacement_char_ratio = ratio(replacement);
+        let ocr_confidence = metadata
+            .get(OCR_CONFIDENCE_KEY)
+            .and_then(|values| values.first())
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .map(|c| (if c > 1.0 { c / 100.0 } else { c }).clamp(0.0, 1.0));
+        let text_density = if input_size == 0 {
+            0.0
+        } else {
+            text.len() as f64 / input_size as f64
+        };
+
+        // Every sub-score is 1 when nothing is wrong, so the overall score drops with any problem
+        let mut score = (alphanumeric_ratio / MIN_ALPHANUMERIC_RATIO).min(1.0)
+            * (1.0 - replacement_char_ratio / MAX_REPLACEMENT_RATIO).max(0.0);
+        if input_size >= MIN_SIZE_FOR_DENSITY {
+            score *= (text_density / MIN_TEXT_DENSITY).min(1.0);
+        }
+        if let Some(confidence) = ocr_confidence {
+            score *= confidence;
+        }
+
+        Self {
+            alphanumeric_ratio,
+            replacement_char_ratio,
+            ocr_confidence,
+            text_density,
+            score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natural_text_scores_high() {
+        let text = "The quick brown fox jumps over the lazy dog.";
+        let quality = QualityScore::compute(text, &Metadata::new(), text.len());
+        assert!(quality.alphanumeric_ratio > 0.9);
+        assert_eq!(quality.replacement_char_ratio, 0.0);
+        assert_eq!(quality.ocr_confidence, None);
+        assert_eq!(quality.score, 1.0);
+    }
+
+    #[test]
+    fn empty_text_scores_zero() {
+        let quality = QualityScore::compute("  \n", &Metadata::new(), 3);
+        assert_eq!(quality.score, 0.0);
+    }
+
+    #[test]
+    fn replacement_characters_lower_the_score() {
+        let text = "Gr\u{FFFD}\u{FFFD}e aus M\u{FFFD}nchen";
+        let quality = QualityScore::compute(text, &Metadata::new(), text.len());
+        assert!(quality.replacement_char_ratio > 0.1);
+        assert_eq!(quality.score, 0.0);
+    }
+
+    #[test]
+    fn short_body_of_large_input_lowers_the_score() {
+        let quality = QualityScore::compute("Page 1", &Metadata::new(), 1_000_000);
+        assert!(quality.text_density < MIN_TEXT_DENSITY);
+        assert!(quality.score < 0.01);
+    }
+
+    #[test]
+    fn ocr_confidence_from_metadata() {
+        let metadata = Metadata::from([(OCR_CONFIDENCE_KEY.to_string(), vec!["80".to_string()])]);
+        let quality = QualityScore::compute("Hello world", &metadata, 11);
+        assert_eq!(quality.ocr_confidence, Some(0.8));
+        assert!((quality.score - 0.8).abs() < 1e-9);
+    }
+}
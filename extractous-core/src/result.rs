@@ -0,0 +1,79 @@
+use crate::{Metadata, MetadataExt};
+
+/// Metadata keys used by the java side to return the detected language and its probability
+const DETECTED_LANGUAGE_KEY: &str = "X-Detected-Language";
+const DETECTED_LANGUAGE_CONFIDENCE_KEY: &str = "X-Detected-Language-Confidence";
+
+/// Media type of the documents whose type could not be detected
+const UNKNOWN_MIME_TYPE: &str = "application/octet-stream";
+
+/// Everything an extraction finds about a document, as returned by the `extract_*_full`
+/// functions of [`crate::Extractor`] from a single parse
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractionResult {
+    /// The extracted content, of maximum length of the extractor's `extract_string_max_length`
+    pub content: String,
+    /// The metadata of the document
+    pub metadata: Metadata,
+    /// The detected media type, without its parameters, e.g. `application/pdf`
+    pub mime_type: String,
+    /// The ISO 639-1 code of the language of the content, e.g. `en`, or None if the content is
+    /// empty or its language cannot be told with reasonable certainty
+    pub detected_language: Option<String>,
+    /// The probability, from 0 to 1, of the detected language, or None if no language was
+    /// detected
+    pub detected_language_confidence: Option<f32>,
+}
+
+/// The language of a text, as returned by [`crate::Extractor::detect_language`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedLanguage {
+    /// The ISO 639-1 code of the language, e.g. `en`
+    pub language: String,
+    /// The probability of the language, from 0 to 1
+    pub confidence: f32,
+}
+
+impl DetectedLanguage {
+    /// Takes the detected language and its probability out of the metadata tika returns, if
+    /// the language could be told
+    pub(crate) fn take_from(metadata: &mut Metadata) -> Option<Self> {
+        let confidence = metadata
+            .remove(DETECTED_LANGUAGE_CONFIDENCE_KEY)
+            .and_then(|values| values.into_iter().next())
+            .and_then(|value| value.parse().ok());
+        let language = metadata
+            .remove(DETECTED_LANGUAGE_KEY)
+            .and_then(|values| values.into_iter().next())?;
+        Some(Self {
+            language,
+            confidence: confidence.unwrap_or(0.0),
+        })
+    }
+}
+
+impl ExtractionResult {
+    /// Builds a result from the content and the metadata tika returns for it, which carries the
+    /// detected language
+    pub(crate) fn from_parts(content: String, mut metadata: Metadata) -> Self {
+        let (detected_language, detected_language_confidence) =
+            match DetectedLanguage::take_from(&mut metadata) {
+                Some(detected) => (Some(detected.language), Some(detected.confidence)),
+                None => (None, None),
+            };
+        let mime_type = metadata
+            .content_type()
+            .and_then(|content_type| content_type.split(';').next())
+            .map(str::trim)
+            .filter(|mime_type| !mime_type.is_empty())
+            .unwrap_or(UNKNOWN_MIME_TYPE)
+            .to_string();
+        Self {
+            content,
+            metadata,
+            mime_type,
+            detected_language,
+            detected_language_confidence,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+/// An event of the XHTML document Tika produces while parsing, as passed to the handler of
+/// [`crate::Extractor::extract_bytes_to_handler`]. Elements are named by their local name, e.g.
+/// `p`, `h1`, `table` or `td`, and the pages of a PDF are `div` elements with the class `page`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaxEvent<'a> {
+    /// An element starts, with its attributes as `(name, value)` pairs
+    StartElement {
+        name: &'a str,
+        attributes: &'a [(String, String)],
+    },
+    /// Text of the current element. A run of text may be split over several events
+    Characters(&'a str),
+    /// An element ends
+    EndElement { name: &'a str },
+}
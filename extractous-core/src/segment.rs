@@ -0,0 +1,188 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::errors::{Error, ExtractResult};
+use crate::tika;
+use crate::{Extractor, StreamReader, DEFAULT_BUF_SIZE};
+
+/// Maximum number of segmented extractions kept alive at the same time. When exceeded, the
+/// oldest one is dropped and is transparently re-parsed if it is resumed later.
+const MAX_SESSIONS: usize = 16;
+
+/// Position in the text of a segmented extraction, see [`Extractor::extract_segment`]
+///
+/// Within a process the cursor resumes the ongoing parse. Only the [`Cursor::offset`], the
+/// number of characters already emitted, is meaningful across processes: persist it and resume
+/// with [`Cursor::from_offset`], at the cost of re-parsing the document up to that offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    offset: u64,
+    session: u64,
+}
+
+impl Cursor {
+    /// Creates a cursor resuming after the given number of characters
+    pub fn from_offset(offset: u64) -> Self {
+        Self { offset, session: 0 }
+    }
+
+    /// Returns the number of characters emitted before this cursor
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+/// An ongoing segmented extraction. It outlives the call that started it, so its stream owns a
+/// copy of the input rather than borrowing the caller's buffer
+struct Session {
+    fingerprint: u64,
+    offset: u64,
+    reader: StreamReader<'static>,
+    /// Text read from the stream but not emitted yet
+    text: String,
+    /// Number of characters in `text`
+    text_chars: usize,
+    /// Trailing bytes of an incomplete utf-8 character
+    incomplete: Vec<u8>,
+    eof: bool,
+}
+
+impl Session {
+    fn new(fingerprint: u64, reader: StreamReader<'static>) -> Self {
+        Self {
+            fingerprint,
+            offset: 0,
+            reader,
+            text: String::new(),
+            text_chars: 0,
+            incomplete: Vec::new(),
+            eof: false,
+        }
+    }
+
+    /// Reads from the stream until at least `chars` characters are buffered or the end is reached
+    fn fill(&mut self, chars: usize) -> ExtractResult<()> {
+        let mut buf = vec![0u8; DEFAULT_BUF_SIZE];
+        while !self.eof && self.text_chars < chars {
+            let num_read = self
+                .reader
+                .read(&mut buf)
+                .map_err(|e| Error::IoError(e.to_string()))?;
+            if num_read == 0 {
+                self.eof = true;
+                if !self.incomplete.is_empty() {
+                    std::str::from_utf8(&self.incomplete)?;
+                }
+                break;
+            }
+
+            self.incomplete.extend_from_slice(&buf[..num_read]);
+            let valid_up_to = match std::str::from_utf8(&self.incomplete) {
+                Ok(_) => self.incomplete.len(),
+                // An error_len means invalid bytes rather than a character cut by the buffer
+                Err(e) if e.error_len().is_some() => return Err(e.into()),
+                Err(e) => e.valid_up_to(),
+            };
+            let valid: Vec<u8> = self.incomplete.drain(..valid_up_to).collect();
+            let valid = std::str::from_utf8(&valid)?;
+            self.text_chars += valid.chars().count();
+            self.text.push_str(valid);
+        }
+        Ok(())
+    }
+
+    /// Removes and returns up to `chars` characters from the buffered text
+    fn take(&mut self, chars: usize) -> String {
+        let end = self
+            .text
+            .char_indices()
+            .nth(chars)
+            .map_or(self.text.len(), |(idx, _)| idx);
+        let taken: String = self.text.drain(..end).collect();
+        let taken_chars = chars.min(self.text_chars);
+        self.text_chars -= taken_chars;
+        self.offset += taken_chars as u64;
+        taken
+    }
+
+    /// Reads and discards characters until the session reaches the given offset
+    fn skip_to(&mut self, offset: u64) -> ExtractResult<()> {
+        while self.offset < offset {
+            let chars = (offset - self.offset).min(DEFAULT_BUF_SIZE as u64) as usize;
+            self.fill(chars)?;
+            if self.take(chars).is_empty() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn sessions() -> &'static Mutex<HashMap<u64, Session>> {
+    static SESSIONS: OnceLock<Mutex<HashMap<u64, Session>>> = OnceLock::new();
+    SESSIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Identifies the input and the configuration used to extract it, so a cursor is never resumed
+/// on a different document or with a different extractor
+fn fingerprint(buffer: &[u8], extractor: &Extractor) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    buffer.hash(&mut hasher);
+    format!("{:?}", extractor).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the next segment of at most `max_chars` characters and the cursor to resume from,
+/// or None once the end of the text is reached
+pub(crate) fn extract_segment(
+    extractor: &Extractor,
+    buffer: &[u8],
+    cursor: Option<Cursor>,
+    max_chars: usize,
+) -> ExtractResult<(String, Option<Cursor>)> {
+    static NEXT_SESSION: AtomicU64 = AtomicU64::new(1);
+
+    let cursor = cursor.unwrap_or(Cursor::from_offset(0));
+    let fingerprint = fingerprint(buffer, extractor);
+    let lock_err = |_e| Error::Unknown("Segment sessions are poisoned".to_string());
+
+    // Resume the ongoing parse if it is exactly where the cursor points to
+    let resumed = sessions()
+        .lock()
+        .map_err(lock_err)?
+        .remove(&cursor.session)
+        .filter(|s| s.fingerprint == fingerprint && s.offset == cursor.offset);
+    let mut session = match resumed {
+        Some(session) => session,
+        None => {
+            let (reader, _metadata) = tika::parse_shared_bytes(Arc::from(buffer), extractor)?;
+            let mut session = Session::new(fingerprint, reader);
+            session.skip_to(cursor.offset)?;
+            session
+        }
+    };
+
+    // Read one character more than needed to know whether anything is left afterward
+    session.fill(max_chars + 1)?;
+    let segment = session.take(max_chars);
+    if session.eof && session.text.is_empty() {
+        return Ok((segment, None));
+    }
+
+    let next = Cursor {
+        offset: session.offset,
+        session: NEXT_SESSION.fetch_add(1, Ordering::Relaxed),
+    };
+    let mut sessions = sessions().lock().map_err(lock_err)?;
+    if sessions.len() >= MAX_SESSIONS {
+        if let Some(oldest) = sessions.keys().min().copied() {
+            sessions.remove(&oldest);
+        }
+    }
+    sessions.insert(next.session, session);
+    Ok((segment, Some(next)))
+}
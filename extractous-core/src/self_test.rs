@@ -0,0 +1,41 @@
+use crate::errors::{Error, ExtractResult};
+use crate::{supported_media_types, Extractor};
+
+/// Text extracted and checked by the self test
+const SELF_TEST_TEXT: &str = "extractous self test";
+/// Image of the word TEST, OCR'd by the self test when Tesseract is available
+const SELF_TEST_IMAGE: &[u8] = include_bytes!("../resources/self_test.png");
+
+/// Verifies that the whole extraction stack works: starts the embedded Tika if needed, extracts
+/// a small known text and checks the result. When Tesseract is available and the `ocr` feature
+/// is enabled, also OCRs a small embedded image. Meant for startup and readiness checks, so that
+/// a missing native library, a broken JNI setup or a failing OCR install surfaces before the
+/// first real extraction.
+pub fn self_test() -> ExtractResult<()> {
+    let extractor = Extractor::new();
+
+    let (text, _metadata) = extractor.extract_bytes_to_string(SELF_TEST_TEXT.as_bytes())?;
+    if text.trim() != SELF_TEST_TEXT {
+        return Err(Error::Unknown(format!(
+            "Self test extracted {:?} instead of {:?}",
+            text.trim(),
+            SELF_TEST_TEXT
+        )));
+    }
+
+    // Tika only supports the ocr image types when the tesseract executable was found. Without
+    // the ocr feature OCR is skipped
+    let ocr_available = cfg!(feature = "ocr")
+        && supported_media_types()?
+            .iter()
+            .any(|t| t == "image/ocr-png");
+    if ocr_available {
+        let (text, _metadata) = extractor.extract_bytes_to_string(SELF_TEST_IMAGE)?;
+        if text.trim().is_empty() {
+            return Err(Error::Unknown(
+                "Self test OCR returned no text, check the Tesseract installation".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,13 @@
+/// A slide of a presentation, as returned by [`crate::Extractor::extract_slides`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slide {
+    /// The index of the slide in the presentation, starting at 0
+    pub index: usize,
+    /// The text of the title placeholder of the slide, if it has one
+    pub title: Option<String>,
+    /// The text of the other shapes of the slide, tables and groups included, in shape order,
+    /// one line per paragraph and the cells of table rows separated by tabs
+    pub body: String,
+    /// The speaker notes of the slide, empty if it has none
+    pub notes: String,
+}
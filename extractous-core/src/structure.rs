@@ -0,0 +1,464 @@
+use crate::tables::TableBuilder;
+use crate::xhtml::{heading_level, walk_body, XhtmlElement, XhtmlEvent};
+
+/// A heading of a document outline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    /// The heading level, from 1 (`<h1>`) to 6 (`<h6>`)
+    pub level: u8,
+    /// The text of the heading
+    pub text: String,
+    /// The character offset of the heading in the plain text body
+    pub offset: usize,
+}
+
+/// Builds the document outline from the `<h1>`-`<h6>` elements of tika's XHTML output
+pub(crate) fn outline_from_xhtml(xhtml: &str) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut offset = 0;
+    // The heading currently being read, if any
+    let mut current: Option<Heading> = None;
+
+    walk_body(xhtml, |event| match event {
+        XhtmlEvent::Start(element) => {
+            if let Some(level) = heading_level(&element.name) {
+                current = Some(Heading {
+                    level,
+                    text: String::new(),
+                    offset,
+                });
+            }
+        }
+        XhtmlEvent::End(name) => {
+            if heading_level(name).is_some() {
+                if let Some(mut heading) = current.take() {
+                    heading.text = heading.text.trim().to_string();
+                    if !heading.text.is_empty() {
+                        headings.push(heading);
+                    }
+                }
+            }
+        }
+        XhtmlEvent::Text(text) => {
+            if let Some(heading) = current.as_mut() {
+                heading.text.push_str(text);
+            }
+            offset += text.chars().count();
+        }
+    });
+
+    headings
+}
+
+/// The style of a paragraph, derived from the block element it belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParagraphStyle {
+    /// A heading, with its level from 1 (`<h1>`) to 6 (`<h6>`)
+    Heading(u8),
+    /// An item of an ordered or unordered list
+    ListItem,
+    /// A quotation (`<blockquote>`)
+    Blockquote,
+    /// Any other block of text, such as a `<p>` or a table cell
+    Normal,
+}
+
+/// A paragraph of a document, see [`crate::Extractor::extract_paragraphs`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paragraph {
+    /// The text of the paragraph, with its whitespace collapsed
+    pub text: String,
+    /// The style of the paragraph
+    pub style: ParagraphStyle,
+}
+
+/// Returns true if the element separates paragraphs
+fn is_block(name: &str) -> bool {
+    heading_level(name).is_some()
+        || matches!(
+            name,
+            "p" | "div"
+                | "li"
+                | "ul"
+                | "ol"
+                | "dl"
+                | "dt"
+                | "dd"
+                | "blockquote"
+                | "pre"
+                | "table"
+                | "tr"
+                | "td"
+                | "th"
+        )
+}
+
+/// Returns the style of text within the given block elements, innermost last
+fn paragraph_style(blocks: &[String]) -> ParagraphStyle {
+    if let Some(level) = blocks.iter().rev().find_map(|name| heading_level(name)) {
+        ParagraphStyle::Heading(level)
+    } else if blocks.iter().any(|name| name == "li") {
+        ParagraphStyle::ListItem
+    } else if blocks.iter().any(|name| name == "blockquote") {
+        ParagraphStyle::Blockquote
+    } else {
+        ParagraphStyle::Normal
+    }
+}
+
+/// Splits tika's XHTML output into paragraphs at every block element. Text directly within a
+/// container, such as the text of a list item before a nested list, is a paragraph of its own.
+/// Empty paragraphs are dropped.
+pub(crate) fn paragraphs_from_xhtml(xhtml: &str) -> Vec<Paragraph> {
+    let mut paragraphs = Vec::new();
+    // The block elements currently open, innermost last
+    let mut blocks: Vec<String> = Vec::new();
+    let mut text = String::new();
+
+    let mut flush = |text: &mut String, blocks: &[String]| {
+        let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !collapsed.is_empty() {
+            paragraphs.push(Paragraph {
+                text: collapsed,
+                style: paragraph_style(blocks),
+            });
+        }
+        text.clear();
+    };
+
+    walk_body(xhtml, |event| match event {
+        XhtmlEvent::Start(element) => {
+            if element.name == "br" {
+                text.push(' ');
+            } else if is_block(&element.name) {
+                flush(&mut text, &blocks);
+                blocks.push(element.name.clone());
+            }
+        }
+        XhtmlEvent::End(name) => {
+            if is_block(name) {
+                flush(&mut text, &blocks);
+                // Tolerate unbalanced elements by closing up to the matching one
+                if let Some(pos) = blocks.iter().rposition(|block| block == name) {
+                    blocks.truncate(pos);
+                }
+            }
+        }
+        XhtmlEvent::Text(t) => text.push_str(t),
+    });
+    flush(&mut text, &blocks);
+
+    paragraphs
+}
+
+/// Number of characters of the text of a page in its [`PageMeta::preview`]
+pub const PAGE_PREVIEW_CHARS: usize = 200;
+
+/// Lightweight description of a page, sheet or slide, passed to the predicate of
+/// [`crate::Extractor::extract_pages_where`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageMeta {
+    /// The index of the page in the document, starting at 0
+    pub index: usize,
+    /// The first [`PAGE_PREVIEW_CHARS`] characters of the text of the page, with its whitespace
+    /// collapsed
+    pub preview: String,
+}
+
+/// A page, sheet or slide of a document, see [`crate::Extractor::extract_pages_where`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    /// The index of the page in the document, starting at 0
+    pub index: usize,
+    /// The text of the page
+    pub text: String,
+}
+
+impl Page {
+    /// Returns the description of this page passed to the predicate
+    pub(crate) fn meta(&self) -> PageMeta {
+        let mut preview = self.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if let Some((end, _)) = preview.char_indices().nth(PAGE_PREVIEW_CHARS) {
+            preview.truncate(end);
+        }
+        PageMeta {
+            index: self.index,
+            preview,
+        }
+    }
+}
+
+/// Returns true if the element holds a page: the pages of a PDF, the sheets of a spreadsheet
+/// and the slides of a presentation
+fn is_page(element: &XhtmlElement) -> bool {
+    element.name == "div" && (element.has_class("page") || element.has_class("slide-content"))
+}
+
+/// Splits tika's XHTML output into pages at the `<div class="page">` and
+/// `<div class="slide-content">` elements. The text outside of them, such as slide notes, is
+/// not part of any page. A document without such elements is a single page.
+pub(crate) fn pages_from_xhtml(xhtml: &str) -> Vec<Page> {
+    let mut pages = Vec::new();
+    let mut body = String::new();
+    // The text of the page currently being read, and the depth of the divs nested in it
+    let mut current: Option<(String, usize)> = None;
+
+    let finish = |text: String, pages: &mut Vec<Page>| {
+        pages.push(Page {
+            index: pages.len(),
+            text: text.trim().to_string(),
+        });
+    };
+
+    walk_body(xhtml, |event| match event {
+        XhtmlEvent::Start(element) => match current.as_mut() {
+            Some((_, depth)) if element.name == "div" => *depth += 1,
+            None if is_page(element) => current = Some((String::new(), 0)),
+            _ => (),
+        },
+        XhtmlEvent::End(name) => {
+            if name == "div" {
+                match current.take() {
+                    Some((text, 0)) => finish(text, &mut pages),
+                    Some((text, depth)) => current = Some((text, depth - 1)),
+                    None => (),
+                }
+            }
+        }
+        XhtmlEvent::Text(text) => match current.as_mut() {
+            Some((page, _)) => page.push_str(text),
+            None => body.push_str(text),
+        },
+    });
+
+    // A page left open by truncated XHTML is kept with what was read
+    if let Some((text, _)) = current {
+        finish(text, &mut pages);
+    }
+    if pages.is_empty() {
+        finish(body, &mut pages);
+    }
+    pages
+}
+
+/// The text of a cell of a spreadsheet, as formatted in the sheet, with its whitespace collapsed
+pub type Cell = String;
+
+/// A sheet of a spreadsheet, see [`crate::Extractor::extract_sheets`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sheet {
+    /// The name of the sheet, empty if it is not known
+    pub name: String,
+    /// The rows of the sheet, each a row of cells. The empty cells between the filled ones are
+    /// kept, so the cells of a column have the same index in every row
+    pub rows: Vec<Vec<Cell>>,
+}
+
+/// A sheet being read from the XHTML
+#[derive(Default)]
+struct SheetBuilder {
+    name: String,
+    /// Whether the heading naming the sheet is being read
+    in_name: bool,
+    table: TableBuilder,
+    /// The depth of the divs nested in the sheet
+    depth: usize,
+}
+
+impl SheetBuilder {
+    fn finish(self) -> Sheet {
+        Sheet {
+            name: self.name.split_whitespace().collect::<Vec<_>>().join(" "),
+            rows: self.table.finish_rows(),
+        }
+    }
+}
+
+/// Splits tika's XHTML output of a spreadsheet into sheets at the `<div class="page">`
+/// elements, each named after the `<h1>` heading it starts with and holding the rows of its
+/// tables. A document without such elements is a single sheet without a name, or no sheet if
+/// it has no table either.
+pub(crate) fn sheets_from_xhtml(xhtml: &str) -> Vec<Sheet> {
+    let mut sheets = Vec::new();
+    let mut current: Option<SheetBuilder> = None;
+    // The tables outside of the sheets, for documents without them
+    let mut loose = SheetBuilder::default();
+
+    walk_body(xhtml, |event| match event {
+        XhtmlEvent::Start(element) => {
+            if element.name == "div" {
+                match current.as_mut() {
+                    Some(sheet) => sheet.depth += 1,
+                    None if element.has_class("page") => current = Some(SheetBuilder::default()),
+                    None => (),
+                }
+                return;
+            }
+            let sheet = current.as_mut().unwrap_or(&mut loose);
+            match element.name.as_str() {
+                "h1" if sheet.name.is_empty() && sheet.table.rows.is_empty() => {
+                    sheet.in_name = true
+                }
+                "tr" => sheet.table.start_row(),
+                "td" | "th" => sheet.table.start_cell(element),
+                _ => (),
+            }
+        }
+        XhtmlEvent::End(name) => {
+            if name == "div" {
+                match current.take() {
+                    Some(sheet) if sheet.depth == 0 => sheets.push(sheet.finish()),
+                    Some(mut sheet) => {
+                        sheet.depth -= 1;
+                        current = Some(sheet);
+                    }
+                    None => (),
+                }
+            }
+            let sheet = current.as_mut().unwrap_or(&mut loose);
+            match name {
+                "h1" => sheet.in_name = false,
+                "tr" => sheet.table.end_row(),
+                "td" | "th" => sheet.table.end_cell(),
+                // Keep the words of separate paragraphs within a cell apart
+                "p" | "div" | "br" | "li" => {
+                    if let Some((text, _, _)) = sheet.table.cell.as_mut() {
+                        text.push(' ');
+                    }
+                }
+                _ => (),
+            }
+        }
+        XhtmlEvent::Text(text) => {
+            let sheet = current.as_mut().unwrap_or(&mut loose);
+            if sheet.in_name {
+                sheet.name.push_str(text);
+            } else if let Some((cell_text, _, _)) = sheet.table.cell.as_mut() {
+                cell_text.push_str(text);
+            }
+        }
+    });
+
+    // A sheet left open by truncated XHTML is kept with what was read
+    if let Some(sheet) = current {
+        sheets.push(sheet.finish());
+    }
+    if sheets.is_empty() {
+        let sheet = loose.finish();
+        if !sheet.rows.is_empty() {
+            sheets.push(sheet);
+        }
+    }
+    sheets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XHTML: &str = r#"<html xmlns="http://www.w3.org/1999/xhtml"><head><title>t</title></head>
+<body><h1>Intro</h1>
+<p>Some &amp; text</p>
+<h2> Details </h2>
+<p>More</p>
+<h3></h3>
+</body></html>"#;
+
+    #[test]
+    fn outline_from_xhtml_test() {
+        let outline = outline_from_xhtml(XHTML);
+        assert_eq!(
+            outline,
+            vec![
+                Heading {
+                    level: 1,
+                    text: "Intro".to_string(),
+                    offset: 0
+                },
+                Heading {
+                    level: 2,
+                    text: "Details".to_string(),
+                    offset: 18
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn outline_from_xhtml_no_headings_test() {
+        let outline = outline_from_xhtml("<html><body><p>text</p></body></html>");
+        assert!(outline.is_empty());
+    }
+
+    #[test]
+    fn paragraphs_from_xhtml_test() {
+        let xhtml = r#"<html><body><h2>Title</h2>
+<p>First   paragraph<br/>continued</p><p> </p>
+<ul><li>item<ul><li>nested</li></ul></li></ul>
+<blockquote><p>quoted</p></blockquote>
+<table><tr><td>cell</td></tr></table></body></html>"#;
+        let paragraph = |text: &str, style| Paragraph {
+            text: text.to_string(),
+            style,
+        };
+        assert_eq!(
+            paragraphs_from_xhtml(xhtml),
+            vec![
+                paragraph("Title", ParagraphStyle::Heading(2)),
+                paragraph("First paragraph continued", ParagraphStyle::Normal),
+                paragraph("item", ParagraphStyle::ListItem),
+                paragraph("nested", ParagraphStyle::ListItem),
+                paragraph("quoted", ParagraphStyle::Blockquote),
+                paragraph("cell", ParagraphStyle::Normal),
+            ]
+        );
+    }
+
+    #[test]
+    fn pages_from_xhtml_test() {
+        let xhtml = r#"<html><body><div class="page"><p>First <b>page</b></p>
+<div>nested</div></div>
+<div class="slide-content"><p>Second</p></div><div class="slide-notes">notes</div>
+</body></html>"#;
+        let pages = pages_from_xhtml(xhtml);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].index, 0);
+        assert_eq!(pages[0].meta().preview, "First page nested");
+        assert_eq!(pages[1].text, "Second");
+
+        let pages = pages_from_xhtml("<html><body><p>only   text</p></body></html>");
+        assert_eq!(
+            pages.iter().map(Page::meta).collect::<Vec<_>>(),
+            vec![PageMeta {
+                index: 0,
+                preview: "only text".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn sheets_from_xhtml_test() {
+        let xhtml = r#"<html><body><div class="page"><h1>Revenue</h1>
+<table><tbody><tr><td>Q1</td><td/><td>870</td></tr><tr><td>Q2</td></tr></tbody></table></div>
+<div class="page"><h1>Empty</h1></div></body></html>"#;
+        let sheets = sheets_from_xhtml(xhtml);
+        assert_eq!(sheets.len(), 2);
+        assert_eq!(sheets[0].name, "Revenue");
+        assert_eq!(
+            sheets[0].rows,
+            vec![
+                vec!["Q1".to_string(), String::new(), "870".to_string()],
+                vec!["Q2".to_string()],
+            ]
+        );
+        assert_eq!(sheets[1].name, "Empty");
+        assert!(sheets[1].rows.is_empty());
+
+        let sheets =
+            sheets_from_xhtml("<html><body><table><tr><td>a</td></tr></table></body></html>");
+        assert_eq!(sheets.len(), 1);
+        assert_eq!(sheets[0].name, "");
+        assert_eq!(sheets[0].rows, vec![vec!["a".to_string()]]);
+        assert!(sheets_from_xhtml("<html><body><p>text</p></body></html>").is_empty());
+    }
+}
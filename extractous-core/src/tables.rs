@@ -0,0 +1,285 @@
+use crate::xhtml::{walk_body, XhtmlElement, XhtmlEvent};
+
+/// A table of a document, see [`crate::Extractor::extract_tables`]
+///
+/// Cells spanning several columns or rows (`colspan`/`rowspan`) have their text repeated in
+/// every column and row they cover, so all the rows of a table have the same number of cells
+/// when the document's spans are consistent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    /// The cells of the header row: the first row of the table when it is part of a `<thead>`
+    /// or made of `<th>` cells only. Empty if the table has no header row, which is also the
+    /// case of the formats that do not mark them
+    pub headers: Vec<String>,
+    /// The rows of the table below the header row, each a row of cells
+    pub rows: Vec<Vec<String>>,
+}
+
+/// A table being read from the XHTML
+#[derive(Default)]
+pub(crate) struct TableBuilder {
+    pub(crate) rows: Vec<Vec<String>>,
+    row: Option<Vec<String>>,
+    /// The text, colspan and rowspan of the cell currently being read
+    pub(crate) cell: Option<(String, usize, usize)>,
+    /// For each column, the text and remaining rows of a cell spanning rows from above
+    row_spans: Vec<Option<(String, usize)>>,
+    /// Whether the rows being read are part of a `<thead>`
+    in_head: bool,
+    /// Whether the row being read only has header cells so far
+    row_is_header: bool,
+    /// Whether the first row of the table is a header row
+    has_header: bool,
+}
+
+impl TableBuilder {
+    /// Fills the columns covered by cells spanning from the rows above
+    fn fill_row_spans(&mut self) {
+        if let Some(row) = self.row.as_mut() {
+            while let Some(Some((text, remaining))) = self.row_spans.get_mut(row.len()) {
+                row.push(text.clone());
+                *remaining -= 1;
+                if *remaining == 0 {
+                    self.row_spans[row.len() - 1] = None;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn start_head(&mut self) {
+        self.in_head = true;
+    }
+
+    pub(crate) fn end_head(&mut self) {
+        self.end_row();
+        self.in_head = false;
+    }
+
+    pub(crate) fn start_row(&mut self) {
+        self.end_row();
+        self.row = Some(Vec::new());
+        self.row_is_header = true;
+    }
+
+    pub(crate) fn end_row(&mut self) {
+        self.end_cell();
+        self.fill_row_spans();
+        if let Some(row) = self.row.take() {
+            if !row.is_empty() {
+                if self.rows.is_empty() {
+                    self.has_header = self.row_is_header;
+                }
+                self.rows.push(row);
+            }
+        }
+    }
+
+    pub(crate) fn start_cell(&mut self, element: &XhtmlElement) {
+        self.end_cell();
+        if self.row.is_none() {
+            // Tolerate cells outside of a row
+            self.row = Some(Vec::new());
+            self.row_is_header = true;
+        }
+        if element.name != "th" && !self.in_head {
+            self.row_is_header = false;
+        }
+        self.fill_row_spans();
+        let span = |name| {
+            element
+                .attribute(name)
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(1)
+        };
+        self.cell = Some((String::new(), span("colspan"), span("rowspan")));
+    }
+
+    pub(crate) fn end_cell(&mut self) {
+        let (Some((text, colspan, rowspan)), Some(row)) = (self.cell.take(), self.row.as_mut())
+        else {
+            return;
+        };
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let column = row.len();
+        for _ in 0..colspan {
+            row.push(text.clone());
+        }
+        if rowspan > 1 {
+            if self.row_spans.len() < column + colspan {
+                self.row_spans.resize(column + colspan, None);
+            }
+            for span in &mut self.row_spans[column..column + colspan] {
+                *span = Some((text.clone(), rowspan - 1));
+            }
+        }
+    }
+
+    /// Returns all the rows of the table, the header row included
+    pub(crate) fn finish_rows(mut self) -> Vec<Vec<String>> {
+        self.end_row();
+        self.rows
+    }
+
+    pub(crate) fn finish(mut self) -> Table {
+        self.end_row();
+        let mut rows = self.rows;
+        let headers = if self.has_header {
+            rows.remove(0)
+        } else {
+            Vec::new()
+        };
+        Table { headers, rows }
+    }
+}
+
+/// Builds the tables from the `<table>`, `<thead>`, `<tr>`, `<td>` and `<th>` elements of
+/// tika's XHTML output, in the order the tables start. Nested tables are returned as separate
+/// tables, and their text is also part of the enclosing cell.
+pub(crate) fn tables_from_xhtml(xhtml: &str) -> Vec<Table> {
+    let mut tables: Vec<Option<Table>> = Vec::new();
+    // The tables currently open, with their index in `tables`
+    let mut open: Vec<(usize, TableBuilder)> = Vec::new();
+
+    walk_body(xhtml, |event| match event {
+        XhtmlEvent::Start(element) => match element.name.as_str() {
+            "table" => {
+                open.push((tables.len(), TableBuilder::default()));
+                tables.push(None);
+            }
+            "thead" => {
+                if let Some((_, table)) = open.last_mut() {
+                    table.start_head();
+                }
+            }
+            "tr" => {
+                if let Some((_, table)) = open.last_mut() {
+                    table.start_row();
+                }
+            }
+            "td" | "th" => {
+                if let Some((_, table)) = open.last_mut() {
+                    table.start_cell(element);
+                }
+            }
+            _ => (),
+        },
+        XhtmlEvent::End(name) => match name {
+            "table" => {
+                if let Some((index, table)) = open.pop() {
+                    let table = table.finish();
+                    // The text of a nested table also belongs to the enclosing cell
+                    if let Some((_, outer)) = open.last_mut() {
+                        if let Some((text, _, _)) = outer.cell.as_mut() {
+                            for row in std::iter::once(&table.headers).chain(&table.rows) {
+                                if row.is_empty() {
+                                    continue;
+                                }
+                                text.push(' ');
+                                text.push_str(&row.join(" "));
+                            }
+                        }
+                    }
+                    tables[index] = Some(table);
+                }
+            }
+            "thead" => {
+                if let Some((_, table)) = open.last_mut() {
+                    table.end_head();
+                }
+            }
+            "tr" => {
+                if let Some((_, table)) = open.last_mut() {
+                    table.end_row();
+                }
+            }
+            "td" | "th" => {
+                if let Some((_, table)) = open.last_mut() {
+                    table.end_cell();
+                }
+            }
+            // Keep the words of separate paragraphs within a cell apart
+            "p" | "div" | "br" | "li" => {
+                if let Some((text, _, _)) = open.last_mut().and_then(|(_, t)| t.cell.as_mut()) {
+                    text.push(' ');
+                }
+            }
+            _ => (),
+        },
+        XhtmlEvent::Text(text) => {
+            if let Some((cell_text, _, _)) = open.last_mut().and_then(|(_, t)| t.cell.as_mut()) {
+                cell_text.push_str(text);
+            }
+        }
+    });
+
+    // Tables left open by truncated XHTML are kept with what was read
+    while let Some((index, table)) = open.pop() {
+        tables[index] = Some(table.finish());
+    }
+    tables.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    #[test]
+    fn tables_from_xhtml_test() {
+        let xhtml = r#"<html><body><p>before</p><table>
+<tbody><tr><th>Name</th><th>Value</th></tr>
+<tr><td><p>a</p><p>b</p></td><td>1</td></tr></tbody>
+</table></body></html>"#;
+        assert_eq!(
+            tables_from_xhtml(xhtml),
+            vec![Table {
+                headers: row(&["Name", "Value"]),
+                rows: vec![row(&["a b", "1"])],
+            }]
+        );
+    }
+
+    #[test]
+    fn tables_from_xhtml_headers_test() {
+        let xhtml = r#"<html><body><table>
+<thead><tr><td>Name</td><td>Value</td></tr></thead>
+<tbody><tr><th>a</th><td>1</td></tr></tbody>
+</table><table><tr><td>x</td></tr><tr><th>not a header</th></tr></table></body></html>"#;
+        let tables = tables_from_xhtml(xhtml);
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].headers, row(&["Name", "Value"]));
+        assert_eq!(tables[0].rows, vec![row(&["a", "1"])]);
+        assert!(tables[1].headers.is_empty());
+        assert_eq!(tables[1].rows, vec![row(&["x"]), row(&["not a header"])]);
+    }
+
+    #[test]
+    fn tables_from_xhtml_spans_test() {
+        let xhtml = r#"<html><body><table>
+<tr><td colspan="2">wide</td><td rowspan="2">tall</td></tr>
+<tr><td>x</td><td>y</td></tr>
+</table></body></html>"#;
+        assert_eq!(
+            tables_from_xhtml(xhtml),
+            vec![Table {
+                headers: Vec::new(),
+                rows: vec![row(&["wide", "wide", "tall"]), row(&["x", "y", "tall"])],
+            }]
+        );
+    }
+
+    #[test]
+    fn tables_from_xhtml_nested_test() {
+        let xhtml = r#"<html><body><table><tr><td>outer<table><tr><td>inner</td></tr></table></td></tr></table>
+<table><tr><td>second</td></tr></table></body></html>"#;
+        let tables = tables_from_xhtml(xhtml);
+        assert_eq!(tables.len(), 3);
+        assert_eq!(tables[0].rows, vec![row(&["outer inner"])]);
+        assert_eq!(tables[1].rows, vec![row(&["inner"])]);
+        assert_eq!(tables[2].rows, vec![row(&["second"])]);
+    }
+}
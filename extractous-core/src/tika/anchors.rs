@@ -0,0 +1,35 @@
+use crate::errors::ExtractResult;
+use crate::tika::jni_utils::*;
+use crate::tika::parse::get_vm_attach_current_thread;
+use crate::tika::wrappers::JAnchoredTextResult;
+use crate::{AnchorGranularity, TextAnchor};
+use jni::objects::JValue;
+
+/// Extracts the text of a PDF with PDFBox, with an anchor per word or line giving the page and
+/// the box the text is drawn in.
+pub fn extract_text_anchors(
+    buffer: &[u8],
+    granularity: AnchorGranularity,
+) -> ExtractResult<(String, Vec<TextAnchor>)> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "extractTextAnchors",
+        "(Ljava/nio/ByteBuffer;Z)Lai/yobix/AnchoredTextResult;",
+        &[
+            (&byte_buffer).into(),
+            JValue::from(granularity == AnchorGranularity::Line),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JAnchoredTextResult::new(&mut env, call_result_obj)?;
+    Ok((result.text, result.anchors))
+}
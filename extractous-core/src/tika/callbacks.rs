@@ -0,0 +1,649 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Read};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use bytemuck::cast_slice;
+
+use crate::errors::{Error, ExtractResult};
+use crate::tika::jni_utils::jni_jobject_to_string;
+#[cfg(feature = "ocr")]
+use crate::OcrBackend;
+use crate::{DocumentInfo, EmbeddedFile, EmbeddedMeta, PageText, SaxEvent};
+use jni::objects::{JByteArray, JClass, JObjectArray, JString};
+use jni::sys::{jboolean, jfloat, jint, jlong, jstring, JNI_FALSE, JNI_TRUE};
+use jni::{JNIEnv, NativeMethod};
+
+/// Filter deciding which embedded documents are extracted
+pub(crate) type EmbeddedFilter = dyn FnMut(&EmbeddedMeta) -> bool + Send;
+
+/// Sink receiving the embedded documents unpacked from a parse
+pub(crate) type EmbeddedSink = dyn FnMut(EmbeddedFile) -> io::Result<()> + Send;
+
+/// Handler receiving the text of the pages of a parse. Returning false stops the parse
+pub(crate) type PageHandler = dyn FnMut(PageText) -> bool + Send;
+
+/// Handler receiving the SAX events of a parse
+pub(crate) type SaxHandler = dyn FnMut(SaxEvent) + Send;
+
+/// Reader of the input of a parse
+pub(crate) type InputReader = dyn Read + Send;
+
+/// Callback receiving the estimated progress of a parse
+pub(crate) type ProgressCallback = dyn Fn(f32) + Send + Sync;
+
+/// Shared reference to a [`ProgressCallback`] that can be cloned along with the extractor
+#[derive(Clone)]
+pub(crate) struct ProgressCallbackRef(pub(crate) Arc<ProgressCallback>);
+
+impl fmt::Debug for ProgressCallbackRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// Provider of the passwords of encrypted documents
+pub(crate) type PasswordProvider = dyn Fn(&DocumentInfo) -> Option<String> + Send + Sync;
+
+/// Shared reference to a [`PasswordProvider`] that can be cloned along with the extractor
+#[derive(Clone)]
+pub(crate) struct PasswordProviderRef(pub(crate) Arc<PasswordProvider>);
+
+impl fmt::Debug for PasswordProviderRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PasswordProvider")
+    }
+}
+
+/// Rust callbacks that the java side can invoke through native methods
+#[derive(Clone)]
+enum Callback {
+    #[cfg(feature = "ocr")]
+    Ocr(Arc<dyn OcrBackend>),
+    EmbeddedFilter(Arc<Mutex<Box<EmbeddedFilter>>>),
+    EmbeddedSink(Arc<Mutex<Box<EmbeddedSink>>>),
+    SaxHandler(Arc<Mutex<Box<SaxHandler>>>),
+    PageHandler(Arc<Mutex<Box<PageHandler>>>),
+    Progress(Arc<ProgressCallback>),
+    Password(Arc<PasswordProvider>),
+    Reader(Arc<Mutex<Box<InputReader>>>),
+}
+
+/// Registry of the callbacks, keyed by the handle passed to the java side.
+/// Java only ever sees the handle, so a callback invoked after its guard was dropped simply
+/// finds nothing instead of reading freed memory.
+fn registry() -> &'static Mutex<HashMap<jlong, Callback>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<jlong, Callback>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn lookup(handle: jlong) -> Option<Callback> {
+    registry().lock().ok()?.get(&handle).cloned()
+}
+
+/// Keeps a callback registered for as long as it is alive
+pub(crate) struct CallbackGuard {
+    handle: jlong,
+}
+
+impl CallbackGuard {
+    fn register(env: &mut JNIEnv, callback: Callback) -> ExtractResult<Self> {
+        static NEXT_HANDLE: AtomicI64 = AtomicI64::new(1);
+
+        register_natives(env)?;
+        let handle = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+        registry()
+            .lock()
+            .map_err(|_e| Error::Unknown("Callback registry is poisoned".to_string()))?
+            .insert(handle, callback);
+        Ok(Self { handle })
+    }
+
+    /// Registers an OCR backend, which is passed to java using the returned guard's handle
+    #[cfg(feature = "ocr")]
+    pub(crate) fn register_ocr_backend(
+        env: &mut JNIEnv,
+        backend: &Arc<dyn OcrBackend>,
+    ) -> ExtractResult<Self> {
+        Self::register(env, Callback::Ocr(Arc::clone(backend)))
+    }
+
+    /// Registers a filter for embedded documents, passed to java using the returned guard's handle
+    pub(crate) fn register_embedded_filter(
+        env: &mut JNIEnv,
+        filter: Box<EmbeddedFilter>,
+    ) -> ExtractResult<Self> {
+        Self::register(env, Callback::EmbeddedFilter(Arc::new(Mutex::new(filter))))
+    }
+
+    /// Registers a sink for unpacked embedded documents, passed to java using the returned
+    /// guard's handle
+    pub(crate) fn register_embedded_sink(
+        env: &mut JNIEnv,
+        sink: Box<EmbeddedSink>,
+    ) -> ExtractResult<Self> {
+        Self::register(env, Callback::EmbeddedSink(Arc::new(Mutex::new(sink))))
+    }
+
+    /// Registers a handler for SAX events, passed to java using the returned guard's handle
+    pub(crate) fn register_sax_handler(
+        env: &mut JNIEnv,
+        handler: Box<SaxHandler>,
+    ) -> ExtractResult<Self> {
+        Self::register(env, Callback::SaxHandler(Arc::new(Mutex::new(handler))))
+    }
+
+    /// Registers a handler for the text of pages, passed to java using the returned guard's
+    /// handle
+    pub(crate) fn register_page_handler(
+        env: &mut JNIEnv,
+        handler: Box<PageHandler>,
+    ) -> ExtractResult<Self> {
+        Self::register(env, Callback::PageHandler(Arc::new(Mutex::new(handler))))
+    }
+
+    /// Registers a progress callback, passed to java using the returned guard's handle
+    pub(crate) fn register_progress_callback(
+        env: &mut JNIEnv,
+        callback: &ProgressCallbackRef,
+    ) -> ExtractResult<Self> {
+        Self::register(env, Callback::Progress(Arc::clone(&callback.0)))
+    }
+
+    /// Registers a password provider, passed to java using the returned guard's handle
+    pub(crate) fn register_password_provider(
+        env: &mut JNIEnv,
+        provider: &PasswordProviderRef,
+    ) -> ExtractResult<Self> {
+        Self::register(env, Callback::Password(Arc::clone(&provider.0)))
+    }
+
+    /// Registers the reader of the input of a parse, passed to java using the returned guard's
+    /// handle
+    pub(crate) fn register_reader(
+        env: &mut JNIEnv,
+        reader: Box<InputReader>,
+    ) -> ExtractResult<Self> {
+        Self::register(env, Callback::Reader(Arc::new(Mutex::new(reader))))
+    }
+
+    /// The handle identifying the callback on the java side
+    pub(crate) fn handle(&self) -> jlong {
+        self.handle
+    }
+}
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        if let Ok(mut callbacks) = registry().lock() {
+            callbacks.remove(&self.handle);
+        }
+    }
+}
+
+/// Registers the rust implementations of the java native methods. Only done once per process
+fn register_natives(env: &mut JNIEnv) -> ExtractResult<()> {
+    static REGISTERED: Mutex<bool> = Mutex::new(false);
+
+    let mut registered = REGISTERED
+        .lock()
+        .map_err(|_e| Error::Unknown("Native registration is poisoned".to_string()))?;
+    if !*registered {
+        #[cfg(feature = "ocr")]
+        env.register_native_methods(
+            "ai/yobix/RustOcrParser",
+            &[NativeMethod {
+                name: "ocr".into(),
+                sig: "(J[B)Ljava/lang/String;".into(),
+                fn_ptr: native_ocr as *mut std::ffi::c_void,
+            }],
+        )?;
+        env.register_native_methods(
+            "ai/yobix/RustDocumentSelector",
+            &[NativeMethod {
+                name: "select".into(),
+                sig: "(JLjava/lang/String;Ljava/lang/String;J)Z".into(),
+                fn_ptr: native_select as *mut std::ffi::c_void,
+            }],
+        )?;
+        env.register_native_methods(
+            "ai/yobix/EmbeddedUnpacker",
+            &[NativeMethod {
+                name: "unpack".into(),
+                sig: "(JLjava/lang/String;Ljava/lang/String;Ljava/lang/String;[B)V".into(),
+                fn_ptr: native_unpack as *mut std::ffi::c_void,
+            }],
+        )?;
+        env.register_native_methods(
+            "ai/yobix/RustContentHandler",
+            &[
+                NativeMethod {
+                    name: "startElement".into(),
+                    sig: "(JLjava/lang/String;[Ljava/lang/String;)V".into(),
+                    fn_ptr: native_start_element as *mut std::ffi::c_void,
+                },
+                NativeMethod {
+                    name: "characters".into(),
+                    sig: "(JLjava/lang/String;)V".into(),
+                    fn_ptr: native_characters as *mut std::ffi::c_void,
+                },
+                NativeMethod {
+                    name: "endElement".into(),
+                    sig: "(JLjava/lang/String;)V".into(),
+                    fn_ptr: native_end_element as *mut std::ffi::c_void,
+                },
+            ],
+        )?;
+        env.register_native_methods(
+            "ai/yobix/PageTextHandler",
+            &[NativeMethod {
+                name: "page".into(),
+                sig: "(JILjava/lang/String;)V".into(),
+                fn_ptr: native_page as *mut std::ffi::c_void,
+            }],
+        )?;
+        env.register_native_methods(
+            "ai/yobix/ProgressReporter",
+            &[NativeMethod {
+                name: "progress".into(),
+                sig: "(JF)V".into(),
+                fn_ptr: native_progress as *mut std::ffi::c_void,
+            }],
+        )?;
+        env.register_native_methods(
+            "ai/yobix/RustPasswordProvider",
+            &[NativeMethod {
+                name: "password".into(),
+                sig: "(JLjava/lang/String;Ljava/lang/String;)Ljava/lang/String;".into(),
+                fn_ptr: native_password as *mut std::ffi::c_void,
+            }],
+        )?;
+        env.register_native_methods(
+            "ai/yobix/RustInputStream",
+            &[NativeMethod {
+                name: "read".into(),
+                sig: "(J[BII)I".into(),
+                fn_ptr: native_read as *mut std::ffi::c_void,
+            }],
+        )?;
+        *registered = true;
+    }
+    Ok(())
+}
+
+/// Implementation of `ai.yobix.RustOcrParser.ocr(long handle, byte[] image)`
+/// Errors and panics are turned into a java `IOException`
+#[cfg(feature = "ocr")]
+extern "system" fn native_ocr<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    image: JByteArray<'local>,
+) -> jstring {
+    let backend = match lookup(handle) {
+        Some(Callback::Ocr(backend)) => backend,
+        _ => {
+            env.throw_new("java/io/IOException", "OCR backend is no longer available")
+                .ok();
+            return std::ptr::null_mut();
+        }
+    };
+
+    let result = env
+        .convert_byte_array(&image)
+        .map_err(Error::JniError)
+        .and_then(|bytes| {
+            catch_unwind(AssertUnwindSafe(|| backend.ocr(&bytes)))
+                .unwrap_or_else(|_| Err(Error::Unknown("OCR backend panicked".to_string())))
+        });
+
+    match result.and_then(|text| env.new_string(text).map_err(Error::JniError)) {
+        Ok(text) => text.into_raw(),
+        Err(e) => {
+            env.throw_new("java/io/IOException", e.to_string()).ok();
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Implementation of `ai.yobix.RustDocumentSelector.select(long handle, String name,
+/// String mediaType, long size)`. Panics are turned into a java `RuntimeException`
+extern "system" fn native_select<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    name: JString<'local>,
+    media_type: JString<'local>,
+    size: jlong,
+) -> jboolean {
+    // Without a filter everything is extracted
+    let Some(Callback::EmbeddedFilter(filter)) = lookup(handle) else {
+        return JNI_TRUE;
+    };
+
+    let mut optional_string = |s: JString<'local>| {
+        if s.is_null() {
+            None
+        } else {
+            jni_jobject_to_string(&mut env, s.into()).ok()
+        }
+    };
+    let meta = EmbeddedMeta {
+        name: optional_string(name),
+        media_type: optional_string(media_type),
+        size: u64::try_from(size).ok(),
+    };
+
+    let selected = catch_unwind(AssertUnwindSafe(|| {
+        filter
+            .lock()
+            .map(|mut filter| (*filter)(&meta))
+            .unwrap_or(true)
+    }));
+    match selected {
+        Ok(true) => JNI_TRUE,
+        Ok(false) => JNI_FALSE,
+        Err(_) => {
+            env.throw_new("java/lang/RuntimeException", "Embedded filter panicked")
+                .ok();
+            JNI_FALSE
+        }
+    }
+}
+
+/// Implementation of `ai.yobix.EmbeddedUnpacker.unpack(long handle, String path, String name,
+/// String mediaType, byte[] data)`. Errors and panics of the sink, and a sink that is no longer
+/// available, are turned into a java `IOException`
+extern "system" fn native_unpack<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    path: JString<'local>,
+    name: JString<'local>,
+    media_type: JString<'local>,
+    data: JByteArray<'local>,
+) {
+    let Some(Callback::EmbeddedSink(sink)) = lookup(handle) else {
+        env.throw_new(
+            "java/io/IOException",
+            "Embedded sink is no longer available",
+        )
+        .ok();
+        return;
+    };
+
+    let read = |env: &mut JNIEnv<'local>| -> ExtractResult<EmbeddedFile> {
+        let name = if name.is_null() {
+            None
+        } else {
+            Some(jni_jobject_to_string(env, name.into())?)
+        };
+        Ok(EmbeddedFile {
+            path: jni_jobject_to_string(env, path.into())?,
+            name,
+            mime_type: jni_jobject_to_string(env, media_type.into())?,
+            data: env.convert_byte_array(&data)?,
+        })
+    };
+
+    let result = read(&mut env).map_err(io::Error::from).and_then(|file| {
+        catch_unwind(AssertUnwindSafe(|| {
+            let mut sink = sink
+                .lock()
+                .map_err(|_e| io::Error::other("Embedded sink is poisoned"))?;
+            (*sink)(file)
+        }))
+        .unwrap_or_else(|_| Err(io::Error::other("Embedded sink panicked")))
+    });
+    if let Err(e) = result {
+        env.throw_new("java/io/IOException", e.to_string()).ok();
+    }
+}
+
+/// Passes an event to the SAX handler behind the handle. Panics of the handler, and a handler
+/// that is no longer available, are turned into a java `SAXException`, which stops the parse
+fn dispatch_sax_event(env: &mut JNIEnv, handle: jlong, event: SaxEvent) {
+    let Some(Callback::SaxHandler(handler)) = lookup(handle) else {
+        env.throw_new(
+            "org/xml/sax/SAXException",
+            "Content handler is no longer available",
+        )
+        .ok();
+        return;
+    };
+
+    let handled = catch_unwind(AssertUnwindSafe(|| {
+        handler.lock().map(|mut handler| (*handler)(event)).is_ok()
+    }));
+    if !matches!(handled, Ok(true)) {
+        env.throw_new("org/xml/sax/SAXException", "Content handler panicked")
+            .ok();
+    }
+}
+
+/// Implementation of `ai.yobix.RustContentHandler.startElement(long handle, String name,
+/// String[] attributes)`, the attributes holding names and values in pairs
+extern "system" fn native_start_element<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    name: JString<'local>,
+    attributes: JObjectArray<'local>,
+) {
+    let read = |env: &mut JNIEnv<'local>| -> ExtractResult<(String, Vec<(String, String)>)> {
+        let name = jni_jobject_to_string(env, name.into())?;
+        let length = env.get_array_length(&attributes)?;
+        let mut pairs = Vec::with_capacity(length as usize / 2);
+        for i in (0..length - 1).step_by(2) {
+            let attribute_name = env.get_object_array_element(&attributes, i)?;
+            let attribute_value = env.get_object_array_element(&attributes, i + 1)?;
+            pairs.push((
+                jni_jobject_to_string(env, attribute_name)?,
+                jni_jobject_to_string(env, attribute_value)?,
+            ));
+        }
+        Ok((name, pairs))
+    };
+
+    match read(&mut env) {
+        Ok((name, attributes)) => dispatch_sax_event(
+            &mut env,
+            handle,
+            SaxEvent::StartElement {
+                name: &name,
+                attributes: &attributes,
+            },
+        ),
+        Err(e) => {
+            env.throw_new("org/xml/sax/SAXException", e.to_string())
+                .ok();
+        }
+    }
+}
+
+/// Implementation of `ai.yobix.RustContentHandler.characters(long handle, String text)`
+extern "system" fn native_characters<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    text: JString<'local>,
+) {
+    match jni_jobject_to_string(&mut env, text.into()) {
+        Ok(text) => dispatch_sax_event(&mut env, handle, SaxEvent::Characters(&text)),
+        Err(e) => {
+            env.throw_new("org/xml/sax/SAXException", e.to_string())
+                .ok();
+        }
+    }
+}
+
+/// Implementation of `ai.yobix.RustContentHandler.endElement(long handle, String name)`
+extern "system" fn native_end_element<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    name: JString<'local>,
+) {
+    match jni_jobject_to_string(&mut env, name.into()) {
+        Ok(name) => dispatch_sax_event(&mut env, handle, SaxEvent::EndElement { name: &name }),
+        Err(e) => {
+            env.throw_new("org/xml/sax/SAXException", e.to_string())
+                .ok();
+        }
+    }
+}
+
+/// Implementation of `ai.yobix.PageTextHandler.page(long handle, int pageNumber, String text)`.
+/// A handler that stops the parse, panics or is no longer available is turned into a java
+/// `SAXException`, which stops the parse
+extern "system" fn native_page<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    page_number: jint,
+    text: JString<'local>,
+) {
+    let Some(Callback::PageHandler(handler)) = lookup(handle) else {
+        env.throw_new(
+            "org/xml/sax/SAXException",
+            "Page handler is no longer available",
+        )
+        .ok();
+        return;
+    };
+
+    let text = match jni_jobject_to_string(&mut env, text.into()) {
+        Ok(text) => text,
+        Err(e) => {
+            env.throw_new("org/xml/sax/SAXException", e.to_string())
+                .ok();
+            return;
+        }
+    };
+    let page = PageText {
+        page_number: page_number.max(0) as u32,
+        text,
+    };
+    let handled = catch_unwind(AssertUnwindSafe(|| {
+        handler
+            .lock()
+            .map(|mut handler| (*handler)(page))
+            .unwrap_or(false)
+    }));
+    match handled {
+        Ok(true) => {}
+        Ok(false) => {
+            env.throw_new("org/xml/sax/SAXException", "Page handler stopped")
+                .ok();
+        }
+        Err(_) => {
+            env.throw_new("org/xml/sax/SAXException", "Page handler panicked")
+                .ok();
+        }
+    }
+}
+
+/// Implementation of `ai.yobix.ProgressReporter.progress(long handle, float fraction)`.
+/// Progress is only informative, so a callback that is no longer available or that panics is
+/// ignored and the parse goes on
+extern "system" fn native_progress<'local>(
+    _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    fraction: jfloat,
+) {
+    if let Some(Callback::Progress(callback)) = lookup(handle) {
+        catch_unwind(AssertUnwindSafe(|| callback(fraction))).ok();
+    }
+}
+
+/// Implementation of `ai.yobix.RustPasswordProvider.password(long handle, String name,
+/// String mediaType)`. Returns null when there is no password, which is also the case of a
+/// provider that is no longer available. Panics are turned into a java `RuntimeException`
+extern "system" fn native_password<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    name: JString<'local>,
+    media_type: JString<'local>,
+) -> jstring {
+    let Some(Callback::Password(provider)) = lookup(handle) else {
+        return std::ptr::null_mut();
+    };
+
+    let mut optional_string = |s: JString<'local>| {
+        if s.is_null() {
+            None
+        } else {
+            jni_jobject_to_string(&mut env, s.into()).ok()
+        }
+    };
+    let info = DocumentInfo {
+        name: optional_string(name),
+        media_type: optional_string(media_type),
+    };
+
+    match catch_unwind(AssertUnwindSafe(|| provider(&info))) {
+        Ok(Some(password)) => match env.new_string(password) {
+            Ok(password) => password.into_raw(),
+            // The pending java exception is thrown when returning
+            Err(_e) => std::ptr::null_mut(),
+        },
+        Ok(None) => std::ptr::null_mut(),
+        Err(_) => {
+            env.throw_new("java/lang/RuntimeException", "Password provider panicked")
+                .ok();
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Implementation of `ai.yobix.RustInputStream.read(long handle, byte[] b, int off, int len)`.
+/// Returns the number of bytes read, or -1 at the end of the input. Errors and panics of the
+/// reader, and a reader that is no longer available, are turned into a java `IOException`
+extern "system" fn native_read<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    b: JByteArray<'local>,
+    off: jint,
+    len: jint,
+) -> jint {
+    let Some(Callback::Reader(reader)) = lookup(handle) else {
+        env.throw_new("java/io/IOException", "Input reader is no longer available")
+            .ok();
+        return -1;
+    };
+
+    let mut buf = vec![0u8; len.max(0) as usize];
+    let read = catch_unwind(AssertUnwindSafe(|| {
+        let mut reader = reader
+            .lock()
+            .map_err(|_e| io::Error::other("Input reader is poisoned"))?;
+        loop {
+            match reader.read(&mut buf) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                result => return result,
+            }
+        }
+    }))
+    .unwrap_or_else(|_| Err(io::Error::other("Input reader panicked")));
+
+    match read {
+        Ok(0) if !buf.is_empty() => -1,
+        Ok(num_read) => {
+            let bytes: &[i8] = cast_slice(&buf[..num_read]);
+            match env.set_byte_array_region(&b, off, bytes) {
+                Ok(()) => num_read as jint,
+                // The pending java exception is thrown when returning
+                Err(_e) => -1,
+            }
+        }
+        Err(e) => {
+            env.throw_new("java/io/IOException", e.to_string()).ok();
+            -1
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::errors::ExtractResult;
+use crate::tika::jni_utils::*;
+use crate::tika::parse::get_vm_attach_current_thread;
+use crate::tika::wrappers::JCommentListResult;
+use crate::Comment;
+
+/// Collects the comments of an OOXML document with POI, with their author, timestamp and the
+/// comment they reply to.
+pub fn extract_comments(buffer: &[u8]) -> ExtractResult<Vec<Comment>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "extractComments",
+        "(Ljava/nio/ByteBuffer;)Lai/yobix/CommentListResult;",
+        &[(&byte_buffer).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JCommentListResult::new(&mut env, call_result_obj)?;
+    Ok(result.comments)
+}
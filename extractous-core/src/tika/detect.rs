@@ -0,0 +1,227 @@
+use crate::errors::{Error, ExtractResult};
+use crate::tika::jni_utils::*;
+use crate::tika::parse::{get_vm_attach_current_thread, new_ocr_config, register_ocr_backend};
+use crate::tika::wrappers::{JPDFParserConfig, JParseOptions, JStringResult};
+use crate::{DetectedLanguage, Detection, DetectionConfidence, ExtractionPlan, Extractor};
+use std::str::FromStr;
+
+/// Metadata key used by the java side to return the media type hierarchy
+const MEDIA_TYPE_HIERARCHY_KEY: &str = "X-Media-Type-Hierarchy";
+/// Metadata key used by the java side to return the confidence of a detection
+const DETECTION_CONFIDENCE_KEY: &str = "X-Detection-Confidence";
+/// Metadata key used by the java side to return the supported media types
+const SUPPORTED_MEDIA_TYPES_KEY: &str = "X-Supported-Media-Types";
+/// Metadata key used by the java side to return the installed OCR languages
+#[cfg(feature = "ocr")]
+const INSTALLED_OCR_LANGUAGES_KEY: &str = "X-Installed-OCR-Languages";
+/// Metadata keys used by the java side to return the extraction plan
+const SELECTED_PARSER_KEY: &str = "X-Selected-Parser";
+const OCR_WOULD_RUN_KEY: &str = "X-Ocr-Would-Run";
+
+/// Detects the media type of the given bytes with the extractor's detection mode and returns its
+/// type hierarchy, from the most specific type to the most generic one, using Tika's
+/// `MediaTypeRegistry`.
+pub fn media_type_hierarchy(buffer: &[u8], extractor: &Extractor) -> ExtractResult<Vec<String>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "mediaTypeHierarchy",
+        "(Ljava/nio/ByteBuffer;Lai/yobix/ParseOptions;)Lai/yobix/StringResult;",
+        &[(&byte_buffer).into(), (&j_parse_options.internal).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    // The content holds the most specific type and the metadata the whole chain
+    let mut result = JStringResult::new(&mut env, call_result_obj)?;
+    let hierarchy = result
+        .metadata
+        .remove(MEDIA_TYPE_HIERARCHY_KEY)
+        .unwrap_or_else(|| vec![result.content]);
+    Ok(hierarchy)
+}
+
+/// Detects the media type of the given bytes with the extractor's detection mode and rates the
+/// detection against the types detected from the content only and from the name only.
+pub fn detect_bytes(buffer: &[u8], extractor: &Extractor) -> ExtractResult<Detection> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "detectBytes",
+        "(Ljava/nio/ByteBuffer;Lai/yobix/ParseOptions;)Lai/yobix/StringResult;",
+        &[(&byte_buffer).into(), (&j_parse_options.internal).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    to_detection(result)
+}
+
+/// Detects the media type of the given file, from its content and its name, see [`detect_bytes`]
+pub fn detect_file(file_path: &str, extractor: &Extractor) -> ExtractResult<Detection> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "detectFile",
+        "(Ljava/lang/String;Lai/yobix/ParseOptions;)Lai/yobix/StringResult;",
+        &[(&file_path_val).into(), (&j_parse_options.internal).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    to_detection(result)
+}
+
+/// Reads the detected type and its confidence from the result of a detection
+fn to_detection(mut result: JStringResult) -> ExtractResult<Detection> {
+    let confidence = result
+        .metadata
+        .remove(DETECTION_CONFIDENCE_KEY)
+        .and_then(|values| values.into_iter().next())
+        .ok_or_else(|| Error::Unknown("The detection confidence is missing".to_string()))?;
+    let confidence = DetectionConfidence::from_str(&confidence)
+        .map_err(|e| Error::Unknown(format!("Invalid detection confidence: {}", e)))?;
+
+    Ok(Detection {
+        mime_type: result.content,
+        confidence,
+    })
+}
+
+/// Detects the language of the given text with Tika's Optimaize language detector
+pub fn detect_language(text: &str) -> ExtractResult<Option<DetectedLanguage>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let text_val = jni_new_string_as_jvalue(&mut env, text)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "detectLanguage",
+        "(Ljava/lang/String;)Lai/yobix/StringResult;",
+        &[(&text_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let mut result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(DetectedLanguage::take_from(&mut result.metadata))
+}
+
+/// Returns the sorted list of media types that the bundled Tika parsers can handle
+pub fn supported_media_types() -> ExtractResult<Vec<String>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "supportedMediaTypes",
+        "()Lai/yobix/StringResult;",
+        &[],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let mut result = JStringResult::new(&mut env, call_result_obj)?;
+    let mut types = result
+        .metadata
+        .remove(SUPPORTED_MEDIA_TYPES_KEY)
+        .unwrap_or_default();
+    types.sort();
+    Ok(types)
+}
+
+/// Returns the sorted list of languages installed for Tesseract
+#[cfg(feature = "ocr")]
+pub fn installed_ocr_languages() -> ExtractResult<Vec<String>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "installedOcrLanguages",
+        "()Lai/yobix/StringResult;",
+        &[],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let mut result = JStringResult::new(&mut env, call_result_obj)?;
+    let mut languages = result
+        .metadata
+        .remove(INSTALLED_OCR_LANGUAGES_KEY)
+        .unwrap_or_default();
+    languages.sort();
+    Ok(languages)
+}
+
+/// Detects the media type of the given bytes and resolves the parser that would handle them with
+/// the extractor's configuration, without parsing.
+pub fn explain(buffer: &[u8], extractor: &Extractor) -> ExtractResult<ExtractionPlan> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    // The backend is never called, but registering it makes its parser show up as selected
+    let ocr_backend = register_ocr_backend(&mut env, extractor)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, &extractor.pdf_config)?;
+    let j_ocr_conf = new_ocr_config(&mut env, extractor, ocr_backend.as_ref())?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "explain",
+        "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        )Lai/yobix/StringResult;",
+        &[
+            (&byte_buffer).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_ocr_conf).into(),
+            (&j_parse_options.internal).into(),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let mut result = JStringResult::new(&mut env, call_result_obj)?;
+    let mut take_first = |key: &str| {
+        result
+            .metadata
+            .remove(key)
+            .and_then(|values| values.into_iter().next())
+    };
+    let selected_parser = take_first(SELECTED_PARSER_KEY).filter(|parser| !parser.is_empty());
+    let ocr_would_run = take_first(OCR_WOULD_RUN_KEY).is_some_and(|value| value == "true");
+
+    Ok(ExtractionPlan {
+        detected_type: result.content,
+        selected_parser,
+        ocr_would_run,
+        pdf_config: extractor.pdf_config.clone(),
+        office_config: extractor.office_config.clone(),
+        #[cfg(feature = "ocr")]
+        ocr_config: extractor.ocr_config.clone(),
+    })
+}
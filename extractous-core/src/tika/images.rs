@@ -0,0 +1,28 @@
+use crate::errors::ExtractResult;
+use crate::tika::jni_utils::*;
+use crate::tika::parse::get_vm_attach_current_thread;
+use crate::tika::wrappers::JImageListResult;
+use crate::ImageRef;
+
+/// Collects the images of a document: the images drawn on the pages of a PDF, found with PDFBox,
+/// or the images embedded in other formats, found by a recursive parse.
+pub fn extract_images(buffer: &[u8]) -> ExtractResult<Vec<ImageRef>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "extractImages",
+        "(Ljava/nio/ByteBuffer;)Lai/yobix/ImageListResult;",
+        &[(&byte_buffer).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JImageListResult::new(&mut env, call_result_obj)?;
+    Ok(result.images)
+}
@@ -0,0 +1,28 @@
+use crate::errors::ExtractResult;
+use crate::tika::jni_utils::*;
+use crate::tika::parse::get_vm_attach_current_thread;
+use crate::tika::wrappers::JStringResult;
+
+/// Tells whether a document contains macros by looking for the storage of its VBA project, or
+/// of its other macros, without parsing it.
+pub fn has_macros(buffer: &[u8]) -> ExtractResult<bool> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "hasMacros",
+        "(Ljava/nio/ByteBuffer;)Lai/yobix/StringResult;",
+        &[(&byte_buffer).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    // The content holds true or false
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.content == "true")
+}
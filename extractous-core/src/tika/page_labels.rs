@@ -0,0 +1,34 @@
+use crate::errors::ExtractResult;
+use crate::tika::jni_utils::*;
+use crate::tika::parse::get_vm_attach_current_thread;
+use crate::tika::wrappers::JStringResult;
+
+/// Metadata key used by the java side to return the page labels
+const PDF_PAGE_LABELS_KEY: &str = "X-PDF-Page-Labels";
+
+/// Reads the page labels of a PDF with PDFBox's `PDPageLabels`, one per page in physical order.
+pub fn extract_pdf_page_labels(buffer: &[u8]) -> ExtractResult<Vec<String>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "extractPdfPageLabels",
+        "(Ljava/nio/ByteBuffer;)Lai/yobix/StringResult;",
+        &[(&byte_buffer).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    // The content holds the number of pages and the metadata the label of each
+    let mut result = JStringResult::new(&mut env, call_result_obj)?;
+    let labels = result
+        .metadata
+        .remove(PDF_PAGE_LABELS_KEY)
+        .unwrap_or_default();
+    Ok(labels)
+}
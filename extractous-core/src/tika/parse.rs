@@ -1,13 +1,14 @@
-use std::sync::OnceLock;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, OnceLock};
 
 use crate::errors::ExtractResult;
+use crate::tika::callbacks::{EmbeddedFilter, EmbeddedSink, InputReader, PageHandler, SaxHandler};
 use crate::tika::jni_utils::*;
 use crate::tika::wrappers::*;
-use crate::{
-    CharSet, Metadata, OfficeParserConfig, PdfParserConfig, StreamReader, TesseractOcrConfig,
-};
-use jni::objects::JValue;
-use jni::{AttachGuard, JavaVM};
+use crate::tika::CallbackGuard;
+use crate::{Extractor, Metadata, StreamReader};
+use jni::objects::{JObject, JValue};
+use jni::{AttachGuard, JNIEnv, JavaVM};
 
 /// Returns a reference to the shared VM isolate
 /// Instead of creating a new VM for every tika call, we create a single VM that is shared
@@ -18,28 +19,134 @@ pub(crate) fn vm() -> &'static JavaVM {
     GRAAL_VM.get_or_init(create_vm_isolate)
 }
 
-fn get_vm_attach_current_thread<'local>() -> ExtractResult<AttachGuard<'local>> {
+/// The environment of a thread attached to the VM for the duration of a call, with its own frame
+/// of local references. Threads that were already attached, such as the thread that created the
+/// VM, are not detached at the end of the call, so without the frame every local reference the
+/// call creates would be kept as long as the thread lives. On drop the frame is popped, freeing
+/// them, and the thread is detached if it was attached for this call.
+pub(crate) struct AttachedEnv<'local> {
+    env: AttachGuard<'local>,
+}
+
+impl<'local> Deref for AttachedEnv<'local> {
+    type Target = JNIEnv<'local>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.env
+    }
+}
+
+impl<'local> DerefMut for AttachedEnv<'local> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.env
+    }
+}
+
+impl Drop for AttachedEnv<'_> {
+    fn drop(&mut self) {
+        // Safety: the local references of the frame do not outlive the call that created this
+        // env, their values are converted to Rust values or global references before returning
+        unsafe {
+            self.env.pop_local_frame(&JObject::null()).ok();
+        }
+    }
+}
+
+pub(crate) fn get_vm_attach_current_thread<'local>() -> ExtractResult<AttachedEnv<'local>> {
     // Attaching a thead that is already attached is a no-op. Good to have this in case this method
     // is called from another thread
     let env = vm().attach_current_thread()?;
-    Ok(env)
+    // The capacity is only a minimum, the frame grows as needed
+    env.push_local_frame(16)?;
+    Ok(AttachedEnv { env })
+}
+
+/// Registers the extractor's OCR backend, if any, so the java side can call it while parsing
+#[cfg(feature = "ocr")]
+pub(crate) fn register_ocr_backend(
+    env: &mut JNIEnv,
+    extractor: &Extractor,
+) -> ExtractResult<Option<CallbackGuard>> {
+    extractor
+        .ocr_backend
+        .as_ref()
+        .map(|backend| CallbackGuard::register_ocr_backend(env, &backend.0))
+        .transpose()
+}
+
+/// Without the `ocr` feature there is no OCR backend to register
+#[cfg(not(feature = "ocr"))]
+pub(crate) fn register_ocr_backend(
+    _env: &mut JNIEnv,
+    _extractor: &Extractor,
+) -> ExtractResult<Option<CallbackGuard>> {
+    Ok(None)
+}
+
+/// Creates the java OCR config of the extractor, holding the handle of its OCR backend, if any.
+/// When the extractor keeps OCR warm, the config is created once and reused.
+#[cfg(feature = "ocr")]
+pub(crate) fn new_ocr_config<'local>(
+    env: &mut JNIEnv<'local>,
+    extractor: &Extractor,
+    ocr_backend: Option<&CallbackGuard>,
+) -> ExtractResult<JObject<'local>> {
+    // The handle of the backend changes with every extraction, so its config cannot be reused
+    let warm = match (&extractor.warm_ocr_config, ocr_backend) {
+        (Some(warm), None) => warm,
+        _ => {
+            let config = JTesseractOcrConfig::new(
+                env,
+                &extractor.ocr_config,
+                ocr_backend.map_or(0, CallbackGuard::handle),
+            )?;
+            return Ok(config.internal);
+        }
+    };
+
+    let mut cached = warm
+        .0
+        .lock()
+        .map_err(|_e| crate::Error::Unknown("Warm OCR config lock is poisoned".to_string()))?;
+    match cached.as_ref() {
+        Some((ocr_config, global)) if *ocr_config == extractor.ocr_config => {
+            Ok(env.new_local_ref(global)?)
+        }
+        _ => {
+            let config = JTesseractOcrConfig::new(env, &extractor.ocr_config, 0)?;
+            let global = env.new_global_ref(&config.internal)?;
+            *cached = Some((extractor.ocr_config.clone(), global));
+            Ok(config.internal)
+        }
+    }
+}
+
+/// Without the `ocr` feature the java side gets a config that skips OCR, as its parse methods
+/// still take one
+#[cfg(not(feature = "ocr"))]
+pub(crate) fn new_ocr_config<'local>(
+    env: &mut JNIEnv<'local>,
+    _extractor: &Extractor,
+    _ocr_backend: Option<&CallbackGuard>,
+) -> ExtractResult<JObject<'local>> {
+    let config = JDisabledOcrConfig::new(env)?;
+    Ok(config.internal)
 }
 
 fn parse_to_stream(
-    mut env: AttachGuard,
+    mut env: AttachedEnv,
     data_source_val: JValue,
-    char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    extractor: &Extractor,
     method_name: &str,
     signature: &str,
-) -> ExtractResult<(StreamReader, Metadata)> {
-    let charset_name_val = jni_new_string_as_jvalue(&mut env, &char_set.to_string())?;
-    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
-    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
-    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+) -> ExtractResult<(StreamReader<'static>, Metadata)> {
+    let charset_name_val =
+        jni_new_string_as_jvalue(&mut env, &extractor.stream_config.charset.to_string())?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, &extractor.pdf_config)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, &extractor.office_config)?;
+    let ocr_backend = register_ocr_backend(&mut env, extractor)?;
+    let j_ocr_conf = new_ocr_config(&mut env, extractor, ocr_backend.as_ref())?;
+    let mut j_parse_options = JParseOptions::new(&mut env, extractor)?;
 
     // Make the java parse call
     let call_result = jni_call_static_method(
@@ -52,57 +159,68 @@ fn parse_to_stream(
             (&charset_name_val).into(),
             (&j_pdf_conf.internal).into(),
             (&j_office_conf.internal).into(),
-            (&j_ocr_conf.internal).into(),
-            JValue::Bool(if as_xml { 1 } else { 0 }),
+            (&j_ocr_conf).into(),
+            (&j_parse_options.internal).into(),
         ],
     );
     let call_result_obj = call_result?.l()?;
 
     // Create and process the JReaderResult
     let result = JReaderResult::new(&mut env, call_result_obj)?;
-    let j_reader = JReaderInputStream::new(&mut env, result.java_reader)?;
+    let j_reader = JReaderInputStream::new(
+        &mut env,
+        result.java_reader,
+        extractor.stream_config.buffer_size,
+    )?;
 
-    Ok((StreamReader { inner: j_reader }, result.metadata))
+    // The callbacks must outlive the stream, as parsing continues while the stream is read
+    let mut reader = StreamReader::new(j_reader);
+    reader.ocr_backend = ocr_backend;
+    reader.progress = j_parse_options.progress.take();
+    reader.password = j_parse_options.password.take();
+    Ok((reader, result.metadata))
 }
 
 pub fn parse_file(
     file_path: &str,
-    char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool
-) -> ExtractResult<(StreamReader, Metadata)> {
+    extractor: &Extractor,
+) -> ExtractResult<(StreamReader<'static>, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
     let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
     parse_to_stream(
         env,
         (&file_path_val).into(),
-        char_set,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        extractor,
         "parseFile",
         "(Ljava/lang/String;\
         Ljava/lang/String;\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
+        Lai/yobix/ParseOptions;\
         )Lai/yobix/ReaderResult;",
     )
 }
 
-pub fn parse_bytes(
+/// Parses a byte buffer that Tika reads in place while the returned stream is read, so the stream
+/// borrows it
+pub fn parse_bytes<'a>(
+    buffer: &'a [u8],
+    extractor: &Extractor,
+) -> ExtractResult<(StreamReader<'a>, Metadata)> {
+    // Safety: the returned stream borrows the buffer
+    unsafe { parse_bytes_in_place(buffer, extractor) }
+}
+
+/// Parses a byte buffer that Tika reads in place while the returned stream is read
+///
+/// # Safety
+/// The buffer must be kept alive and unchanged as long as the returned stream
+unsafe fn parse_bytes_in_place(
     buffer: &[u8],
-    char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
-) -> ExtractResult<(StreamReader, Metadata)> {
+    extractor: &Extractor,
+) -> ExtractResult<(StreamReader<'static>, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
     // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
@@ -114,67 +232,88 @@ pub fn parse_bytes(
     parse_to_stream(
         env,
         (&byte_buffer).into(),
-        char_set,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        extractor,
         "parseBytes",
         "(Ljava/nio/ByteBuffer;\
         Ljava/lang/String;\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
+        Lai/yobix/ParseOptions;\
         )Lai/yobix/ReaderResult;",
     )
 }
 
-pub fn parse_url(
-    url: &str,
-    char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
-) -> ExtractResult<(StreamReader, Metadata)> {
+/// Parses a byte buffer shared with the returned stream. Tika reads the buffer in place while the
+/// stream is read, so the stream keeps it alive
+pub fn parse_shared_bytes(
+    buffer: Arc<[u8]>,
+    extractor: &Extractor,
+) -> ExtractResult<(StreamReader<'static>, Metadata)> {
+    // Safety: the returned stream holds the buffer
+    let (mut reader, metadata) = unsafe { parse_bytes_in_place(&buffer, extractor)? };
+    reader.bytes = Some(buffer);
+    Ok((reader, metadata))
+}
+
+/// Parses the input read from a rust reader. Tika reads it through a java `InputStream` while
+/// the returned stream is read, so the reader is kept by the returned stream
+pub fn parse_reader(
+    reader: Box<InputReader>,
+    extractor: &Extractor,
+) -> ExtractResult<(StreamReader<'static>, Metadata)> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let input = CallbackGuard::register_reader(&mut env, reader)?;
+    let (mut reader, metadata) = parse_to_stream(
+        env,
+        JValue::Long(input.handle()),
+        extractor,
+        "parseReader",
+        "(J\
+        Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        )Lai/yobix/ReaderResult;",
+    )?;
+    reader.input = Some(input);
+    Ok((reader, metadata))
+}
+
+pub fn parse_url(url: &str, extractor: &Extractor) -> ExtractResult<(StreamReader<'static>, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
     let url_val = jni_new_string_as_jvalue(&mut env, url)?;
     parse_to_stream(
         env,
         (&url_val).into(),
-        char_set,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        extractor,
         "parseUrl",
         "(Ljava/lang/String;\
         Ljava/lang/String;\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
+        Lai/yobix/ParseOptions;\
         )Lai/yobix/ReaderResult;",
     )
 }
 
 /// Parses a file to a JStringResult using the Apache Tika library.
 pub fn parse_to_string(
-    mut env: AttachGuard,
+    mut env: AttachedEnv,
     data_source_val: JValue,
-    max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    extractor: &Extractor,
     method_name: &str,
     signature: &str,
 ) -> ExtractResult<(String, Metadata)> {
-    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
-    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
-    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, &extractor.pdf_config)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, &extractor.office_config)?;
+    let ocr_backend = register_ocr_backend(&mut env, extractor)?;
+    let j_ocr_conf = new_ocr_config(&mut env, extractor, ocr_backend.as_ref())?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
 
     let call_result = jni_call_static_method(
         &mut env,
@@ -183,11 +322,11 @@ pub fn parse_to_string(
         signature,
         &[
             data_source_val,
-            JValue::Int(max_length),
+            JValue::Int(extractor.extract_string_max_length),
             (&j_pdf_conf.internal).into(),
             (&j_office_conf.internal).into(),
-            (&j_ocr_conf.internal).into(),
-            JValue::Bool(if as_xml { 1 } else { 0 }),
+            (&j_ocr_conf).into(),
+            (&j_parse_options.internal).into(),
         ],
     );
     let call_result_obj = call_result?.l()?;
@@ -200,11 +339,7 @@ pub fn parse_to_string(
 /// Parses a file to a string using the Apache Tika library.
 pub fn parse_file_to_string(
     file_path: &str,
-    max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    extractor: &Extractor,
 ) -> ExtractResult<(String, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
@@ -212,18 +347,14 @@ pub fn parse_file_to_string(
     parse_to_string(
         env,
         (&file_path_val).into(),
-        max_length,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        extractor,
         "parseFileToString",
         "(Ljava/lang/String;\
         I\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
+        Lai/yobix/ParseOptions;\
         )Lai/yobix/StringResult;",
     )
 }
@@ -231,11 +362,7 @@ pub fn parse_file_to_string(
 /// Parses bytes to a string using the Apache Tika library.
 pub fn parse_bytes_to_string(
     buffer: &[u8],
-    max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    extractor: &Extractor,
 ) -> ExtractResult<(String, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
@@ -248,49 +375,336 @@ pub fn parse_bytes_to_string(
     parse_to_string(
         env,
         (&byte_buffer).into(),
-        max_length,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        extractor,
         "parseBytesToString",
         "(Ljava/nio/ByteBuffer;\
         I\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
+        Lai/yobix/ParseOptions;\
         )Lai/yobix/StringResult;",
     )
 }
 
 /// Parses a url to a string using the Apache Tika library.
-pub fn parse_url_to_string(
-    url: &str,
-    max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
-) -> ExtractResult<(String, Metadata)> {
+pub fn parse_url_to_string(url: &str, extractor: &Extractor) -> ExtractResult<(String, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
     let url_val = jni_new_string_as_jvalue(&mut env, url)?;
     parse_to_string(
         env,
         (&url_val).into(),
-        max_length,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        extractor,
         "parseUrlToString",
         "(Ljava/lang/String;\
         I\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
+        Lai/yobix/ParseOptions;\
         )Lai/yobix/StringResult;",
     )
 }
+
+/// Parses a file for its metadata only using the Apache Tika library. The content is discarded,
+/// OCR is skipped and the embedded documents are not parsed.
+pub fn parse_file_to_metadata(file_path: &str, extractor: &Extractor) -> ExtractResult<Metadata> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, &extractor.pdf_config)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, &extractor.office_config)?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseFileToMetadata",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lai/yobix/ParseOptions;\
+        )Lai/yobix/StringResult;",
+        &[
+            (&file_path_val).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_parse_options.internal).into(),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.metadata)
+}
+
+/// Parses the file, passing the text of each of its pages to the handler, and returns the
+/// metadata
+pub fn parse_file_to_pages(
+    file_path: &str,
+    extractor: &Extractor,
+    handler: Box<PageHandler>,
+) -> ExtractResult<Metadata> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let handler = CallbackGuard::register_page_handler(&mut env, handler)?;
+    let ocr_backend = register_ocr_backend(&mut env, extractor)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, &extractor.pdf_config)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, &extractor.office_config)?;
+    let j_ocr_conf = new_ocr_config(&mut env, extractor, ocr_backend.as_ref())?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseFileToPages",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        J\
+        )Lai/yobix/StringResult;",
+        &[
+            (&file_path_val).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf).into(),
+            (&j_parse_options.internal).into(),
+            JValue::Long(handler.handle()),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.metadata)
+}
+
+/// Parses bytes and the documents embedded in them, recursively, using the Apache Tika library.
+/// Returns the metadata of every document, the container first, each holding its content.
+pub fn parse_bytes_recursive(
+    buffer: &[u8],
+    extractor: &Extractor,
+    filter: Option<Box<EmbeddedFilter>>,
+) -> ExtractResult<Vec<Metadata>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    parse_recursive(
+        env,
+        (&byte_buffer).into(),
+        extractor,
+        filter,
+        "parseBytesRecursive",
+        "(Ljava/nio/ByteBuffer;\
+        I\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        J\
+        )Lai/yobix/MetadataListResult;",
+    )
+}
+
+/// Parses a file and the documents embedded in it, recursively, see [`parse_bytes_recursive`]
+pub fn parse_file_recursive(
+    file_path: &str,
+    extractor: &Extractor,
+    filter: Option<Box<EmbeddedFilter>>,
+) -> ExtractResult<Vec<Metadata>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    parse_recursive(
+        env,
+        (&file_path_val).into(),
+        extractor,
+        filter,
+        "parseFileRecursive",
+        "(Ljava/lang/String;\
+        I\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        J\
+        )Lai/yobix/MetadataListResult;",
+    )
+}
+
+fn parse_recursive(
+    mut env: AttachedEnv,
+    data_source_val: JValue,
+    extractor: &Extractor,
+    filter: Option<Box<EmbeddedFilter>>,
+    method_name: &str,
+    signature: &str,
+) -> ExtractResult<Vec<Metadata>> {
+    let filter = filter
+        .map(|filter| CallbackGuard::register_embedded_filter(&mut env, filter))
+        .transpose()?;
+    let ocr_backend = register_ocr_backend(&mut env, extractor)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, &extractor.pdf_config)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, &extractor.office_config)?;
+    let j_ocr_conf = new_ocr_config(&mut env, extractor, ocr_backend.as_ref())?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        method_name,
+        signature,
+        &[
+            data_source_val,
+            JValue::Int(extractor.extract_string_max_length),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf).into(),
+            (&j_parse_options.internal).into(),
+            JValue::Long(filter.as_ref().map_or(0, CallbackGuard::handle)),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JMetadataListResult::new(&mut env, call_result_obj)?;
+    Ok(result.metadata_list)
+}
+
+/// Parses the bytes and returns the entities found in the document, as metadata
+pub fn parse_bytes_to_entities(buffer: &[u8], extractor: &Extractor) -> ExtractResult<Metadata> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let ocr_backend = register_ocr_backend(&mut env, extractor)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, &extractor.pdf_config)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, &extractor.office_config)?;
+    let j_ocr_conf = new_ocr_config(&mut env, extractor, ocr_backend.as_ref())?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseBytesToEntities",
+        "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        )Lai/yobix/StringResult;",
+        &[
+            (&byte_buffer).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf).into(),
+            (&j_parse_options.internal).into(),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.metadata)
+}
+
+/// Parses the bytes, passing the SAX events of the parse to the handler, and returns the metadata
+pub fn parse_bytes_to_handler(
+    buffer: &[u8],
+    extractor: &Extractor,
+    handler: Box<SaxHandler>,
+) -> ExtractResult<Metadata> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let handler = CallbackGuard::register_sax_handler(&mut env, handler)?;
+    let ocr_backend = register_ocr_backend(&mut env, extractor)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, &extractor.pdf_config)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, &extractor.office_config)?;
+    let j_ocr_conf = new_ocr_config(&mut env, extractor, ocr_backend.as_ref())?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseBytesToHandler",
+        "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        J\
+        )Lai/yobix/StringResult;",
+        &[
+            (&byte_buffer).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf).into(),
+            (&j_parse_options.internal).into(),
+            JValue::Long(handler.handle()),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.metadata)
+}
+
+/// Parses the bytes, passing each embedded document of the parse to the sink, and returns the
+/// metadata of the container
+pub fn parse_bytes_to_unpacker(
+    buffer: &[u8],
+    extractor: &Extractor,
+    sink: Box<EmbeddedSink>,
+) -> ExtractResult<Metadata> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let sink = CallbackGuard::register_embedded_sink(&mut env, sink)?;
+    let ocr_backend = register_ocr_backend(&mut env, extractor)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, &extractor.pdf_config)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, &extractor.office_config)?;
+    let j_ocr_conf = new_ocr_config(&mut env, extractor, ocr_backend.as_ref())?;
+    let j_parse_options = JParseOptions::new(&mut env, extractor)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "unpackBytes",
+        "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        J\
+        )Lai/yobix/StringResult;",
+        &[
+            (&byte_buffer).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf).into(),
+            (&j_parse_options.internal).into(),
+            JValue::Long(sink.handle()),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.metadata)
+}
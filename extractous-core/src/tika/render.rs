@@ -0,0 +1,32 @@
+use crate::errors::ExtractResult;
+use crate::tika::jni_utils::*;
+use crate::tika::parse::get_vm_attach_current_thread;
+use crate::tika::wrappers::JBytesResult;
+use jni::objects::JValue;
+
+/// Renders the given page, starting at 1, of a PDF to a PNG image at the given resolution,
+/// using PDFBox's `PDFRenderer`.
+pub fn render_pdf_page(buffer: &[u8], page: u32, dpi: u32) -> ExtractResult<Vec<u8>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "renderPdfPage",
+        "(Ljava/nio/ByteBuffer;II)Lai/yobix/BytesResult;",
+        &[
+            (&byte_buffer).into(),
+            JValue::Int(page.min(i32::MAX as u32) as i32),
+            JValue::Int(dpi.min(i32::MAX as u32) as i32),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JBytesResult::new(&mut env, call_result_obj)?;
+    Ok(result.content)
+}
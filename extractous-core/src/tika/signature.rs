@@ -0,0 +1,49 @@
+use crate::errors::ExtractResult;
+use crate::tika::jni_utils::*;
+use crate::tika::parse::get_vm_attach_current_thread;
+use crate::tika::wrappers::JStringResult;
+use crate::SignatureStatus;
+
+/// Metadata keys used by the java side to return the signer or the reason of the failure
+const SIGNATURE_SIGNER_KEY: &str = "X-Signature-Signer";
+const SIGNATURE_REASON_KEY: &str = "X-Signature-Reason";
+
+/// Verifies the digital signatures of a PDF, with PDFBox and Bouncy Castle, or of an OOXML
+/// document, with POI.
+pub fn verify_signature(buffer: &[u8]) -> ExtractResult<SignatureStatus> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "verifySignature",
+        "(Ljava/nio/ByteBuffer;)Lai/yobix/StringResult;",
+        &[(&byte_buffer).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    // The content holds the status and the metadata its detail
+    let mut result = JStringResult::new(&mut env, call_result_obj)?;
+    let mut take_first = |key: &str| {
+        result
+            .metadata
+            .remove(key)
+            .and_then(|values| values.into_iter().next())
+            .unwrap_or_default()
+    };
+    let status = match result.content.as_str() {
+        "VALID" => SignatureStatus::Valid {
+            signer: take_first(SIGNATURE_SIGNER_KEY),
+        },
+        "INVALID" => SignatureStatus::Invalid {
+            reason: take_first(SIGNATURE_REASON_KEY),
+        },
+        _ => SignatureStatus::Unsigned,
+    };
+    Ok(status)
+}
@@ -3,10 +3,19 @@ use crate::tika::jni_utils::{
     jni_call_method, jni_jobject_to_string, jni_new_string_as_jvalue,
     jni_tika_metadata_to_rust_metadata,
 };
-use crate::tika::vm;
-use crate::{Metadata, OfficeParserConfig, PdfParserConfig, TesseractOcrConfig, DEFAULT_BUF_SIZE};
+use crate::tika::parse::get_vm_attach_current_thread;
+use crate::tika::CallbackGuard;
+#[cfg(feature = "ocr")]
+use crate::TesseractOcrConfig;
+use crate::{
+    Annotation, Comment, Extractor, FontInfo, FormField, FormFieldKind, HttpConfig, ImageRef,
+    Metadata, OfficeParserConfig, Outline, PdfAttachment, PdfOcrStrategy, PdfParserConfig, Slide,
+    TextAnchor,
+};
 use bytemuck::cast_slice_mut;
-use jni::objects::{GlobalRef, JByteArray, JObject, JValue};
+use jni::objects::{GlobalRef, JByteArray, JFloatArray, JIntArray, JObject, JObjectArray, JValue};
+#[cfg(feature = "ocr")]
+use jni::sys::jlong;
 use jni::sys::jsize;
 use jni::JNIEnv;
 
@@ -24,9 +33,10 @@ impl JReaderInputStream {
     pub(crate) fn new<'local>(
         env: &mut JNIEnv<'local>,
         obj: JObject<'local>,
+        buffer_size: usize,
     ) -> ExtractResult<Self> {
-        // Creates new jbyte array
-        let capacity = DEFAULT_BUF_SIZE as jsize;
+        // Creates new jbyte array, grown by read if it is given a larger buffer
+        let capacity = buffer_size.min(jsize::MAX as usize) as jsize;
         let jbyte_array = env.new_byte_array(capacity)?;
 
         Ok(Self {
@@ -37,7 +47,7 @@ impl JReaderInputStream {
     }
 
     pub(crate) fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let mut env = vm().attach_current_thread().map_err(Error::JniError)?;
+        let mut env = get_vm_attach_current_thread()?;
 
         let length = buf.len() as jsize;
 
@@ -98,13 +108,41 @@ impl JReaderInputStream {
 
 impl Drop for JReaderInputStream {
     fn drop(&mut self) {
-        if let Ok(mut env) = vm().attach_current_thread() {
+        if let Ok(mut env) = get_vm_attach_current_thread() {
             // Call the Java Reader's `close` method
             jni_call_method(&mut env, &self.internal, "close", "()V", &[]).ok();
         }
     }
 }
 
+/// Converts the status and error message of a failed Java result object into an [`Error`].
+/// The message is not read for the statuses that have an allocation-free variant
+fn jni_result_error<'local>(
+    env: &mut JNIEnv<'local>,
+    obj: &JObject<'local>,
+) -> ExtractResult<Error> {
+    let status = jni_call_method(env, obj, "getStatus", "()B", &[])?.b()?;
+    match status {
+        5 => return Ok(Error::Rejected),
+        6 => return Ok(Error::UnexpectedEof),
+        7 => return Ok(Error::Timeout),
+        9 => return Ok(Error::EncryptedDocument),
+        _ => {}
+    }
+
+    let msg_obj = env
+        .call_method(obj, "getErrorMessage", "()Ljava/lang/String;", &[])?
+        .l()?;
+    let msg = jni_jobject_to_string(env, msg_obj)?;
+    Ok(match status {
+        1 => Error::IoError(msg),
+        2 => Error::ParseError(msg),
+        4 => Error::NoParser(msg),
+        8 => Error::OcrLanguageMissing(msg),
+        _ => Error::Unknown(msg),
+    })
+}
+
 /// Wrapper for the Java class  `ai.yobix.StringResult`
 /// Upon creation it parses the java StringResult object and saves the converted Rust string
 pub struct JStringResult {
@@ -117,16 +155,7 @@ impl<'local> JStringResult {
         let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
 
         if is_error {
-            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
-            let msg_obj = env
-                .call_method(&obj, "getErrorMessage", "()Ljava/lang/String;", &[])?
-                .l()?;
-            let msg = jni_jobject_to_string(env, msg_obj)?;
-            match status {
-                1 => Err(Error::IoError(msg)),
-                2 => Err(Error::ParseError(msg)),
-                _ => Err(Error::Unknown(msg)),
-            }
+            Err(jni_result_error(env, &obj)?)
         } else {
             let call_result_obj = env
                 .call_method(&obj, "getContent", "()Ljava/lang/String;", &[])?
@@ -146,6 +175,575 @@ impl<'local> JStringResult {
     }
 }
 
+/// Wrapper for the Java class  `ai.yobix.BytesResult`
+/// Upon creation it copies the bytes of the java BytesResult object into a Rust vector
+pub struct JBytesResult {
+    pub content: Vec<u8>,
+}
+
+impl<'local> JBytesResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let array_obj = jni_call_method(env, &obj, "getContent", "()[B", &[])?.l()?;
+            let content = env.convert_byte_array(JByteArray::from(array_obj))?;
+            Ok(Self { content })
+        }
+    }
+}
+
+/// Wrapper for the Java class  `ai.yobix.MetadataListResult`
+/// Upon creation it converts the metadata of every document of a recursive parse
+pub struct JMetadataListResult {
+    pub metadata_list: Vec<Metadata>,
+}
+
+impl<'local> JMetadataListResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let array_obj = jni_call_method(
+                env,
+                &obj,
+                "getMetadataList",
+                "()[Lorg/apache/tika/metadata/Metadata;",
+                &[],
+            )?
+            .l()?;
+            let array = JObjectArray::from(array_obj);
+            let length = env.get_array_length(&array)?;
+
+            let mut metadata_list = Vec::with_capacity(length as usize);
+            for i in 0..length {
+                let tika_metadata_obj = env.get_object_array_element(&array, i)?;
+                metadata_list.push(jni_tika_metadata_to_rust_metadata(env, tika_metadata_obj)?);
+            }
+            Ok(Self { metadata_list })
+        }
+    }
+}
+
+/// Wrapper for the Java class  `ai.yobix.ImageListResult`
+/// Upon creation it converts every `ai.yobix.ExtractedImage` of the result
+pub struct JImageListResult {
+    pub images: Vec<ImageRef>,
+}
+
+impl<'local> JImageListResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let array_obj =
+                jni_call_method(env, &obj, "getImages", "()[Lai/yobix/ExtractedImage;", &[])?
+                    .l()?;
+            let array = JObjectArray::from(array_obj);
+            let length = env.get_array_length(&array)?;
+
+            let mut images = Vec::with_capacity(length as usize);
+            for i in 0..length {
+                let image_obj = env.get_object_array_element(&array, i)?;
+
+                let data_obj = jni_call_method(env, &image_obj, "getData", "()[B", &[])?.l()?;
+                let data = env.convert_byte_array(JByteArray::from(data_obj))?;
+                let mime_obj =
+                    jni_call_method(env, &image_obj, "getMimeType", "()Ljava/lang/String;", &[])?
+                        .l()?;
+                let mime_type = jni_jobject_to_string(env, mime_obj)?;
+                // The java side uses 0 for an unknown page and null for an unknown bbox
+                let page = jni_call_method(env, &image_obj, "getPage", "()I", &[])?.i()?;
+                let bbox_obj = jni_call_method(env, &image_obj, "getBbox", "()[F", &[])?.l()?;
+                let bbox = if bbox_obj.is_null() {
+                    None
+                } else {
+                    let mut values = [0f32; 4];
+                    env.get_float_array_region(JFloatArray::from(bbox_obj), 0, &mut values)?;
+                    Some((values[0], values[1], values[2], values[3]))
+                };
+
+                images.push(ImageRef {
+                    data,
+                    mime_type,
+                    page: (page > 0).then_some(page as u32),
+                    bbox,
+                });
+                env.delete_local_ref(image_obj)?;
+            }
+            Ok(Self { images })
+        }
+    }
+}
+
+/// Wrapper for the Java class `ai.yobix.FontListResult`
+/// Upon creation it converts the java ExtractedFont objects into [`FontInfo`]s
+pub struct JFontListResult {
+    pub fonts: Vec<FontInfo>,
+}
+
+impl<'local> JFontListResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let array_obj =
+                jni_call_method(env, &obj, "getFonts", "()[Lai/yobix/ExtractedFont;", &[])?.l()?;
+            let array = JObjectArray::from(array_obj);
+            let length = env.get_array_length(&array)?;
+
+            let mut fonts = Vec::with_capacity(length as usize);
+            for i in 0..length {
+                let font_obj = env.get_object_array_element(&array, i)?;
+
+                let name_obj =
+                    jni_call_method(env, &font_obj, "getName", "()Ljava/lang/String;", &[])?.l()?;
+                let name = jni_jobject_to_string(env, name_obj)?;
+                let embedded = jni_call_method(env, &font_obj, "isEmbedded", "()Z", &[])?.z()?;
+                let subset = jni_call_method(env, &font_obj, "isSubset", "()Z", &[])?.z()?;
+                let type_obj =
+                    jni_call_method(env, &font_obj, "getFontType", "()Ljava/lang/String;", &[])?
+                        .l()?;
+                let font_type = jni_jobject_to_string(env, type_obj)?;
+
+                fonts.push(FontInfo {
+                    name,
+                    embedded,
+                    subset,
+                    font_type,
+                });
+                env.delete_local_ref(font_obj)?;
+            }
+            Ok(Self { fonts })
+        }
+    }
+}
+
+/// Wrapper for the Java class `ai.yobix.AnnotationListResult`
+/// Upon creation it converts the java ExtractedAnnotation objects into [`Annotation`]s
+pub struct JAnnotationListResult {
+    pub annotations: Vec<Annotation>,
+}
+
+impl<'local> JAnnotationListResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let array_obj = jni_call_method(
+                env,
+                &obj,
+                "getAnnotations",
+                "()[Lai/yobix/ExtractedAnnotation;",
+                &[],
+            )?
+            .l()?;
+            let array = JObjectArray::from(array_obj);
+            let length = env.get_array_length(&array)?;
+
+            let mut annotations = Vec::with_capacity(length as usize);
+            for i in 0..length {
+                let annotation_obj = env.get_object_array_element(&array, i)?;
+
+                let get_string = |env: &mut JNIEnv<'local>, method: &str| {
+                    let value_obj =
+                        jni_call_method(env, &annotation_obj, method, "()Ljava/lang/String;", &[])?
+                            .l()?;
+                    // The java side uses null for the values that are not set
+                    if value_obj.is_null() {
+                        Ok::<_, Error>(None)
+                    } else {
+                        Ok(Some(jni_jobject_to_string(env, value_obj)?))
+                    }
+                };
+                let annotation_type = get_string(env, "getSubtype")?.unwrap_or_default();
+                let author = get_string(env, "getAuthor")?;
+                let text = get_string(env, "getText")?;
+                let page = jni_call_method(env, &annotation_obj, "getPage", "()I", &[])?.i()?;
+                let bbox_obj =
+                    jni_call_method(env, &annotation_obj, "getBbox", "()[F", &[])?.l()?;
+                let mut values = [0f32; 4];
+                env.get_float_array_region(JFloatArray::from(bbox_obj), 0, &mut values)?;
+
+                annotations.push(Annotation {
+                    annotation_type,
+                    author,
+                    text,
+                    page: page as u32,
+                    bbox: (values[0], values[1], values[2], values[3]),
+                });
+                env.delete_local_ref(annotation_obj)?;
+            }
+            Ok(Self { annotations })
+        }
+    }
+}
+
+/// Wrapper for the Java class `ai.yobix.AttachmentListResult`
+/// Upon creation it converts the java ExtractedAttachment objects into [`PdfAttachment`]s
+pub struct JAttachmentListResult {
+    pub attachments: Vec<PdfAttachment>,
+}
+
+impl<'local> JAttachmentListResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let array_obj = jni_call_method(
+                env,
+                &obj,
+                "getAttachments",
+                "()[Lai/yobix/ExtractedAttachment;",
+                &[],
+            )?
+            .l()?;
+            let array = JObjectArray::from(array_obj);
+            let length = env.get_array_length(&array)?;
+
+            let mut attachments = Vec::with_capacity(length as usize);
+            for i in 0..length {
+                let attachment_obj = env.get_object_array_element(&array, i)?;
+
+                let get_string = |env: &mut JNIEnv<'local>, method: &str| {
+                    let value_obj =
+                        jni_call_method(env, &attachment_obj, method, "()Ljava/lang/String;", &[])?
+                            .l()?;
+                    // The java side uses null for the values that are not set
+                    if value_obj.is_null() {
+                        Ok::<_, Error>(None)
+                    } else {
+                        Ok(Some(jni_jobject_to_string(env, value_obj)?))
+                    }
+                };
+                let name = get_string(env, "getName")?.unwrap_or_default();
+                let file_name = get_string(env, "getFileName")?;
+                let description = get_string(env, "getDescription")?;
+                let relationship = get_string(env, "getRelationship")?;
+                let mime_type = get_string(env, "getMimeType")?;
+                let creation_date = get_string(env, "getCreationDate")?;
+                let modification_date = get_string(env, "getModificationDate")?;
+                let data_obj =
+                    jni_call_method(env, &attachment_obj, "getData", "()[B", &[])?.l()?;
+                let data = env.convert_byte_array(JByteArray::from(data_obj))?;
+
+                attachments.push(PdfAttachment {
+                    name,
+                    file_name,
+                    description,
+                    relationship,
+                    mime_type,
+                    creation_date,
+                    modification_date,
+                    data,
+                });
+                env.delete_local_ref(attachment_obj)?;
+            }
+            Ok(Self { attachments })
+        }
+    }
+}
+
+/// Wrapper for the Java class `ai.yobix.FormFieldListResult`
+/// Upon creation it reads the fields of the result into [`FormField`]s
+pub struct JFormFieldListResult {
+    pub fields: Vec<FormField>,
+}
+
+impl<'local> JFormFieldListResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let array_obj = jni_call_method(
+                env,
+                &obj,
+                "getFields",
+                "()[Lai/yobix/ExtractedFormField;",
+                &[],
+            )?
+            .l()?;
+            let array = JObjectArray::from(array_obj);
+            let length = env.get_array_length(&array)?;
+
+            let mut fields = Vec::with_capacity(length as usize);
+            for i in 0..length {
+                let field_obj = env.get_object_array_element(&array, i)?;
+
+                let get_string = |env: &mut JNIEnv<'local>, method: &str| {
+                    let value_obj =
+                        jni_call_method(env, &field_obj, method, "()Ljava/lang/String;", &[])?
+                            .l()?;
+                    // The java side uses null for the values that are not set
+                    if value_obj.is_null() {
+                        Ok::<_, Error>(None)
+                    } else {
+                        Ok(Some(jni_jobject_to_string(env, value_obj)?))
+                    }
+                };
+                let name = get_string(env, "getName")?.unwrap_or_default();
+                let kind = get_string(env, "getKind")?
+                    .and_then(|kind| kind.parse().ok())
+                    .unwrap_or(FormFieldKind::Other);
+                let value = get_string(env, "getValue")?;
+
+                fields.push(FormField { name, kind, value });
+                env.delete_local_ref(field_obj)?;
+            }
+            Ok(Self { fields })
+        }
+    }
+}
+
+/// Wrapper for the Java class `ai.yobix.OutlineListResult`
+/// Upon creation it reads the items of the result, and their children, into [`Outline`]s
+pub struct JOutlineListResult {
+    pub items: Vec<Outline>,
+}
+
+impl<'local> JOutlineListResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let array_obj = jni_call_method(
+                env,
+                &obj,
+                "getItems",
+                "()[Lai/yobix/ExtractedOutlineItem;",
+                &[],
+            )?
+            .l()?;
+            let items = Self::read_items(env, JObjectArray::from(array_obj))?;
+            Ok(Self { items })
+        }
+    }
+
+    /// Reads an array of `ai.yobix.ExtractedOutlineItem`, recursing into their children
+    fn read_items(
+        env: &mut JNIEnv<'local>,
+        array: JObjectArray<'local>,
+    ) -> ExtractResult<Vec<Outline>> {
+        let length = env.get_array_length(&array)?;
+
+        let mut items = Vec::with_capacity(length as usize);
+        for i in 0..length {
+            let item_obj = env.get_object_array_element(&array, i)?;
+
+            let title_obj =
+                jni_call_method(env, &item_obj, "getTitle", "()Ljava/lang/String;", &[])?.l()?;
+            let title = jni_jobject_to_string(env, title_obj)?;
+            let page = jni_call_method(env, &item_obj, "getPage", "()I", &[])?.i()?;
+            let children_obj = jni_call_method(
+                env,
+                &item_obj,
+                "getChildren",
+                "()[Lai/yobix/ExtractedOutlineItem;",
+                &[],
+            )?
+            .l()?;
+            let children = Self::read_items(env, JObjectArray::from(children_obj))?;
+
+            items.push(Outline {
+                title,
+                // The java side uses 0 for the items that point to no page
+                page: (page > 0).then_some(page as u32),
+                children,
+            });
+            env.delete_local_ref(item_obj)?;
+        }
+        env.delete_local_ref(array)?;
+        Ok(items)
+    }
+}
+
+/// Wrapper for the Java class `ai.yobix.CommentListResult`
+/// Upon creation it reads the comments of the result into [`Comment`]s
+pub struct JCommentListResult {
+    pub comments: Vec<Comment>,
+}
+
+impl<'local> JCommentListResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let array_obj = jni_call_method(
+                env,
+                &obj,
+                "getComments",
+                "()[Lai/yobix/ExtractedComment;",
+                &[],
+            )?
+            .l()?;
+            let array = JObjectArray::from(array_obj);
+            let length = env.get_array_length(&array)?;
+
+            let mut comments = Vec::with_capacity(length as usize);
+            for i in 0..length {
+                let comment_obj = env.get_object_array_element(&array, i)?;
+
+                let get_string = |env: &mut JNIEnv<'local>, method: &str| {
+                    let value_obj =
+                        jni_call_method(env, &comment_obj, method, "()Ljava/lang/String;", &[])?
+                            .l()?;
+                    // The java side uses null for the values that are not set
+                    if value_obj.is_null() {
+                        Ok::<_, Error>(None)
+                    } else {
+                        Ok(Some(jni_jobject_to_string(env, value_obj)?))
+                    }
+                };
+                comments.push(Comment {
+                    id: get_string(env, "getId")?.unwrap_or_default(),
+                    author: get_string(env, "getAuthor")?,
+                    timestamp: get_string(env, "getTimestamp")?,
+                    text: get_string(env, "getText")?.unwrap_or_default(),
+                    parent: get_string(env, "getParentId")?,
+                });
+                env.delete_local_ref(comment_obj)?;
+            }
+            Ok(Self { comments })
+        }
+    }
+}
+
+/// Wrapper for the Java class `ai.yobix.SlideListResult`
+/// Upon creation it converts the java ExtractedSlide objects into [`Slide`]s
+pub struct JSlideListResult {
+    pub slides: Vec<Slide>,
+}
+
+impl<'local> JSlideListResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let array_obj =
+                jni_call_method(env, &obj, "getSlides", "()[Lai/yobix/ExtractedSlide;", &[])?
+                    .l()?;
+            let array = JObjectArray::from(array_obj);
+            let length = env.get_array_length(&array)?;
+
+            let mut slides = Vec::with_capacity(length as usize);
+            for i in 0..length {
+                let slide_obj = env.get_object_array_element(&array, i)?;
+
+                let get_string = |env: &mut JNIEnv<'local>, method: &str| {
+                    let value_obj =
+                        jni_call_method(env, &slide_obj, method, "()Ljava/lang/String;", &[])?
+                            .l()?;
+                    // The java side uses null for the values that are not set
+                    if value_obj.is_null() {
+                        Ok::<_, Error>(None)
+                    } else {
+                        Ok(Some(jni_jobject_to_string(env, value_obj)?))
+                    }
+                };
+                let index = jni_call_method(env, &slide_obj, "getIndex", "()I", &[])?.i()?;
+                slides.push(Slide {
+                    index: index as usize,
+                    title: get_string(env, "getTitle")?,
+                    body: get_string(env, "getBody")?.unwrap_or_default(),
+                    notes: get_string(env, "getNotes")?.unwrap_or_default(),
+                });
+                env.delete_local_ref(slide_obj)?;
+            }
+            Ok(Self { slides })
+        }
+    }
+}
+
+/// Wrapper for the Java class `ai.yobix.AnchoredTextResult`
+/// Upon creation it reads the text and turns the parallel arrays of the anchors into
+/// [`TextAnchor`]s, converting their UTF-16 offsets to character offsets
+pub struct JAnchoredTextResult {
+    pub text: String,
+    pub anchors: Vec<TextAnchor>,
+}
+
+impl<'local> JAnchoredTextResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            Err(jni_result_error(env, &obj)?)
+        } else {
+            let text_obj =
+                jni_call_method(env, &obj, "getText", "()Ljava/lang/String;", &[])?.l()?;
+            let text = jni_jobject_to_string(env, text_obj)?;
+
+            let pages_obj = jni_call_method(env, &obj, "getPages", "()[I", &[])?.l()?;
+            let pages_array = JIntArray::from(pages_obj);
+            let count = env.get_array_length(&pages_array)? as usize;
+            let mut pages = vec![0; count];
+            env.get_int_array_region(&pages_array, 0, &mut pages)?;
+
+            let offsets_obj = jni_call_method(env, &obj, "getOffsets", "()[I", &[])?.l()?;
+            let mut offsets = vec![0; 2 * count];
+            env.get_int_array_region(JIntArray::from(offsets_obj), 0, &mut offsets)?;
+
+            let boxes_obj = jni_call_method(env, &obj, "getBoxes", "()[F", &[])?.l()?;
+            let mut boxes = vec![0f32; 4 * count];
+            env.get_float_array_region(JFloatArray::from(boxes_obj), 0, &mut boxes)?;
+
+            // The offsets only grow, so they are all converted in a single pass over the text
+            let mut chars = text.chars();
+            let (mut utf16, mut position) = (0, 0);
+            let offsets: Vec<usize> = offsets
+                .into_iter()
+                .map(|offset| {
+                    while utf16 < offset.max(0) as usize {
+                        match chars.next() {
+                            Some(c) => {
+                                utf16 += c.len_utf16();
+                                position += 1;
+                            }
+                            None => break,
+                        }
+                    }
+                    position
+                })
+                .collect();
+
+            let anchors = (0..count)
+                .map(|i| TextAnchor {
+                    offset: offsets[2 * i],
+                    len: offsets[2 * i + 1].saturating_sub(offsets[2 * i]),
+                    page: pages[i].max(0) as u32,
+                    bbox: (
+                        boxes[4 * i],
+                        boxes[4 * i + 1],
+                        boxes[4 * i + 2],
+                        boxes[4 * i + 3],
+                    ),
+                })
+                .collect();
+            Ok(Self { text, anchors })
+        }
+    }
+}
+
 /// Wrapper for the Java class  `ai.yobix.ReaderResult`
 /// Upon creation it parses the java ReaderResult object and saves the java
 /// `org.apache.commons.io.input.ReaderInputStream` object, which later can be used for reading
@@ -159,16 +757,7 @@ impl<'local> JReaderResult<'local> {
         let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
 
         if is_error {
-            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
-            let msg_obj = env
-                .call_method(&obj, "getErrorMessage", "()Ljava/lang/String;", &[])?
-                .l()?;
-            let msg = jni_jobject_to_string(env, msg_obj)?;
-            match status {
-                1 => Err(Error::IoError(msg)),
-                2 => Err(Error::ParseError(msg)),
-                _ => Err(Error::Unknown(msg)),
-            }
+            Err(jni_result_error(env, &obj)?)
         } else {
             let reader_obj = jni_call_method(
                 env,
@@ -197,7 +786,8 @@ impl<'local> JReaderResult<'local> {
     }
 }
 
-/// Wrapper for [`JObject`]s that contain `org.apache.tika.parser.pdf.PDFParserConfig`.
+/// Wrapper for [`JObject`]s that contain `ai.yobix.ExtendedPDFParserConfig`, which extends
+/// `org.apache.tika.parser.pdf.PDFParserConfig`.
 /// Looks up the class and method IDs on creation rather than for every method call.
 pub(crate) struct JPDFParserConfig<'local> {
     pub(crate) internal: JObject<'local>,
@@ -207,8 +797,8 @@ impl<'local> JPDFParserConfig<'local> {
     /// Creates a new object instance of `JPDFParserConfig` in the java world
     /// keeps reference to the object and method IDs for later use
     pub(crate) fn new(env: &mut JNIEnv<'local>, config: &PdfParserConfig) -> ExtractResult<Self> {
-        // Create the java object
-        let class = env.find_class("org/apache/tika/parser/pdf/PDFParserConfig")?;
+        // Create the java object, extended with the extractous specific settings
+        let class = env.find_class("ai/yobix/ExtendedPDFParserConfig")?;
         let obj = env.new_object(&class, "()V", &[])?;
 
         // Call the setters
@@ -242,9 +832,53 @@ impl<'local> JPDFParserConfig<'local> {
             "(Z)V",
             &[JValue::from(config.extract_annotation_text)],
         )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setSortByPosition",
+            "(Z)V",
+            &[JValue::from(config.sort_by_position)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setSuppressDuplicateOverlappingText",
+            "(Z)V",
+            &[JValue::from(config.suppress_duplicate_overlapping_text)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setDetectAngles",
+            "(Z)V",
+            &[JValue::from(config.detect_angles)],
+        )?;
+        // The tolerances are boxed on the java side, keep PDFBox's defaults unless they were set
+        let tolerances = [
+            ("setAverageCharTolerance", config.average_char_tolerance),
+            ("setSpacingTolerance", config.spacing_tolerance),
+        ];
+        for (setter, tolerance) in tolerances {
+            if let Some(tolerance) = tolerance {
+                let boxed =
+                    env.new_object("java/lang/Float", "(F)V", &[JValue::Float(tolerance)])?;
+                jni_call_method(
+                    env,
+                    &obj,
+                    setter,
+                    "(Ljava/lang/Float;)V",
+                    &[(&boxed).into()],
+                )?;
+            }
+        }
         // The PdfOcrStrategy enum names must match the Java org.apache.tika.parser.pdf
-        // .PDFParserConfig$OCR_STRATEGY enum names
-        let ocr_str_val = jni_new_string_as_jvalue(env, &config.ocr_strategy.to_string())?;
+        // .PDFParserConfig$OCR_STRATEGY enum names. Without the `ocr` feature pages are never OCR'd
+        let ocr_strategy = if cfg!(feature = "ocr") {
+            config.ocr_strategy
+        } else {
+            PdfOcrStrategy::NO_OCR
+        };
+        let ocr_str_val = jni_new_string_as_jvalue(env, &ocr_strategy.to_string())?;
         jni_call_method(
             env,
             &obj,
@@ -252,12 +886,63 @@ impl<'local> JPDFParserConfig<'local> {
             "(Ljava/lang/String;)V",
             &[(&ocr_str_val).into()],
         )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOcrInlineImages",
+            "(Z)V",
+            &[JValue::from(config.ocr_inline_images)],
+        )?;
+        // Keep tika's default dpi unless it was explicitly set
+        if let Some(dpi) = config.ocr_render_dpi {
+            jni_call_method(env, &obj, "setOcrDPI", "(I)V", &[JValue::from(dpi)])?;
+        }
+        // The PdfOcrImageType names match the org.apache.pdfbox.rendering.ImageType enum names
+        let ocr_image_type_val = jni_new_string_as_jvalue(env, &config.ocr_image_type.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOcrImageType",
+            "(Ljava/lang/String;)V",
+            &[(&ocr_image_type_val).into()],
+        )?;
+        if let Some(quality) = config.ocr_image_quality {
+            jni_call_method(
+                env,
+                &obj,
+                "setOcrImageQuality",
+                "(F)V",
+                &[JValue::Float(quality)],
+            )?;
+        }
+        if let Some(password) = &config.password {
+            let password_val = jni_new_string_as_jvalue(env, password)?;
+            jni_call_method(
+                env,
+                &obj,
+                "setPassword",
+                "(Ljava/lang/String;)V",
+                &[(&password_val).into()],
+            )?;
+        }
+        if let Some((first, last)) = config.page_range {
+            // Java counts pages with ints, 0 meaning no range
+            let to_int = |page: u32| i32::try_from(page).unwrap_or(i32::MAX);
+            jni_call_method(
+                env,
+                &obj,
+                "setPageRange",
+                "(II)V",
+                &[JValue::Int(to_int(first.max(1))), JValue::Int(to_int(last))],
+            )?;
+        }
 
         Ok(Self { internal: obj })
     }
 }
 
-/// Wrapper for [`JObject`]s that contain `org.apache.tika.parser.microsoft.OfficeParserConfig`.
+/// Wrapper for [`JObject`]s that contain `ai.yobix.ExtendedOfficeParserConfig`, which extends
+/// `org.apache.tika.parser.microsoft.OfficeParserConfig`.
 pub(crate) struct JOfficeParserConfig<'local> {
     pub(crate) internal: JObject<'local>,
 }
@@ -269,8 +954,8 @@ impl<'local> JOfficeParserConfig<'local> {
         env: &mut JNIEnv<'local>,
         config: &OfficeParserConfig,
     ) -> ExtractResult<Self> {
-        // Create the java object
-        let class = env.find_class("org/apache/tika/parser/microsoft/OfficeParserConfig")?;
+        // Create the java object, extended with the extractous specific settings
+        let class = env.find_class("ai/yobix/ExtendedOfficeParserConfig")?;
         let obj = env.new_object(&class, "()V", &[])?;
 
         // Call the setters
@@ -346,24 +1031,58 @@ impl<'local> JOfficeParserConfig<'local> {
             "(Z)V",
             &[JValue::from(config.extract_all_alternatives_from_msg)],
         )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setExtractCustomProperties",
+            "(Z)V",
+            &[JValue::from(config.extract_custom_properties)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setIncludeChartContent",
+            "(Z)V",
+            &[JValue::from(config.include_chart_content)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setIncludeSmartArtContent",
+            "(Z)V",
+            &[JValue::from(config.include_smartart_content)],
+        )?;
+        let formula_output_val = jni_new_string_as_jvalue(env, &config.formula_output.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setFormulaOutput",
+            "(Ljava/lang/String;)V",
+            &[(&formula_output_val).into()],
+        )?;
 
         Ok(Self { internal: obj })
     }
 }
 
-/// Wrapper for [`JObject`]s that contain `org.apache.tika.parser.ocr.TesseractOCRConfig`.
+/// Wrapper for [`JObject`]s that contain `ai.yobix.ExtendedTesseractOCRConfig`, which extends
+/// `org.apache.tika.parser.ocr.TesseractOCRConfig`.
+#[cfg(feature = "ocr")]
 pub(crate) struct JTesseractOcrConfig<'local> {
     pub(crate) internal: JObject<'local>,
 }
+
+#[cfg(feature = "ocr")]
 impl<'local> JTesseractOcrConfig<'local> {
     /// Creates a new object instance of `JTesseractOcrConfig` in the java world
     /// keeps reference to the object for later use
     pub(crate) fn new(
         env: &mut JNIEnv<'local>,
         config: &TesseractOcrConfig,
+        ocr_backend_handle: jlong,
     ) -> ExtractResult<Self> {
-        // Create the java object
-        let class = env.find_class("org/apache/tika/parser/ocr/TesseractOCRConfig")?;
+        // Create the java object, extended with the extractous specific settings
+        let class = env.find_class("ai/yobix/ExtendedTesseractOCRConfig")?;
         let obj = env.new_object(&class, "()V", &[])?;
 
         // Call the setters
@@ -407,7 +1126,365 @@ impl<'local> JTesseractOcrConfig<'local> {
             "(Ljava/lang/String;)V",
             &[(&lang_string_val).into()],
         )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOcrBackendHandle",
+            "(J)V",
+            &[JValue::from(ocr_backend_handle)],
+        )?;
+        for language in &config.language_fallback {
+            let language_val = jni_new_string_as_jvalue(env, language)?;
+            jni_call_method(
+                env,
+                &obj,
+                "addLanguageFallback",
+                "(Ljava/lang/String;)V",
+                &[(&language_val).into()],
+            )?;
+        }
+        jni_call_method(
+            env,
+            &obj,
+            "setLanguageFallbackMaxAttempts",
+            "(I)V",
+            &[JValue::from(config.language_fallback_max_attempts)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setLanguageFallbackMinConfidence",
+            "(F)V",
+            &[JValue::from(config.language_fallback_min_confidence)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setFallbackOnMissingLanguage",
+            "(Z)V",
+            &[JValue::from(config.fallback_on_missing_language)],
+        )?;
+
+        Ok(Self { internal: obj })
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain a `org.apache.tika.parser.ocr.TesseractOCRConfig` that
+/// skips OCR, passed to java when the `ocr` feature is disabled.
+#[cfg(not(feature = "ocr"))]
+pub(crate) struct JDisabledOcrConfig<'local> {
+    pub(crate) internal: JObject<'local>,
+}
+
+#[cfg(not(feature = "ocr"))]
+impl<'local> JDisabledOcrConfig<'local> {
+    /// Creates a new object instance of `JDisabledOcrConfig` in the java world
+    /// keeps reference to the object for later use
+    pub(crate) fn new(env: &mut JNIEnv<'local>) -> ExtractResult<Self> {
+        let class = env.find_class("org/apache/tika/parser/ocr/TesseractOCRConfig")?;
+        let obj = env.new_object(&class, "()V", &[])?;
+        jni_call_method(env, &obj, "setSkipOcr", "(Z)V", &[JValue::from(true)])?;
+
+        Ok(Self { internal: obj })
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `ai.yobix.HttpConfig`, which opens the connections of
+/// the url extractions.
+pub(crate) struct JHttpConfig<'local> {
+    pub(crate) internal: JObject<'local>,
+}
+
+impl<'local> JHttpConfig<'local> {
+    /// Creates a new object instance of `JHttpConfig` in the java world
+    /// keeps reference to the object for later use
+    pub(crate) fn new(env: &mut JNIEnv<'local>, config: &HttpConfig) -> ExtractResult<Self> {
+        let class = env.find_class("ai/yobix/HttpConfig")?;
+        let obj = env.new_object(&class, "()V", &[])?;
+
+        for (name, value) in &config.headers {
+            let name_val = jni_new_string_as_jvalue(env, name)?;
+            let value_val = jni_new_string_as_jvalue(env, value)?;
+            jni_call_method(
+                env,
+                &obj,
+                "addHeader",
+                "(Ljava/lang/String;Ljava/lang/String;)V",
+                &[(&name_val).into(), (&value_val).into()],
+            )?;
+        }
+        if let Some(authorization) = &config.authorization {
+            let authorization_val = jni_new_string_as_jvalue(env, authorization)?;
+            jni_call_method(
+                env,
+                &obj,
+                "setAuthorization",
+                "(Ljava/lang/String;)V",
+                &[(&authorization_val).into()],
+            )?;
+        }
+        if let Some((host, port)) = &config.proxy {
+            let host_val = jni_new_string_as_jvalue(env, host)?;
+            jni_call_method(
+                env,
+                &obj,
+                "setProxy",
+                "(Ljava/lang/String;I)V",
+                &[(&host_val).into(), JValue::from(i32::from(*port))],
+            )?;
+        }
+        let redirect_policy_val =
+            jni_new_string_as_jvalue(env, &config.redirect_policy.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setRedirectPolicy",
+            "(Ljava/lang/String;)V",
+            &[(&redirect_policy_val).into()],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setMaxRedirects",
+            "(I)V",
+            &[JValue::from(config.max_redirects)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setConnectTimeoutSeconds",
+            "(I)V",
+            &[JValue::from(config.connect_timeout_seconds)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setReadTimeoutSeconds",
+            "(I)V",
+            &[JValue::from(config.read_timeout_seconds)],
+        )?;
 
         Ok(Self { internal: obj })
     }
 }
+
+/// Wrapper for [`JObject`]s that contain `ai.yobix.ParseOptions`, which holds the extractor
+/// level options that are not part of any tika parser config.
+pub(crate) struct JParseOptions<'local> {
+    pub(crate) internal: JObject<'local>,
+    /// Keeps the progress callback of the extractor registered while parsing
+    pub(crate) progress: Option<CallbackGuard>,
+    /// Keeps the password provider of the extractor registered while parsing
+    pub(crate) password: Option<CallbackGuard>,
+}
+
+impl<'local> JParseOptions<'local> {
+    /// Creates a new object instance of `JParseOptions` in the java world
+    /// keeps reference to the object for later use
+    pub(crate) fn new(env: &mut JNIEnv<'local>, extractor: &Extractor) -> ExtractResult<Self> {
+        let class = env.find_class("ai/yobix/ParseOptions")?;
+        let obj = env.new_object(&class, "()V", &[])?;
+
+        let locale_val = jni_new_string_as_jvalue(env, &extractor.locale)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setLocale",
+            "(Ljava/lang/String;)V",
+            &[(&locale_val).into()],
+        )?;
+        let output_format_val =
+            jni_new_string_as_jvalue(env, &extractor.output_format.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOutputFormat",
+            "(Ljava/lang/String;)V",
+            &[(&output_format_val).into()],
+        )?;
+        // The quote and the line breaks delimit the quoted cells and the records
+        if matches!(extractor.csv_delimiter, '"' | '\r' | '\n') {
+            return Err(Error::ParseError(format!(
+                "Invalid CSV delimiter: {:?}",
+                extractor.csv_delimiter
+            )));
+        }
+        let csv_delimiter_val =
+            jni_new_string_as_jvalue(env, &extractor.csv_delimiter.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setCsvDelimiter",
+            "(Ljava/lang/String;)V",
+            &[(&csv_delimiter_val).into()],
+        )?;
+        let charset_policy_val =
+            jni_new_string_as_jvalue(env, &extractor.charset_policy.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setCharsetPolicy",
+            "(Ljava/lang/String;)V",
+            &[(&charset_policy_val).into()],
+        )?;
+        let detection_mode_val =
+            jni_new_string_as_jvalue(env, &extractor.detection_mode.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setDetectionMode",
+            "(Ljava/lang/String;)V",
+            &[(&detection_mode_val).into()],
+        )?;
+        let block_break_val =
+            jni_new_string_as_jvalue(env, &extractor.line_break_policy.block_break.to_string())?;
+        let br_break_val =
+            jni_new_string_as_jvalue(env, &extractor.line_break_policy.br_break.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setLineBreakPolicy",
+            "(Ljava/lang/String;Ljava/lang/String;)V",
+            &[(&block_break_val).into(), (&br_break_val).into()],
+        )?;
+        for namespace in extractor.metadata_namespaces.iter().flatten() {
+            let namespace_val = jni_new_string_as_jvalue(env, namespace)?;
+            jni_call_method(
+                env,
+                &obj,
+                "addMetadataNamespace",
+                "(Ljava/lang/String;)V",
+                &[(&namespace_val).into()],
+            )?;
+        }
+        for (name, value) in &extractor.metadata_hints {
+            let name_val = jni_new_string_as_jvalue(env, name)?;
+            let value_val = jni_new_string_as_jvalue(env, value)?;
+            jni_call_method(
+                env,
+                &obj,
+                "addMetadataHint",
+                "(Ljava/lang/String;Ljava/lang/String;)V",
+                &[(&name_val).into(), (&value_val).into()],
+            )?;
+        }
+        if let Some(max_resources) = extractor.max_embedded_resources {
+            let max_resources = i32::try_from(max_resources).unwrap_or(i32::MAX);
+            jni_call_method(
+                env,
+                &obj,
+                "setMaxEmbeddedResources",
+                "(I)V",
+                &[JValue::from(max_resources)],
+            )?;
+        }
+        for media_type in &extractor.accepted_media_types {
+            let media_type_val = jni_new_string_as_jvalue(env, media_type)?;
+            jni_call_method(
+                env,
+                &obj,
+                "addAcceptedMediaType",
+                "(Ljava/lang/String;)V",
+                &[(&media_type_val).into()],
+            )?;
+        }
+        for parser in &extractor.disabled_parsers {
+            let parser_val = jni_new_string_as_jvalue(env, parser)?;
+            jni_call_method(
+                env,
+                &obj,
+                "addDisabledParser",
+                "(Ljava/lang/String;)V",
+                &[(&parser_val).into()],
+            )?;
+        }
+        jni_call_method(
+            env,
+            &obj,
+            "setDetectLanguage",
+            "(Z)V",
+            &[JValue::from(extractor.detect_language)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setRejectMacroDocuments",
+            "(Z)V",
+            &[JValue::from(extractor.reject_macro_documents)],
+        )?;
+        if let Some(tika_config) = &extractor.tika_config {
+            let tika_config_val = jni_new_string_as_jvalue(env, tika_config)?;
+            jni_call_method(
+                env,
+                &obj,
+                "setTikaConfig",
+                "(Ljava/lang/String;)V",
+                &[(&tika_config_val).into()],
+            )?;
+        }
+        let buffer_size = i32::try_from(extractor.stream_config.buffer_size).unwrap_or(i32::MAX);
+        jni_call_method(
+            env,
+            &obj,
+            "setStreamBufferSize",
+            "(I)V",
+            &[JValue::from(buffer_size)],
+        )?;
+        // The java side keeps its defaults, the same as the Rust ones, unless configured
+        if extractor.http_config != HttpConfig::default() {
+            let http_config = JHttpConfig::new(env, &extractor.http_config)?;
+            jni_call_method(
+                env,
+                &obj,
+                "setHttpConfig",
+                "(Lai/yobix/HttpConfig;)V",
+                &[(&http_config.internal).into()],
+            )?;
+        }
+        if let Some(content_type) = &extractor.content_type_hint {
+            let content_type_val = jni_new_string_as_jvalue(env, content_type)?;
+            jni_call_method(
+                env,
+                &obj,
+                "setContentType",
+                "(Ljava/lang/String;)V",
+                &[(&content_type_val).into()],
+            )?;
+        }
+        let progress = extractor
+            .progress_callback
+            .as_ref()
+            .map(|callback| CallbackGuard::register_progress_callback(env, callback))
+            .transpose()?;
+        if let Some(progress) = &progress {
+            jni_call_method(
+                env,
+                &obj,
+                "setProgressHandle",
+                "(J)V",
+                &[JValue::Long(progress.handle())],
+            )?;
+        }
+
+        let password = extractor
+            .password_provider
+            .as_ref()
+            .map(|provider| CallbackGuard::register_password_provider(env, provider))
+            .transpose()?;
+        if let Some(password) = &password {
+            jni_call_method(
+                env,
+                &obj,
+                "setPasswordHandle",
+                "(J)V",
+                &[JValue::Long(password.handle())],
+            )?;
+        }
+
+        Ok(Self {
+            internal: obj,
+            progress,
+            password,
+        })
+    }
+}
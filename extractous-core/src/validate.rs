@@ -0,0 +1,186 @@
+use crate::errors::{Error, ExtractResult};
+
+const PDF_MAGIC: &[u8] = b"%PDF-";
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const ZIP_EOCD_MAGIC: &[u8] = b"PK\x05\x06";
+const PNG_MAGIC: &[u8] = b"\x89PNG\r\n\x1a\n";
+const JPEG_SOI: &[u8] = b"\xff\xd8";
+const JPEG_EOI: &[u8] = b"\xff\xd9";
+
+/// Size of the end of central directory record without the trailing comment
+const ZIP_EOCD_LEN: usize = 22;
+/// Number of bytes at the end of a pdf in which the trailer is looked for
+const PDF_TRAILER_SEARCH_LEN: usize = 2048;
+
+/// Checks the container structure of the most common formats without parsing their content.
+/// Formats that are not recognized are assumed to be valid.
+pub(crate) fn validate_structure(buffer: &[u8]) -> ExtractResult<()> {
+    if buffer.is_empty() {
+        return Err(corrupt("input", "the input is empty"));
+    }
+
+    if buffer.starts_with(PDF_MAGIC) {
+        validate_pdf(buffer)
+    } else if buffer.starts_with(ZIP_MAGIC) {
+        validate_zip(buffer)
+    } else if buffer.starts_with(PNG_MAGIC) {
+        validate_png(buffer)
+    } else if buffer.starts_with(JPEG_SOI) {
+        validate_jpeg(buffer)
+    } else {
+        Ok(())
+    }
+}
+
+fn corrupt(format: &str, reason: &str) -> Error {
+    Error::ParseError(format!("Truncated or corrupt {}: {}", format, reason))
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
+/// A pdf must end with a trailer pointing to the cross reference table, followed by `%%EOF`
+fn validate_pdf(buffer: &[u8]) -> ExtractResult<()> {
+    let tail = &buffer[buffer.len().saturating_sub(PDF_TRAILER_SEARCH_LEN)..];
+    let eof = rfind(tail, b"%%EOF").ok_or_else(|| corrupt("PDF", "missing %%EOF marker"))?;
+    let startxref = rfind(&tail[..eof], b"startxref")
+        .ok_or_else(|| corrupt("PDF", "missing startxref before %%EOF"))?;
+
+    // The offset after startxref must point inside the file
+    let offset = std::str::from_utf8(&tail[startxref + b"startxref".len()..eof])
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .ok_or_else(|| corrupt("PDF", "invalid startxref offset"))?;
+    if offset >= buffer.len() {
+        return Err(corrupt("PDF", "startxref points past the end of the file"));
+    }
+    Ok(())
+}
+
+/// A zip, which includes the OOXML and ODF formats, must end with an end of central directory
+/// record, and the central directory it points to must lie within the file
+fn validate_zip(buffer: &[u8]) -> ExtractResult<()> {
+    if buffer.len() < ZIP_EOCD_LEN {
+        return Err(corrupt("zip", "missing end of central directory"));
+    }
+    // The record is followed by a comment of at most u16::MAX bytes
+    let search_start = buffer
+        .len()
+        .saturating_sub(ZIP_EOCD_LEN + u16::MAX as usize);
+    let eocd = rfind(&buffer[search_start..], ZIP_EOCD_MAGIC)
+        .map(|pos| search_start + pos)
+        .filter(|pos| pos + ZIP_EOCD_LEN <= buffer.len())
+        .ok_or_else(|| corrupt("zip", "missing end of central directory"))?;
+
+    let record = &buffer[eocd..eocd + ZIP_EOCD_LEN];
+    let cd_size = u32::from_le_bytes([record[12], record[13], record[14], record[15]]) as usize;
+    let cd_offset = u32::from_le_bytes([record[16], record[17], record[18], record[19]]) as usize;
+    // Zip64 archives store the real values elsewhere and mark them with all ones
+    if cd_size == u32::MAX as usize || cd_offset == u32::MAX as usize {
+        return Ok(());
+    }
+    if cd_offset + cd_size > eocd {
+        return Err(corrupt("zip", "central directory lies outside the file"));
+    }
+    if cd_size > 0 && !buffer[cd_offset..].starts_with(b"PK\x01\x02") {
+        return Err(corrupt("zip", "central directory is damaged"));
+    }
+    Ok(())
+}
+
+/// A png is a sequence of length prefixed chunks that must end with an `IEND` chunk
+fn validate_png(buffer: &[u8]) -> ExtractResult<()> {
+    let mut pos = PNG_MAGIC.len();
+    loop {
+        // Every chunk has a 4 bytes length, 4 bytes type, the data and a 4 bytes crc
+        if pos + 8 > buffer.len() {
+            return Err(corrupt("PNG", "missing IEND chunk"));
+        }
+        let length = u32::from_be_bytes([
+            buffer[pos],
+            buffer[pos + 1],
+            buffer[pos + 2],
+            buffer[pos + 3],
+        ]) as usize;
+        let chunk_type = &buffer[pos + 4..pos + 8];
+        let chunk_end = pos + 12 + length;
+        if chunk_end > buffer.len() {
+            return Err(corrupt("PNG", "chunk extends past the end of the file"));
+        }
+        if chunk_type == b"IEND" {
+            return Ok(());
+        }
+        pos = chunk_end;
+    }
+}
+
+/// A jpeg must end with an end of image marker, possibly followed by some padding
+fn validate_jpeg(buffer: &[u8]) -> ExtractResult<()> {
+    let end = buffer
+        .iter()
+        .rposition(|&b| b != 0x00 && !b.is_ascii_whitespace())
+        .map_or(0, |pos| pos + 1);
+    if find(&buffer[end.saturating_sub(JPEG_EOI.len())..end], JPEG_EOI).is_none() {
+        return Err(corrupt("JPEG", "missing end of image marker"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(chunks: &[(&[u8], &[u8])]) -> Vec<u8> {
+        let mut buffer = PNG_MAGIC.to_vec();
+        for (chunk_type, data) in chunks {
+            buffer.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            buffer.extend_from_slice(chunk_type);
+            buffer.extend_from_slice(data);
+            buffer.extend_from_slice(&[0, 0, 0, 0]);
+        }
+        buffer
+    }
+
+    #[test]
+    fn empty_input_is_corrupt() {
+        assert!(validate_structure(b"").is_err());
+    }
+
+    #[test]
+    fn unknown_formats_are_valid() {
+        assert!(validate_structure(b"plain text").is_ok());
+    }
+
+    #[test]
+    fn pdf_trailer() {
+        let pdf = b"%PDF-1.4\n1 0 obj\n<<>>\nendobj\nstartxref\n9\n%%EOF\n";
+        assert!(validate_structure(pdf).is_ok());
+        assert!(validate_structure(&pdf[..pdf.len() - 7]).is_err());
+        assert!(validate_structure(b"%PDF-1.4\nstartxref\n999\n%%EOF").is_err());
+    }
+
+    #[test]
+    fn png_chunks() {
+        let valid = png(&[(b"IHDR", &[0; 13]), (b"IEND", &[])]);
+        assert!(validate_structure(&valid).is_ok());
+        assert!(validate_structure(&valid[..valid.len() - 12]).is_err());
+        assert!(validate_structure(&valid[..20]).is_err());
+    }
+
+    #[test]
+    fn jpeg_markers() {
+        assert!(validate_structure(b"\xff\xd8\xff\xe0data\xff\xd9").is_ok());
+        assert!(validate_structure(b"\xff\xd8\xff\xe0data\xff\xd9\x00\x00").is_ok());
+        assert!(validate_structure(b"\xff\xd8\xff\xe0data").is_err());
+    }
+
+    #[test]
+    fn zip_without_central_directory() {
+        assert!(validate_structure(b"PK\x03\x04 truncated local file header").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+/// An element of the XHTML produced by tika, with its local name and attributes
+pub(crate) struct XhtmlElement {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+}
+
+impl XhtmlElement {
+    fn from_start(start: &BytesStart) -> Self {
+        let name = String::from_utf8_lossy(start.local_name().as_ref()).to_string();
+        let attributes = start
+            .attributes()
+            .flatten()
+            .map(|attr| {
+                let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).to_string();
+                let value = attr
+                    .unescape_value()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                (key, value)
+            })
+            .collect();
+        Self { name, attributes }
+    }
+
+    /// Returns the value of the attribute with the given name if present
+    pub(crate) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns true if the `class` attribute contains the given class name
+    pub(crate) fn has_class(&self, class: &str) -> bool {
+        self.attribute("class")
+            .map(|classes| classes.split_whitespace().any(|c| c == class))
+            .unwrap_or(false)
+    }
+}
+
+/// Events emitted while walking the `<body>` of a tika XHTML document
+pub(crate) enum XhtmlEvent<'a> {
+    Start(&'a XhtmlElement),
+    End(&'a str),
+    Text(&'a str),
+}
+
+/// Walks the `<body>` of the XHTML produced by tika's `ToXMLContentHandler` and calls `handler`
+/// for every start element, end element and text node. Empty elements such as `<br/>` are
+/// reported as a start followed by an end.
+///
+/// The concatenation of all the text events is the same text the plain text output would
+/// produce, so it can be used to compute offsets into the plain text body.
+///
+/// Malformed or truncated XHTML, for example when the output reached the maximum string
+/// length, does not fail: the walk simply stops at the first error.
+pub(crate) fn walk_body<F>(xhtml: &str, mut handler: F)
+where
+    F: FnMut(XhtmlEvent),
+{
+    let mut reader = Reader::from_str(xhtml);
+    // Whitespace is significant because it maps to the plain text newlines
+    reader.config_mut().trim_text(false);
+    let mut inside_body = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => {
+                let element = XhtmlElement::from_start(e);
+                if inside_body {
+                    handler(XhtmlEvent::Start(&element));
+                } else if element.name == "body" {
+                    inside_body = true;
+                }
+            }
+            Ok(Event::Empty(ref e)) if inside_body => {
+                let element = XhtmlElement::from_start(e);
+                handler(XhtmlEvent::Start(&element));
+                handler(XhtmlEvent::End(&element.name));
+            }
+            Ok(Event::End(ref e)) if inside_body => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                if name == "body" {
+                    inside_body = false;
+                } else {
+                    handler(XhtmlEvent::End(&name));
+                }
+            }
+            Ok(Event::Text(ref e)) if inside_body => match e.unescape() {
+                Ok(text) => handler(XhtmlEvent::Text(&text)),
+                Err(_) => break,
+            },
+            Ok(Event::CData(ref e)) if inside_body => {
+                handler(XhtmlEvent::Text(&String::from_utf8_lossy(e.as_ref())));
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => (),
+        }
+    }
+}
+
+/// Returns the heading level of the element name if it is one of `h1` to `h6`
+pub(crate) fn heading_level(name: &str) -> Option<u8> {
+    match name {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "tokio")]
+
+use extractous::{AsyncExtractor, Error, Extractor};
+use std::fs;
+use tokio::io::AsyncReadExt;
+
+#[tokio::test]
+async fn test_extract_bytes_async() {
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let (expected, _metadata) = Extractor::new().extract_bytes_to_string(&bytes).unwrap();
+
+    let extractor = AsyncExtractor::new(Extractor::new());
+    let (mut reader, metadata) = extractor.extract_bytes(bytes.clone()).await.unwrap();
+    assert_eq!(metadata["Content-Type"], vec!["application/pdf"]);
+    let mut streamed = String::new();
+    reader.read_to_string(&mut streamed).await.unwrap();
+    assert_eq!(reader.bytes_read(), streamed.len() as u64);
+    assert!(!streamed.is_empty());
+
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(bytes).await.unwrap();
+    assert_eq!(extracted, expected);
+}
+
+#[tokio::test]
+async fn test_extract_file_async() {
+    let extractor = AsyncExtractor::from(Extractor::new());
+    let (mut reader, _metadata) = extractor
+        .extract_file("../test_files/documents/simple.odt")
+        .await
+        .unwrap();
+    let mut streamed = String::new();
+    reader.read_to_string(&mut streamed).await.unwrap();
+    let (extracted, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/simple.odt")
+        .await
+        .unwrap();
+    assert_eq!(streamed.trim(), extracted.trim());
+
+    assert!(matches!(
+        extractor
+            .extract_file("../test_files/documents/missing.pdf")
+            .await,
+        Err(Error::IoError(_))
+    ));
+}
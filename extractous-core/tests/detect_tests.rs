@@ -0,0 +1,160 @@
+use extractous::{
+    supported_media_types, DetectionConfidence, DetectionMode, Extractor, PdfOcrStrategy,
+    PdfParserConfig,
+};
+use std::fs;
+
+#[test]
+fn test_media_type_hierarchy_docx() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/category-level.docx").unwrap();
+
+    let hierarchy = extractor.media_type_hierarchy(&bytes).unwrap();
+
+    assert_eq!(
+        hierarchy.first().map(String::as_str),
+        Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document")
+    );
+    assert!(hierarchy.contains(&"application/zip".to_string()));
+    assert_eq!(
+        hierarchy.last().map(String::as_str),
+        Some("application/octet-stream")
+    );
+}
+
+#[test]
+fn test_media_type_hierarchy_csv() {
+    let extractor = Extractor::new();
+    let bytes =
+        fs::read("../test_files/documents/table-multi-row-column-cells-actual.csv").unwrap();
+
+    let hierarchy = extractor.media_type_hierarchy(&bytes).unwrap();
+
+    assert!(hierarchy.contains(&"text/plain".to_string()));
+    assert_eq!(
+        hierarchy.last().map(String::as_str),
+        Some("application/octet-stream")
+    );
+}
+
+#[test]
+fn test_detection_mode() {
+    let path = "../test_files/documents/table-multi-row-column-cells-actual.csv";
+    let content_type = |mode| {
+        let extractor = Extractor::new().set_detection_mode(mode);
+        let (_text, metadata) = extractor.extract_file_to_string(path).unwrap();
+        metadata["Content-Type"][0].clone()
+    };
+
+    // The content is plain text, only the file name tells it is a csv file
+    assert!(content_type(DetectionMode::ContentAndName).starts_with("text/csv"));
+    assert!(content_type(DetectionMode::NameOnly).starts_with("text/csv"));
+    assert!(content_type(DetectionMode::ContentOnly).starts_with("text/plain"));
+
+    // Bytes have no name to detect from
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let extractor = Extractor::new().set_detection_mode(DetectionMode::NameOnly);
+    assert_eq!(
+        extractor.explain(&bytes).unwrap().detected_type,
+        "application/octet-stream"
+    );
+    let extractor = Extractor::new().set_detection_mode(DetectionMode::ContentOnly);
+    assert_eq!(
+        extractor.media_type_hierarchy(&bytes).unwrap()[0],
+        "application/pdf"
+    );
+}
+
+#[test]
+fn test_detect_file_and_bytes() {
+    let extractor = Extractor::new();
+
+    let detection = extractor
+        .detect_file("../test_files/documents/category-level.docx")
+        .unwrap();
+    assert_eq!(
+        detection.mime_type,
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+    );
+    assert_eq!(detection.confidence, DetectionConfidence::High);
+
+    // The content is plain text, only the file name tells it is a csv file
+    let detection = extractor
+        .detect_file("../test_files/documents/table-multi-row-column-cells-actual.csv")
+        .unwrap();
+    assert_eq!(detection.mime_type, "text/csv");
+    assert_eq!(detection.confidence, DetectionConfidence::Medium);
+
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let detection = extractor.detect_bytes(&bytes).unwrap();
+    assert_eq!(detection.mime_type, "application/pdf");
+    assert_eq!(detection.confidence, DetectionConfidence::High);
+
+    let detection = extractor.detect_bytes(&[]).unwrap();
+    assert_eq!(detection.mime_type, "application/octet-stream");
+    assert_eq!(detection.confidence, DetectionConfidence::None);
+
+    assert!(extractor.detect_file("../test_files/missing.pdf").is_err());
+}
+
+#[test]
+fn test_supported_media_types() {
+    let types = supported_media_types().unwrap();
+
+    assert!(types.contains(&"application/pdf".to_string()));
+    assert!(types.contains(
+        &"application/vnd.openxmlformats-officedocument.wordprocessingml.document".to_string()
+    ));
+    assert!(types.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_explain_pdf() {
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+
+    let pdf_config = PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::NO_OCR);
+    let extractor = Extractor::new().set_pdf_config(pdf_config.clone());
+    let plan = extractor.explain(&bytes).unwrap();
+
+    assert_eq!(plan.detected_type, "application/pdf");
+    assert_eq!(
+        plan.selected_parser.as_deref(),
+        Some("org.apache.tika.parser.pdf.PDFParser")
+    );
+    assert!(!plan.ocr_would_run);
+    assert_eq!(plan.pdf_config, pdf_config);
+}
+
+#[test]
+fn test_explain_pdf_presets() {
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+
+    let pdf_config = PdfParserConfig::for_born_digital();
+    let extractor = Extractor::new().set_pdf_config(pdf_config.clone());
+    let plan = extractor.explain(&bytes).unwrap();
+    assert!(!plan.ocr_would_run);
+    assert_eq!(plan.pdf_config, pdf_config);
+
+    // The presets are plain configs that can still be tweaked
+    let pdf_config = PdfParserConfig::for_scanned_documents().set_ocr_render_dpi(300);
+    assert_ne!(pdf_config, PdfParserConfig::for_scanned_documents());
+    let plan = Extractor::new()
+        .set_pdf_config(pdf_config.clone())
+        .explain(&bytes)
+        .unwrap();
+    assert_eq!(plan.pdf_config, pdf_config);
+}
+
+#[test]
+fn test_explain_docx() {
+    let bytes = fs::read("../test_files/documents/category-level.docx").unwrap();
+
+    let plan = Extractor::new().explain(&bytes).unwrap();
+
+    assert_eq!(
+        plan.detected_type,
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+    );
+    assert!(plan.selected_parser.is_some());
+    assert!(!plan.ocr_would_run);
+}
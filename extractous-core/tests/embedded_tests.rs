@@ -0,0 +1,233 @@
+use extractous::{Error, Extractor, EMBEDDED_RESOURCE_LIMIT_REACHED};
+use std::sync::{Arc, Mutex};
+use std::{fs, io};
+
+#[test]
+fn test_extract_embedded_pptx() {
+    let bytes = fs::read("../test_files/documents/science-exploration-1p.pptx").unwrap();
+
+    let documents = Extractor::new().extract_embedded(&bytes).unwrap();
+
+    assert!(documents.len() > 1);
+    assert!(documents[0].is_container());
+    assert!(!documents[0].content.is_empty());
+    assert!(documents[1..].iter().all(|doc| !doc.is_container()));
+}
+
+#[test]
+fn test_extract_embedded_filtered_skips_images() {
+    let bytes = fs::read("../test_files/documents/science-exploration-1p.pptx").unwrap();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let seen_by_filter = Arc::clone(&seen);
+    let documents = Extractor::new()
+        .extract_embedded_filtered(&bytes, move |meta| {
+            seen_by_filter.lock().unwrap().push(meta.clone());
+            !meta
+                .name
+                .as_deref()
+                .is_some_and(|name| name.ends_with(".png"))
+        })
+        .unwrap();
+
+    assert!(!seen.lock().unwrap().is_empty());
+    assert!(documents.iter().all(|doc| !doc.path.ends_with(".png")));
+}
+
+#[test]
+fn test_extract_embedded_tar_gz() {
+    let bytes = fs::read("../test_files/documents/archive.tar.gz").unwrap();
+
+    let documents = Extractor::new().extract_embedded(&bytes).unwrap();
+
+    let find = |name: &str| {
+        documents
+            .iter()
+            .find(|doc| doc.name() == Some(name))
+            .unwrap_or_else(|| panic!("no entry named {}", name))
+    };
+    assert!(find("docs/readme.txt")
+        .content
+        .contains("Hello from the tarball"));
+    assert!(!find("docs/simple.odt").content.trim().is_empty());
+    // Entries of an archive inside the archive are extracted too
+    let deep = find("deep.txt");
+    assert!(deep.path.contains("nested.tar"));
+    assert!(deep.content.contains("Entry of the nested archive"));
+}
+
+#[test]
+fn test_extract_file_embedded() {
+    let path = "../test_files/documents/archive.tar.gz";
+    let extractor = Extractor::new();
+
+    let from_file = extractor.extract_file_embedded(path).unwrap();
+    let from_bytes = extractor
+        .extract_embedded(&fs::read(path).unwrap())
+        .unwrap();
+
+    assert_eq!(from_file.len(), from_bytes.len());
+    assert!(from_file[0].is_container());
+    assert_eq!(from_file[0].name(), Some("archive.tar.gz"));
+    assert!(from_file
+        .iter()
+        .any(|doc| doc.name() == Some("docs/readme.txt")
+            && doc.content.contains("Hello from the tarball")));
+
+    assert!(extractor
+        .extract_file_embedded("../test_files/documents/missing.tar.gz")
+        .is_err());
+}
+
+#[test]
+fn test_list_embedded() {
+    let extractor = Extractor::new();
+    let path = "../test_files/documents/science-exploration-1p.pptx";
+
+    let listed = extractor.list_embedded(path).unwrap();
+    let extracted = extractor.extract_file_embedded(path).unwrap();
+
+    assert!(!listed.is_empty());
+    assert!(listed.len() < extracted.len());
+    assert!(listed.iter().any(|meta| meta
+        .name
+        .as_deref()
+        .is_some_and(|name| name.ends_with(".png"))));
+}
+
+#[test]
+fn test_unpack_embedded() {
+    let bytes = fs::read("../test_files/documents/archive.tar.gz").unwrap();
+    let unpacked = Arc::new(Mutex::new(Vec::new()));
+
+    let unpacked_by_callback = Arc::clone(&unpacked);
+    Extractor::new()
+        .unpack_embedded(&bytes, move |file| {
+            unpacked_by_callback.lock().unwrap().push(file);
+            Ok(())
+        })
+        .unwrap();
+
+    let unpacked = unpacked.lock().unwrap();
+    let readme = unpacked
+        .iter()
+        .find(|file| file.name.as_deref() == Some("docs/readme.txt"))
+        .unwrap();
+    assert!(String::from_utf8_lossy(&readme.data).contains("Hello from the tarball"));
+    assert_eq!(readme.mime_type, "text/plain");
+    // Entries of an archive inside the archive are unpacked too
+    let deep = unpacked
+        .iter()
+        .find(|file| file.name.as_deref() == Some("deep.txt"))
+        .unwrap();
+    assert!(deep.path.contains("nested.tar"));
+
+    // An error of the callback stops the unpacking
+    let result = Extractor::new().unpack_embedded(&bytes, |_file| {
+        Err(io::Error::other("disk full"))
+    });
+    assert!(matches!(result, Err(Error::IoError(msg)) if msg.contains("disk full")));
+}
+
+#[test]
+fn test_unpack_embedded_to_dir() {
+    let bytes = fs::read("../test_files/documents/science-exploration-1p.pptx").unwrap();
+    let dir = std::env::temp_dir().join(format!("extractous-unpack-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let extractor = Extractor::new();
+    let written = extractor.unpack_embedded_to_dir(&bytes, &dir).unwrap();
+    assert!(written
+        .iter()
+        .any(|path| path.extension().is_some_and(|ext| ext == "png")));
+    assert!(written
+        .iter()
+        .all(|path| path.parent() == Some(dir.as_path()) && path.is_file()));
+
+    // Unpacking again does not overwrite the files written the first time
+    let rewritten = extractor.unpack_embedded_to_dir(&bytes, &dir).unwrap();
+    assert_eq!(rewritten.len(), written.len());
+    assert!(rewritten.iter().all(|path| !written.contains(path)));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_extract_pdf_attachments() {
+    let bytes = fs::read("../test_files/documents/attachments.pdf").unwrap();
+    let extractor = Extractor::new();
+
+    let attachments = extractor.extract_pdf_attachments(&bytes).unwrap();
+    assert_eq!(attachments.len(), 1);
+    let attachment = &attachments[0];
+    assert_eq!(attachment.name, "invoice-data.csv");
+    assert_eq!(attachment.file_name.as_deref(), Some("invoice-data.csv"));
+    assert_eq!(
+        attachment.description.as_deref(),
+        Some("Line items of the invoice")
+    );
+    assert_eq!(attachment.relationship.as_deref(), Some("Data"));
+    assert_eq!(attachment.mime_type.as_deref(), Some("text/csv"));
+    assert_eq!(
+        attachment.modification_date.as_deref(),
+        Some("2024-01-02T03:04:05Z")
+    );
+    assert_eq!(attachment.creation_date, None);
+    assert_eq!(attachment.data, b"id,amount\n1,42\n2,17\n");
+
+    // The text of the attachment is extracted with the embedded documents
+    let documents = extractor.extract_embedded(&bytes).unwrap();
+    assert!(documents
+        .iter()
+        .any(|doc| doc.name() == Some("invoice-data.csv") && doc.content.contains("1,42")));
+
+    // A PDF without attachments has none
+    let bytes = fs::read("../test_files/documents/annotations.pdf").unwrap();
+    assert!(extractor
+        .extract_pdf_attachments(&bytes)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_extract_embedded_max_length_per_document() {
+    let bytes = fs::read("../test_files/documents/archive.tar.gz").unwrap();
+
+    let documents = Extractor::new()
+        .set_extract_string_max_length(30)
+        .extract_embedded(&bytes)
+        .unwrap();
+
+    // Every entry is truncated to its own budget rather than the first ones using it up
+    for name in ["docs/readme.txt", "deep.txt"] {
+        let doc = documents
+            .iter()
+            .find(|doc| doc.name() == Some(name))
+            .unwrap();
+        assert!(!doc.content.trim().is_empty());
+        assert!(doc.content.chars().count() <= 30);
+    }
+}
+
+#[test]
+fn test_extract_embedded_max_resources() {
+    let bytes = fs::read("../test_files/documents/archive.tar.gz").unwrap();
+
+    let all = Extractor::new().extract_embedded(&bytes).unwrap();
+    assert!(all.len() > 3);
+    assert!(!all[0]
+        .metadata
+        .contains_key(EMBEDDED_RESOURCE_LIMIT_REACHED));
+
+    // The first documents are still returned, and the container is flagged
+    let documents = Extractor::new()
+        .set_max_embedded_resources(2)
+        .extract_embedded(&bytes)
+        .unwrap();
+    assert_eq!(documents.len(), 3);
+    assert!(documents[0].is_container());
+    assert_eq!(
+        documents[0].metadata.get(EMBEDDED_RESOURCE_LIMIT_REACHED),
+        Some(&vec!["true".to_string()])
+    );
+}
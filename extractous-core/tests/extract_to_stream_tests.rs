@@ -1,6 +1,13 @@
-use extractous::{Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
-use std::fs;
-use std::io::Read;
+use extractous::{
+    CharSet, Cursor, DecodeErrorPolicy, Extractor, OfficeParserConfig, OutputFormat, StreamConfig,
+};
+#[cfg(feature = "ocr")]
+use extractous::{PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::sync::mpsc;
+use std::thread;
 use test_case::test_case;
 use textdistance::nstr::cosine;
 
@@ -23,11 +30,13 @@ fn test_extract_bytes_to_stream(file_name: &str, target_dist: f64) {
     let extractor = Extractor::new();
 
     let bytes = fs::read(&format!("../test_files/documents/{}", file_name)).unwrap();
-    let (mut stream, metadata) = extractor.extract_bytes(&bytes).unwrap();
+    let (stream, metadata) = extractor.extract_bytes(&bytes).unwrap();
 
-    let mut buffer = Vec::new();
-    stream.read_to_end(&mut buffer).unwrap();
-    let extracted = String::from_utf8_lossy(&buffer);
+    // Decoding fails on invalid UTF-8 rather than hiding it behind replacement characters
+    let extracted = stream
+        .into_chars(DecodeErrorPolicy::Error)
+        .collect::<Result<String, _>>()
+        .unwrap();
 
     // read expected string
     let expected =
@@ -58,6 +67,7 @@ fn test_extract_bytes_to_stream(file_name: &str, target_dist: f64) {
     );
 }
 
+#[cfg(feature = "ocr")]
 #[test]
 fn test_extract_bytes_to_stream_ara_ocr_png() {
     let extractor = Extractor::new()
@@ -86,3 +96,290 @@ fn test_extract_bytes_to_stream_ara_ocr_png() {
     );
     println!("{}: {}", "ara-ocr.png", dist);
 }
+
+#[test]
+fn test_extract_bytes_to_stream_bytes_read() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+    let (mut stream, _metadata) = extractor.extract_bytes(&bytes).unwrap();
+    assert_eq!(stream.bytes_read(), 0);
+
+    let mut buffer = Vec::new();
+    stream.read_to_end(&mut buffer).unwrap();
+    assert_eq!(stream.bytes_read(), buffer.len() as u64);
+}
+
+#[test]
+fn test_extract_bytes_owned_outlives_buffer() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+    let (expected, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+
+    // The vector is dropped once copied into the stream's own buffer
+    let (mut stream, _metadata) = extractor.extract_bytes_owned(bytes).unwrap();
+    let mut content = String::new();
+    stream.read_to_string(&mut content).unwrap();
+    assert_eq!(content, expected);
+}
+
+#[test]
+fn test_extract_segment_matches_stream() {
+    let extractor = Extractor::new().set_extract_string_max_length(500);
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+
+    let (mut stream, _metadata) = extractor.extract_bytes(&bytes).unwrap();
+    let mut expected = String::new();
+    stream.read_to_string(&mut expected).unwrap();
+
+    let mut segments = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (segment, next) = extractor.extract_segment(&bytes, cursor).unwrap();
+        assert!(segment.chars().count() <= 500);
+        segments.push(segment);
+        match next {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    assert!(segments.len() > 1);
+    assert_eq!(segments.concat(), expected);
+}
+
+#[test]
+fn test_extract_segment_outlives_input() {
+    let extractor = Extractor::new().set_extract_string_max_length(500);
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let (expected, _metadata) = extractor
+        .clone()
+        .set_extract_string_max_length(-1)
+        .extract_bytes_to_string(&bytes)
+        .unwrap();
+
+    // Every call gets its own copy of the input, freed before the next call resumes the parse
+    let mut segments = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (segment, next) = extractor.extract_segment(&bytes.clone(), cursor).unwrap();
+        segments.push(segment);
+        match next {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    assert!(segments.len() > 1);
+    assert_eq!(segments.concat(), expected);
+}
+
+#[test]
+fn test_extract_segment_resume_from_offset() {
+    let extractor = Extractor::new().set_extract_string_max_length(100);
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+
+    let (_first, cursor) = extractor.extract_segment(&bytes, None).unwrap();
+    let cursor = cursor.unwrap();
+    let (second, _next) = extractor.extract_segment(&bytes, Some(cursor)).unwrap();
+
+    // A cursor rebuilt from the offset alone, as after a restart, gives the same segment
+    let restored = Cursor::from_offset(cursor.offset());
+    let (resumed, _next) = extractor.extract_segment(&bytes, Some(restored)).unwrap();
+    assert_eq!(resumed, second);
+}
+
+#[test]
+fn test_extract_bytes_to_stream_charset() {
+    let text = "Grüße aus München, à bientôt";
+    let (expected, _metadata) = Extractor::new()
+        .extract_bytes_to_string(text.as_bytes())
+        .unwrap();
+    let extract = |config: StreamConfig| {
+        let extractor = Extractor::new().set_stream_config(config);
+        let (mut stream, _metadata) = extractor.extract_bytes(text.as_bytes()).unwrap();
+        let mut buffer = Vec::new();
+        stream.read_to_end(&mut buffer).unwrap();
+        buffer
+    };
+
+    // Latin-1 encodes each of these characters in a single byte
+    let latin1 = extract(
+        StreamConfig::new()
+            .set_charset(CharSet::ISO_8859_1)
+            .set_buffer_size(16),
+    );
+    let decoded: String = latin1.iter().map(|byte| *byte as char).collect();
+    assert_eq!(decoded, expected);
+
+    let utf16 = extract(StreamConfig::new().set_charset(CharSet::UTF_16LE));
+    let units: Vec<u16> = utf16
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    assert_eq!(String::from_utf16(&units).unwrap(), expected);
+}
+
+#[test]
+fn test_extract_bytes_to_stream_markdown() {
+    let extractor = Extractor::new().set_output_format(OutputFormat::Markdown);
+    let bytes = fs::read("../test_files/documents/category-level.docx").unwrap();
+
+    let (mut reader, _metadata) = extractor.extract_bytes(&bytes).unwrap();
+    let mut streamed = String::new();
+    reader.read_to_string(&mut streamed).unwrap();
+
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert_eq!(streamed, extracted);
+}
+
+#[test]
+fn test_extract_reader() {
+    let extractor = Extractor::new();
+    for file in [
+        "../test_files/documents/2022_Q3_AAPL.pdf",
+        "../test_files/documents/simple.odt",
+    ] {
+        let (mut stream, metadata) = extractor.extract_reader(File::open(file).unwrap()).unwrap();
+        let mut extracted = String::new();
+        stream.read_to_string(&mut extracted).unwrap();
+
+        let (mut stream, expected_metadata) = extractor.extract_file(file).unwrap();
+        let mut expected = String::new();
+        stream.read_to_string(&mut expected).unwrap();
+        assert_eq!(extracted, expected);
+        assert_eq!(metadata["Content-Type"], expected_metadata["Content-Type"]);
+    }
+
+    // Any reader works, e.g. one over bytes already in memory
+    let reader = io::Cursor::new(b"text read through a reader".to_vec());
+    let (mut stream, _metadata) = extractor.extract_reader(reader).unwrap();
+    let mut extracted = String::new();
+    stream.read_to_string(&mut extracted).unwrap();
+    assert_eq!(extracted.trim(), "text read through a reader");
+}
+
+#[test]
+fn test_extract_file_to_writer() {
+    let extractor = Extractor::new();
+    let file = "../test_files/documents/2022_Q3_AAPL.pdf";
+
+    let mut written = Vec::new();
+    let (num_written, metadata) = extractor
+        .extract_file_to_writer(file, &mut written)
+        .unwrap();
+    assert_eq!(num_written, written.len() as u64);
+    assert_eq!(metadata["Content-Type"], vec!["application/pdf"]);
+
+    let (mut stream, _metadata) = extractor.extract_file(file).unwrap();
+    let mut expected = Vec::new();
+    stream.read_to_end(&mut expected).unwrap();
+    assert_eq!(written, expected);
+}
+
+#[test]
+fn test_extract_to_temp_file() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+
+    let (path, metadata) = extractor.extract_to_temp_file(&bytes).unwrap();
+    assert!(!metadata.is_empty());
+    let written = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let (mut stream, _metadata) = extractor.extract_bytes(&bytes).unwrap();
+    let mut expected = String::new();
+    stream.read_to_string(&mut expected).unwrap();
+    assert_eq!(written, expected);
+
+    // Every call writes a new file
+    let (first, _metadata) = extractor.extract_to_temp_file(&bytes).unwrap();
+    let (second, _metadata) = extractor.extract_to_temp_file(&bytes).unwrap();
+    assert_ne!(first, second);
+    fs::remove_file(first).unwrap();
+    fs::remove_file(second).unwrap();
+}
+
+#[test]
+fn test_extract_lines() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+
+    let lines = extractor
+        .extract_lines(&bytes)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let (mut stream, _metadata) = extractor.extract_bytes(&bytes).unwrap();
+    let mut expected = String::new();
+    stream.read_to_string(&mut expected).unwrap();
+    assert_eq!(lines, expected.lines().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_extract_to_channel() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let consumer =
+        thread::spawn(move || rx.into_iter().collect::<Result<Vec<String>, _>>().unwrap());
+    extractor.extract_to_channel(&bytes, tx).unwrap();
+    let chunks = consumer.join().unwrap();
+    assert!(chunks.len() > 1);
+
+    let (mut stream, _metadata) = extractor.extract_bytes(&bytes).unwrap();
+    let mut expected = String::new();
+    stream.read_to_string(&mut expected).unwrap();
+    assert_eq!(chunks.concat(), expected);
+}
+
+#[test_case(true; "with custom properties")]
+#[test_case(false; "without custom properties")]
+fn test_extract_bytes_custom_properties(extract: bool) {
+    let extractor = Extractor::new()
+        .set_office_config(OfficeParserConfig::new().set_extract_custom_properties(extract));
+    let bytes = fs::read("../test_files/documents/custom-properties.docx").unwrap();
+
+    let (mut stream, metadata) = extractor.extract_bytes(&bytes).unwrap();
+    let mut content = String::new();
+    stream.read_to_string(&mut content).unwrap();
+    assert!(content.contains("The mission report is attached for review."));
+
+    if extract {
+        assert_eq!(metadata.get("custom:Project").unwrap(), &vec!["Apollo"]);
+        assert_eq!(
+            metadata.get("custom:Classification").unwrap(),
+            &vec!["Internal"]
+        );
+    } else {
+        assert!(metadata.keys().all(|key| !key.starts_with("custom:")));
+    }
+}
+
+#[test]
+fn test_extract_bytes_with_input_metadata() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+    let hints = HashMap::from([
+        ("resourceName".to_string(), "report.odt".to_string()),
+        (
+            "Content-Type".to_string(),
+            "application/octet-stream".to_string(),
+        ),
+        ("custom:source".to_string(), "upload".to_string()),
+    ]);
+
+    let (mut stream, metadata) = extractor
+        .extract_bytes_with_input_metadata(&bytes, hints)
+        .unwrap();
+    let mut content = String::new();
+    stream.read_to_string(&mut content).unwrap();
+    assert!(!content.is_empty());
+
+    assert_eq!(metadata.get("resourceName").unwrap(), &vec!["report.odt"]);
+    assert_eq!(metadata.get("custom:source").unwrap(), &vec!["upload"]);
+    // The detected type takes precedence over the hint
+    assert_eq!(
+        metadata.get("Content-Type").unwrap(),
+        &vec!["application/vnd.oasis.opendocument.text"]
+    );
+}
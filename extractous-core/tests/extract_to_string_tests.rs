@@ -1,5 +1,17 @@
-use extractous::{Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
+use extractous::batch::walk_dir;
+#[cfg(feature = "ocr")]
+use extractous::{
+    installed_ocr_languages, ExtractResult, OcrBackend, PdfOcrImageType, TesseractOcrConfig,
+};
+use extractous::{
+    self_test, AnchorGranularity, CharsetPolicy, Error, ExtractOverrides, Extractor, FormFieldKind,
+    FormulaOutput, LineBreak, LineBreakPolicy, MetadataExt, OfficeParserConfig, OutputFormat,
+    PdfOcrStrategy, PdfParserConfig, SignatureStatus, METADATA_NO_NAMESPACE,
+};
 use std::fs;
+use std::path::PathBuf;
+#[cfg(feature = "ocr")]
+use std::sync::{Arc, Mutex};
 use test_case::test_case;
 use textdistance::nstr::cosine;
 
@@ -50,6 +62,7 @@ fn test_extract_file_to_string(file_name: &str, target_dist: f64) {
     ));
 }
 
+#[cfg(feature = "ocr")]
 #[test]
 fn test_extract_file_to_string_ara_ocr_png() {
     let extractor = Extractor::new()
@@ -74,6 +87,82 @@ fn test_extract_file_to_string_ara_ocr_png() {
     );
 }
 
+#[cfg(feature = "ocr")]
+#[test]
+fn test_extract_file_to_string_ocr_language_fallback() {
+    // The confidence cannot reach 1, so both languages are tried
+    let ocr_config = TesseractOcrConfig::new()
+        .set_language_fallback(vec!["eng".to_string(), "ara".to_string()])
+        .set_language_fallback_min_confidence(1.0);
+    let extractor = Extractor::new().set_ocr_config(ocr_config);
+    let (extracted, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/ara-ocr.png")
+        .unwrap();
+
+    assert_eq!(metadata["X-OCR-Language"], vec!["ara".to_string()]);
+    let confidence: f64 = metadata["X-OCR-Confidence"][0].parse().unwrap();
+    assert!(confidence > 0.0 && confidence <= 1.0);
+    let expected = fs::read_to_string("../test_files/expected_result/ara-ocr.png.txt").unwrap();
+    let dist = cosine(&expected, &extracted);
+    assert!(
+        dist > 0.9,
+        "Cosine similarity is less than 0.9, dist: {}",
+        dist
+    );
+}
+
+#[cfg(feature = "ocr")]
+#[test]
+fn test_extract_file_to_string_ocr_language_missing() {
+    let installed = installed_ocr_languages().unwrap();
+    assert!(installed.iter().any(|language| language == "ara"));
+    assert!(!installed.iter().any(|language| language == "xyz"));
+
+    // The missing language is dropped and the installed one still OCRs the image
+    let file = "../test_files/documents/ara-ocr.png";
+    let ocr_config = TesseractOcrConfig::new().set_language("ara+xyz");
+    let extractor = Extractor::new().set_ocr_config(ocr_config.clone());
+    let (extracted, metadata) = extractor.extract_file_to_string(file).unwrap();
+    assert_eq!(
+        metadata["X-OCR-Warning"],
+        vec!["OCR language xyz is not installed, using ara".to_string()]
+    );
+    assert!(!extracted.trim().is_empty());
+
+    let extractor =
+        Extractor::new().set_ocr_config(ocr_config.set_fallback_on_missing_language(false));
+    assert!(matches!(
+        extractor.extract_file_to_string(file),
+        Err(Error::OcrLanguageMissing(language)) if language == "xyz"
+    ));
+}
+
+#[cfg(feature = "ocr")]
+#[test]
+fn test_extract_file_to_string_ocr_keep_warm() {
+    let extractor = Extractor::new()
+        .set_ocr_config(TesseractOcrConfig::new().set_language("ara"))
+        .set_ocr_keep_warm(true);
+    let expected = fs::read_to_string("../test_files/expected_result/ara-ocr.png.txt").unwrap();
+
+    // The clone shares the warm config, which must not leak into an extractor with another one
+    let clone = extractor.clone();
+    let other = extractor
+        .clone()
+        .set_ocr_config(TesseractOcrConfig::new().set_language("eng"));
+    for extractor in [&extractor, &clone, &extractor] {
+        let (extracted, _metadata) = extractor
+            .extract_file_to_string("../test_files/documents/ara-ocr.png")
+            .unwrap();
+        assert!(cosine(&expected, &extracted) > 0.9);
+    }
+    let (extracted, _metadata) = other
+        .extract_file_to_string("../test_files/documents/ara-ocr.png")
+        .unwrap();
+    assert!(cosine(&expected, &extracted) < 0.9);
+}
+
+#[cfg(feature = "ocr")]
 #[cfg(not(target_os = "macos"))]
 #[test]
 fn test_extract_file_to_string_ocr_only_strategy_deu_ocr_pdf() {
@@ -102,6 +191,7 @@ fn test_extract_file_to_string_ocr_only_strategy_deu_ocr_pdf() {
     );
 }
 
+#[cfg(feature = "ocr")]
 #[cfg(not(target_os = "macos"))]
 #[test]
 fn test_test_extract_file_to_string_no_ocr_strategy_deu_ocr_pdf() {
@@ -115,3 +205,1320 @@ fn test_test_extract_file_to_string_no_ocr_strategy_deu_ocr_pdf() {
 
     assert_eq!("", extracted.trim())
 }
+
+#[test]
+fn test_extract_bytes_to_string_with_config_overrides_max_length() {
+    let extractor = Extractor::new().set_extract_string_max_length(1000000);
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+
+    let (extracted, _metadata) = extractor
+        .extract_bytes_to_string_with_config(
+            &bytes,
+            ExtractOverrides::new().set_extract_string_max_length(100),
+        )
+        .unwrap();
+    assert!(extracted.chars().count() <= 100);
+
+    // The extractor itself must not be affected by the overrides
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert!(extracted.chars().count() > 100);
+}
+
+#[test]
+fn test_extract_file_to_string_with_config_overrides_office_config() {
+    let extractor = Extractor::new();
+    let file = "../test_files/documents/formulas.xlsx";
+
+    let (formulas, _metadata) = extractor
+        .extract_file_to_string_with_config(
+            file,
+            ExtractOverrides::new().set_office_config(
+                OfficeParserConfig::new().set_formula_output(FormulaOutput::Formula),
+            ),
+        )
+        .unwrap();
+    assert!(formulas.contains("=SUM(B2:B3)") && !formulas.contains("2000"));
+
+    // The extractor itself must not be affected by the overrides
+    let (computed, _metadata) = extractor.extract_file_to_string(file).unwrap();
+    assert!(computed.contains("2000") && !computed.contains("=SUM(B2:B3)"));
+}
+
+#[test]
+fn test_extract_file_to_string_tika_config() {
+    let file = "../test_files/documents/simple.odt";
+    let tika_config = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <properties>
+          <parsers>
+            <parser class="org.apache.tika.parser.DefaultParser">
+              <parser-exclude class="org.apache.tika.parser.odf.OpenDocumentParser"/>
+            </parser>
+          </parsers>
+        </properties>"#;
+
+    let extractor = Extractor::new().set_tika_config(tika_config);
+    let result = extractor.extract_file_to_string(file);
+    assert!(matches!(result, Err(Error::NoParser(_))));
+    // The other parsers are kept
+    let (content, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/simple.doc")
+        .unwrap();
+    assert!(!content.trim().is_empty());
+
+    let extractor = Extractor::new().set_tika_config("<properties><parsers>");
+    let result = extractor.extract_file_to_string(file);
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
+
+#[test]
+fn test_extract_file_to_string_disabled_parsers() {
+    let extractor = Extractor::new().set_disabled_parsers(vec![
+        "org.apache.tika.parser.odf.OpenDocumentParser".to_string(),
+    ]);
+    let result = extractor.extract_file_to_string("../test_files/documents/simple.odt");
+    assert!(matches!(result, Err(Error::NoParser(_))));
+
+    // The other parsers are kept
+    let (content, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/simple.doc")
+        .unwrap();
+    assert!(!content.trim().is_empty());
+}
+
+#[test_case(true; "with custom properties")]
+#[test_case(false; "without custom properties")]
+fn test_extract_file_to_string_custom_properties(extract: bool) {
+    let extractor = Extractor::new()
+        .set_office_config(OfficeParserConfig::new().set_extract_custom_properties(extract));
+    let (extracted, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/custom-properties.docx")
+        .unwrap();
+
+    assert!(extracted.contains("The mission report is attached for review."));
+    assert_eq!(metadata.get("dc:title").unwrap(), &vec!["Mission Report"]);
+    if extract {
+        assert_eq!(metadata.get("custom:Project").unwrap(), &vec!["Apollo"]);
+        assert_eq!(
+            metadata.get("custom:Classification").unwrap(),
+            &vec!["Internal"]
+        );
+    } else {
+        assert!(metadata.keys().all(|key| !key.starts_with("custom:")));
+    }
+}
+
+#[cfg(feature = "ocr")]
+struct FixedOcrBackend;
+
+#[cfg(feature = "ocr")]
+impl OcrBackend for FixedOcrBackend {
+    fn ocr(&self, image: &[u8]) -> ExtractResult<String> {
+        assert!(!image.is_empty());
+        Ok("recognized by the custom backend".to_string())
+    }
+}
+
+#[cfg(feature = "ocr")]
+#[test]
+fn test_extract_file_to_string_with_ocr_backend() {
+    let extractor = Extractor::new().set_ocr_backend(Box::new(FixedOcrBackend));
+    let (extracted, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/ara-ocr.png")
+        .unwrap();
+
+    assert!(extracted.contains("recognized by the custom backend"));
+}
+
+#[cfg(feature = "ocr")]
+#[test_case(150; "Test 150 DPI")]
+#[test_case(300; "Test 300 DPI")]
+fn test_extract_file_to_string_ocr_render_dpi_eng_ocr_pdf(dpi: i32) {
+    let extractor = Extractor::new()
+        .set_ocr_config(TesseractOcrConfig::new().set_language("eng"))
+        .set_pdf_config(
+            PdfParserConfig::new()
+                .set_ocr_strategy(PdfOcrStrategy::OCR_ONLY)
+                .set_ocr_render_dpi(dpi),
+        );
+    let (extracted, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/eng-ocr.pdf")
+        .unwrap();
+    assert!(
+        !extracted.trim().is_empty(),
+        "No text was OCR'd from eng-ocr.pdf at {} DPI",
+        dpi
+    );
+}
+
+/// Records the width of the images it is given, without recognizing anything
+#[cfg(feature = "ocr")]
+struct ImageWidthOcrBackend(Arc<Mutex<Vec<u32>>>);
+
+#[cfg(feature = "ocr")]
+impl OcrBackend for ImageWidthOcrBackend {
+    fn ocr(&self, image: &[u8]) -> ExtractResult<String> {
+        // The pages are rendered to PNG images, which store their width first in the IHDR chunk
+        let width = u32::from_be_bytes(image[16..20].try_into().unwrap());
+        self.0.lock().unwrap().push(width);
+        Ok(String::new())
+    }
+}
+
+#[cfg(feature = "ocr")]
+#[test]
+fn test_extract_file_to_string_ocr_render_dpi_scales_images() {
+    let widths = |dpi: i32| {
+        let widths = Arc::new(Mutex::new(Vec::new()));
+        let extractor = Extractor::new()
+            .set_ocr_backend(Box::new(ImageWidthOcrBackend(widths.clone())))
+            .set_pdf_config(
+                PdfParserConfig::new()
+                    .set_ocr_strategy(PdfOcrStrategy::OCR_ONLY)
+                    .set_ocr_render_dpi(dpi),
+            );
+        extractor
+            .extract_file_to_string("../test_files/documents/eng-ocr.pdf")
+            .unwrap();
+        let widths = widths.lock().unwrap().clone();
+        widths
+    };
+
+    let low = widths(150);
+    let high = widths(300);
+    assert!(!low.is_empty());
+    assert_eq!(low.len(), high.len());
+    // Twice the DPI renders the pages twice as wide, give or take rounding
+    for (low, high) in low.iter().zip(&high) {
+        assert!(high.abs_diff(low * 2) <= 2, "{} vs {} pixels", low, high);
+    }
+}
+
+#[cfg(feature = "ocr")]
+#[test_case(PdfOcrImageType::Gray; "Test gray images")]
+#[test_case(PdfOcrImageType::Rgb; "Test rgb images")]
+#[test_case(PdfOcrImageType::Binary; "Test binary images")]
+fn test_extract_file_to_string_ocr_image_type_eng_ocr_pdf(image_type: PdfOcrImageType) {
+    let extractor = Extractor::new()
+        .set_ocr_config(TesseractOcrConfig::new().set_language("eng"))
+        .set_pdf_config(
+            PdfParserConfig::new()
+                .set_ocr_strategy(PdfOcrStrategy::OCR_ONLY)
+                .set_ocr_image_type(image_type)
+                .set_ocr_image_quality(0.9),
+        );
+    let (extracted, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/eng-ocr.pdf")
+        .unwrap();
+    assert!(
+        !extracted.trim().is_empty(),
+        "No text was OCR'd from eng-ocr.pdf with {} images",
+        image_type
+    );
+}
+
+#[test]
+fn test_extract_file_to_string_locale() {
+    let extract_with_locale = |locale: &str| {
+        Extractor::new()
+            .set_locale(locale)
+            .extract_file_to_string("../test_files/documents/vodafone.xlsx")
+            .unwrap()
+            .0
+    };
+
+    let en = extract_with_locale("en-US");
+    let de = extract_with_locale("de-DE");
+    // Formatted numbers and dates differ, e.g. 1,234.5 vs 1.234,5
+    assert_ne!(en, de);
+    // The same locale always gives the same output
+    assert_eq!(en, extract_with_locale("en-US"));
+}
+
+#[test]
+fn test_extract_file_to_string_reconfigure_in_place() {
+    let file = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let mut extractor = Extractor::new();
+    extractor.set_extract_string_max_length_mut(100);
+    let (content, _metadata) = extractor.extract_file_to_string(file).unwrap();
+    assert!(content.chars().count() <= 100);
+
+    // A stream started before the change keeps its settings
+    let (mut reader, _metadata) = extractor.extract_file(file).unwrap();
+    extractor
+        .set_extract_string_max_length_mut(20)
+        .set_output_format_mut(OutputFormat::Xhtml);
+    let mut streamed = String::new();
+    std::io::Read::read_to_string(&mut reader, &mut streamed).unwrap();
+    assert!(!streamed.contains("<html"));
+
+    let (content, _metadata) = extractor.extract_file_to_string(file).unwrap();
+    assert!(content.chars().count() <= 20);
+}
+
+#[test]
+fn test_extract_base64_and_data_uri() {
+    let extractor = Extractor::new();
+
+    let (extracted, _metadata) = extractor
+        .extract_base64("SGVsbG8gZXh0cmFjdG91cw==")
+        .unwrap();
+    assert_eq!(extracted.trim(), "Hello extractous");
+
+    let (extracted, metadata) = extractor
+        .extract_data_uri("data:text/plain;base64,SGVsbG8gZXh0cmFjdG91cw==")
+        .unwrap();
+    assert_eq!(extracted.trim(), "Hello extractous");
+    assert!(metadata["Content-Type"][0].starts_with("text/plain"));
+}
+
+#[test]
+fn test_extract_invalid_base64() {
+    let extractor = Extractor::new();
+    assert!(extractor.extract_base64("not base64!").is_err());
+    assert!(extractor.extract_data_uri("data:text/plain").is_err());
+}
+
+#[cfg(feature = "ocr")]
+#[test]
+fn test_extract_file_to_string_page_ocr_without_inline_image_ocr() {
+    let extractor = Extractor::new()
+        .set_ocr_config(TesseractOcrConfig::new().set_language("deu"))
+        .set_pdf_config(
+            PdfParserConfig::new()
+                .set_ocr_strategy(PdfOcrStrategy::OCR_ONLY)
+                .set_extract_inline_images(true)
+                .set_ocr_inline_images(false),
+        );
+    let (extracted, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/deu-ocr.pdf")
+        .unwrap();
+
+    // Whole pages are still OCR'd
+    let expected = fs::read_to_string("../test_files/expected_result/deu-ocr.pdf.txt").unwrap();
+    let dist = cosine(&expected, &extracted);
+    assert!(
+        dist > 0.9,
+        "Cosine similarity is less than 0.9 for file: deu-ocr.pdf, dist: {}",
+        dist
+    );
+}
+
+#[test]
+fn test_self_test() {
+    assert!(self_test().is_ok());
+}
+
+#[test]
+fn test_extract_bytes_to_string_markdown() {
+    let html = b"<html><body>\
+        <h2>Results</h2>\
+        <p>Some <strong>bold</strong> and <em>italic</em> text.</p>\
+        <ul><li>first</li><li>second</li></ul>\
+        <table><tr><th>Name</th><th>Value</th></tr><tr><td>a|b</td><td>1</td></tr></table>\
+        </body></html>";
+    let extractor = Extractor::new().set_output_format(OutputFormat::Markdown);
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(html).unwrap();
+
+    assert!(extracted.contains("## Results"));
+    assert!(extracted.contains("Some **bold** and *italic* text."));
+    assert!(extracted.contains("- first\n- second"));
+    assert!(extracted.contains("| Name | Value |\n| --- | --- |\n| a\\|b | 1 |"));
+}
+
+#[test]
+fn test_extract_bytes_to_string_markdown_overlapping_markup() {
+    let html = b"<html><body><p><b>bold <i>both</b> italic</i><b> </b></p></body></html>";
+    let extractor = Extractor::new().set_output_format(OutputFormat::Markdown);
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(html).unwrap();
+
+    assert_eq!(extracted.trim(), "**bold *both*** *italic*");
+}
+
+#[test]
+fn test_extract_bytes_to_string_markdown_links_and_code() {
+    let html = b"<html><body>\
+        <p>See <a href=\"https://example.com/docs\"><b>the docs</b></a> and <a href=\"#top\">top</a>.</p>\
+        <pre>fn main() {\n    println!(\"```\");\n}</pre>\
+        </body></html>";
+    let extractor = Extractor::new().set_output_format(OutputFormat::Markdown);
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(html).unwrap();
+
+    assert!(extracted.contains("See [**the docs**](https://example.com/docs) and top."));
+    // The fence is longer than the backticks of the code
+    assert!(extracted.contains("````\nfn main() {\n    println!(\"```\");\n}\n````"));
+}
+
+#[test]
+fn test_extract_bytes_to_string_ndjson() {
+    let html = b"<html><body>\
+        <h2>Results</h2>\
+        <p>Some \"quoted\" text.</p>\
+        <ul><li>first</li></ul>\
+        <table><tr><th>Name</th><th>Value</th></tr><tr><td>a</td><td>1</td></tr></table>\
+        <img src=\"chart.png\" alt=\"A chart\"/>\
+        </body></html>";
+    let extractor = Extractor::new().set_output_format(OutputFormat::Ndjson);
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(html).unwrap();
+
+    let records: Vec<serde_json::Value> = extracted
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let types: Vec<&str> = records
+        .iter()
+        .map(|r| r["type"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            "heading",
+            "paragraph",
+            "list_item",
+            "table_row",
+            "table_row",
+            "image"
+        ]
+    );
+    assert_eq!(records[0]["text"], "Results");
+    assert_eq!(records[0]["level"], 2);
+    assert_eq!(records[1]["text"], "Some \"quoted\" text.");
+    assert_eq!(records[4]["cells"], serde_json::json!(["a", "1"]));
+    assert_eq!(records[4]["row"], 1);
+    assert_eq!(records[5]["src"], "chart.png");
+    for (index, record) in records.iter().enumerate() {
+        assert_eq!(record["index"], index);
+        assert!(record["page"].is_null());
+    }
+
+    // The pages of a PDF are numbered from 1
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    let last: serde_json::Value = serde_json::from_str(extracted.lines().last().unwrap()).unwrap();
+    assert!(last["page"].as_u64().unwrap() > 1);
+}
+
+#[test]
+fn test_extract_bytes_to_string_csv() {
+    let html = b"<html><body>\
+        <h1>Prices</h1>\
+        <table><tr><th>Name</th><th>Price</th></tr>\
+        <tr><td>Widget, large</td><td>1,50</td></tr>\
+        <tr><td><p>Say \"hi\"</p><p>twice</p></td><td>2</td></tr></table>\
+        <p>Between the tables</p>\
+        <table><tr><td>second</td></tr></table>\
+        </body></html>";
+    let extractor = Extractor::new().set_output_format(OutputFormat::Csv);
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(html).unwrap();
+
+    assert_eq!(
+        extracted,
+        "Name,Price\r\n\"Widget, large\",\"1,50\"\r\n\"Say \"\"hi\"\"\ntwice\",2\r\n\r\nsecond\r\n"
+    );
+
+    let extractor = extractor.set_csv_delimiter(';');
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(html).unwrap();
+    assert!(extracted.starts_with("Name;Price\r\nWidget, large;1,50\r\n"));
+
+    let bytes = fs::read("../test_files/documents/simple.ods").unwrap();
+    let (extracted, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert!(extracted.contains("870"));
+    assert!(extracted.ends_with("\r\n"));
+
+    // Delimiters that would be mistaken for quoting or record separators are rejected
+    for delimiter in ['"', '\r', '\n'] {
+        let result = extractor
+            .clone()
+            .set_csv_delimiter(delimiter)
+            .extract_bytes_to_string(html);
+        assert!(
+            matches!(result, Err(Error::ParseError(_))),
+            "{:?}",
+            delimiter
+        );
+    }
+}
+
+#[test]
+fn test_extract_bytes_with_quality() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let (extracted, _metadata, quality) = extractor.extract_bytes_with_quality(&bytes).unwrap();
+    assert!(!extracted.is_empty());
+    assert!(quality.score > 0.5, "score: {}", quality.score);
+
+    // A scanned document without OCR yields (almost) no text
+    let extractor = Extractor::new()
+        .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::NO_OCR));
+    let bytes = fs::read("../test_files/documents/deu-ocr.pdf").unwrap();
+    let (_extracted, _metadata, quality) = extractor.extract_bytes_with_quality(&bytes).unwrap();
+    assert!(quality.score < 0.5, "score: {}", quality.score);
+}
+
+#[test]
+fn test_extract_bytes_to_string_charset_policy() {
+    // Declared as latin-1 but actually encoded as utf-8
+    let html = "<html><head><meta charset=\"ISO-8859-1\"><title>Umlaute</title></head><body>\
+        <p>Grüße aus München, schöne Größe, übermäßig süße Äpfel und Öl.</p></body></html>";
+
+    let (extracted, _metadata) = Extractor::new()
+        .extract_bytes_to_string(html.as_bytes())
+        .unwrap();
+    assert!(extracted.contains("GrÃ¼Ã"), "extracted: {}", extracted);
+
+    for policy in [CharsetPolicy::PreferDetected, CharsetPolicy::ForceUtf8] {
+        let (extracted, _metadata) = Extractor::new()
+            .set_charset_policy(policy)
+            .extract_bytes_to_string(html.as_bytes())
+            .unwrap();
+        assert!(
+            extracted.contains("Grüße aus München"),
+            "policy: {}, extracted: {}",
+            policy,
+            extracted
+        );
+    }
+}
+
+#[cfg(feature = "ocr")]
+#[test]
+fn test_extract_file_to_string_skips_pages_on_ocr_timeout() {
+    // A timeout of 0 seconds makes the OCR of every page time out
+    let extractor = Extractor::new()
+        .set_ocr_config(
+            TesseractOcrConfig::new()
+                .set_language("deu")
+                .set_timeout_seconds(0),
+        )
+        .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_ONLY));
+    let (_extracted, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/deu-ocr.pdf")
+        .unwrap();
+
+    let skipped = &metadata["X-OCR-Skipped-Pages"];
+    assert!(!skipped.is_empty());
+    assert!(skipped.iter().all(|page| page.parse::<u32>().unwrap() >= 1));
+}
+
+#[test]
+fn test_warmup() {
+    let extractor = Extractor::new();
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let extractor = extractor.clone();
+            std::thread::spawn(move || extractor.warmup())
+        })
+        .collect();
+    for handle in handles {
+        assert!(handle.join().unwrap().is_ok());
+    }
+    assert!(extractor.warmup().is_ok());
+}
+
+#[test_case(true, true, true; "with chart and smartart")]
+#[test_case(false, true, true; "without chart")]
+#[test_case(true, false, true; "without smartart")]
+#[test_case(true, true, false; "with chart and smartart without shapes")]
+#[test_case(false, false, false; "without chart, smartart and shapes")]
+fn test_extract_file_to_string_chart_and_smartart_content(
+    chart: bool,
+    smartart: bool,
+    shapes: bool,
+) {
+    let extractor = Extractor::new().set_office_config(
+        OfficeParserConfig::new()
+            .set_include_chart_content(chart)
+            .set_include_smartart_content(smartart)
+            .set_include_shape_based_content(shapes),
+    );
+    let (extracted, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/chart-smartart.pptx")
+        .unwrap();
+
+    assert!(extracted.contains("Title Slide"));
+    // Chart title and axis title
+    assert_eq!(extracted.contains("Revenue by Region"), chart);
+    assert_eq!(extracted.contains("Sales region"), chart);
+    // SmartArt nodes
+    assert_eq!(extracted.contains("Discovery phase"), smartart);
+    assert_eq!(extracted.contains("Delivery phase"), smartart);
+}
+
+#[test]
+fn test_extract_bytes_to_string_no_parser() {
+    // Binary data without any known signature
+    let bytes = [0x00, 0x01, 0x02, 0x03, 0xfe, 0xff, 0x00, 0x10].repeat(8);
+    let result = Extractor::new().extract_bytes_to_string(&bytes);
+    match result {
+        Err(Error::NoParser(media_type)) => assert_eq!(media_type, "application/octet-stream"),
+        other => panic!("expected a NoParser error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_extract_bytes_to_string_accepted_media_types() {
+    let extractor = Extractor::new().set_accepted_media_types(vec!["application/pdf".to_string()]);
+
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let (content, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert!(!content.is_empty());
+
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+    assert!(matches!(
+        extractor.extract_bytes_to_string(&bytes),
+        Err(Error::Rejected)
+    ));
+    assert!(matches!(
+        extractor.extract_bytes(&bytes),
+        Err(Error::Rejected)
+    ));
+}
+
+#[test]
+fn test_extract_bytes_to_string_reject_macro_documents() {
+    let macros = fs::read("../test_files/documents/macros.docm").unwrap();
+    let docx = fs::read("../test_files/documents/category-level.docx").unwrap();
+    let doc = fs::read("../test_files/documents/simple.doc").unwrap();
+    let extractor = Extractor::new();
+    assert!(extractor.has_macros(&macros).unwrap());
+    assert!(!extractor.has_macros(&docx).unwrap());
+    assert!(!extractor.has_macros(&doc).unwrap());
+
+    // Macro documents are parsed unless rejected
+    let (content, _metadata) = extractor.extract_bytes_to_string(&macros).unwrap();
+    assert!(content.contains("This document carries a VBA project."));
+
+    let extractor = extractor.set_reject_macro_documents(true);
+    assert!(matches!(
+        extractor.extract_bytes_to_string(&macros),
+        Err(Error::Rejected)
+    ));
+    assert!(extractor.extract_bytes_to_string(&docx).is_ok());
+}
+
+#[test]
+fn test_extract_bytes_to_string_truncated() {
+    // Cut in the middle of the id of the first element after the EBML header
+    let bytes = fs::read("../test_files/documents/subtitles.mkv").unwrap();
+    match Extractor::new().extract_bytes_to_string(&bytes[..42]) {
+        Err(Error::UnexpectedEof) => {}
+        other => panic!("expected an UnexpectedEof error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_extract_full() {
+    let extractor = Extractor::new();
+    let result = extractor
+        .extract_file_full("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    let (content, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    assert_eq!(result.content, content);
+    assert_eq!(result.mime_type, "application/pdf");
+    assert_eq!(result.detected_language.as_deref(), Some("en"));
+    assert!(result.detected_language_confidence.unwrap() > 0.5);
+    // The detected language is not left in the metadata
+    assert!(!result.metadata.contains_key("X-Detected-Language"));
+    assert!(!result
+        .metadata
+        .contains_key("X-Detected-Language-Confidence"));
+
+    let german = "Die Sonne scheint heute über der ganzen Stadt, und die Kinder spielen im Park. \
+        Am Abend treffen sich die Nachbarn zum Essen und erzählen einander von ihrem Tag.";
+    let result = extractor.extract_bytes_full(german.as_bytes()).unwrap();
+    assert_eq!(result.mime_type, "text/plain");
+    assert_eq!(result.detected_language.as_deref(), Some("de"));
+
+    let result = extractor.extract_bytes_full(b"").unwrap();
+    assert_eq!(result.detected_language, None);
+    assert_eq!(result.detected_language_confidence, None);
+}
+
+#[test]
+fn test_detect_language() {
+    let extractor = Extractor::new();
+
+    let french = "Le soleil brille aujourd'hui sur toute la ville et les enfants jouent dans le \
+        parc. Le soir, les voisins se retrouvent pour dîner et se racontent leur journée.";
+    let detected = extractor.detect_language(french).unwrap().unwrap();
+    assert_eq!(detected.language, "fr");
+    assert!(detected.confidence > 0.5 && detected.confidence <= 1.0);
+
+    assert_eq!(extractor.detect_language("   ").unwrap(), None);
+}
+
+#[test]
+fn test_extract_file_to_string_typed_metadata() {
+    let (_content, metadata) = Extractor::new()
+        .extract_file_to_string("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    assert_eq!(metadata.title(), Some("0000320193-22-000070"));
+    assert_eq!(
+        metadata.author(),
+        Some("EDGAR Online, a division of Donnelley Financial Solutions")
+    );
+    assert_eq!(metadata.content_type(), Some("application/pdf"));
+    assert_eq!(metadata.created(), Some("2022-07-29T10:03:21Z"));
+    assert_eq!(metadata.page_count(), Some(28));
+
+    // Missing keys are None
+    let (_content, metadata) = Extractor::new()
+        .extract_bytes_to_string(b"plain text")
+        .unwrap();
+    assert_eq!(metadata.title(), None);
+    assert_eq!(metadata.page_count(), None);
+}
+
+#[test]
+fn test_extract_file_to_string_metadata_namespaces() {
+    let file = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let extractor =
+        Extractor::new().set_metadata_namespaces(vec!["dc".to_string(), "pdf".to_string()]);
+    let (_content, metadata) = extractor.extract_file_to_string(file).unwrap();
+    assert!(!metadata.is_empty());
+    assert!(metadata
+        .keys()
+        .all(|key| key.starts_with("dc:") || key.starts_with("pdf:")));
+
+    let extractor =
+        Extractor::new().set_metadata_namespaces(vec![METADATA_NO_NAMESPACE.to_string()]);
+    let (_content, metadata) = extractor.extract_file_to_string(file).unwrap();
+    assert!(metadata.contains_key("Content-Type"));
+    assert!(metadata.keys().all(|key| !key.contains(':')));
+}
+
+#[test]
+fn test_extract_file_to_string_matroska_subtitles() {
+    let extractor = Extractor::new();
+    let (content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/subtitles.mkv")
+        .unwrap();
+    assert!(content.contains("Hello from the first subtitle."));
+    assert!(content.contains("two lines."));
+    assert_eq!(metadata.get("Content-Type").unwrap()[0], "video/x-matroska");
+    assert_eq!(metadata.get("xmpDM:duration").unwrap()[0], "6.0");
+    assert_eq!(metadata.get("tiff:ImageWidth").unwrap()[0], "320");
+    assert_eq!(metadata.get("tiff:ImageLength").unwrap()[0], "240");
+    assert_eq!(
+        metadata.get("xmpDM:videoCompressor").unwrap()[0],
+        "V_MPEG4/ISO/AVC"
+    );
+}
+
+#[test]
+fn test_render_pdf_page() {
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let extractor = Extractor::new();
+
+    let png_width = |png: &[u8]| u32::from_be_bytes(png[16..20].try_into().unwrap());
+    let image = extractor.render_pdf_page(&bytes, 1, 72).unwrap();
+    assert!(image.starts_with(b"\x89PNG\r\n\x1a\n"));
+    let large_image = extractor.render_pdf_page(&bytes, 1, 144).unwrap();
+    assert_eq!(png_width(&large_image), 2 * png_width(&image));
+
+    for page in [0, 1000] {
+        match extractor.render_pdf_page(&bytes, page, 72) {
+            Err(Error::IoError(_)) => {}
+            other => panic!("expected an IoError for page {}, got {:?}", page, other),
+        }
+    }
+}
+
+#[test]
+fn test_extract_images() {
+    let extractor = Extractor::new();
+
+    let bytes = fs::read("../test_files/documents/deu-ocr.pdf").unwrap();
+    let images = extractor.extract_images(&bytes).unwrap();
+    assert!(!images.is_empty());
+    assert_eq!(images[0].page, Some(1));
+    let (x, y, width, height) = images[0].bbox.unwrap();
+    assert!(x >= 0.0 && y >= 0.0 && width > 0.0 && height > 0.0);
+    assert!(x + width <= 596.0 && y + height <= 842.0);
+
+    let bytes = fs::read("../test_files/documents/science-exploration-1p.pptx").unwrap();
+    let images = extractor.extract_images(&bytes).unwrap();
+    assert_eq!(images.len(), 2);
+    for image in images {
+        assert_eq!(image.mime_type, "image/png");
+        assert!(image.data.starts_with(b"\x89PNG"));
+        assert_eq!(image.page, None);
+        assert_eq!(image.bbox, None);
+    }
+}
+
+#[test]
+fn test_extract_pdf_with_anchors() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+
+    let (text, words) = extractor
+        .extract_pdf_with_anchors(&bytes, AnchorGranularity::Word)
+        .unwrap();
+    let chars: Vec<char> = text.chars().collect();
+    assert!(!words.is_empty());
+    let mut previous_end = 0;
+    for anchor in &words {
+        assert!(anchor.offset >= previous_end && anchor.len > 0);
+        previous_end = anchor.offset + anchor.len;
+        assert!(previous_end <= chars.len());
+        let word: String = chars[anchor.offset..previous_end].iter().collect();
+        assert!(!word.trim().is_empty());
+
+        let (_x, _y, width, height) = anchor.bbox;
+        assert!(anchor.page >= 1 && width >= 0.0 && height >= 0.0);
+    }
+    let apple = words
+        .iter()
+        .find(|anchor| {
+            chars[anchor.offset..anchor.offset + anchor.len].starts_with(&['A', 'p', 'p', 'l', 'e'])
+        })
+        .unwrap();
+    assert_eq!(apple.page, 1);
+
+    let (line_text, lines) = extractor
+        .extract_pdf_with_anchors(&bytes, AnchorGranularity::Line)
+        .unwrap();
+    assert_eq!(line_text, text);
+    assert!(!lines.is_empty() && lines.len() < words.len());
+}
+
+#[test]
+fn test_extract_encrypted_pdf() {
+    let bytes = fs::read("../test_files/documents/encrypted.pdf").unwrap();
+
+    let extractor = Extractor::new();
+    assert!(matches!(
+        extractor.extract_bytes_to_string(&bytes),
+        Err(Error::EncryptedDocument)
+    ));
+    let extractor = Extractor::new().set_pdf_config(PdfParserConfig::new().set_password("wrong"));
+    assert!(matches!(
+        extractor.extract_bytes_to_string(&bytes),
+        Err(Error::EncryptedDocument)
+    ));
+
+    let extractor = Extractor::new().set_pdf_config(PdfParserConfig::new().set_password("secret"));
+    let (content, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert!(content.contains("Confidential salary report"));
+    // The password is not printed
+    assert!(!format!("{:?}", extractor).contains("secret"));
+}
+
+#[test]
+fn test_extract_encrypted_pdf_password_provider() {
+    let bytes = fs::read("../test_files/documents/encrypted.pdf").unwrap();
+
+    let extractor = Extractor::new().set_password_provider(|_info| None);
+    assert!(matches!(
+        extractor.extract_bytes_to_string(&bytes),
+        Err(Error::EncryptedDocument)
+    ));
+
+    let media_types = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = std::sync::Arc::clone(&media_types);
+    let extractor = Extractor::new().set_password_provider(move |info| {
+        seen.lock().unwrap().push(info.media_type.clone());
+        info.media_type
+            .as_deref()
+            .filter(|media_type| media_type.starts_with("application/pdf"))
+            .map(|_| "secret".to_string())
+    });
+    let (content, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert!(content.contains("Confidential salary report"));
+    assert!(!media_types.lock().unwrap().is_empty());
+
+    // Without a password from the provider, the password of the pdf config is used
+    let extractor = Extractor::new()
+        .set_pdf_config(PdfParserConfig::new().set_password("secret"))
+        .set_password_provider(|_info| None);
+    let (content, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert!(content.contains("Confidential salary report"));
+}
+
+#[test]
+fn test_extract_pdf_sort_by_position() {
+    let bytes = fs::read("../test_files/documents/reading-order.pdf").unwrap();
+    let first_before_second = |extractor: Extractor| {
+        let (content, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+        let first = content.find("First paragraph").unwrap();
+        let second = content.find("Second paragraph").unwrap();
+        first < second
+    };
+
+    // The second paragraph is drawn first, below the first one
+    assert!(!first_before_second(Extractor::new()));
+    assert!(first_before_second(
+        Extractor::new().set_pdf_config(
+            PdfParserConfig::new()
+                .set_sort_by_position(true)
+                .set_average_char_tolerance(0.3)
+                .set_spacing_tolerance(0.5)
+        )
+    ));
+}
+
+#[test]
+fn test_extract_pdf_duplicate_text_and_angles() {
+    let bytes = fs::read("../test_files/documents/stamped.pdf").unwrap();
+
+    // The text of the page is drawn twice at the same position, under a stamp rotated by 45°
+    let extractor = Extractor::new().set_pdf_config(
+        PdfParserConfig::new()
+            .set_suppress_duplicate_overlapping_text(true)
+            .set_detect_angles(true),
+    );
+    let (content, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert_eq!(content.matches("Quarterly report").count(), 1);
+    assert!(content.contains("APPROVED"));
+}
+
+#[test]
+fn test_extract_pdf_page_range() {
+    let bytes = fs::read("../test_files/documents/outline.pdf").unwrap();
+
+    let extractor = Extractor::new().set_pdf_config(PdfParserConfig::new().set_page_range(2..=3));
+    let (content, metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert!(!content.contains("Introduction"));
+    assert!(content.contains("Results"));
+    assert!(content.contains("Details of the results"));
+    // The page count is the one of the whole document
+    assert_eq!(metadata.page_count(), Some(3));
+
+    // Pages past the end of the document are ignored
+    let extractor = Extractor::new().set_pdf_config(PdfParserConfig::new().set_page_range(3..=100));
+    let (content, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert!(content.contains("Details of the results"));
+    assert!(!content.contains("Introduction"));
+}
+
+#[test]
+fn test_extract_pdf_outline() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/outline.pdf").unwrap();
+
+    let outline = extractor.extract_pdf_outline(&bytes).unwrap();
+    let titles: Vec<&str> = outline.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, vec!["Introduction", "Results", "Appendix"]);
+    assert_eq!(outline[0].page, Some(1));
+    assert!(outline[0].children.is_empty());
+    assert_eq!(outline[1].page, Some(2));
+    // The page of a bookmark is also resolved through its GoTo action
+    assert_eq!(outline[1].children.len(), 1);
+    assert_eq!(outline[1].children[0].title, "Details");
+    assert_eq!(outline[1].children[0].page, Some(3));
+    assert_eq!(outline[2].page, None);
+
+    // A PDF without bookmarks has an empty outline
+    let bytes = fs::read("../test_files/documents/annotations.pdf").unwrap();
+    assert!(extractor.extract_pdf_outline(&bytes).unwrap().is_empty());
+}
+
+#[test]
+fn test_extract_pdf_forms() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/form.pdf").unwrap();
+
+    let fields = extractor.extract_pdf_forms(&bytes).unwrap();
+    let field = |name: &str| fields.iter().find(|field| field.name == name).unwrap();
+    assert_eq!(fields.len(), 6);
+
+    // The name of a field includes the names of the fields grouping it
+    let name = field("applicant.name");
+    assert_eq!(name.kind, FormFieldKind::Text);
+    assert_eq!(name.value.as_deref(), Some("Jane Doe"));
+    assert_eq!(field("email").value, None);
+
+    assert_eq!(field("agree").kind, FormFieldKind::Checkbox);
+    assert_eq!(field("agree").value.as_deref(), Some("Yes"));
+    assert_eq!(field("plan").kind, FormFieldKind::RadioButton);
+    assert_eq!(field("plan").value.as_deref(), Some("Premium"));
+    assert_eq!(field("country").kind, FormFieldKind::ComboBox);
+    assert_eq!(field("country").value.as_deref(), Some("Germany"));
+    assert_eq!(field("submit").kind, FormFieldKind::PushButton);
+    assert_eq!(field("submit").value, None);
+
+    // A PDF without a form has no fields
+    let bytes = fs::read("../test_files/documents/annotations.pdf").unwrap();
+    assert!(extractor.extract_pdf_forms(&bytes).unwrap().is_empty());
+}
+
+#[test]
+fn test_extract_pdf_annotations() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/annotations.pdf").unwrap();
+
+    // The popup of the note is left out
+    let annotations = extractor.extract_pdf_annotations(&bytes).unwrap();
+    let types: Vec<&str> = annotations
+        .iter()
+        .map(|annotation| annotation.annotation_type.as_str())
+        .collect();
+    assert_eq!(types, vec!["Text", "Highlight", "Stamp"]);
+
+    let note = &annotations[0];
+    assert_eq!(note.author.as_deref(), Some("Alice Reviewer"));
+    assert_eq!(note.text.as_deref(), Some("Check the termination clause"));
+    assert_eq!(note.page, 1);
+    assert_eq!(note.bbox, (400.0, 72.0, 20.0, 20.0));
+
+    // A highlight without a comment is still listed
+    let highlight = &annotations[1];
+    assert_eq!(highlight.author.as_deref(), Some("Bob Counsel"));
+    assert_eq!(highlight.text, None);
+    assert_eq!(highlight.page, 2);
+
+    let stamp = &annotations[2];
+    assert_eq!(stamp.author, None);
+    assert_eq!(stamp.text.as_deref(), Some("Approved"));
+}
+
+#[test]
+fn test_extract_pdf_annotations_kind_and_rect() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/annotations.pdf").unwrap();
+
+    // The rectangles are converted from the bottom left origin of the PDF to the top left
+    let annotations = extractor.extract_pdf_annotations(&bytes).unwrap();
+    let kinds_and_rects: Vec<_> = annotations
+        .iter()
+        .map(|annotation| (annotation.kind(), annotation.rect()))
+        .collect();
+    assert_eq!(
+        kinds_and_rects,
+        vec![
+            ("Text", (400.0, 72.0, 20.0, 20.0)),
+            ("Highlight", (72.0, 80.0, 228.0, 22.0)),
+            ("Stamp", (350.0, 632.0, 200.0, 60.0)),
+        ]
+    );
+}
+
+#[test]
+fn test_extract_comments() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/comments.docx").unwrap();
+
+    let comments = extractor.extract_comments(&bytes).unwrap();
+    assert_eq!(comments.len(), 3);
+
+    let question = &comments[0];
+    assert_eq!(question.author.as_deref(), Some("Alice Reviewer"));
+    assert_eq!(question.timestamp.as_deref(), Some("2024-03-01T09:30:00Z"));
+    assert_eq!(
+        question.text,
+        "Should this be 14 days?\nThe old contract said 14."
+    );
+    assert_eq!(question.parent, None);
+
+    // The reply points to the comment it answers, the next thread starts anew
+    let reply = &comments[1];
+    assert_eq!(reply.author.as_deref(), Some("Bob Counsel"));
+    assert_eq!(reply.parent.as_ref(), Some(&question.id));
+    assert_eq!(comments[2].parent, None);
+
+    // Documents without comments, OOXML or not, have none
+    for file in ["bug_16.docx", "simple.odt"] {
+        let bytes = fs::read(format!("../test_files/documents/{}", file)).unwrap();
+        assert!(extractor.extract_comments(&bytes).unwrap().is_empty());
+    }
+}
+
+#[test]
+fn test_extract_pdf_fonts() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+
+    let fonts = extractor.extract_pdf_fonts(&bytes).unwrap();
+    assert!(fonts.iter().any(|font| font.name.contains("Arial")));
+    for font in &fonts {
+        assert!(!font.font_type.is_empty());
+        // Subset fonts are tagged with six upper case letters and a plus sign
+        assert_eq!(font.subset, font.name.find('+') == Some(6));
+    }
+
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+    assert!(matches!(
+        extractor.extract_pdf_fonts(&bytes),
+        Err(Error::IoError(_))
+    ));
+}
+
+#[test]
+fn test_extract_pdf_page_labels() {
+    let extractor = Extractor::new();
+
+    // Roman front matter, then an appendix with a prefix, then a range restarting at 1
+    let bytes = fs::read("../test_files/documents/page_labels.pdf").unwrap();
+    let labels = extractor.extract_pdf_page_labels(&bytes).unwrap();
+    assert_eq!(labels, vec!["i", "ii", "A-1", "A-2", "1"]);
+
+    // Without page labels, every page is labeled with its physical page number
+    let bytes = fs::read("../test_files/documents/annotations.pdf").unwrap();
+    let labels = extractor.extract_pdf_page_labels(&bytes).unwrap();
+    assert_eq!(labels, vec!["1", "2"]);
+
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+    assert!(matches!(
+        extractor.extract_pdf_page_labels(&bytes),
+        Err(Error::IoError(_))
+    ));
+}
+
+#[test]
+fn test_extract_bytes_to_string_progress() {
+    let reported = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = std::sync::Arc::clone(&reported);
+    let extractor =
+        Extractor::new().set_progress_callback(move |fraction| sink.lock().unwrap().push(fraction));
+
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    extractor.extract_bytes_to_string(&bytes).unwrap();
+
+    // The estimate goes from 0 to 1 without ever going back, with steps for the pages between
+    let reported = reported.lock().unwrap();
+    assert_eq!(reported.first(), Some(&0.0));
+    assert_eq!(reported.last(), Some(&1.0));
+    assert!(reported.len() > 2);
+    assert!(reported.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn test_verify_signature() {
+    let extractor = Extractor::new();
+
+    let bytes = fs::read("../test_files/documents/signed.pdf").unwrap();
+    match extractor.verify_signature(&bytes).unwrap() {
+        SignatureStatus::Valid { signer } => assert!(signer.contains("Extractous Test")),
+        other => panic!("expected a valid signature, got {:?}", other),
+    }
+
+    // Changing the signed text must break the signature
+    let mut tampered = bytes.clone();
+    let position = tampered
+        .windows(6)
+        .position(|window| window == b"Signed")
+        .unwrap();
+    tampered[position..position + 6].copy_from_slice(b"Forged");
+    assert!(matches!(
+        extractor.verify_signature(&tampered).unwrap(),
+        SignatureStatus::Invalid { .. }
+    ));
+
+    let unsigned = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    assert_eq!(
+        extractor.verify_signature(&unsigned).unwrap(),
+        SignatureStatus::Unsigned
+    );
+    let unsigned = fs::read("../test_files/documents/simple.pptx").unwrap();
+    assert_eq!(
+        extractor.verify_signature(&unsigned).unwrap(),
+        SignatureStatus::Unsigned
+    );
+}
+
+#[test]
+fn test_extract_file_to_string_parsed_by() {
+    let extractor = Extractor::new();
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    let parsed_by = metadata.get("X-Parsed-By").unwrap();
+    assert!(parsed_by.contains(&"org.apache.tika.parser.pdf.PDFParser".to_string()));
+}
+
+#[test]
+fn test_extract_bytes_to_string_line_break_policy() {
+    let html = b"<html><body><p>One</p><p>Two<br>Three</p></body></html>";
+
+    let (content, _metadata) = Extractor::new().extract_bytes_to_string(html).unwrap();
+    assert!(content.contains("One\nTwo\nThree"));
+
+    let policy = LineBreakPolicy::new()
+        .set_block_break(LineBreak::DoubleNewline)
+        .set_br_break(LineBreak::Space);
+    let (content, _metadata) = Extractor::new()
+        .set_line_break_policy(policy)
+        .extract_bytes_to_string(html)
+        .unwrap();
+    assert!(content.contains("One\n\nTwo Three"));
+}
+
+#[test_case("formulas.xlsx", "=SUM(B2:B3)"; "Test XLSX formulas")]
+#[test_case("formulas.ods", "=SUM([.B2:.B3])"; "Test ODS formulas")]
+fn test_extract_file_to_string_formula_output(file_name: &str, formula: &str) {
+    let file = format!("../test_files/documents/{}", file_name);
+    let extract = |output: FormulaOutput| {
+        let extractor = Extractor::new()
+            .set_office_config(OfficeParserConfig::new().set_formula_output(output));
+        extractor.extract_file_to_string(&file).unwrap().0
+    };
+
+    let computed = extract(FormulaOutput::ComputedValue);
+    assert!(computed.contains("2000") && !computed.contains(formula));
+    assert_eq!(
+        computed,
+        Extractor::new().extract_file_to_string(&file).unwrap().0
+    );
+
+    let formulas = extract(FormulaOutput::Formula);
+    assert!(formulas.contains(formula) && !formulas.contains("2000"));
+    // Cells without formula are kept
+    assert!(formulas.contains("1200") && formulas.contains("Revenue"));
+
+    let both = extract(FormulaOutput::Both);
+    assert!(both.contains(&format!("{} = 2000", formula)));
+}
+
+#[test]
+fn test_extract_file_to_string_legal_review_preset() {
+    let extractor = Extractor::new().set_office_config(OfficeParserConfig::for_legal_review());
+    let (content, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/formulas.xlsx")
+        .unwrap();
+
+    // Formulas are shown next to their values rather than replaced by them
+    assert!(content.contains("=SUM(B2:B3) = 2000"));
+    assert!(content.contains("Revenue"));
+}
+
+#[test]
+fn test_extract_file_to_string_odf_metadata() {
+    let extractor = Extractor::new();
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/simple.ods")
+        .unwrap();
+    // The statistics and the generator of meta.xml use the same keys as for OOXML files
+    assert_eq!(metadata["meta:table-count"], vec!["2"]);
+    assert_eq!(metadata["meta:cell-count"], vec!["12"]);
+    assert!(metadata["generator"][0].starts_with("LibreOffice/7.6"));
+    assert_eq!(
+        metadata["extended-properties:Application"],
+        metadata["generator"]
+    );
+
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/simple.odp")
+        .unwrap();
+    assert_eq!(metadata["meta:slide-count"], vec!["2"]);
+    assert_eq!(metadata["meta:object-count"], vec!["5"]);
+}
+
+#[test]
+fn test_extract_batch() {
+    let paths = vec![
+        PathBuf::from("../test_files/documents/2022_Q3_AAPL.pdf"),
+        PathBuf::from("../test_files/documents/missing.pdf"),
+        PathBuf::from("../test_files/documents/simple.odt"),
+        PathBuf::from("../test_files/documents/category-level.docx"),
+    ];
+    let extractor = Extractor::new();
+    let mut results: Vec<_> = extractor.extract_batch(paths.clone(), 2).collect();
+
+    // Results come in as the files complete, every file exactly once
+    results.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut sorted_paths = paths.clone();
+    sorted_paths.sort();
+    let extracted: Vec<PathBuf> = results.iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(extracted, sorted_paths);
+
+    for (path, result) in results {
+        match extractor.extract_file_to_string(&path.to_string_lossy()) {
+            Ok((expected, _)) => {
+                let (content, metadata) = result.unwrap();
+                assert_eq!(content, expected, "{}", path.display());
+                assert!(metadata.contains_key("Content-Type"));
+            }
+            Err(_) => assert!(matches!(result, Err(Error::IoError(_)))),
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_extract_batch_non_utf8_path() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = PathBuf::from(OsStr::from_bytes(b"../test_files/documents/\xffsimple.odt"));
+    let results: Vec<_> = Extractor::new()
+        .extract_batch(vec![path.clone()], 1)
+        .collect();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].0, path);
+    assert!(matches!(&results[0].1, Err(Error::IoError(msg)) if msg.contains("not valid UTF-8")));
+}
+
+#[test]
+fn test_walk_dir() {
+    let extractor = Extractor::new();
+    let walked: Vec<_> = walk_dir("../test_files", &extractor)
+        .add_glob("documents/simple.od?")
+        .collect();
+
+    // Entries are visited in the order of their names
+    let paths: Vec<PathBuf> = walked.iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            PathBuf::from("../test_files/documents/simple.odp"),
+            PathBuf::from("../test_files/documents/simple.ods"),
+            PathBuf::from("../test_files/documents/simple.odt"),
+        ]
+    );
+    let (_, result) = &walked[2];
+    let result = result.as_ref().unwrap();
+    assert_eq!(result.mime_type, "application/vnd.oasis.opendocument.text");
+    assert!(!result.content.trim().is_empty());
+
+    // Files of the other media types are skipped
+    let walked: Vec<_> = walk_dir("../test_files/documents", &extractor)
+        .add_glob("simple.*")
+        .set_media_types(vec!["application/vnd.oasis.opendocument.text".to_string()])
+        .map(|(path, result)| (path, result.unwrap().mime_type))
+        .collect();
+    assert_eq!(
+        walked,
+        vec![(
+            PathBuf::from("../test_files/documents/simple.odt"),
+            "application/vnd.oasis.opendocument.text".to_string()
+        )]
+    );
+
+    let mut missing = walk_dir("../test_files/missing", &extractor);
+    assert!(matches!(missing.next(), Some((_, Err(Error::IoError(_))))));
+    assert!(missing.next().is_none());
+}
+
+#[test]
+fn test_catalog() {
+    let paths = vec![
+        PathBuf::from("../test_files/documents/2022_Q3_AAPL.pdf"),
+        PathBuf::from("../test_files/documents/missing.pdf"),
+        PathBuf::from("../test_files/documents/simple.odt"),
+        PathBuf::from("../test_files/documents/category-level.docx"),
+    ];
+    let catalog = Extractor::new().catalog(paths.clone(), 2);
+
+    // Results are in the order of the paths, whatever thread parsed them
+    let cataloged: Vec<PathBuf> = catalog.iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(cataloged, paths);
+
+    let content_type = |index: usize| match &catalog[index].1 {
+        Ok(metadata) => metadata["Content-Type"][0].clone(),
+        Err(e) => panic!("{} failed: {}", paths[index].display(), e),
+    };
+    assert_eq!(content_type(0), "application/pdf");
+    assert!(matches!(catalog[1].1, Err(Error::IoError(_))));
+    assert_eq!(content_type(2), "application/vnd.oasis.opendocument.text");
+    assert_eq!(
+        content_type(3),
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+    );
+    let Ok(metadata) = &catalog[0].1 else {
+        unreachable!()
+    };
+    assert!(metadata.contains_key("xmpTPg:NPages"));
+}
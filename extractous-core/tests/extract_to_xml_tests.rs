@@ -1,5 +1,6 @@
-use extractous::Extractor;
+use extractous::{Extractor, OfficeParserConfig, OutputFormat, SaxEvent};
 use std::fs;
+use std::sync::{Arc, Mutex};
 use test_case::test_case;
 use textdistance::nstr::cosine;
 use quick_xml::reader::Reader;
@@ -88,3 +89,93 @@ fn test_extract_file_to_xml(file_name: &str, target_dist: f64) {
         &extracted_metadata
     ));
 }
+
+#[test]
+fn test_extract_ods_to_xml_sheets() {
+    let extractor = Extractor::new().set_xml_output(true);
+    let (xml, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/simple.ods")
+        .unwrap();
+
+    // Every sheet is a page headed by its name, like for xlsx files
+    assert_eq!(xml.matches("<div class=\"page\">").count(), 2);
+    let revenue = xml.find("<h1>Revenue</h1>").unwrap();
+    let costs = xml.find("<h1>Costs</h1>").unwrap();
+    assert!(revenue < xml.find("1350").unwrap());
+    assert!(costs > xml.find("1350").unwrap() && costs < xml.find("870").unwrap());
+}
+
+#[test]
+fn test_extract_ods_to_html() {
+    let bytes = fs::read("../test_files/documents/simple.ods").unwrap();
+    let extractor = Extractor::new().set_output_format(OutputFormat::Html);
+    let (html, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+
+    // The structure is kept, but void elements are not closed as in XHTML
+    assert!(html.contains("<h1>Revenue</h1>"));
+    assert!(html.contains("<table>") || html.contains("<table "));
+    assert!(html.contains("<meta "));
+    assert!(!html.contains("/>"));
+
+    let (mut reader, _metadata) = extractor.extract_bytes(&bytes).unwrap();
+    let mut streamed = String::new();
+    std::io::Read::read_to_string(&mut reader, &mut streamed).unwrap();
+    assert!(streamed.contains("<h1>Revenue</h1>"));
+    assert!(!streamed.contains("/>"));
+}
+
+#[test]
+fn test_extract_odp_to_xml_slides() {
+    let file = "../test_files/documents/simple.odp";
+    let extractor = Extractor::new().set_xml_output(true);
+    let (xml, _metadata) = extractor.extract_file_to_string(file).unwrap();
+
+    // Every slide and its notes get their own div, like for pptx files
+    assert_eq!(xml.matches("<div class=\"slide-content\">").count(), 2);
+    assert_eq!(xml.matches("<div class=\"slide-notes\">").count(), 1);
+    let notes = xml.find("Mention the new customers").unwrap();
+    assert!(xml.find("Revenue grew").unwrap() < notes && notes < xml.find("Outlook").unwrap());
+
+    let extractor =
+        extractor.set_office_config(OfficeParserConfig::new().set_include_slide_notes(false));
+    let (xml, _metadata) = extractor.extract_file_to_string(file).unwrap();
+    assert_eq!(xml.matches("<div class=\"slide-content\">").count(), 2);
+    assert!(!xml.contains("slide-notes"));
+    assert!(!xml.contains("Mention the new customers"));
+    assert!(xml.contains("Costs stay flat"));
+}
+
+#[test]
+fn test_extract_bytes_to_handler() {
+    let bytes = fs::read("../test_files/documents/simple.ods").unwrap();
+    let headings = Arc::new(Mutex::new(Vec::new()));
+    let depth = Arc::new(Mutex::new(0i32));
+
+    let (headings_seen, depth_seen) = (Arc::clone(&headings), Arc::clone(&depth));
+    let mut in_heading = false;
+    let metadata = Extractor::new()
+        .extract_bytes_to_handler(&bytes, move |event| match event {
+            SaxEvent::StartElement { name, .. } => {
+                *depth_seen.lock().unwrap() += 1;
+                if name == "h1" {
+                    in_heading = true;
+                    headings_seen.lock().unwrap().push(String::new());
+                }
+            }
+            SaxEvent::Characters(text) if in_heading => {
+                let mut headings = headings_seen.lock().unwrap();
+                headings.last_mut().unwrap().push_str(text);
+            }
+            SaxEvent::Characters(_) => {}
+            SaxEvent::EndElement { name } => {
+                *depth_seen.lock().unwrap() -= 1;
+                in_heading &= name != "h1";
+            }
+        })
+        .unwrap();
+
+    // Every element that starts also ends, and the sheet names are the headings
+    assert_eq!(*depth.lock().unwrap(), 0);
+    assert_eq!(*headings.lock().unwrap(), vec!["Revenue", "Costs"]);
+    assert!(metadata.contains_key("Content-Type"));
+}
@@ -0,0 +1,70 @@
+// Kept in its own test binary so that the memory of other tests does not blur the measure
+#![cfg(target_os = "linux")]
+
+use extractous::Extractor;
+use std::fs;
+use std::io::Read;
+
+/// Returns the resident set size of the process in bytes
+fn resident_memory() -> u64 {
+    let status = fs::read_to_string("/proc/self/status").unwrap();
+    // Reported in kB, whatever the page size
+    let line = status
+        .lines()
+        .find(|line| line.starts_with("VmRSS:"))
+        .unwrap();
+    let kilobytes: u64 = line.split_whitespace().nth(1).unwrap().parse().unwrap();
+    kilobytes * 1024
+}
+
+fn extract_with_new_extractor(bytes: &[u8]) {
+    let extractor = Extractor::new().set_extract_string_max_length(1000);
+    let (content, _metadata) = extractor.extract_bytes_to_string(bytes).unwrap();
+    assert!(!content.is_empty());
+
+    let (mut stream, _metadata) = extractor.extract_bytes(bytes).unwrap();
+    let mut content = String::new();
+    stream.read_to_string(&mut content).unwrap();
+    assert!(!content.is_empty());
+}
+
+#[test]
+fn test_many_short_lived_extractors_do_not_leak() {
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+
+    // Let the VM heap and the parsers settle until the memory reaches a plateau
+    let mut plateau = resident_memory();
+    for _ in 0..10 {
+        for _ in 0..200 {
+            extract_with_new_extractor(&bytes);
+        }
+        let current = resident_memory();
+        let settled = current.abs_diff(plateau) < 1024 * 1024;
+        plateau = current;
+        if settled {
+            break;
+        }
+    }
+
+    for _ in 0..2000 {
+        extract_with_new_extractor(&bytes);
+    }
+    // Extractions on other threads attach and detach them
+    for _ in 0..20 {
+        let bytes = bytes.clone();
+        std::thread::spawn(move || extract_with_new_extractor(&bytes))
+            .join()
+            .unwrap();
+    }
+    let after = resident_memory();
+
+    // A leak of a few KiB per extraction, such as a global reference or a registered
+    // callback that is never released, exceeds the margin
+    let growth = after.saturating_sub(plateau);
+    assert!(
+        growth < 4 * 1024 * 1024,
+        "memory grew by {} bytes over 2000 extractions after settling at {} bytes",
+        growth,
+        plateau
+    );
+}
@@ -0,0 +1,46 @@
+// Run with `cargo test --no-default-features` to check the crate without the ocr feature
+#![cfg(not(feature = "ocr"))]
+
+use extractous::{Extractor, PdfOcrStrategy, PdfParserConfig};
+use std::fs;
+
+#[test]
+fn test_extract_file_to_string_without_ocr_feature() {
+    let extractor = Extractor::new();
+    let (content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    assert!(content.contains("Apple Inc."));
+    assert_eq!(
+        metadata.get("Content-Type").unwrap(),
+        &vec!["application/pdf"]
+    );
+}
+
+#[test]
+fn test_pdf_ocr_strategy_is_ignored_without_ocr_feature() {
+    let extractor = Extractor::new()
+        .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_ONLY));
+    let bytes = fs::read("../test_files/documents/deu-ocr.pdf").unwrap();
+
+    let plan = extractor.explain(&bytes).unwrap();
+    assert!(!plan.ocr_would_run);
+
+    // The scanned pages have no text layer, so nothing is extracted without OCR
+    let (content, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert!(content.trim().is_empty());
+}
+
+#[test]
+fn test_ocr_parser_is_left_out_without_ocr_feature() {
+    let bytes = fs::read("../test_files/documents/table-multi-row-column-cells.png").unwrap();
+
+    // The native library is built without the OCR parser, images go to the image parser
+    let plan = Extractor::new().explain(&bytes).unwrap();
+    assert_eq!(plan.detected_type, "image/png");
+    assert_ne!(
+        plan.selected_parser.as_deref(),
+        Some("org.apache.tika.parser.ocr.TesseractOCRParser")
+    );
+    assert!(!plan.ocr_would_run);
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "serde")]
+
+use extractous::{
+    Extractor, FormulaOutput, HttpConfig, OfficeParserConfig, PdfOcrStrategy, PdfParserConfig,
+};
+
+#[test]
+fn test_config_round_trip() {
+    let pdf_config = PdfParserConfig::new()
+        .set_ocr_strategy(PdfOcrStrategy::NO_OCR)
+        .set_ocr_render_dpi(150);
+    let json = serde_json::to_string(&pdf_config).unwrap();
+    assert!(json.contains("\"ocr_strategy\":\"NO_OCR\""));
+    let deserialized: PdfParserConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, pdf_config);
+
+    let http_config = HttpConfig::new()
+        .add_header("X-Api-Key", "key")
+        .set_connect_timeout_seconds(10);
+    let json = serde_json::to_string(&http_config).unwrap();
+    let deserialized: HttpConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, http_config);
+}
+
+#[test]
+fn test_extractor_profile() {
+    // Missing settings keep their default values
+    let profile = r#"{
+        "extract_string_max_length": 1000,
+        "office_config": { "formula_output": "FORMULA" }
+    }"#;
+    let extractor: Extractor = serde_json::from_str(profile).unwrap();
+    let expected = OfficeParserConfig::new().set_formula_output(FormulaOutput::Formula);
+    let json = serde_json::to_value(&extractor).unwrap();
+    assert_eq!(json["extract_string_max_length"], 1000);
+    assert_eq!(
+        json["office_config"],
+        serde_json::to_value(&expected).unwrap()
+    );
+    assert_eq!(json["locale"], "en-US");
+
+    let (content, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/formulas.xlsx")
+        .unwrap();
+    assert!(content.contains("=SUM(B2:B3)"));
+
+    let reloaded: Extractor = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&reloaded).unwrap(), json);
+}
@@ -0,0 +1,244 @@
+use extractous::{Extractor, ParagraphStyle};
+use std::fs;
+
+#[test]
+fn test_extract_outline_offsets_match_plain_text() {
+    let extractor = Extractor::new().set_extract_string_max_length(1000000);
+    let bytes = fs::read("../test_files/documents/category-level.docx").unwrap();
+
+    let outline = extractor.extract_outline(&bytes).unwrap();
+    let (text, _metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+
+    let headings: Vec<_> = outline
+        .iter()
+        .map(|heading| (heading.level, heading.text.as_str()))
+        .collect();
+    assert_eq!(
+        headings,
+        vec![
+            (1, "A Heading 1"),
+            (2, "A Heading 2"),
+            (1, "Another Heading 1")
+        ]
+    );
+    for heading in outline {
+        let at_offset: String = text.chars().skip(heading.offset).collect();
+        assert!(
+            at_offset.trim_start().starts_with(&heading.text),
+            "heading {:?} not found at its offset",
+            heading
+        );
+    }
+}
+
+#[test]
+fn test_extract_outline_without_headings() {
+    let extractor = Extractor::new();
+    let bytes =
+        fs::read("../test_files/documents/table-multi-row-column-cells-actual.csv").unwrap();
+
+    let outline = extractor.extract_outline(&bytes).unwrap();
+    assert!(outline.is_empty());
+}
+
+#[test]
+fn test_extract_outline_beyond_max_length() {
+    let extractor = Extractor::new();
+    let filler = "lorem ipsum ".repeat(50_000);
+    let html = format!(
+        "<html><body><h1>First</h1><p>{}</p><h1>Last</h1><p>end</p></body></html>",
+        filler
+    );
+
+    // The heading after the first 500_000 characters is still found
+    let outline = extractor.extract_outline(html.as_bytes()).unwrap();
+    let headings: Vec<_> = outline.iter().map(|h| h.text.as_str()).collect();
+    assert_eq!(headings, vec!["First", "Last"]);
+    assert!(outline[1].offset > 500_000);
+}
+
+#[test]
+fn test_extract_tables_xlsx() {
+    let extractor = Extractor::new().set_extract_string_max_length(1000000);
+    let bytes = fs::read("../test_files/documents/vodafone.xlsx").unwrap();
+
+    let tables = extractor.extract_tables(&bytes).unwrap();
+    assert!(!tables.is_empty());
+    assert!(tables
+        .iter()
+        .all(|table| table.rows.iter().all(|row| !row.is_empty())));
+}
+
+#[test]
+fn test_extract_tables_html_headers() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/table.html").unwrap();
+
+    let tables = extractor.extract_tables(&bytes).unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].headers, vec!["Country", "Capital", "Population"]);
+    assert_eq!(tables[0].rows.len(), 3);
+    assert_eq!(tables[0].rows[0], vec!["France", "Paris", "68 million"]);
+    assert!(tables[0].rows.iter().all(|row| row.len() == 3));
+}
+
+#[test]
+fn test_extract_tables_without_tables() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/simple.odt").unwrap();
+
+    let tables = extractor.extract_tables(&bytes).unwrap();
+    assert!(tables.is_empty());
+}
+
+#[test]
+fn test_extract_paragraphs_docx() {
+    let extractor = Extractor::new().set_extract_string_max_length(1000000);
+    let bytes = fs::read("../test_files/documents/category-level.docx").unwrap();
+
+    let paragraphs = extractor.extract_paragraphs(&bytes).unwrap();
+    assert!(!paragraphs.is_empty());
+    assert!(paragraphs.iter().all(|p| !p.text.trim().is_empty()));
+
+    // Every heading of the outline is also a heading paragraph
+    let outline = extractor.extract_outline(&bytes).unwrap();
+    for heading in outline {
+        let text = heading
+            .text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert!(paragraphs
+            .iter()
+            .any(|p| p.style == ParagraphStyle::Heading(heading.level) && p.text == text));
+    }
+}
+
+#[test]
+fn test_extract_pages_where_sheets() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/simple.ods").unwrap();
+
+    // Every sheet is a page, starting with its name
+    let pages = extractor.extract_pages_where(&bytes, |_| true).unwrap();
+    assert_eq!(pages.len(), 2);
+    assert!(pages[0].text.starts_with("Revenue"));
+
+    let pages = extractor
+        .extract_pages_where(&bytes, |page| page.preview.starts_with("Costs"))
+        .unwrap();
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].index, 1);
+    assert!(pages[0].text.contains("870"));
+}
+
+#[test]
+fn test_extract_sheets() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/vodafone.xlsx").unwrap();
+
+    let sheets = extractor.extract_sheets(&bytes).unwrap();
+    assert_eq!(sheets.len(), 1);
+    assert_eq!(sheets[0].name, "Index");
+    // Cells keep their boundaries instead of being merged into tab separated lines
+    let cells: Vec<&str> = sheets[0]
+        .rows
+        .iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    assert!(cells.contains(&"Financial performance"));
+    assert!(cells.contains(&"Quarterly revenue"));
+    assert!(!cells.iter().any(|cell| cell.contains('\t')));
+
+    let bytes = fs::read("../test_files/documents/simple.ods").unwrap();
+    let sheets = extractor.extract_sheets(&bytes).unwrap();
+    assert_eq!(sheets.len(), 2);
+    assert!(sheets[1]
+        .rows
+        .iter()
+        .flatten()
+        .any(|cell| cell.contains("870")));
+}
+
+#[test]
+fn test_extract_pdf_pages() {
+    let extractor = Extractor::new();
+    let path = "../test_files/documents/2022_Q3_AAPL.pdf";
+
+    let pages: Vec<_> = extractor
+        .extract_pdf_pages(path)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let expected = extractor
+        .extract_pages_where(&fs::read(path).unwrap(), |_| true)
+        .unwrap();
+    assert!(pages.len() > 1);
+    assert_eq!(pages.len(), expected.len());
+    for (i, page) in pages.iter().enumerate() {
+        assert_eq!(page.page_number as usize, i + 1);
+    }
+    assert!(pages[0].text.contains("Apple Inc."));
+
+    // Stopping early does not parse the whole document first
+    let first = extractor.extract_pdf_pages(path).next().unwrap().unwrap();
+    assert_eq!(first, pages[0]);
+
+    let mut missing = extractor.extract_pdf_pages("../test_files/documents/missing.pdf");
+    assert!(matches!(missing.next(), Some(Err(_))));
+    assert!(missing.next().is_none());
+}
+
+#[test]
+fn test_extract_slides() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/simple.pptx").unwrap();
+
+    let slides = extractor.extract_slides(&bytes).unwrap();
+    assert_eq!(slides.len(), 2);
+    assert_eq!(slides[0].index, 0);
+    assert_eq!(slides[0].title.as_deref(), Some("Title Slide"));
+    assert_eq!(slides[0].body, "With a subtitle");
+    assert_eq!(slides[1].title.as_deref(), Some("Things to think about"));
+    // The title is not repeated in the body, the runs of a paragraph are joined
+    assert!(slides[1].body.starts_with("How much is enough?\n"));
+    assert!(slides[1].body.contains("What were you thinking?"));
+    assert!(!slides[1].body.contains("Things to think about"));
+    assert!(slides.iter().all(|slide| slide.notes.is_empty()));
+
+    // Documents that are not presentations have no slides
+    for file in ["bug_16.docx", "simple.odp"] {
+        let bytes = fs::read(format!("../test_files/documents/{}", file)).unwrap();
+        assert!(extractor.extract_slides(&bytes).unwrap().is_empty());
+    }
+}
+
+#[test]
+fn test_extract_entities() {
+    let bytes = fs::read("../test_files/documents/contacts.html").unwrap();
+
+    let entities = Extractor::new().extract_entities(&bytes).unwrap();
+
+    // Addresses are lower cased, so the two spellings of the same address are merged
+    assert_eq!(
+        entities.emails,
+        vec!["jane.doe@example.com", "support@example.org"]
+    );
+    // Both formats of the same number are reduced to the same digits
+    assert_eq!(
+        entities
+            .phones
+            .iter()
+            .filter(|phone| phone.ends_with("8015961887"))
+            .count(),
+        1
+    );
+    assert_eq!(
+        entities.urls,
+        vec![
+            "https://example.com/about",
+            "http://www.example.net/downloads",
+            "https://docs.example.com/start",
+        ]
+    );
+}
@@ -0,0 +1,26 @@
+use extractous::{Error, Extractor};
+use std::fs;
+use test_case::test_case;
+
+#[test_case("2022_Q3_AAPL.pdf"; "Test PDF file")]
+#[test_case("category-level.docx"; "Test DOCX file")]
+#[test_case("vodafone.xlsx"; "Test XLSX file")]
+#[test_case("table-multi-row-column-cells.png"; "Test PNG file")]
+#[test_case("simple.doc"; "Test DOC file")]
+fn test_validate_intact_file(file_name: &str) {
+    let bytes = fs::read(format!("../test_files/documents/{}", file_name)).unwrap();
+    assert!(Extractor::new().validate(&bytes).is_ok());
+}
+
+#[test_case("2022_Q3_AAPL.pdf"; "Test PDF file")]
+#[test_case("category-level.docx"; "Test DOCX file")]
+#[test_case("table-multi-row-column-cells.png"; "Test PNG file")]
+fn test_validate_truncated_file(file_name: &str) {
+    let bytes = fs::read(format!("../test_files/documents/{}", file_name)).unwrap();
+    let truncated = &bytes[..bytes.len() / 2];
+
+    match Extractor::new().validate(truncated) {
+        Err(Error::ParseError(msg)) => assert!(msg.starts_with("Truncated or corrupt")),
+        other => panic!("Expected a parse error, got {:?}", other),
+    }
+}
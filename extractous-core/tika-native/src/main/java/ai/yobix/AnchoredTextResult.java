@@ -0,0 +1,139 @@
+package ai.yobix;
+
+import java.util.Arrays;
+
+/**
+ * The text of a document with its anchors. The anchors are stored in parallel arrays: the start
+ * and end offsets of each anchor in the text, in UTF-16 code units, its page, starting at 1, and
+ * its x, y, width and height in points from the top left corner of the page.
+ */
+public class AnchoredTextResult {
+
+    private final String text;
+    private final int[] offsets;
+    private final int[] pages;
+    private final float[] boxes;
+    private final byte status;
+    private final String errorMessage;
+
+    public AnchoredTextResult(String text, int[] offsets, int[] pages, float[] boxes) {
+        this.text = text;
+        this.offsets = offsets;
+        this.pages = pages;
+        this.boxes = boxes;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public AnchoredTextResult(byte status, String errorMessage) {
+        this.text = null;
+        this.offsets = null;
+        this.pages = null;
+        this.boxes = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    public String getText() {
+        return text;
+    }
+
+    /**
+     * Returns the start and end offsets of the anchors, two per anchor
+     */
+    public int[] getOffsets() {
+        return offsets;
+    }
+
+    public int[] getPages() {
+        return pages;
+    }
+
+    /**
+     * Returns the x, y, width and height of the anchors, four per anchor
+     */
+    public float[] getBoxes() {
+        return boxes;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    /**
+     * Collects the anchors, growing the arrays as needed. Boxes are collected as left, top,
+     * right and bottom so they can be extended, and converted to x, y, width and height at
+     * the end.
+     */
+    static class Builder {
+        private int count = 0;
+        private int[] offsets = new int[64];
+        private int[] pages = new int[32];
+        private float[] edges = new float[128];
+
+        void add(int start, int end, int page, float left, float top, float right, float bottom) {
+            if (count == pages.length) {
+                offsets = Arrays.copyOf(offsets, offsets.length * 2);
+                pages = Arrays.copyOf(pages, pages.length * 2);
+                edges = Arrays.copyOf(edges, edges.length * 2);
+            }
+            offsets[2 * count] = start;
+            offsets[2 * count + 1] = end;
+            pages[count] = page;
+            edges[4 * count] = left;
+            edges[4 * count + 1] = top;
+            edges[4 * count + 2] = right;
+            edges[4 * count + 3] = bottom;
+            count++;
+        }
+
+        /**
+         * Extends the last anchor to the given end offset and box
+         */
+        void extend(int end, float left, float top, float right, float bottom) {
+            if (count == 0) {
+                return;
+            }
+            final int last = count - 1;
+            offsets[2 * last + 1] = end;
+            edges[4 * last] = Math.min(edges[4 * last], left);
+            edges[4 * last + 1] = Math.min(edges[4 * last + 1], top);
+            edges[4 * last + 2] = Math.max(edges[4 * last + 2], right);
+            edges[4 * last + 3] = Math.max(edges[4 * last + 3], bottom);
+        }
+
+        AnchoredTextResult build(String text) {
+            final float[] boxes = new float[4 * count];
+            for (int i = 0; i < count; i++) {
+                boxes[4 * i] = edges[4 * i];
+                boxes[4 * i + 1] = edges[4 * i + 1];
+                boxes[4 * i + 2] = edges[4 * i + 2] - edges[4 * i];
+                boxes[4 * i + 3] = edges[4 * i + 3] - edges[4 * i + 1];
+            }
+            return new AnchoredTextResult(
+                    text, Arrays.copyOf(offsets, 2 * count), Arrays.copyOf(pages, count), boxes);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+package ai.yobix;
+
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.pdmodel.common.PDRectangle;
+import org.apache.pdfbox.pdmodel.interactive.annotation.PDAnnotation;
+import org.apache.pdfbox.pdmodel.interactive.annotation.PDAnnotationMarkup;
+
+import java.io.IOException;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * Collects the annotations of a PDF with PDFBox, such as highlights, notes and stamps, with
+ * their author and text. Popups, which only display the text of their parent annotation, and
+ * the widgets of form fields are left out. The annotations of a page that cannot be read are
+ * skipped, the other pages are still collected.
+ */
+public class AnnotationCollector {
+
+    private static final Set<String> SKIPPED_SUBTYPES = Set.of("Popup", "Widget");
+
+    /**
+     * Returns the annotations of the given PDF, in page order
+     */
+    public static List<ExtractedAnnotation> collect(byte[] bytes) throws IOException {
+        final List<ExtractedAnnotation> annotations = new ArrayList<>();
+        try (final PDDocument document = PDDocument.load(bytes)) {
+            int pageNumber = 0;
+            for (PDPage page : document.getPages()) {
+                pageNumber++;
+                try {
+                    annotations.addAll(collectPage(page, pageNumber));
+                } catch (IOException | RuntimeException e) {
+                    // Skip the annotations of this page only
+                }
+            }
+        }
+        return annotations;
+    }
+
+    private static List<ExtractedAnnotation> collectPage(PDPage page, int pageNumber) throws IOException {
+        final List<ExtractedAnnotation> annotations = new ArrayList<>();
+        final PDRectangle box = page.getCropBox();
+        for (PDAnnotation annotation : page.getAnnotations()) {
+            final String subtype = annotation.getSubtype();
+            if (subtype == null || SKIPPED_SUBTYPES.contains(subtype)) {
+                continue;
+            }
+            final String author = annotation instanceof PDAnnotationMarkup
+                    ? ((PDAnnotationMarkup) annotation).getTitlePopup()
+                    : null;
+
+            // PDF coordinates start at the bottom left of the page
+            final PDRectangle rect = annotation.getRectangle();
+            final float[] bbox = rect == null ? new float[4] : new float[]{
+                    rect.getLowerLeftX() - box.getLowerLeftX(), box.getUpperRightY() - rect.getUpperRightY(),
+                    rect.getWidth(), rect.getHeight()
+            };
+            annotations.add(new ExtractedAnnotation(
+                    subtype, emptyToNull(author), emptyToNull(annotation.getContents()), pageNumber, bbox));
+        }
+        return annotations;
+    }
+
+    private static String emptyToNull(String value) {
+        return value == null || value.trim().isEmpty() ? null : value;
+    }
+}
@@ -0,0 +1,56 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class AnnotationListResult {
+
+    private final List<ExtractedAnnotation> annotations;
+    private final byte status;
+    private final String errorMessage;
+
+    public AnnotationListResult(List<ExtractedAnnotation> annotations) {
+        this.annotations = annotations;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public AnnotationListResult(byte status, String errorMessage) {
+        this.annotations = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the annotations of the document, in page order, or null if there is an error
+     * @return array of annotations
+     */
+    public ExtractedAnnotation[] getAnnotations() {
+        return annotations == null ? null : annotations.toArray(new ExtractedAnnotation[0]);
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
@@ -0,0 +1,56 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class AttachmentListResult {
+
+    private final List<ExtractedAttachment> attachments;
+    private final byte status;
+    private final String errorMessage;
+
+    public AttachmentListResult(List<ExtractedAttachment> attachments) {
+        this.attachments = attachments;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public AttachmentListResult(byte status, String errorMessage) {
+        this.attachments = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the attachments of the document, in name order, or null if there is an error
+     * @return array of attachments
+     */
+    public ExtractedAttachment[] getAttachments() {
+        return attachments == null ? null : attachments.toArray(new ExtractedAttachment[0]);
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
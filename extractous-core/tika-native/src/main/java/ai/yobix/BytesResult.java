@@ -0,0 +1,54 @@
+package ai.yobix;
+
+public class BytesResult {
+
+    private final byte[] content;
+    private final byte status;
+    private final String errorMessage;
+
+    public BytesResult(byte[] content) {
+        this.content = content;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public BytesResult(byte status, String errorMessage) {
+        this.content = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the result bytes or null if there is an error
+     * @return byte content
+     */
+    public byte[] getContent() {
+        return content;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
@@ -0,0 +1,39 @@
+package ai.yobix;
+
+import org.apache.tika.detect.CompositeEncodingDetector;
+import org.apache.tika.detect.EncodingDetector;
+import org.apache.tika.parser.html.HtmlEncodingDetector;
+import org.apache.tika.parser.txt.Icu4jEncodingDetector;
+import org.apache.tika.parser.txt.UniversalEncodingDetector;
+
+import java.nio.charset.StandardCharsets;
+import java.util.List;
+
+/**
+ * How the charset of text based formats is resolved when the declared and the detected charset
+ * differ, mirrors the CharsetPolicy enum of the Rust crate.
+ */
+public enum CharsetPolicy {
+    PREFER_DECLARED,
+    PREFER_DETECTED,
+    FORCE_UTF8;
+
+    /**
+     * Returns the encoding detector implementing the policy, or null to keep the default of Tika,
+     * which tries the declared charset first
+     */
+    public EncodingDetector newEncodingDetector() {
+        switch (this) {
+            case PREFER_DETECTED:
+                // The declaration is only used when the statistical detectors give up
+                return new CompositeEncodingDetector(List.of(
+                        new UniversalEncodingDetector(),
+                        new Icu4jEncodingDetector(),
+                        new HtmlEncodingDetector()));
+            case FORCE_UTF8:
+                return (input, metadata) -> StandardCharsets.UTF_8;
+            default:
+                return null;
+        }
+    }
+}
@@ -0,0 +1,243 @@
+package ai.yobix;
+
+import org.apache.poi.ooxml.util.DocumentHelper;
+import org.apache.poi.openxml4j.exceptions.InvalidFormatException;
+import org.apache.poi.openxml4j.opc.OPCPackage;
+import org.apache.poi.openxml4j.opc.PackagePart;
+import org.w3c.dom.Attr;
+import org.w3c.dom.Document;
+import org.w3c.dom.Element;
+import org.w3c.dom.NamedNodeMap;
+import org.w3c.dom.Node;
+import org.w3c.dom.NodeList;
+import org.xml.sax.SAXException;
+
+import java.io.ByteArrayInputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.Comparator;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Collects the comments of OOXML documents with their reply threading, which the Tika parsers
+ * flatten into the body text: the comments of Word documents, threaded through their
+ * commentsExtended part, the threaded comments of Excel workbooks and the modern comments of
+ * PowerPoint presentations. The legacy notes of Excel and PowerPoint carry no threading and are
+ * not collected. Documents that are not OOXML packages have no comments.
+ */
+public class CommentCollector {
+
+    private static final byte[] ZIP_MAGIC = {'P', 'K', 3, 4};
+
+    private static final String WORD_COMMENTS =
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.comments+xml";
+    private static final String WORD_COMMENTS_EXTENDED =
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.commentsExtended+xml";
+    private static final String EXCEL_THREADED_COMMENTS = "application/vnd.ms-excel.threadedcomments+xml";
+    private static final String EXCEL_PERSONS = "application/vnd.ms-excel.person+xml";
+    private static final String POWERPOINT_COMMENTS = "application/vnd.ms-powerpoint.comments+xml";
+    private static final String POWERPOINT_AUTHORS = "application/vnd.ms-powerpoint.authors+xml";
+
+    /**
+     * Returns the comments of the given document, in the order of the parts holding them and
+     * then in document order
+     */
+    public static List<ExtractedComment> collect(byte[] bytes) throws IOException {
+        final List<ExtractedComment> comments = new ArrayList<>();
+        if (bytes.length < ZIP_MAGIC.length || !Arrays.equals(Arrays.copyOf(bytes, ZIP_MAGIC.length), ZIP_MAGIC)) {
+            return comments;
+        }
+
+        final OPCPackage pkg;
+        try {
+            pkg = OPCPackage.open(new ByteArrayInputStream(bytes));
+        } catch (InvalidFormatException e) {
+            // A zip file that is not an OOXML package
+            return comments;
+        }
+        try {
+            collectWord(pkg, comments);
+            collectExcel(pkg, comments);
+            collectPowerPoint(pkg, comments);
+        } catch (SAXException e) {
+            throw new IOException("Cannot read the comments: " + e.getMessage(), e);
+        } finally {
+            // Read only access, revert closes the package without saving anything
+            pkg.revert();
+        }
+        return comments;
+    }
+
+    /**
+     * Word keeps the comments in the comments part, and their threading in the commentsExtended
+     * part, which links the last paragraph of a reply to the last paragraph of its parent
+     */
+    private static void collectWord(OPCPackage pkg, List<ExtractedComment> comments)
+            throws IOException, SAXException {
+        final Map<String, String> parentParagraphs = new HashMap<>();
+        for (Document extended : read(pkg, WORD_COMMENTS_EXTENDED)) {
+            for (Element entry : elements(extended.getDocumentElement(), "commentEx")) {
+                final String parent = attribute(entry, "paraIdParent");
+                if (parent != null) {
+                    parentParagraphs.put(attribute(entry, "paraId"), parent);
+                }
+            }
+        }
+
+        for (Document document : read(pkg, WORD_COMMENTS)) {
+            final List<Element> entries = elements(document.getDocumentElement(), "comment");
+            final Map<String, String> idsByParagraph = new HashMap<>();
+            for (Element entry : entries) {
+                final List<Element> paragraphs = elements(entry, "p");
+                if (!paragraphs.isEmpty()) {
+                    final String paragraph = attribute(paragraphs.get(paragraphs.size() - 1), "paraId");
+                    if (paragraph != null) {
+                        idsByParagraph.put(paragraph, attribute(entry, "id"));
+                    }
+                }
+            }
+            for (Element entry : entries) {
+                final List<Element> paragraphs = elements(entry, "p");
+                String parentId = null;
+                if (!paragraphs.isEmpty()) {
+                    final String parent =
+                            parentParagraphs.get(attribute(paragraphs.get(paragraphs.size() - 1), "paraId"));
+                    parentId = parent == null ? null : idsByParagraph.get(parent);
+                }
+                comments.add(new ExtractedComment(attribute(entry, "id"), attribute(entry, "author"),
+                        attribute(entry, "date"), text(entry), parentId));
+            }
+        }
+    }
+
+    /**
+     * Excel keeps the threaded comments of each sheet in a part of its own, referring to their
+     * authors in the persons part
+     */
+    private static void collectExcel(OPCPackage pkg, List<ExtractedComment> comments)
+            throws IOException, SAXException {
+        final Map<String, String> persons = new HashMap<>();
+        for (Document document : read(pkg, EXCEL_PERSONS)) {
+            for (Element person : elements(document.getDocumentElement(), "person")) {
+                persons.put(attribute(person, "id"), attribute(person, "displayName"));
+            }
+        }
+
+        for (Document document : read(pkg, EXCEL_THREADED_COMMENTS)) {
+            for (Element entry : elements(document.getDocumentElement(), "threadedComment")) {
+                final List<Element> texts = elements(entry, "text");
+                comments.add(new ExtractedComment(attribute(entry, "id"),
+                        persons.get(attribute(entry, "personId")), attribute(entry, "dT"),
+                        texts.isEmpty() ? "" : texts.get(0).getTextContent().trim(),
+                        attribute(entry, "parentId")));
+            }
+        }
+    }
+
+    /**
+     * PowerPoint keeps the modern comments of each slide in a part of its own, with the replies
+     * of a comment nested in its reply list, referring to their authors in the authors part
+     */
+    private static void collectPowerPoint(OPCPackage pkg, List<ExtractedComment> comments)
+            throws IOException, SAXException {
+        final Map<String, String> authors = new HashMap<>();
+        for (Document document : read(pkg, POWERPOINT_AUTHORS)) {
+            for (Element author : elements(document.getDocumentElement(), "author")) {
+                authors.put(attribute(author, "id"), attribute(author, "name"));
+            }
+        }
+
+        for (Document document : read(pkg, POWERPOINT_COMMENTS)) {
+            for (Element entry : elements(document.getDocumentElement(), "cm")) {
+                final String id = attribute(entry, "id");
+                comments.add(powerPointComment(entry, authors, null));
+                for (Element reply : elements(entry, "reply")) {
+                    comments.add(powerPointComment(reply, authors, id));
+                }
+            }
+        }
+    }
+
+    private static ExtractedComment powerPointComment(Element entry, Map<String, String> authors, String parentId) {
+        // The text of a comment excludes the text of its replies
+        final Element body = children(entry, "txBody").stream().findFirst().orElse(null);
+        return new ExtractedComment(attribute(entry, "id"), authors.get(attribute(entry, "authorId")),
+                attribute(entry, "created"), body == null ? "" : text(body), parentId);
+    }
+
+    /**
+     * Reads the parts of the given content type, in the order of their names
+     */
+    private static List<Document> read(OPCPackage pkg, String contentType) throws IOException, SAXException {
+        final List<PackagePart> parts = new ArrayList<>(pkg.getPartsByContentType(contentType));
+        parts.sort(Comparator.comparing(part -> part.getPartName().getName()));
+        final List<Document> documents = new ArrayList<>();
+        for (PackagePart part : parts) {
+            try (InputStream stream = part.getInputStream()) {
+                documents.add(DocumentHelper.readDocument(stream));
+            }
+        }
+        return documents;
+    }
+
+    /**
+     * Returns the text of the paragraphs of the element, separated by new lines. The text of a
+     * paragraph is the text of its runs
+     */
+    private static String text(Element element) {
+        final List<String> paragraphs = new ArrayList<>();
+        for (Element paragraph : elements(element, "p")) {
+            final StringBuilder text = new StringBuilder();
+            for (Element run : elements(paragraph, "t")) {
+                text.append(run.getTextContent());
+            }
+            paragraphs.add(text.toString());
+        }
+        return String.join("\n", paragraphs).trim();
+    }
+
+    /**
+     * Returns the descendants of the element with the given local name, whatever their namespace
+     */
+    private static List<Element> elements(Element element, String localName) {
+        final NodeList nodes = element.getElementsByTagNameNS("*", localName);
+        final List<Element> elements = new ArrayList<>(nodes.getLength());
+        for (int i = 0; i < nodes.getLength(); i++) {
+            elements.add((Element) nodes.item(i));
+        }
+        return elements;
+    }
+
+    /**
+     * Returns the children of the element with the given local name, whatever their namespace
+     */
+    private static List<Element> children(Element element, String localName) {
+        final List<Element> children = new ArrayList<>();
+        for (Node child = element.getFirstChild(); child != null; child = child.getNextSibling()) {
+            if (child instanceof Element && localName.equals(child.getLocalName())) {
+                children.add((Element) child);
+            }
+        }
+        return children;
+    }
+
+    /**
+     * Returns the value of the attribute with the given local name, whatever its namespace, or
+     * null if the element has none
+     */
+    private static String attribute(Element element, String localName) {
+        final NamedNodeMap attributes = element.getAttributes();
+        for (int i = 0; i < attributes.getLength(); i++) {
+            final Attr attribute = (Attr) attributes.item(i);
+            final String name = attribute.getLocalName() == null ? attribute.getName() : attribute.getLocalName();
+            if (localName.equals(name)) {
+                return attribute.getValue();
+            }
+        }
+        return null;
+    }
+}
@@ -0,0 +1,56 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class CommentListResult {
+
+    private final List<ExtractedComment> comments;
+    private final byte status;
+    private final String errorMessage;
+
+    public CommentListResult(List<ExtractedComment> comments) {
+        this.comments = comments;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public CommentListResult(byte status, String errorMessage) {
+        this.comments = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the comments of the document, in document order, or null if there is an error
+     * @return array of comments
+     */
+    public ExtractedComment[] getComments() {
+        return comments == null ? null : comments.toArray(new ExtractedComment[0]);
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
@@ -0,0 +1,239 @@
+package ai.yobix;
+
+import org.xml.sax.Attributes;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+import java.io.IOException;
+import java.io.StringWriter;
+import java.io.Writer;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Locale;
+
+/**
+ * Converts the tables of a parse, such as the sheets of a spreadsheet, into RFC 4180 CSV: one
+ * record per table row terminated by CRLF, the cells separated by the delimiter and quoted when
+ * they contain the delimiter, a double quote or a line break, the double quotes being doubled.
+ * <p>
+ * The tables are written one after the other, separated by an empty line. The text outside of
+ * the tables, such as the names of the sheets, is dropped. Paragraphs and line breaks within a
+ * cell become line breaks of the value, and tables nested in a cell are flattened into it.
+ * Records are written as soon as their row ends, so the output can be streamed.
+ */
+public class CsvContentHandler extends DefaultHandler {
+
+    private final Writer writer;
+    private final String delimiter;
+
+    /**
+     * Depth of ignored elements, such as the head or scripts
+     */
+    private int skipDepth = 0;
+
+    private int tableDepth = 0;
+    /**
+     * Whether a record was written, so that the next table is preceded by an empty line
+     */
+    private boolean written = false;
+    private boolean tableWritten = false;
+    private List<String> row = null;
+    private StringBuilder cell = null;
+
+    public CsvContentHandler(Writer writer, String delimiter) {
+        this.writer = writer;
+        this.delimiter = delimiter;
+    }
+
+    private static String name(String localName, String qName) {
+        final String name = localName == null || localName.isEmpty() ? qName : localName;
+        return name.toLowerCase(Locale.ROOT);
+    }
+
+    private static boolean isSkipped(String name) {
+        return name.equals("head") || name.equals("script") || name.equals("style");
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts) throws SAXException {
+        final String name = name(localName, qName);
+        if (isSkipped(name) || skipDepth > 0) {
+            skipDepth++;
+            return;
+        }
+        switch (name) {
+            case "table":
+                if (tableDepth++ == 0) {
+                    tableWritten = false;
+                }
+                break;
+            case "tr":
+                if (tableDepth == 1) {
+                    endRow();
+                    row = new ArrayList<>();
+                }
+                break;
+            case "td":
+            case "th":
+                if (tableDepth == 1) {
+                    endCell();
+                    if (row == null) {
+                        // Tolerate cells outside of a row
+                        row = new ArrayList<>();
+                    }
+                    cell = new StringBuilder();
+                }
+                break;
+            case "br":
+                lineBreak();
+                break;
+            default:
+                break;
+        }
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        final String name = name(localName, qName);
+        if (skipDepth > 0) {
+            skipDepth--;
+            return;
+        }
+        switch (name) {
+            case "table":
+                if (tableDepth > 0 && --tableDepth == 0) {
+                    endRow();
+                } else {
+                    lineBreak();
+                }
+                break;
+            case "tr":
+                if (tableDepth == 1) {
+                    endRow();
+                } else {
+                    lineBreak();
+                }
+                break;
+            case "td":
+            case "th":
+                if (tableDepth == 1) {
+                    endCell();
+                } else if (cell != null) {
+                    cell.append(' ');
+                }
+                break;
+            case "p":
+            case "div":
+            case "li":
+                lineBreak();
+                break;
+            default:
+                break;
+        }
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        if (skipDepth == 0 && cell != null) {
+            cell.append(ch, start, length);
+        }
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        characters(ch, start, length);
+    }
+
+    @Override
+    public void endDocument() throws SAXException {
+        tableDepth = 0;
+        endRow();
+        try {
+            writer.flush();
+        } catch (IOException e) {
+            throw new SAXException("Error writing csv", e);
+        }
+    }
+
+    /**
+     * Ends a line of the current cell, if any, for the paragraphs and line breaks within cells
+     */
+    private void lineBreak() {
+        if (cell != null && cell.length() > 0 && cell.charAt(cell.length() - 1) != '\n') {
+            cell.append('\n');
+        }
+    }
+
+    private void endCell() {
+        if (cell == null) {
+            return;
+        }
+        row.add(cell.toString().trim());
+        cell = null;
+    }
+
+    /**
+     * Writes the record of the current table row, if it has any non empty cell
+     */
+    private void endRow() throws SAXException {
+        if (row == null) {
+            return;
+        }
+        endCell();
+        final List<String> cells = row;
+        row = null;
+        if (cells.stream().allMatch(String::isEmpty)) {
+            return;
+        }
+
+        final StringBuilder record = new StringBuilder();
+        if (written && !tableWritten) {
+            record.append("\r\n");
+        }
+        for (int i = 0; i < cells.size(); i++) {
+            if (i > 0) {
+                record.append(delimiter);
+            }
+            appendField(record, cells.get(i));
+        }
+        record.append("\r\n");
+        try {
+            writer.write(record.toString());
+        } catch (IOException e) {
+            throw new SAXException("Error writing csv", e);
+        }
+        written = true;
+        tableWritten = true;
+    }
+
+    /**
+     * Appends the value as a CSV field, quoted if it contains the delimiter, a double quote or a
+     * line break
+     */
+    private void appendField(StringBuilder record, String value) {
+        final boolean quoted = value.contains(delimiter) || value.indexOf('"') >= 0
+                || value.indexOf('\n') >= 0 || value.indexOf('\r') >= 0;
+        if (!quoted) {
+            record.append(value);
+            return;
+        }
+        record.append('"').append(value.replace("\"", "\"\"")).append('"');
+    }
+
+    /**
+     * Returns the records written so far, when writing to a StringWriter. The row collected
+     * before the parse was interrupted, for example by a write limit, is included.
+     */
+    @Override
+    public String toString() {
+        if (!(writer instanceof StringWriter)) {
+            return super.toString();
+        }
+        try {
+            endRow();
+        } catch (SAXException e) {
+            // Writing to a StringWriter does not fail
+        }
+        return writer.toString();
+    }
+}
@@ -0,0 +1,41 @@
+package ai.yobix;
+
+import org.apache.tika.detect.Detector;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+
+/**
+ * What the detection of the media type of a document relies on, mirrors the DetectionMode enum
+ * of the Rust crate.
+ */
+public enum DetectionMode {
+    CONTENT_AND_NAME,
+    CONTENT_ONLY,
+    NAME_ONLY;
+
+    /**
+     * Returns a detector running the given one according to the mode. The content only
+     * detector hides the resource name from the given detector, and the name only detector
+     * hides the content, so that Tika detects from the name and the declared Content-Type only.
+     */
+    public Detector wrap(Detector detector) {
+        switch (this) {
+            case CONTENT_ONLY:
+                return (input, metadata) -> {
+                    final Metadata withoutName = new Metadata();
+                    for (String name : metadata.names()) {
+                        if (!TikaCoreProperties.RESOURCE_NAME_KEY.equals(name)) {
+                            for (String value : metadata.getValues(name)) {
+                                withoutName.add(name, value);
+                            }
+                        }
+                    }
+                    return detector.detect(input, withoutName);
+                };
+            case NAME_ONLY:
+                return (input, metadata) -> detector.detect(null, metadata);
+            default:
+                return detector;
+        }
+    }
+}
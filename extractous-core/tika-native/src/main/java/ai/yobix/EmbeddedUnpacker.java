@@ -0,0 +1,100 @@
+package ai.yobix;
+
+import org.apache.commons.io.IOUtils;
+import org.apache.tika.detect.Detector;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.extractor.EmbeddedDocumentExtractor;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * Passes the raw bytes of every document embedded in a parse, such as attachments, archive
+ * entries or images, to the Rust callback behind the handle, like the unpacker resource of Tika
+ * server. The embedded documents are then parsed in turn, so the documents embedded in them are
+ * unpacked too.
+ * <p>
+ * Parsers commonly swallow the exceptions of their embedded documents, so the first failure of
+ * the callback is recorded, stops the unpacking and is reported by getFailure once the parse
+ * returns.
+ */
+public class EmbeddedUnpacker implements EmbeddedDocumentExtractor {
+
+    private final long handle;
+    private final Parser parser;
+    private final Detector detector;
+    private final ParseContext context;
+
+    /**
+     * Path of the document whose embedded documents are being unpacked, empty for the container
+     */
+    private String parentPath = "";
+    private int unnamed = 0;
+    private IOException failure = null;
+
+    public EmbeddedUnpacker(long handle, Parser parser, Detector detector, ParseContext context) {
+        this.handle = handle;
+        this.parser = parser;
+        this.detector = detector;
+        this.context = context;
+    }
+
+    /**
+     * Implemented in Rust, receives an embedded document. A null name means the name is not
+     * known. Throws an IOException when the callback fails
+     */
+    private static native void unpack(long handle, String path, String name, String mediaType, byte[] data)
+            throws IOException;
+
+    /**
+     * Returns the first failure of the callback, or null if it never failed
+     */
+    public IOException getFailure() {
+        return failure;
+    }
+
+    @Override
+    public boolean shouldParseEmbedded(Metadata metadata) {
+        return failure == null;
+    }
+
+    @Override
+    public void parseEmbedded(InputStream stream, ContentHandler handler, Metadata metadata, boolean outputHtml)
+            throws SAXException, IOException {
+        if (failure != null) {
+            return;
+        }
+        final byte[] bytes = IOUtils.toByteArray(stream);
+        final String name = metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY);
+        final String path = parentPath + "/" + (name != null ? name : "embedded-" + (++unnamed));
+        final String mediaType;
+        try (final TikaInputStream tis = TikaInputStream.get(bytes)) {
+            mediaType = detector.detect(tis, metadata).getBaseType().toString();
+        }
+
+        try {
+            unpack(handle, path, name, mediaType, bytes);
+        } catch (IOException e) {
+            failure = e;
+            return;
+        }
+
+        final String previousPath = parentPath;
+        parentPath = path;
+        try (final TikaInputStream tis = TikaInputStream.get(bytes)) {
+            parser.parse(tis, new DefaultHandler(), metadata, context);
+        } catch (TikaException e) {
+            // The embedded documents that cannot be parsed have no embedded documents to unpack
+        } finally {
+            parentPath = previousPath;
+        }
+    }
+}
@@ -0,0 +1,124 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.apache.tika.sax.Link;
+import org.apache.tika.sax.LinkContentHandler;
+import org.apache.tika.sax.PhoneExtractingContentHandler;
+import org.xml.sax.Attributes;
+import org.xml.sax.SAXException;
+
+import java.util.LinkedHashSet;
+import java.util.Locale;
+import java.util.Set;
+import java.util.regex.Matcher;
+import java.util.regex.Pattern;
+
+/**
+ * ContentHandler collecting the entities of a document: the phone numbers found by Tika's
+ * PhoneExtractingContentHandler, the links found by its LinkContentHandler, and the e-mail
+ * addresses and URLs written in the text. The entities are normalized and de-duplicated, keeping
+ * the order they are first found in: e-mail addresses are lower cased, phone numbers are reduced
+ * to their digits and URLs starting with www. get the http:// scheme.
+ */
+public class EntityCollector extends ContentHandlerDecorator {
+
+    public static final String EMAIL = "email";
+    public static final String PHONE = "phone";
+    public static final String URL = "url";
+
+    /**
+     * Metadata key under which the PhoneExtractingContentHandler stores the phone numbers
+     */
+    private static final String PHONE_NUMBERS = "phonenumbers";
+
+    private static final Pattern EMAIL_PATTERN =
+            Pattern.compile("[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\\.[A-Za-z0-9-]+)*\\.[A-Za-z]{2,}");
+    private static final Pattern URL_PATTERN =
+            Pattern.compile("(?:https?://|www\\.)[^\\s<>\"']+", Pattern.CASE_INSENSITIVE);
+    /**
+     * Punctuation ending a sentence after a URL rather than being part of it
+     */
+    private static final Pattern URL_TRAILER = Pattern.compile("[.,;:!?)\\]}]+$");
+
+    private final Metadata phoneMetadata = new Metadata();
+    private final LinkContentHandler links = new LinkContentHandler();
+    private final StringBuilder text = new StringBuilder();
+
+    public EntityCollector() {
+        super();
+        setContentHandler(new PhoneExtractingContentHandler(links, phoneMetadata));
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+        // Keeps the text of separate elements from running into a single entity
+        text.append('\n');
+        super.startElement(uri, localName, name, atts);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String name) throws SAXException {
+        text.append('\n');
+        super.endElement(uri, localName, name);
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        text.append(ch, start, length);
+        super.characters(ch, start, length);
+    }
+
+    /**
+     * Returns the entities found, under the EMAIL, PHONE and URL keys. Must be called once the
+     * parse is over
+     */
+    public Metadata getEntities() {
+        final Set<String> emails = new LinkedHashSet<>();
+        final Set<String> urls = new LinkedHashSet<>();
+
+        final Matcher email = EMAIL_PATTERN.matcher(text);
+        while (email.find()) {
+            emails.add(email.group().toLowerCase(Locale.ROOT));
+        }
+        final Matcher url = URL_PATTERN.matcher(text);
+        while (url.find()) {
+            urls.add(normalizeUrl(url.group()));
+        }
+        for (Link link : links.getLinks()) {
+            final String target = link.getUri().trim();
+            final String lower = target.toLowerCase(Locale.ROOT);
+            if (lower.startsWith("mailto:")) {
+                final Matcher address = EMAIL_PATTERN.matcher(target);
+                if (address.find()) {
+                    emails.add(address.group().toLowerCase(Locale.ROOT));
+                }
+            } else if (lower.startsWith("http://") || lower.startsWith("https://")) {
+                urls.add(normalizeUrl(target));
+            }
+        }
+
+        final Set<String> phones = new LinkedHashSet<>();
+        for (String phone : phoneMetadata.getValues(PHONE_NUMBERS)) {
+            final String digits = phone.replaceAll("[^0-9]", "");
+            if (!digits.isEmpty()) {
+                phones.add(phone.trim().startsWith("+") ? "+" + digits : digits);
+            }
+        }
+
+        final Metadata entities = new Metadata();
+        emails.forEach(value -> entities.add(EMAIL, value));
+        phones.forEach(value -> entities.add(PHONE, value));
+        urls.forEach(value -> entities.add(URL, value));
+        return entities;
+    }
+
+    private static String normalizeUrl(String url) {
+        final String normalized = URL_TRAILER.matcher(url).replaceFirst("");
+        final int schemeEnd = normalized.indexOf("://");
+        if (schemeEnd < 0) {
+            return "http://" + normalized;
+        }
+        return normalized.substring(0, schemeEnd).toLowerCase(Locale.ROOT) + normalized.substring(schemeEnd);
+    }
+}
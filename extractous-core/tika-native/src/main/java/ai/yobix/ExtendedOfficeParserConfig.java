@@ -0,0 +1,47 @@
+package ai.yobix;
+
+import org.apache.tika.parser.microsoft.OfficeParserConfig;
+
+/**
+ * Tika's OfficeParserConfig extended with the extractous specific settings.
+ * Tika parsers only see the OfficeParserConfig part, the extra settings are applied by extractous.
+ */
+public class ExtendedOfficeParserConfig extends OfficeParserConfig {
+
+    private boolean extractCustomProperties = true;
+    private boolean includeChartContent = true;
+    private boolean includeSmartArtContent = true;
+    private String formulaOutput = FormulaOutput.COMPUTED_VALUE.name();
+
+    public boolean isExtractCustomProperties() {
+        return extractCustomProperties;
+    }
+
+    public void setExtractCustomProperties(boolean extractCustomProperties) {
+        this.extractCustomProperties = extractCustomProperties;
+    }
+
+    public boolean isIncludeChartContent() {
+        return includeChartContent;
+    }
+
+    public void setIncludeChartContent(boolean includeChartContent) {
+        this.includeChartContent = includeChartContent;
+    }
+
+    public boolean isIncludeSmartArtContent() {
+        return includeSmartArtContent;
+    }
+
+    public void setIncludeSmartArtContent(boolean includeSmartArtContent) {
+        this.includeSmartArtContent = includeSmartArtContent;
+    }
+
+    public FormulaOutput getFormulaOutput() {
+        return FormulaOutput.valueOf(formulaOutput);
+    }
+
+    public void setFormulaOutput(String formulaOutput) {
+        this.formulaOutput = formulaOutput;
+    }
+}
@@ -0,0 +1,72 @@
+package ai.yobix;
+
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.PasswordProvider;
+import org.apache.tika.parser.pdf.PDFParserConfig;
+
+/**
+ * Tika's PDFParserConfig extended with the extractous specific settings.
+ * Tika parsers only see the PDFParserConfig part, the extra settings are applied by extractous.
+ */
+public class ExtendedPDFParserConfig extends PDFParserConfig {
+
+    private boolean ocrInlineImages = true;
+
+    /**
+     * Password of encrypted PDFs, null if not set
+     */
+    private String password = null;
+
+    /**
+     * First and last page parsed, counted from 1, see PdfPageRange. 0 when all pages are parsed
+     */
+    private int firstPage = 0;
+    private int lastPage = 0;
+
+    /**
+     * Sets the config in the context, along with a PasswordProvider returning its password if
+     * it is an ExtendedPDFParserConfig with a password
+     */
+    public static void applyTo(ParseContext context, PDFParserConfig config) {
+        context.set(PDFParserConfig.class, config);
+        if (config instanceof ExtendedPDFParserConfig) {
+            final String password = ((ExtendedPDFParserConfig) config).getPassword();
+            if (password != null) {
+                context.set(PasswordProvider.class, metadata -> password);
+            }
+        }
+    }
+
+    public boolean isOcrInlineImages() {
+        return ocrInlineImages;
+    }
+
+    public void setOcrInlineImages(boolean ocrInlineImages) {
+        this.ocrInlineImages = ocrInlineImages;
+    }
+
+    public String getPassword() {
+        return password;
+    }
+
+    public void setPassword(String password) {
+        this.password = password;
+    }
+
+    public boolean hasPageRange() {
+        return firstPage > 0;
+    }
+
+    public int getFirstPage() {
+        return firstPage;
+    }
+
+    public int getLastPage() {
+        return lastPage;
+    }
+
+    public void setPageRange(int firstPage, int lastPage) {
+        this.firstPage = firstPage;
+        this.lastPage = lastPage;
+    }
+}
@@ -0,0 +1,93 @@
+package ai.yobix;
+
+import org.apache.tika.parser.ocr.TesseractOCRConfig;
+
+import java.util.ArrayList;
+import java.util.List;
+
+/**
+ * Tika's TesseractOCRConfig extended with the extractous specific settings.
+ * Tika parsers only see the TesseractOCRConfig part, the extra settings are applied by extractous.
+ */
+public class ExtendedTesseractOCRConfig extends TesseractOCRConfig {
+
+    /**
+     * Handle of the Rust OCR backend to use instead of Tesseract, 0 when none is set
+     */
+    private long ocrBackendHandle = 0;
+    /**
+     * Languages tried in order by the OcrLanguageFallback, empty to only use the language
+     */
+    private final List<String> languageFallback = new ArrayList<>();
+    private int languageFallbackMaxAttempts = 3;
+    /**
+     * Mean word confidence, from 0 to 1, at which a fallback language is kept right away
+     */
+    private float languageFallbackMinConfidence = 0.9f;
+    /**
+     * Whether a language that is not installed is replaced by the installed ones, with a
+     * warning, rather than failing the parse
+     */
+    private boolean fallbackOnMissingLanguage = true;
+
+    /**
+     * Returns a copy of this config with the settings extractous sets, so that a parse can change
+     * them without affecting the other parses sharing this config
+     */
+    public ExtendedTesseractOCRConfig copy() {
+        final ExtendedTesseractOCRConfig copy = new ExtendedTesseractOCRConfig();
+        copy.setDensity(getDensity());
+        copy.setDepth(getDepth());
+        copy.setTimeoutSeconds(getTimeoutSeconds());
+        copy.setEnableImagePreprocessing(isEnableImagePreprocessing());
+        copy.setApplyRotation(isApplyRotation());
+        copy.setLanguage(getLanguage());
+        copy.setSkipOcr(isSkipOcr());
+        copy.ocrBackendHandle = ocrBackendHandle;
+        copy.languageFallback.addAll(languageFallback);
+        copy.languageFallbackMaxAttempts = languageFallbackMaxAttempts;
+        copy.languageFallbackMinConfidence = languageFallbackMinConfidence;
+        copy.fallbackOnMissingLanguage = fallbackOnMissingLanguage;
+        return copy;
+    }
+
+    public long getOcrBackendHandle() {
+        return ocrBackendHandle;
+    }
+
+    public void setOcrBackendHandle(long ocrBackendHandle) {
+        this.ocrBackendHandle = ocrBackendHandle;
+    }
+
+    public List<String> getLanguageFallback() {
+        return languageFallback;
+    }
+
+    public void addLanguageFallback(String language) {
+        this.languageFallback.add(language);
+    }
+
+    public int getLanguageFallbackMaxAttempts() {
+        return languageFallbackMaxAttempts;
+    }
+
+    public void setLanguageFallbackMaxAttempts(int languageFallbackMaxAttempts) {
+        this.languageFallbackMaxAttempts = languageFallbackMaxAttempts;
+    }
+
+    public float getLanguageFallbackMinConfidence() {
+        return languageFallbackMinConfidence;
+    }
+
+    public void setLanguageFallbackMinConfidence(float languageFallbackMinConfidence) {
+        this.languageFallbackMinConfidence = languageFallbackMinConfidence;
+    }
+
+    public boolean isFallbackOnMissingLanguage() {
+        return fallbackOnMissingLanguage;
+    }
+
+    public void setFallbackOnMissingLanguage(boolean fallbackOnMissingLanguage) {
+        this.fallbackOnMissingLanguage = fallbackOnMissingLanguage;
+    }
+}
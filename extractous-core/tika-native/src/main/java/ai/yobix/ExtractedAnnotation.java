@@ -0,0 +1,58 @@
+package ai.yobix;
+
+/**
+ * An annotation of a PDF page, such as a highlight, a note or a stamp, with its author and text
+ * when set
+ */
+public class ExtractedAnnotation {
+
+    private final String subtype;
+    private final String author;
+    private final String text;
+    private final int page;
+    private final float[] bbox;
+
+    public ExtractedAnnotation(String subtype, String author, String text, int page, float[] bbox) {
+        this.subtype = subtype;
+        this.author = author;
+        this.text = text;
+        this.page = page;
+        this.bbox = bbox;
+    }
+
+    /**
+     * Returns the subtype of the annotation dictionary, e.g. Highlight, Text for a note, or Stamp
+     */
+    public String getSubtype() {
+        return subtype;
+    }
+
+    /**
+     * Returns the author of the annotation, or null if not set
+     */
+    public String getAuthor() {
+        return author;
+    }
+
+    /**
+     * Returns the text of the annotation, or null if not set
+     */
+    public String getText() {
+        return text;
+    }
+
+    /**
+     * Returns the page of the annotation, starting at 1
+     */
+    public int getPage() {
+        return page;
+    }
+
+    /**
+     * Returns the x, y, width and height of the annotation on its page in points, from the top
+     * left corner of the page
+     */
+    public float[] getBbox() {
+        return bbox;
+    }
+}
@@ -0,0 +1,83 @@
+package ai.yobix;
+
+/**
+ * A file attached to a PDF, with the properties of its file specification and of the embedded
+ * file stream when set
+ */
+public class ExtractedAttachment {
+
+    private final String name;
+    private final String fileName;
+    private final String description;
+    private final String relationship;
+    private final String mimeType;
+    private final String creationDate;
+    private final String modificationDate;
+    private final byte[] data;
+
+    public ExtractedAttachment(String name, String fileName, String description, String relationship,
+                               String mimeType, String creationDate, String modificationDate, byte[] data) {
+        this.name = name;
+        this.fileName = fileName;
+        this.description = description;
+        this.relationship = relationship;
+        this.mimeType = mimeType;
+        this.creationDate = creationDate;
+        this.modificationDate = modificationDate;
+        this.data = data;
+    }
+
+    /**
+     * Returns the key of the attachment in the EmbeddedFiles name tree
+     */
+    public String getName() {
+        return name;
+    }
+
+    /**
+     * Returns the original file name of the attachment, or null if not set
+     */
+    public String getFileName() {
+        return fileName;
+    }
+
+    /**
+     * Returns the description of the attachment, or null if not set
+     */
+    public String getDescription() {
+        return description;
+    }
+
+    /**
+     * Returns the AFRelationship of the attachment to the PDF, e.g. Source, Data or Alternative,
+     * or null if not set
+     */
+    public String getRelationship() {
+        return relationship;
+    }
+
+    /**
+     * Returns the media type declared by the embedded file stream, or null if not set
+     */
+    public String getMimeType() {
+        return mimeType;
+    }
+
+    /**
+     * Returns the creation date of the attachment in ISO 8601, or null if not set
+     */
+    public String getCreationDate() {
+        return creationDate;
+    }
+
+    /**
+     * Returns the modification date of the attachment in ISO 8601, or null if not set
+     */
+    public String getModificationDate() {
+        return modificationDate;
+    }
+
+    public byte[] getData() {
+        return data;
+    }
+}
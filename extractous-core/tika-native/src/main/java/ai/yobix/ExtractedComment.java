@@ -0,0 +1,59 @@
+package ai.yobix;
+
+/**
+ * A comment of an Office document, with its author, timestamp and the comment it replies to
+ * when set
+ */
+public class ExtractedComment {
+
+    private final String id;
+    private final String author;
+    private final String timestamp;
+    private final String text;
+    private final String parentId;
+
+    public ExtractedComment(String id, String author, String timestamp, String text, String parentId) {
+        this.id = id;
+        this.author = author;
+        this.timestamp = timestamp;
+        this.text = text;
+        this.parentId = parentId;
+    }
+
+    /**
+     * Returns the identifier of the comment, unique within its document
+     */
+    public String getId() {
+        return id;
+    }
+
+    /**
+     * Returns the display name of the author of the comment, or null if not set
+     */
+    public String getAuthor() {
+        return author;
+    }
+
+    /**
+     * Returns the date and time of the comment as written in the document, in ISO 8601 format,
+     * or null if not set
+     */
+    public String getTimestamp() {
+        return timestamp;
+    }
+
+    /**
+     * Returns the text of the comment, its paragraphs separated by new lines
+     */
+    public String getText() {
+        return text;
+    }
+
+    /**
+     * Returns the identifier of the comment this one replies to, or null for the first comment
+     * of a thread
+     */
+    public String getParentId() {
+        return parentId;
+    }
+}
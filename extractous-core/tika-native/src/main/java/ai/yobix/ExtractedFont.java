@@ -0,0 +1,42 @@
+package ai.yobix;
+
+/**
+ * A font used by a PDF, with whether its program is embedded in the document and whether only
+ * the glyphs the document uses are embedded
+ */
+public class ExtractedFont {
+
+    private final String name;
+    private final boolean embedded;
+    private final boolean subset;
+    private final String fontType;
+
+    public ExtractedFont(String name, boolean embedded, boolean subset, String fontType) {
+        this.name = name;
+        this.embedded = embedded;
+        this.subset = subset;
+        this.fontType = fontType;
+    }
+
+    /**
+     * Returns the base font name, including the subset tag if any, e.g. ABCDEF+Arial
+     */
+    public String getName() {
+        return name;
+    }
+
+    public boolean isEmbedded() {
+        return embedded;
+    }
+
+    public boolean isSubset() {
+        return subset;
+    }
+
+    /**
+     * Returns the subtype of the font dictionary, e.g. Type1, TrueType or Type0
+     */
+    public String getFontType() {
+        return fontType;
+    }
+}
@@ -0,0 +1,39 @@
+package ai.yobix;
+
+/**
+ * A terminal field of the AcroForm of a PDF, with its fully qualified name, kind and value
+ */
+public class ExtractedFormField {
+
+    private final String name;
+    private final String kind;
+    private final String value;
+
+    public ExtractedFormField(String name, String kind, String value) {
+        this.name = name;
+        this.kind = kind;
+        this.value = value;
+    }
+
+    /**
+     * Returns the fully qualified name of the field, the names of its ancestors and its own
+     * separated by dots, e.g. applicant.name
+     */
+    public String getName() {
+        return name;
+    }
+
+    /**
+     * Returns the name of the FormFieldKind of the field, e.g. TEXT or CHECKBOX
+     */
+    public String getKind() {
+        return kind;
+    }
+
+    /**
+     * Returns the value of the field, or null if it has none
+     */
+    public String getValue() {
+        return value;
+    }
+}
@@ -0,0 +1,43 @@
+package ai.yobix;
+
+/**
+ * An image found in a document, with the page it is drawn on and its position on the page
+ * when known
+ */
+public class ExtractedImage {
+
+    private final byte[] data;
+    private final String mimeType;
+    private final int page;
+    private final float[] bbox;
+
+    public ExtractedImage(byte[] data, String mimeType, int page, float[] bbox) {
+        this.data = data;
+        this.mimeType = mimeType;
+        this.page = page;
+        this.bbox = bbox;
+    }
+
+    public byte[] getData() {
+        return data;
+    }
+
+    public String getMimeType() {
+        return mimeType;
+    }
+
+    /**
+     * Returns the page the image is drawn on, starting at 1, or 0 if not known
+     */
+    public int getPage() {
+        return page;
+    }
+
+    /**
+     * Returns the x, y, width and height of the image on its page in points, from the top left
+     * corner of the page, or null if not known
+     */
+    public float[] getBbox() {
+        return bbox;
+    }
+}
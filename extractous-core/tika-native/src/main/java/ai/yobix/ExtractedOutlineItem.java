@@ -0,0 +1,41 @@
+package ai.yobix;
+
+import java.util.List;
+
+/**
+ * An item of the outline of a PDF, a bookmark, with the page it points to and its children
+ */
+public class ExtractedOutlineItem {
+
+    private final String title;
+    private final int page;
+    private final List<ExtractedOutlineItem> children;
+
+    public ExtractedOutlineItem(String title, int page, List<ExtractedOutlineItem> children) {
+        this.title = title;
+        this.page = page;
+        this.children = children;
+    }
+
+    /**
+     * Returns the title of the item, empty if not set
+     */
+    public String getTitle() {
+        return title;
+    }
+
+    /**
+     * Returns the page the item points to, starting at 1, or 0 if it points to no page of the
+     * document
+     */
+    public int getPage() {
+        return page;
+    }
+
+    /**
+     * Returns the items nested in this item, in outline order
+     */
+    public ExtractedOutlineItem[] getChildren() {
+        return children.toArray(new ExtractedOutlineItem[0]);
+    }
+}
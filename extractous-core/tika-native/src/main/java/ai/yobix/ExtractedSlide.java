@@ -0,0 +1,47 @@
+package ai.yobix;
+
+/**
+ * A slide of a presentation, with its title, the text of its other shapes and its notes
+ */
+public class ExtractedSlide {
+
+    private final int index;
+    private final String title;
+    private final String body;
+    private final String notes;
+
+    public ExtractedSlide(int index, String title, String body, String notes) {
+        this.index = index;
+        this.title = title;
+        this.body = body;
+        this.notes = notes;
+    }
+
+    /**
+     * Returns the index of the slide in the presentation, starting at 0
+     */
+    public int getIndex() {
+        return index;
+    }
+
+    /**
+     * Returns the title of the slide, or null if it has none
+     */
+    public String getTitle() {
+        return title;
+    }
+
+    /**
+     * Returns the text of the shapes of the slide other than its title, one line per paragraph
+     */
+    public String getBody() {
+        return body;
+    }
+
+    /**
+     * Returns the text of the notes of the slide, empty if it has none
+     */
+    public String getNotes() {
+        return notes;
+    }
+}
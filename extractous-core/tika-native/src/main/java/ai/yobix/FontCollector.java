@@ -0,0 +1,78 @@
+package ai.yobix;
+
+import org.apache.pdfbox.cos.COSBase;
+import org.apache.pdfbox.cos.COSName;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.pdmodel.PDResources;
+import org.apache.pdfbox.pdmodel.font.PDFont;
+import org.apache.pdfbox.pdmodel.graphics.PDXObject;
+import org.apache.pdfbox.pdmodel.graphics.form.PDFormXObject;
+
+import java.io.IOException;
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.IdentityHashMap;
+import java.util.List;
+import java.util.Set;
+import java.util.regex.Pattern;
+
+/**
+ * Collects the fonts of a PDF from the resources of its pages and of the forms drawn on them.
+ * A font shared by several pages is returned once. The fonts of a page whose resources cannot be
+ * read are skipped, the other pages are still collected.
+ */
+public class FontCollector {
+
+    /**
+     * The tag of six upper case letters a subset font name starts with, e.g. ABCDEF+Arial
+     */
+    private static final Pattern SUBSET_TAG = Pattern.compile("^[A-Z]{6}\\+");
+
+    /**
+     * Returns the fonts of the given PDF, in the order they are first used
+     */
+    public static List<ExtractedFont> collect(byte[] bytes) throws IOException {
+        final List<ExtractedFont> fonts = new ArrayList<>();
+        final Set<COSBase> seen = Collections.newSetFromMap(new IdentityHashMap<>());
+        try (final PDDocument document = PDDocument.load(bytes)) {
+            for (PDPage page : document.getPages()) {
+                final List<ExtractedFont> pageFonts = new ArrayList<>();
+                final Set<COSBase> pageSeen = Collections.newSetFromMap(new IdentityHashMap<>());
+                pageSeen.addAll(seen);
+                try {
+                    collectResources(page.getResources(), pageFonts, pageSeen);
+                } catch (IOException | RuntimeException e) {
+                    // Skip the fonts of this page only
+                    continue;
+                }
+                fonts.addAll(pageFonts);
+                seen.addAll(pageSeen);
+            }
+        }
+        return fonts;
+    }
+
+    private static void collectResources(PDResources resources, List<ExtractedFont> fonts, Set<COSBase> seen)
+            throws IOException {
+        if (resources == null || !seen.add(resources.getCOSObject())) {
+            return;
+        }
+        for (COSName name : resources.getFontNames()) {
+            final PDFont font = resources.getFont(name);
+            if (font == null || !seen.add(font.getCOSObject())) {
+                continue;
+            }
+            // Type3 fonts may have no base font name
+            final String fontName = font.getName() != null ? font.getName() : name.getName();
+            fonts.add(new ExtractedFont(
+                    fontName, font.isEmbedded(), SUBSET_TAG.matcher(fontName).find(), font.getSubType()));
+        }
+        for (COSName name : resources.getXObjectNames()) {
+            final PDXObject xobject = resources.getXObject(name);
+            if (xobject instanceof PDFormXObject) {
+                collectResources(((PDFormXObject) xobject).getResources(), fonts, seen);
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class FontListResult {
+
+    private final List<ExtractedFont> fonts;
+    private final byte status;
+    private final String errorMessage;
+
+    public FontListResult(List<ExtractedFont> fonts) {
+        this.fonts = fonts;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public FontListResult(byte status, String errorMessage) {
+        this.fonts = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the fonts of the document, in the order they are first used, or null if there is
+     * an error
+     * @return array of fonts
+     */
+    public ExtractedFont[] getFonts() {
+        return fonts == null ? null : fonts.toArray(new ExtractedFont[0]);
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
@@ -0,0 +1,97 @@
+package ai.yobix;
+
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.interactive.digitalsignature.PDSignature;
+import org.apache.pdfbox.pdmodel.interactive.form.PDAcroForm;
+import org.apache.pdfbox.pdmodel.interactive.form.PDCheckBox;
+import org.apache.pdfbox.pdmodel.interactive.form.PDChoice;
+import org.apache.pdfbox.pdmodel.interactive.form.PDComboBox;
+import org.apache.pdfbox.pdmodel.interactive.form.PDField;
+import org.apache.pdfbox.pdmodel.interactive.form.PDListBox;
+import org.apache.pdfbox.pdmodel.interactive.form.PDPushButton;
+import org.apache.pdfbox.pdmodel.interactive.form.PDRadioButton;
+import org.apache.pdfbox.pdmodel.interactive.form.PDSignatureField;
+import org.apache.pdfbox.pdmodel.interactive.form.PDTerminalField;
+import org.apache.pdfbox.pdmodel.interactive.form.PDTextField;
+
+import java.io.IOException;
+import java.util.ArrayList;
+import java.util.List;
+
+/**
+ * Collects the terminal fields of the AcroForm of a PDF with PDFBox, the fields holding values
+ * as opposed to the fields only grouping others, with their kind and value. The values of the
+ * fields that cannot be read are left out, the fields are still collected. XFA forms are not
+ * supported.
+ */
+public class FormFieldCollector {
+
+    /**
+     * Returns the fields of the given PDF, in field tree order
+     */
+    public static List<ExtractedFormField> collect(byte[] bytes) throws IOException {
+        final List<ExtractedFormField> fields = new ArrayList<>();
+        try (final PDDocument document = PDDocument.load(bytes)) {
+            final PDAcroForm form = document.getDocumentCatalog().getAcroForm();
+            if (form == null) {
+                return fields;
+            }
+            for (PDField field : form.getFieldTree()) {
+                if (!(field instanceof PDTerminalField)) {
+                    continue;
+                }
+                String value;
+                try {
+                    value = emptyToNull(value(field));
+                } catch (RuntimeException e) {
+                    value = null;
+                }
+                fields.add(new ExtractedFormField(field.getFullyQualifiedName(), kind(field), value));
+            }
+        }
+        return fields;
+    }
+
+    /**
+     * Returns the name of the FormFieldKind of the field
+     */
+    private static String kind(PDField field) {
+        if (field instanceof PDTextField) {
+            return "TEXT";
+        } else if (field instanceof PDCheckBox) {
+            return "CHECKBOX";
+        } else if (field instanceof PDRadioButton) {
+            return "RADIO_BUTTON";
+        } else if (field instanceof PDPushButton) {
+            return "PUSH_BUTTON";
+        } else if (field instanceof PDComboBox) {
+            return "COMBO_BOX";
+        } else if (field instanceof PDListBox) {
+            return "LIST_BOX";
+        } else if (field instanceof PDSignatureField) {
+            return "SIGNATURE";
+        }
+        return "OTHER";
+    }
+
+    /**
+     * Returns the value of the field: the text of text fields, the selected state of check boxes
+     * and radio buttons, the selected options of choice fields one per line and the name of the
+     * signer of signature fields. Push buttons have no value
+     */
+    private static String value(PDField field) {
+        if (field instanceof PDPushButton) {
+            return null;
+        } else if (field instanceof PDChoice) {
+            return String.join("\n", ((PDChoice) field).getValue());
+        } else if (field instanceof PDSignatureField) {
+            final PDSignature signature = ((PDSignatureField) field).getSignature();
+            return signature == null ? null : signature.getName();
+        }
+        return field.getValueAsString();
+    }
+
+    private static String emptyToNull(String value) {
+        return value == null || value.trim().isEmpty() ? null : value;
+    }
+}
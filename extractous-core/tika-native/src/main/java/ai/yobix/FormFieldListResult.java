@@ -0,0 +1,56 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class FormFieldListResult {
+
+    private final List<ExtractedFormField> fields;
+    private final byte status;
+    private final String errorMessage;
+
+    public FormFieldListResult(List<ExtractedFormField> fields) {
+        this.fields = fields;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public FormFieldListResult(byte status, String errorMessage) {
+        this.fields = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the fields of the form, in field tree order, or null if there is an error
+     * @return array of fields
+     */
+    public ExtractedFormField[] getFields() {
+        return fields == null ? null : fields.toArray(new ExtractedFormField[0]);
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
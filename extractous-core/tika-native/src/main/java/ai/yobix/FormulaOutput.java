@@ -0,0 +1,25 @@
+package ai.yobix;
+
+/**
+ * What the formula cells of spreadsheets become in the extracted content, mirrors the
+ * FormulaOutput enum of the Rust crate.
+ */
+public enum FormulaOutput {
+    COMPUTED_VALUE,
+    FORMULA,
+    BOTH;
+
+    /**
+     * Returns the text of a formula cell, from its formula and its formatted computed value
+     */
+    public String render(String formula, String value) {
+        switch (this) {
+            case FORMULA:
+                return formula;
+            case BOTH:
+                return formula + " = " + value;
+            default:
+                return value;
+        }
+    }
+}
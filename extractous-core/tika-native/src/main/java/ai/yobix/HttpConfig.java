@@ -0,0 +1,207 @@
+package ai.yobix;
+
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+
+import java.io.BufferedInputStream;
+import java.io.IOException;
+import java.net.HttpURLConnection;
+import java.net.InetSocketAddress;
+import java.net.Proxy;
+import java.net.URL;
+import java.net.URLConnection;
+import java.util.LinkedHashMap;
+import java.util.Locale;
+import java.util.Map;
+
+/**
+ * HTTP settings of the url extractions: headers, authorization, proxy, redirects and timeouts.
+ * Redirects are followed here rather than by HttpURLConnection, which does not let the number of
+ * redirects be limited per request, nor the headers be dropped on the redirects to other origins.
+ * The headers and the authorization are only sent to the origin, the scheme, host and port, of
+ * the requested url, so credentials never reach another host nor go out in cleartext after a
+ * redirect from https to http.
+ */
+public class HttpConfig {
+
+    public enum RedirectPolicy {
+        /**
+         * Follows redirects to any host with the scheme of the requested url, as
+         * HttpURLConnection does
+         */
+        FOLLOW,
+        /**
+         * Follows redirects to the origin of the requested url only
+         */
+        SAME_ORIGIN,
+        NO_FOLLOW
+    }
+
+    private final Map<String, String> headers = new LinkedHashMap<>();
+
+    /**
+     * Value of the Authorization header, only sent to the origin of the requested url. Null when
+     * the requests are not authenticated
+     */
+    private String authorization = null;
+
+    /**
+     * Proxy of the requests, null to use the proxy selector of the JVM
+     */
+    private Proxy proxy = null;
+
+    private RedirectPolicy redirectPolicy = RedirectPolicy.FOLLOW;
+    private int maxRedirects = 10;
+
+    /**
+     * Timeouts in milliseconds, 0 for no timeout
+     */
+    private int connectTimeout = 0;
+    private int readTimeout = 0;
+
+    public void addHeader(String name, String value) {
+        headers.put(name, value);
+    }
+
+    public void setAuthorization(String authorization) {
+        this.authorization = authorization;
+    }
+
+    public void setProxy(String host, int port) {
+        this.proxy = new Proxy(Proxy.Type.HTTP, InetSocketAddress.createUnresolved(host, port));
+    }
+
+    public void setRedirectPolicy(String redirectPolicy) {
+        this.redirectPolicy = RedirectPolicy.valueOf(redirectPolicy);
+    }
+
+    public void setMaxRedirects(int maxRedirects) {
+        this.maxRedirects = maxRedirects;
+    }
+
+    public void setConnectTimeoutSeconds(int seconds) {
+        this.connectTimeout = Math.max(seconds, 0) * 1000;
+    }
+
+    public void setReadTimeoutSeconds(int seconds) {
+        this.readTimeout = Math.max(seconds, 0) * 1000;
+    }
+
+    private static boolean isHttp(URL url) {
+        final String protocol = url.getProtocol().toLowerCase(Locale.ROOT);
+        return protocol.equals("http") || protocol.equals("https");
+    }
+
+    /**
+     * Returns true if both urls have the same scheme, host and port
+     */
+    private static boolean isSameOrigin(URL a, URL b) {
+        return a.getProtocol().equalsIgnoreCase(b.getProtocol())
+                && a.getHost().equalsIgnoreCase(b.getHost())
+                && port(a) == port(b);
+    }
+
+    private static int port(URL url) {
+        return url.getPort() == -1 ? url.getDefaultPort() : url.getPort();
+    }
+
+    private static boolean isRedirect(int code) {
+        return code == HttpURLConnection.HTTP_MOVED_PERM
+                || code == HttpURLConnection.HTTP_MOVED_TEMP
+                || code == HttpURLConnection.HTTP_SEE_OTHER
+                || code == 307
+                || code == 308;
+    }
+
+    /**
+     * Opens the given url, following its redirects as configured, and records its name, type,
+     * encoding and length in the metadata as TikaInputStream.get(URL, Metadata) does. Urls that
+     * are not http or https are opened by TikaInputStream directly.
+     */
+    public TikaInputStream open(URL url, Metadata metadata) throws IOException {
+        if (!isHttp(url)) {
+            return TikaInputStream.get(url, metadata);
+        }
+
+        URL current = url;
+        for (int redirects = 0; ; redirects++) {
+            final HttpURLConnection connection = connect(url, current);
+            final int code = connection.getResponseCode();
+            if (isRedirect(code)) {
+                final String location = connection.getHeaderField("Location");
+                connection.disconnect();
+                current = redirectTarget(url, current, location, redirects);
+                continue;
+            }
+            if (code >= 400) {
+                connection.disconnect();
+                throw new IOException("Server returned HTTP " + code + " "
+                        + connection.getResponseMessage() + " for " + current);
+            }
+            return open(current, connection, metadata);
+        }
+    }
+
+    private HttpURLConnection connect(URL requested, URL url) throws IOException {
+        final URLConnection connection = proxy == null ? url.openConnection() : url.openConnection(proxy);
+        final HttpURLConnection http = (HttpURLConnection) connection;
+        http.setInstanceFollowRedirects(false);
+        http.setConnectTimeout(connectTimeout);
+        http.setReadTimeout(readTimeout);
+        // The headers may hold credentials, such as api keys, so like the authorization they are
+        // not sent to the other origins the url redirects to
+        if (isSameOrigin(requested, url)) {
+            headers.forEach(http::setRequestProperty);
+            if (authorization != null) {
+                http.setRequestProperty("Authorization", authorization);
+            }
+        }
+        return http;
+    }
+
+    private URL redirectTarget(URL requested, URL url, String location, int redirects) throws IOException {
+        if (location == null) {
+            throw new IOException("Redirect without a Location header for " + url);
+        }
+        final URL target = new URL(url, location);
+        if (redirectPolicy == RedirectPolicy.NO_FOLLOW) {
+            throw new IOException("Redirects are not followed, " + url + " redirects to " + target);
+        }
+        if (redirectPolicy == RedirectPolicy.SAME_ORIGIN && !isSameOrigin(requested, target)) {
+            throw new IOException("Redirects to other origins are not followed, " + url + " redirects to " + target);
+        }
+        if (!url.getProtocol().equalsIgnoreCase(target.getProtocol())) {
+            throw new IOException("Redirects from " + url.getProtocol() + " to " + target.getProtocol()
+                    + " urls are not followed, " + url + " redirects to " + target);
+        }
+        if (redirects >= maxRedirects) {
+            throw new IOException("Too many redirects for " + requested + ", the maximum is " + maxRedirects);
+        }
+        return target;
+    }
+
+    private static TikaInputStream open(URL url, HttpURLConnection connection, Metadata metadata)
+            throws IOException {
+        final String path = url.getPath();
+        final int slash = path.lastIndexOf('/');
+        if (slash + 1 < path.length()) {
+            metadata.set(TikaCoreProperties.RESOURCE_NAME_KEY, path.substring(slash + 1));
+        }
+        final String type = connection.getContentType();
+        if (type != null) {
+            metadata.set(Metadata.CONTENT_TYPE, type);
+        }
+        final String encoding = connection.getContentEncoding();
+        if (encoding != null) {
+            metadata.set(Metadata.CONTENT_ENCODING, encoding);
+        }
+        final long length = connection.getContentLengthLong();
+        if (length >= 0) {
+            metadata.set(Metadata.CONTENT_LENGTH, Long.toString(length));
+        }
+        return TikaInputStream.get(
+                new BufferedInputStream(connection.getInputStream()), new TemporaryResources(), metadata);
+    }
+}
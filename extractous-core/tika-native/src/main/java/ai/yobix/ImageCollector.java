@@ -0,0 +1,245 @@
+package ai.yobix;
+
+import org.apache.commons.io.IOUtils;
+import org.apache.pdfbox.contentstream.PDFGraphicsStreamEngine;
+import org.apache.pdfbox.cos.COSName;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.pdmodel.common.PDRectangle;
+import org.apache.pdfbox.pdmodel.graphics.image.PDImage;
+import org.apache.pdfbox.util.Matrix;
+import org.apache.tika.config.TikaConfig;
+import org.apache.tika.detect.Detector;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.extractor.EmbeddedDocumentExtractor;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.AutoDetectParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+import javax.imageio.ImageIO;
+import java.awt.geom.Point2D;
+import java.awt.image.BufferedImage;
+import java.io.ByteArrayOutputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.List;
+
+/**
+ * Collects the images of a document. The images of a PDF are found by running its page content
+ * streams with PDFBox, which gives the page and the position each image is drawn at. The images
+ * of other formats, such as the pictures of Office documents, are the image documents embedded in
+ * them, found by a recursive Tika parse, and have no page or position.
+ */
+public class ImageCollector {
+
+    /**
+     * Returns the images of the given document, in document order
+     */
+    public static List<ExtractedImage> collect(byte[] bytes) throws IOException, TikaException, SAXException {
+        final TikaConfig config = TikaConfig.getDefaultConfig();
+        final Detector detector = config.getDetector();
+        final Metadata metadata = new Metadata();
+
+        final MediaType type;
+        try (final TikaInputStream stream = TikaInputStream.get(bytes)) {
+            type = detector.detect(stream, metadata).getBaseType();
+        }
+        if (MediaType.application("pdf").equals(type)) {
+            return collectPdf(bytes);
+        }
+
+        final List<ExtractedImage> images = new ArrayList<>();
+        final Parser parser = new AutoDetectParser(config);
+        final ParseContext context = new ParseContext();
+        context.set(Parser.class, parser);
+        context.set(EmbeddedDocumentExtractor.class, new EmbeddedImageCollector(parser, detector, context, images));
+        try (final TikaInputStream stream = TikaInputStream.get(bytes)) {
+            parser.parse(stream, new DefaultHandler(), metadata, context);
+        }
+        return images;
+    }
+
+    /**
+     * Returns the images drawn on the pages of a PDF, in page order
+     */
+    static List<ExtractedImage> collectPdf(byte[] bytes) throws IOException {
+        final List<ExtractedImage> images = new ArrayList<>();
+        try (final PDDocument document = PDDocument.load(bytes)) {
+            int pageNumber = 0;
+            for (PDPage page : document.getPages()) {
+                pageNumber++;
+                new PageImageEngine(page, pageNumber, images).processPage(page);
+            }
+        }
+        return images;
+    }
+
+    /**
+     * Encodes an image: JPEG images are returned as stored, the others are converted to PNG
+     */
+    private static ExtractedImage encode(PDImage image, int page, float[] bbox) throws IOException {
+        if ("jpg".equals(image.getSuffix())) {
+            try (final InputStream in = image.createInputStream(
+                    Collections.singletonList(COSName.DCT_DECODE.getName()))) {
+                return new ExtractedImage(IOUtils.toByteArray(in), "image/jpeg", page, bbox);
+            }
+        }
+        final BufferedImage buffered = image.getImage();
+        final ByteArrayOutputStream out = new ByteArrayOutputStream();
+        ImageIO.write(buffered, "png", out);
+        return new ExtractedImage(out.toByteArray(), "image/png", page, bbox);
+    }
+
+    /**
+     * Runs the content stream of a page and records every image drawn, with the bounding box of
+     * the unit square the current transformation maps the image to
+     */
+    private static class PageImageEngine extends PDFGraphicsStreamEngine {
+        private final int pageNumber;
+        private final List<ExtractedImage> images;
+        private final Point2D.Float currentPoint = new Point2D.Float();
+
+        PageImageEngine(PDPage page, int pageNumber, List<ExtractedImage> images) {
+            super(page);
+            this.pageNumber = pageNumber;
+            this.images = images;
+        }
+
+        @Override
+        public void drawImage(PDImage pdImage) throws IOException {
+            final Matrix ctm = getGraphicsState().getCurrentTransformationMatrix();
+            float minX = Float.MAX_VALUE, minY = Float.MAX_VALUE;
+            float maxX = -Float.MAX_VALUE, maxY = -Float.MAX_VALUE;
+            for (int[] corner : new int[][]{{0, 0}, {1, 0}, {0, 1}, {1, 1}}) {
+                final Point2D.Float point = ctm.transformPoint(corner[0], corner[1]);
+                minX = Math.min(minX, point.x);
+                minY = Math.min(minY, point.y);
+                maxX = Math.max(maxX, point.x);
+                maxY = Math.max(maxY, point.y);
+            }
+            // PDF coordinates start at the bottom left of the page
+            final PDRectangle box = getPage().getCropBox();
+            final float[] bbox = {
+                    minX - box.getLowerLeftX(), box.getUpperRightY() - maxY, maxX - minX, maxY - minY
+            };
+
+            try {
+                images.add(encode(pdImage, pageNumber, bbox));
+            } catch (IOException e) {
+                // Skip the images that cannot be decoded, such as JPEG 2000 without a decoder
+            }
+        }
+
+        @Override
+        public void appendRectangle(Point2D p0, Point2D p1, Point2D p2, Point2D p3) {
+        }
+
+        @Override
+        public void clip(int windingRule) {
+        }
+
+        @Override
+        public void moveTo(float x, float y) {
+            currentPoint.setLocation(x, y);
+        }
+
+        @Override
+        public void lineTo(float x, float y) {
+            currentPoint.setLocation(x, y);
+        }
+
+        @Override
+        public void curveTo(float x1, float y1, float x2, float y2, float x3, float y3) {
+            currentPoint.setLocation(x3, y3);
+        }
+
+        @Override
+        public Point2D getCurrentPoint() {
+            return currentPoint;
+        }
+
+        @Override
+        public void closePath() {
+        }
+
+        @Override
+        public void endPath() {
+        }
+
+        @Override
+        public void strokePath() {
+        }
+
+        @Override
+        public void fillPath(int windingRule) {
+        }
+
+        @Override
+        public void fillAndStrokePath(int windingRule) {
+        }
+
+        @Override
+        public void shadingFill(COSName shadingName) {
+        }
+    }
+
+    /**
+     * Collects the embedded documents that are images, and parses the others to collect the
+     * images embedded in them
+     */
+    private static class EmbeddedImageCollector implements EmbeddedDocumentExtractor {
+        private final Parser parser;
+        private final Detector detector;
+        private final ParseContext context;
+        private final List<ExtractedImage> images;
+
+        EmbeddedImageCollector(Parser parser, Detector detector, ParseContext context, List<ExtractedImage> images) {
+            this.parser = parser;
+            this.detector = detector;
+            this.context = context;
+            this.images = images;
+        }
+
+        @Override
+        public boolean shouldParseEmbedded(Metadata metadata) {
+            return true;
+        }
+
+        @Override
+        public void parseEmbedded(InputStream stream, ContentHandler handler, Metadata metadata, boolean outputHtml)
+                throws SAXException, IOException {
+            final byte[] bytes = IOUtils.toByteArray(stream);
+            final MediaType type;
+            try (final TikaInputStream tis = TikaInputStream.get(bytes)) {
+                type = detector.detect(tis, metadata).getBaseType();
+            }
+
+            if ("image".equals(type.getType())) {
+                images.add(new ExtractedImage(bytes, type.toString(), 0, null));
+            } else if (MediaType.application("pdf").equals(type)) {
+                // The pages of an embedded PDF are not pages of the container
+                try {
+                    for (ExtractedImage image : collectPdf(bytes)) {
+                        images.add(new ExtractedImage(image.getData(), image.getMimeType(), 0, null));
+                    }
+                } catch (IOException e) {
+                    // Skip the embedded PDFs that cannot be loaded
+                }
+            } else {
+                try (final TikaInputStream tis = TikaInputStream.get(bytes)) {
+                    parser.parse(tis, new DefaultHandler(), metadata, context);
+                } catch (TikaException e) {
+                    // Skip the embedded documents that cannot be parsed, like Tika does
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class ImageListResult {
+
+    private final List<ExtractedImage> images;
+    private final byte status;
+    private final String errorMessage;
+
+    public ImageListResult(List<ExtractedImage> images) {
+        this.images = images;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public ImageListResult(byte status, String errorMessage) {
+        this.images = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the images of the document, in document order, or null if there is an error
+     * @return array of images
+     */
+    public ExtractedImage[] getImages() {
+        return images == null ? null : images.toArray(new ExtractedImage[0]);
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 2: TikaException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
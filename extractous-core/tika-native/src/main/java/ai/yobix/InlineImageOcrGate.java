@@ -0,0 +1,48 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.ocr.TesseractOCRConfig;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * Parser decorator that skips OCR for inline images, such as the logos embedded in the pages of
+ * a PDF, while OCR of whole rendered pages keeps running. Inline images are still parsed, so
+ * their metadata is still extracted.
+ */
+public class InlineImageOcrGate extends ParserDecorator {
+
+    public InlineImageOcrGate(Parser parser) {
+        super(parser);
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        final boolean inline = TikaCoreProperties.EmbeddedResourceType.INLINE.toString()
+                .equals(metadata.get(TikaCoreProperties.EMBEDDED_RESOURCE_TYPE));
+        if (!inline) {
+            super.parse(stream, handler, metadata, context);
+            return;
+        }
+
+        // Swap in a config that skips OCR for the duration of the inline image parse
+        final TesseractOCRConfig previous = context.get(TesseractOCRConfig.class);
+        final TesseractOCRConfig skipOcr = new TesseractOCRConfig();
+        skipOcr.setSkipOcr(true);
+        context.set(TesseractOCRConfig.class, skipOcr);
+        try {
+            super.parse(stream, handler, metadata, context);
+        } finally {
+            context.set(TesseractOCRConfig.class, previous);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+package ai.yobix;
+
+import org.apache.tika.langdetect.optimaize.OptimaizeLangDetector;
+import org.apache.tika.language.detect.LanguageDetector;
+import org.apache.tika.language.detect.LanguageResult;
+import org.apache.tika.metadata.Metadata;
+
+import java.io.IOException;
+
+/**
+ * Detects the language of extracted text with Tika's Optimaize language detector. The language
+ * profiles are loaded by the first detection and shared by the later ones.
+ */
+public class LanguageDetection {
+
+    /**
+     * Metadata key of the detected language, added by ParseOptions.finishMetadata
+     */
+    public static final String DETECTED_LANGUAGE = "X-Detected-Language";
+
+    /**
+     * Metadata key of the probability, from 0 to 1, of the detected language
+     */
+    public static final String DETECTED_LANGUAGE_CONFIDENCE = "X-Detected-Language-Confidence";
+
+    /**
+     * Number of characters the detection looks at, more text rarely changes the result
+     */
+    private static final int MAX_TEXT_LENGTH = 10_000;
+
+    private static LanguageDetector detector;
+
+    private static synchronized LanguageDetector detector() throws IOException {
+        if (detector == null) {
+            detector = new OptimaizeLangDetector().loadModels();
+        }
+        return detector;
+    }
+
+    /**
+     * Returns the language of the text, whose ISO 639-1 code, e.g. en, and probability are given
+     * by getLanguage and getRawScore, or null if the text is blank or its language cannot be
+     * told with reasonable certainty
+     */
+    public static LanguageResult detect(String text) throws IOException {
+        if (text == null || text.isBlank()) {
+            return null;
+        }
+        final String sample = text.length() > MAX_TEXT_LENGTH ? text.substring(0, MAX_TEXT_LENGTH) : text;
+        final LanguageDetector detector = detector();
+        final LanguageResult result;
+        // The detector accumulates the text it is given, so detections cannot run concurrently
+        synchronized (detector) {
+            result = detector.detect(sample);
+        }
+        if (!result.isReasonablyCertain() || result.getLanguage().isEmpty()) {
+            return null;
+        }
+        return result;
+    }
+
+    /**
+     * Adds the detected language and its probability to the metadata
+     */
+    public static void addTo(Metadata metadata, LanguageResult language) {
+        metadata.set(DETECTED_LANGUAGE, language.getLanguage());
+        metadata.set(DETECTED_LANGUAGE_CONFIDENCE, Float.toString(language.getRawScore()));
+    }
+}
@@ -0,0 +1,109 @@
+package ai.yobix;
+
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.apache.tika.sax.ContentHandlerFactory;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.OutputStream;
+import java.io.UnsupportedEncodingException;
+import java.nio.charset.Charset;
+
+/**
+ * Content handler decorator that rewrites the line breaks Tika's XHTMLContentHandler emits after
+ * block elements and br elements, according to a LineBreakPolicy of the Rust crate.
+ * The XHTMLContentHandler writes a single newline, as ignorable whitespace, right after the end
+ * of these elements. Every other whitespace is passed through unchanged.
+ */
+public class LineBreakHandler extends ContentHandlerDecorator {
+
+    /**
+     * What a line break becomes, mirrors the LineBreak enum of the Rust crate
+     */
+    public enum LineBreak {
+        NEWLINE("\n"),
+        DOUBLE_NEWLINE("\n\n"),
+        SPACE(" ");
+
+        private final char[] text;
+
+        LineBreak(String text) {
+            this.text = text.toCharArray();
+        }
+    }
+
+    private final LineBreak blockBreak;
+    private final LineBreak brBreak;
+
+    /**
+     * Name of the element that just ended, null once anything else happened
+     */
+    private String ended = null;
+
+    public LineBreakHandler(ContentHandler handler, LineBreak blockBreak, LineBreak brBreak) {
+        super(handler);
+        this.blockBreak = blockBreak;
+        this.brBreak = brBreak;
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts) throws SAXException {
+        ended = null;
+        super.startElement(uri, localName, qName, atts);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        super.endElement(uri, localName, qName);
+        ended = localName == null || localName.isEmpty() ? qName : localName;
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        ended = null;
+        super.characters(ch, start, length);
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        final String element = ended;
+        ended = null;
+        if (element != null && length == 1 && ch[start] == '\n') {
+            final char[] text = element.equals("br") ? brBreak.text : blockBreak.text;
+            super.ignorableWhitespace(text, 0, text.length);
+        } else {
+            super.ignorableWhitespace(ch, start, length);
+        }
+    }
+
+    /**
+     * Factory decorating the handlers of another factory, used to parse embedded documents
+     * recursively
+     */
+    public static class Factory implements ContentHandlerFactory {
+
+        private final ContentHandlerFactory factory;
+        private final LineBreak blockBreak;
+        private final LineBreak brBreak;
+
+        public Factory(ContentHandlerFactory factory, LineBreak blockBreak, LineBreak brBreak) {
+            this.factory = factory;
+            this.blockBreak = blockBreak;
+            this.brBreak = brBreak;
+        }
+
+        public ContentHandler getNewContentHandler() {
+            return new LineBreakHandler(factory.getNewContentHandler(), blockBreak, brBreak);
+        }
+
+        public ContentHandler getNewContentHandler(OutputStream os, String encoding)
+                throws UnsupportedEncodingException {
+            return new LineBreakHandler(factory.getNewContentHandler(os, encoding), blockBreak, brBreak);
+        }
+
+        public ContentHandler getNewContentHandler(OutputStream os, Charset charset) {
+            return new LineBreakHandler(factory.getNewContentHandler(os, charset), blockBreak, brBreak);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+package ai.yobix;
+
+import org.apache.poi.hslf.record.Record;
+import org.apache.poi.hslf.record.RecordContainer;
+import org.apache.poi.hslf.record.RecordTypes;
+import org.apache.poi.hslf.usermodel.HSLFSlideShow;
+import org.apache.poi.poifs.filesystem.DirectoryNode;
+import org.apache.poi.poifs.filesystem.FileMagic;
+import org.apache.poi.poifs.filesystem.POIFSFileSystem;
+import org.apache.tika.io.TikaInputStream;
+
+import java.io.IOException;
+import java.util.Enumeration;
+import java.util.Locale;
+import java.util.zip.ZipEntry;
+import java.util.zip.ZipFile;
+
+/**
+ * Tells whether a document contains macros, by looking for the storage of its macros rather than
+ * parsing it: the VBA project storage of legacy Word and Excel documents, the VBA info record of
+ * legacy PowerPoint presentations, the vbaProject.bin part and the Excel 4 macro sheets of OOXML
+ * documents, and the Basic and Scripts folders of OpenDocument files. Only the document itself is
+ * checked, not the documents embedded in it.
+ */
+public class MacroDetector {
+
+    /**
+     * Returns whether the document of the stream contains macros. The stream is spooled to a
+     * temporary file if it is not backed by one already, and is left at its start.
+     */
+    public static boolean hasMacros(TikaInputStream stream) throws IOException {
+        final FileMagic magic = FileMagic.valueOf(stream);
+        if (magic == FileMagic.OLE2) {
+            try (POIFSFileSystem fs = new POIFSFileSystem(stream.getFile(), true)) {
+                return hasMacros(fs);
+            }
+        }
+        if (magic == FileMagic.OOXML) {
+            // OOXML and OpenDocument files are both zip files
+            try (ZipFile zip = new ZipFile(stream.getFile())) {
+                return hasMacros(zip);
+            }
+        }
+        return false;
+    }
+
+    private static boolean hasMacros(POIFSFileSystem fs) throws IOException {
+        final DirectoryNode root = fs.getRoot();
+        // Word keeps its VBA project in the Macros storage and Excel in the _VBA_PROJECT_CUR one
+        if (root.hasEntry("Macros") || root.hasEntry("_VBA_PROJECT_CUR")) {
+            return true;
+        }
+        if (!root.hasEntry("PowerPoint Document")) {
+            return false;
+        }
+
+        // PowerPoint keeps its VBA project compressed in an embedded object, referenced by the
+        // VBA info of the document record
+        try (HSLFSlideShow ppt = new HSLFSlideShow(fs)) {
+            final Record list = ppt.getDocumentRecord().findFirstOfType(RecordTypes.List.typeID);
+            if (!(list instanceof RecordContainer)) {
+                return false;
+            }
+            final Record info = ((RecordContainer) list).findFirstOfType(RecordTypes.VBAInfo.typeID);
+            return info instanceof RecordContainer
+                    && ((RecordContainer) info).findFirstOfType(RecordTypes.VBAInfoAtom.typeID) != null;
+        } catch (RuntimeException e) {
+            throw new IOException("Cannot read the presentation: " + e.getMessage(), e);
+        }
+    }
+
+    private static boolean hasMacros(ZipFile zip) {
+        final Enumeration<? extends ZipEntry> entries = zip.entries();
+        while (entries.hasMoreElements()) {
+            final String name = entries.nextElement().getName().toLowerCase(Locale.ROOT);
+            if (name.endsWith("vbaproject.bin") || name.startsWith("xl/macrosheets/")
+                    || name.startsWith("basic/") || name.startsWith("scripts/")) {
+                return true;
+            }
+        }
+        return false;
+    }
+}
@@ -0,0 +1,566 @@
+package ai.yobix;
+
+import org.xml.sax.Attributes;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+import java.io.IOException;
+import java.io.StringWriter;
+import java.io.Writer;
+import java.util.ArrayDeque;
+import java.util.ArrayList;
+import java.util.Deque;
+import java.util.List;
+import java.util.Locale;
+
+/**
+ * Converts the XHTML events of a parse into lightweight GitHub flavored Markdown: bold, italic,
+ * headings, lists, tables, links and preformatted text, as fenced code blocks, are kept,
+ * everything else becomes plain text.
+ * <p>
+ * Markup is emitted conservatively so the output is never broken Markdown: emphasis markers
+ * never span blocks and are only written around non blank text, overlapping elements are closed
+ * and reopened in the right order, and tables nested in tables are flattened into their cell.
+ */
+public class MarkdownContentHandler extends DefaultHandler {
+
+    private static final String BOLD = "**";
+    private static final String ITALIC = "*";
+
+    private final Writer writer;
+
+    /**
+     * Text of the current block, without its prefix
+     */
+    private StringBuilder block = new StringBuilder();
+    /**
+     * Heading or list marker written before the current block
+     */
+    private String blockPrefix = "";
+    private boolean blockIsListItem = false;
+    private boolean blockIsCode = false;
+    private boolean lastBlockWasListItem = false;
+    private boolean written = false;
+    private boolean pendingSpace = false;
+
+    /**
+     * Depth of ignored elements, such as the head or scripts
+     */
+    private int skipDepth = 0;
+    private int preDepth = 0;
+    private int boldDepth = 0;
+    private int italicDepth = 0;
+    /**
+     * Emphasis markers written to the current block and not closed yet, innermost last
+     */
+    private final Deque<String> openMarkers = new ArrayDeque<>();
+
+    /**
+     * Enclosing links, innermost first, null for anchors without a target
+     */
+    private final Deque<Link> links = new ArrayDeque<>();
+
+    /**
+     * Item counters of the enclosing lists, -1 for unordered lists
+     */
+    private final Deque<Integer> lists = new ArrayDeque<>();
+
+    private int tableDepth = 0;
+    private List<List<String>> rows = new ArrayList<>();
+    private List<String> row = null;
+    private StringBuilder cell = null;
+
+    public MarkdownContentHandler(Writer writer) {
+        this.writer = writer;
+    }
+
+    private static String name(String localName, String qName) {
+        final String name = localName == null || localName.isEmpty() ? qName : localName;
+        return name.toLowerCase(Locale.ROOT);
+    }
+
+    private static int headingLevel(String name) {
+        if (name.length() == 2 && name.charAt(0) == 'h' && name.charAt(1) >= '1' && name.charAt(1) <= '6') {
+            return name.charAt(1) - '0';
+        }
+        return 0;
+    }
+
+    private static boolean isBlock(String name) {
+        switch (name) {
+            case "p":
+            case "div":
+            case "blockquote":
+            case "pre":
+            case "section":
+            case "article":
+            case "header":
+            case "footer":
+            case "dt":
+            case "dd":
+                return true;
+            default:
+                return false;
+        }
+    }
+
+    private static boolean isSkipped(String name) {
+        return name.equals("head") || name.equals("script") || name.equals("style");
+    }
+
+    /**
+     * Returns where text is currently written to, the table cell or the block
+     */
+    private StringBuilder target() {
+        return cell != null ? cell : block;
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts) throws SAXException {
+        final String name = name(localName, qName);
+        if (isSkipped(name) || skipDepth > 0) {
+            skipDepth++;
+            return;
+        }
+
+        // Inside a table everything but the cells of the outermost table is flattened
+        if (tableDepth > 0) {
+            switch (name) {
+                case "table":
+                    tableDepth++;
+                    pendingSpace = true;
+                    return;
+                case "tr":
+                    if (tableDepth == 1) {
+                        row = new ArrayList<>();
+                    } else {
+                        pendingSpace = true;
+                    }
+                    return;
+                case "td":
+                case "th":
+                    if (tableDepth == 1 && row != null) {
+                        cell = new StringBuilder();
+                        openMarkers.clear();
+                        pendingSpace = false;
+                    } else {
+                        pendingSpace = true;
+                    }
+                    return;
+                default:
+                    break;
+            }
+        }
+
+        switch (name) {
+            case "b":
+            case "strong":
+                boldDepth++;
+                return;
+            case "i":
+            case "em":
+                italicDepth++;
+                return;
+            case "a":
+                startLink(atts.getValue("href"));
+                return;
+            case "br":
+                if (tableDepth > 0 || preDepth == 0) {
+                    pendingSpace = true;
+                } else {
+                    target().append('\n');
+                }
+                return;
+            default:
+                break;
+        }
+        if (tableDepth > 0) {
+            pendingSpace = true;
+            return;
+        }
+
+        final int level = headingLevel(name);
+        if (level > 0) {
+            startBlock("#".repeat(level) + " ", false);
+        } else if (name.equals("ul") || name.equals("ol")) {
+            flushBlock();
+            lists.push(name.equals("ol") ? 0 : -1);
+        } else if (name.equals("li")) {
+            final String indent = "  ".repeat(Math.max(lists.size() - 1, 0));
+            String marker = "- ";
+            if (!lists.isEmpty() && lists.peek() >= 0) {
+                final int number = lists.pop() + 1;
+                lists.push(number);
+                marker = number + ". ";
+            }
+            startBlock(indent + marker, true);
+        } else if (name.equals("table")) {
+            flushBlock();
+            tableDepth = 1;
+            rows = new ArrayList<>();
+        } else if (name.equals("pre")) {
+            if (preDepth == 0) {
+                startBlock("", false);
+                blockIsCode = true;
+            }
+            preDepth++;
+        } else if (isBlock(name)) {
+            startBlock("", false);
+        }
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        final String name = name(localName, qName);
+        if (skipDepth > 0) {
+            skipDepth--;
+            return;
+        }
+
+        if (tableDepth > 0) {
+            switch (name) {
+                case "table":
+                    tableDepth--;
+                    if (tableDepth == 0) {
+                        flushTable();
+                    } else {
+                        pendingSpace = true;
+                    }
+                    return;
+                case "tr":
+                    if (tableDepth == 1 && row != null) {
+                        endCell();
+                        if (!row.isEmpty()) {
+                            rows.add(row);
+                        }
+                        row = null;
+                    }
+                    return;
+                case "td":
+                case "th":
+                    if (tableDepth == 1) {
+                        endCell();
+                    } else {
+                        pendingSpace = true;
+                    }
+                    return;
+                default:
+                    break;
+            }
+        }
+
+        switch (name) {
+            case "b":
+            case "strong":
+                if (boldDepth > 0 && --boldDepth == 0) {
+                    closeMarker(BOLD);
+                }
+                return;
+            case "i":
+            case "em":
+                if (italicDepth > 0 && --italicDepth == 0) {
+                    closeMarker(ITALIC);
+                }
+                return;
+            case "a":
+                endLink();
+                return;
+            default:
+                break;
+        }
+        if (tableDepth > 0) {
+            pendingSpace = true;
+            return;
+        }
+
+        if (name.equals("ul") || name.equals("ol")) {
+            flushBlock();
+            if (!lists.isEmpty()) {
+                lists.pop();
+            }
+        } else if (name.equals("pre")) {
+            if (preDepth == 1) {
+                flushBlock();
+            }
+            preDepth = Math.max(preDepth - 1, 0);
+        } else if (name.equals("li") || name.equals("table") || headingLevel(name) > 0 || isBlock(name)) {
+            flushBlock();
+        }
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        if (skipDepth > 0) {
+            return;
+        }
+        final StringBuilder target = target();
+        if (preDepth > 0 && cell == null) {
+            target.append(ch, start, length);
+            return;
+        }
+
+        for (int i = start; i < start + length; i++) {
+            final char c = ch[i];
+            if (Character.isWhitespace(c)) {
+                pendingSpace = true;
+                continue;
+            }
+            if (pendingSpace && target.length() > 0) {
+                target.append(' ');
+            }
+            pendingSpace = false;
+            openMarkers(target);
+            if (c == '*' || c == '\\' || (c == '|' && cell != null)) {
+                target.append('\\');
+            }
+            target.append(c);
+        }
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        characters(ch, start, length);
+    }
+
+    @Override
+    public void endDocument() throws SAXException {
+        if (tableDepth > 0) {
+            tableDepth = 0;
+            flushTable();
+        }
+        flushBlock();
+        try {
+            if (written) {
+                writer.write('\n');
+            }
+            writer.flush();
+        } catch (IOException e) {
+            throw new SAXException("Error writing markdown", e);
+        }
+    }
+
+    /**
+     * Opens the markers of the emphasis the text is in, right before its first non blank character
+     */
+    private void openMarkers(StringBuilder target) {
+        if (boldDepth > 0 && !openMarkers.contains(BOLD)) {
+            target.append(BOLD);
+            openMarkers.addLast(BOLD);
+        }
+        if (italicDepth > 0 && !openMarkers.contains(ITALIC)) {
+            target.append(ITALIC);
+            openMarkers.addLast(ITALIC);
+        }
+    }
+
+    /**
+     * Closes the given marker, and the markers opened after it, which are reopened by the next
+     * text if they still apply
+     */
+    private void closeMarker(String marker) {
+        if (!openMarkers.contains(marker)) {
+            return;
+        }
+        final StringBuilder target = target();
+        while (!openMarkers.isEmpty()) {
+            final String open = openMarkers.removeLast();
+            target.append(open);
+            if (open.equals(marker)) {
+                break;
+            }
+        }
+    }
+
+    private void closeAllMarkers(StringBuilder target) {
+        while (!openMarkers.isEmpty()) {
+            target.append(openMarkers.removeLast());
+        }
+    }
+
+    /**
+     * Records where the text of a link starts, links to anchors of the document itself are kept
+     * as plain text
+     */
+    private void startLink(String href) {
+        if (href == null || href.isBlank() || href.startsWith("#") || preDepth > 0) {
+            links.push(Link.NONE);
+            return;
+        }
+        final StringBuilder target = target();
+        links.push(new Link(target, target.length(), openMarkers.size(), href.trim()));
+    }
+
+    /**
+     * Turns the text written since the start of the link into a Markdown link, if it has any
+     * and was not split by the end of a block or a cell
+     */
+    private void endLink() {
+        if (links.isEmpty()) {
+            return;
+        }
+        final Link link = links.pop();
+        final StringBuilder target = target();
+        if (link == Link.NONE || link.target != target || link.start > target.length()) {
+            return;
+        }
+        int start = link.start;
+        while (start < target.length() && target.charAt(start) == ' ') {
+            start++;
+        }
+        if (start == target.length()) {
+            return;
+        }
+        // The emphasis opened in the link is closed in it
+        while (openMarkers.size() > link.markers) {
+            target.append(openMarkers.removeLast());
+        }
+        target.insert(start, '[');
+        target.append("](").append(link.href.replace(" ", "%20").replace(")", "%29")).append(')');
+    }
+
+    private void startBlock(String prefix, boolean listItem) throws SAXException {
+        flushBlock();
+        blockPrefix = prefix;
+        blockIsListItem = listItem;
+    }
+
+    /**
+     * Writes the current block, if it has any text, and starts a new unmarked one
+     */
+    private void flushBlock() throws SAXException {
+        closeAllMarkers(block);
+        String text = preDepth > 0 ? block.toString() : block.toString().trim();
+        if (blockIsCode) {
+            text = fence(text.replaceAll("^\\s*\n|\\s+$", ""));
+        }
+        if (!text.isEmpty()) {
+            final String separator = blockIsListItem && lastBlockWasListItem ? "\n" : "\n\n";
+            write((written ? separator : "") + blockPrefix + text);
+            lastBlockWasListItem = blockIsListItem;
+        }
+        block = new StringBuilder();
+        blockPrefix = "";
+        blockIsListItem = false;
+        blockIsCode = false;
+        pendingSpace = false;
+    }
+
+    /**
+     * Wraps the code in a fenced code block, with a fence longer than the backtick runs of the
+     * code. Returns an empty string for blank code
+     */
+    private static String fence(String code) {
+        if (code.isBlank()) {
+            return "";
+        }
+        int longest = 0;
+        int run = 0;
+        for (int i = 0; i < code.length(); i++) {
+            run = code.charAt(i) == '`' ? run + 1 : 0;
+            longest = Math.max(longest, run);
+        }
+        final String fence = "`".repeat(Math.max(3, longest + 1));
+        return fence + "\n" + code + "\n" + fence;
+    }
+
+    private void endCell() {
+        if (cell == null) {
+            return;
+        }
+        closeAllMarkers(cell);
+        row.add(cell.toString().trim());
+        cell = null;
+        pendingSpace = false;
+    }
+
+    /**
+     * Writes the collected rows as a pipe table, the first row being the header
+     */
+    private void flushTable() throws SAXException {
+        if (row != null) {
+            endCell();
+            if (!row.isEmpty()) {
+                rows.add(row);
+            }
+            row = null;
+        }
+        int columns = 0;
+        for (List<String> r : rows) {
+            columns = Math.max(columns, r.size());
+        }
+        if (columns > 0) {
+            final StringBuilder table = new StringBuilder();
+            for (int i = 0; i < rows.size(); i++) {
+                appendRow(table, rows.get(i), columns);
+                if (i == 0) {
+                    table.append('\n');
+                    appendRow(table, List.of(), columns);
+                }
+                if (i < rows.size() - 1) {
+                    table.append('\n');
+                }
+            }
+            write((written ? "\n\n" : "") + table);
+            lastBlockWasListItem = false;
+        }
+        rows = new ArrayList<>();
+        pendingSpace = false;
+    }
+
+    private static void appendRow(StringBuilder table, List<String> cells, int columns) {
+        final boolean separator = cells.isEmpty();
+        table.append('|');
+        for (int i = 0; i < columns; i++) {
+            final String text = separator ? "---" : i < cells.size() ? cells.get(i) : "";
+            table.append(' ').append(text).append(" |");
+        }
+    }
+
+    private void write(String text) throws SAXException {
+        try {
+            writer.write(text);
+            written = true;
+        } catch (IOException e) {
+            throw new SAXException("Error writing markdown", e);
+        }
+    }
+
+    /**
+     * Start of the text of a link in the block or cell it is written to
+     */
+    private static class Link {
+        static final Link NONE = new Link(null, 0, 0, null);
+
+        final StringBuilder target;
+        final int start;
+        /**
+         * Number of emphasis markers open at the start of the link
+         */
+        final int markers;
+        final String href;
+
+        Link(StringBuilder target, int start, int markers, String href) {
+            this.target = target;
+            this.start = start;
+            this.markers = markers;
+            this.href = href;
+        }
+    }
+
+    /**
+     * Returns the Markdown written so far, when writing to a StringWriter. Text collected before
+     * the parse was interrupted, for example by a write limit, is included.
+     */
+    @Override
+    public String toString() {
+        if (!(writer instanceof StringWriter)) {
+            return super.toString();
+        }
+        try {
+            flushBlock();
+        } catch (SAXException e) {
+            // Writing to a StringWriter does not fail
+        }
+        return writer.toString();
+    }
+}
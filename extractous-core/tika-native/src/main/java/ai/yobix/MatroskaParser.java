@@ -0,0 +1,308 @@
+package ai.yobix;
+
+import org.apache.commons.io.IOUtils;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TIFF;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.metadata.XMPDM;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.AbstractParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.sax.XHTMLContentHandler;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.EOFException;
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.ByteBuffer;
+import java.nio.charset.StandardCharsets;
+import java.util.Arrays;
+import java.util.Collections;
+import java.util.HashMap;
+import java.util.HashSet;
+import java.util.Map;
+import java.util.Set;
+
+/**
+ * Parser for Matroska (mkv, mka) and WebM files, which Tika has no parser for. The duration, title,
+ * codecs, resolution and sample rate are returned in the standard xmpDM and tiff metadata, and
+ * the text of the subtitle tracks (S_TEXT/UTF8, S_TEXT/ASS, S_TEXT/SSA and S_TEXT/WEBVTT) is the
+ * content, one paragraph per subtitle. Image based subtitles are ignored.
+ * <p>
+ * The file is read as a stream of EBML elements, descending only into the elements that hold
+ * the needed values. When there are no text subtitles, reading stops at the first cluster, so
+ * the media data of large files is not read.
+ */
+public class MatroskaParser extends AbstractParser {
+
+    private static final Set<MediaType> SUPPORTED_TYPES = Collections.unmodifiableSet(new HashSet<>(Arrays.asList(
+            MediaType.video("x-matroska"), MediaType.audio("x-matroska"),
+            MediaType.video("webm"), MediaType.audio("webm")
+    )));
+
+    // Master elements descended into
+    private static final long SEGMENT = 0x18538067L;
+    private static final long INFO = 0x1549A966L;
+    private static final long TRACKS = 0x1654AE6BL;
+    private static final long TRACK_ENTRY = 0xAEL;
+    private static final long VIDEO = 0xE0L;
+    private static final long AUDIO = 0xE1L;
+    private static final long CLUSTER = 0x1F43B675L;
+    private static final long BLOCK_GROUP = 0xA0L;
+
+    // Elements whose values are read
+    private static final long TIMECODE_SCALE = 0x2AD7B1L;
+    private static final long DURATION = 0x4489L;
+    private static final long TITLE = 0x7BA9L;
+    private static final long TRACK_NUMBER = 0xD7L;
+    private static final long TRACK_TYPE = 0x83L;
+    private static final long CODEC_ID = 0x86L;
+    private static final long PIXEL_WIDTH = 0xB0L;
+    private static final long PIXEL_HEIGHT = 0xBAL;
+    private static final long SAMPLING_FREQUENCY = 0xB5L;
+    private static final long SIMPLE_BLOCK = 0xA3L;
+    private static final long BLOCK = 0xA1L;
+
+    private static final Set<Long> MASTERS = new HashSet<>(Arrays.asList(
+            SEGMENT, INFO, TRACKS, TRACK_ENTRY, VIDEO, AUDIO, CLUSTER, BLOCK_GROUP));
+
+    private static final int TRACK_TYPE_VIDEO = 1;
+    private static final int TRACK_TYPE_AUDIO = 2;
+    private static final int TRACK_TYPE_SUBTITLE = 0x11;
+
+    /**
+     * Values larger than this are skipped rather than read, only blocks and short values are read
+     */
+    private static final long MAX_VALUE_SIZE = 1 << 20;
+
+    private static class Track {
+        long number = -1;
+        int type = 0;
+        String codec = null;
+    }
+
+    @Override
+    public Set<MediaType> getSupportedTypes(ParseContext context) {
+        return SUPPORTED_TYPES;
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        final XHTMLContentHandler xhtml = new XHTMLContentHandler(handler, metadata);
+        xhtml.startDocument();
+
+        long timecodeScale = 1_000_000;
+        Double duration = null;
+        Track track = null;
+        // Text subtitle tracks by number, with their codec
+        final Map<Long, String> subtitles = new HashMap<>();
+        boolean videoSeen = false;
+        boolean audioSeen = false;
+
+        while (true) {
+            final long id = readId(stream);
+            if (id < 0) {
+                break;
+            }
+            final long size = readSize(stream);
+
+            if (id == TRACK_ENTRY) {
+                track = new Track();
+            }
+            if (id == CLUSTER && subtitles.isEmpty()) {
+                // Only media data follows
+                break;
+            }
+            if (MASTERS.contains(id)) {
+                continue;
+            }
+            if (size < 0) {
+                // A leaf element of unknown size cannot be skipped
+                break;
+            }
+            if (size > MAX_VALUE_SIZE || !isRead(id)) {
+                IOUtils.skipFully(stream, size);
+                continue;
+            }
+
+            final byte[] value = IOUtils.toByteArray(stream, (int) size);
+            if (id == TIMECODE_SCALE) {
+                timecodeScale = readUnsigned(value);
+            } else if (id == DURATION) {
+                duration = readFloat(value);
+            } else if (id == TITLE) {
+                metadata.set(TikaCoreProperties.TITLE, readString(value));
+            } else if (track != null && id == TRACK_NUMBER) {
+                track.number = readUnsigned(value);
+            } else if (track != null && id == TRACK_TYPE) {
+                track.type = (int) readUnsigned(value);
+            } else if (track != null && id == CODEC_ID) {
+                track.codec = readString(value);
+                if (track.type == TRACK_TYPE_VIDEO && !videoSeen) {
+                    videoSeen = true;
+                    metadata.set(XMPDM.VIDEO_COMPRESSOR, track.codec);
+                } else if (track.type == TRACK_TYPE_AUDIO && !audioSeen) {
+                    audioSeen = true;
+                    metadata.set(XMPDM.AUDIO_COMPRESSOR, track.codec);
+                } else if (track.type == TRACK_TYPE_SUBTITLE && isTextSubtitle(track.codec) && track.number >= 0) {
+                    subtitles.put(track.number, track.codec);
+                }
+            } else if (id == PIXEL_WIDTH && metadata.get(TIFF.IMAGE_WIDTH) == null) {
+                metadata.set(TIFF.IMAGE_WIDTH, (int) readUnsigned(value));
+            } else if (id == PIXEL_HEIGHT && metadata.get(TIFF.IMAGE_LENGTH) == null) {
+                metadata.set(TIFF.IMAGE_LENGTH, (int) readUnsigned(value));
+            } else if (id == SAMPLING_FREQUENCY && metadata.get(XMPDM.AUDIO_SAMPLE_RATE) == null) {
+                metadata.set(XMPDM.AUDIO_SAMPLE_RATE, (int) Math.round(readFloat(value)));
+            } else if (id == SIMPLE_BLOCK || id == BLOCK) {
+                writeSubtitle(value, subtitles, xhtml);
+            }
+        }
+
+        if (duration != null) {
+            // The duration is in timecode ticks, which are timecodeScale nanoseconds long
+            metadata.set(XMPDM.DURATION, duration * timecodeScale / 1_000_000_000.0);
+        }
+        xhtml.endDocument();
+    }
+
+    private static boolean isRead(long id) {
+        return id == TIMECODE_SCALE || id == DURATION || id == TITLE || id == TRACK_NUMBER
+                || id == TRACK_TYPE || id == CODEC_ID || id == PIXEL_WIDTH || id == PIXEL_HEIGHT
+                || id == SAMPLING_FREQUENCY || id == SIMPLE_BLOCK || id == BLOCK;
+    }
+
+    private static boolean isTextSubtitle(String codec) {
+        return codec.equals("S_TEXT/UTF8") || codec.equals("S_TEXT/ASS") || codec.equals("S_TEXT/SSA")
+                || codec.equals("S_TEXT/WEBVTT");
+    }
+
+    /**
+     * Writes the text of the block if it belongs to a text subtitle track
+     */
+    private static void writeSubtitle(byte[] block, Map<Long, String> subtitles, XHTMLContentHandler xhtml)
+            throws SAXException {
+        // The block starts with the track number, a 16 bits timecode and the flags
+        final ByteBuffer buffer = ByteBuffer.wrap(block);
+        final long trackNumber = readVint(buffer, true);
+        if (trackNumber < 0 || !subtitles.containsKey(trackNumber) || buffer.remaining() < 3) {
+            return;
+        }
+        buffer.position(buffer.position() + 2);
+        final int flags = buffer.get() & 0xFF;
+        if ((flags & 0x06) != 0) {
+            // Laced blocks are not used for subtitles
+            return;
+        }
+        String text = new String(block, buffer.position(), buffer.remaining(), StandardCharsets.UTF_8);
+
+        final String codec = subtitles.get(trackNumber);
+        if (codec.equals("S_TEXT/ASS") || codec.equals("S_TEXT/SSA")) {
+            // ReadOrder, Layer, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+            final String[] fields = text.split(",", 9);
+            text = fields[fields.length - 1]
+                    .replaceAll("\\{[^}]*}", "")
+                    .replace("\\N", "\n")
+                    .replace("\\n", "\n");
+        }
+        text = text.trim();
+        if (!text.isEmpty()) {
+            xhtml.element("p", text);
+        }
+    }
+
+    /**
+     * Reads an element id, keeping its length marker. Returns -1 at the end of the stream
+     */
+    private static long readId(InputStream stream) throws IOException {
+        final int first = stream.read();
+        if (first < 0) {
+            return -1;
+        }
+        final int length = Integer.numberOfLeadingZeros(first) - 24 + 1;
+        if (length > 4) {
+            throw new IOException("Invalid EBML element id");
+        }
+        long id = first;
+        for (int i = 1; i < length; i++) {
+            id = (id << 8) | readByte(stream);
+        }
+        return id;
+    }
+
+    /**
+     * Reads an element size, without its length marker. Returns -1 for an unknown size
+     */
+    private static long readSize(InputStream stream) throws IOException {
+        final int first = readByte(stream);
+        final int length = Integer.numberOfLeadingZeros(first) - 24 + 1;
+        if (length > 8) {
+            throw new IOException("Invalid EBML element size");
+        }
+        long size = first & (0xFF >> length);
+        boolean unknown = size == (0xFF >> length);
+        for (int i = 1; i < length; i++) {
+            final int b = readByte(stream);
+            unknown &= b == 0xFF;
+            size = (size << 8) | b;
+        }
+        return unknown ? -1 : size;
+    }
+
+    /**
+     * Reads a variable length integer from the buffer, without its length marker when
+     * removeMarker is true. Returns -1 if the buffer is too short
+     */
+    private static long readVint(ByteBuffer buffer, boolean removeMarker) {
+        if (!buffer.hasRemaining()) {
+            return -1;
+        }
+        final int first = buffer.get() & 0xFF;
+        final int length = Integer.numberOfLeadingZeros(first) - 24 + 1;
+        if (length > 8 || buffer.remaining() < length - 1) {
+            return -1;
+        }
+        long value = removeMarker ? first & (0xFF >> length) : first;
+        for (int i = 1; i < length; i++) {
+            value = (value << 8) | (buffer.get() & 0xFF);
+        }
+        return value;
+    }
+
+    private static int readByte(InputStream stream) throws IOException {
+        final int b = stream.read();
+        if (b < 0) {
+            throw new EOFException("Truncated EBML element");
+        }
+        return b;
+    }
+
+    private static long readUnsigned(byte[] value) {
+        long result = 0;
+        for (byte b : value) {
+            result = (result << 8) | (b & 0xFF);
+        }
+        return result;
+    }
+
+    private static double readFloat(byte[] value) {
+        final ByteBuffer buffer = ByteBuffer.wrap(value);
+        if (value.length == 4) {
+            return buffer.getFloat();
+        } else if (value.length == 8) {
+            return buffer.getDouble();
+        }
+        return 0;
+    }
+
+    private static String readString(byte[] value) {
+        int end = value.length;
+        // Strings may be padded with zeros
+        while (end > 0 && value[end - 1] == 0) {
+            end--;
+        }
+        return new String(value, 0, end, StandardCharsets.UTF_8);
+    }
+}
@@ -0,0 +1,62 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+
+import java.util.List;
+
+public class MetadataListResult {
+
+    private final List<Metadata> metadataList;
+    private final byte status;
+    private final String errorMessage;
+
+    public MetadataListResult(List<Metadata> metadataList) {
+        this.metadataList = metadataList;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public MetadataListResult(byte status, String errorMessage) {
+        this.metadataList = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the metadata of every parsed document, the container first, or null if there is an error
+     * @return array of tika metadata
+     */
+    public Metadata[] getMetadataList() {
+        return metadataList == null ? null : metadataList.toArray(new Metadata[0]);
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 2: TikaException
+     * 4: NoParserException
+     * 5: RejectedMediaTypeException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 8: MissingOcrLanguageException
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
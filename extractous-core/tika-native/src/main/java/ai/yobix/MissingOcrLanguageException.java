@@ -0,0 +1,21 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+
+/**
+ * Thrown when a language of the OCR config is not installed and falling back to an installed
+ * language is disabled
+ */
+public class MissingOcrLanguageException extends TikaException {
+
+    private final String language;
+
+    public MissingOcrLanguageException(String language) {
+        super("OCR language " + language + " is not installed");
+        this.language = language;
+    }
+
+    public String getLanguage() {
+        return language;
+    }
+}
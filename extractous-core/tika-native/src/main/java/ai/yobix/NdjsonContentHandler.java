@@ -0,0 +1,408 @@
+package ai.yobix;
+
+import org.xml.sax.Attributes;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+import java.io.IOException;
+import java.io.StringWriter;
+import java.io.Writer;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Locale;
+
+/**
+ * Converts the XHTML events of a parse into newline delimited JSON, one self-contained record
+ * per structural element: paragraphs, headings, list items, table rows and images.
+ * <p>
+ * Every record has the type of the element, its text with whitespace collapsed, its index among
+ * the records of the document and the page it is on, counted from 1 over the page and slide
+ * divs of paged documents and null for the others. Headings also have their level, table rows
+ * their cells, the index of their table and their index in it, and images their source and
+ * alternative text. Tables nested in tables are flattened into their cell, like blocks nested
+ * in blocks are split into records of their own. Records are written as soon as their element
+ * ends, so the output can be streamed and split on new lines.
+ */
+public class NdjsonContentHandler extends DefaultHandler {
+
+    private final Writer writer;
+
+    private int index = 0;
+    /**
+     * Number of the current page, 0 before the first page div
+     */
+    private int page = 0;
+
+    /**
+     * Text of the current block
+     */
+    private StringBuilder block = new StringBuilder();
+    private String blockType = "paragraph";
+    private int headingLevel = 0;
+    private boolean pendingSpace = false;
+
+    /**
+     * Depth of ignored elements, such as the head or scripts
+     */
+    private int skipDepth = 0;
+
+    private int tableDepth = 0;
+    private int tables = 0;
+    private int tableRows = 0;
+    private List<String> row = null;
+    private StringBuilder cell = null;
+
+    public NdjsonContentHandler(Writer writer) {
+        this.writer = writer;
+    }
+
+    private static String name(String localName, String qName) {
+        final String name = localName == null || localName.isEmpty() ? qName : localName;
+        return name.toLowerCase(Locale.ROOT);
+    }
+
+    private static int headingLevel(String name) {
+        if (name.length() == 2 && name.charAt(0) == 'h' && name.charAt(1) >= '1' && name.charAt(1) <= '6') {
+            return name.charAt(1) - '0';
+        }
+        return 0;
+    }
+
+    private static boolean isBlock(String name) {
+        switch (name) {
+            case "p":
+            case "div":
+            case "blockquote":
+            case "pre":
+            case "section":
+            case "article":
+            case "header":
+            case "footer":
+            case "dt":
+            case "dd":
+                return true;
+            default:
+                return false;
+        }
+    }
+
+    private static boolean isSkipped(String name) {
+        return name.equals("head") || name.equals("script") || name.equals("style");
+    }
+
+    /**
+     * Returns whether the element starts a page, as the page divs of PDFs and spreadsheets and
+     * the slide divs of presentations do
+     */
+    private static boolean isPage(String name, Attributes atts) {
+        if (!name.equals("div") || atts == null) {
+            return false;
+        }
+        final String cssClass = atts.getValue("class");
+        return "page".equals(cssClass) || "slide-content".equals(cssClass);
+    }
+
+    /**
+     * Returns where text is currently written to, the table cell or the block
+     */
+    private StringBuilder target() {
+        return cell != null ? cell : block;
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts) throws SAXException {
+        final String name = name(localName, qName);
+        if (isSkipped(name) || skipDepth > 0) {
+            skipDepth++;
+            return;
+        }
+        if (name.equals("img")) {
+            writeImage(atts);
+            return;
+        }
+
+        // Inside a table everything but the cells of the outermost table is flattened
+        if (tableDepth > 0) {
+            switch (name) {
+                case "table":
+                    tableDepth++;
+                    break;
+                case "tr":
+                    if (tableDepth == 1) {
+                        row = new ArrayList<>();
+                    }
+                    break;
+                case "td":
+                case "th":
+                    if (tableDepth == 1 && row != null) {
+                        endCell();
+                        cell = new StringBuilder();
+                    }
+                    break;
+                default:
+                    break;
+            }
+            pendingSpace = true;
+            return;
+        }
+
+        if (name.equals("br")) {
+            pendingSpace = true;
+            return;
+        }
+        if (isPage(name, atts)) {
+            flushBlock();
+            page++;
+            return;
+        }
+
+        final int level = headingLevel(name);
+        if (level > 0) {
+            startBlock("heading", level);
+        } else if (name.equals("li")) {
+            startBlock("list_item", 0);
+        } else if (name.equals("table")) {
+            flushBlock();
+            tableDepth = 1;
+            tables++;
+            tableRows = 0;
+        } else if (isBlock(name) || name.equals("ul") || name.equals("ol")) {
+            startBlock("paragraph", 0);
+        }
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        final String name = name(localName, qName);
+        if (skipDepth > 0) {
+            skipDepth--;
+            return;
+        }
+
+        if (tableDepth > 0) {
+            switch (name) {
+                case "table":
+                    tableDepth--;
+                    if (tableDepth == 0) {
+                        endRow();
+                    }
+                    break;
+                case "tr":
+                    if (tableDepth == 1) {
+                        endRow();
+                    }
+                    break;
+                case "td":
+                case "th":
+                    if (tableDepth == 1) {
+                        endCell();
+                    }
+                    break;
+                default:
+                    break;
+            }
+            pendingSpace = true;
+            return;
+        }
+
+        if (name.equals("li") || headingLevel(name) > 0 || isBlock(name) || name.equals("ul") || name.equals("ol")) {
+            flushBlock();
+        }
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        if (skipDepth > 0) {
+            return;
+        }
+        final StringBuilder target = target();
+        for (int i = start; i < start + length; i++) {
+            final char c = ch[i];
+            if (Character.isWhitespace(c)) {
+                pendingSpace = true;
+                continue;
+            }
+            if (pendingSpace && target.length() > 0) {
+                target.append(' ');
+            }
+            pendingSpace = false;
+            target.append(c);
+        }
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        characters(ch, start, length);
+    }
+
+    @Override
+    public void endDocument() throws SAXException {
+        if (tableDepth > 0) {
+            tableDepth = 0;
+            endRow();
+        }
+        flushBlock();
+        try {
+            writer.flush();
+        } catch (IOException e) {
+            throw new SAXException("Error writing ndjson", e);
+        }
+    }
+
+    private void startBlock(String type, int level) throws SAXException {
+        flushBlock();
+        blockType = type;
+        headingLevel = level;
+    }
+
+    /**
+     * Writes the record of the current block, if it has any text, and starts a new paragraph
+     */
+    private void flushBlock() throws SAXException {
+        final String text = block.toString().trim();
+        if (!text.isEmpty()) {
+            final StringBuilder record = startRecord(blockType);
+            appendField(record, "text", text);
+            if (headingLevel > 0) {
+                record.append(",\"level\":").append(headingLevel);
+            }
+            writeRecord(record);
+        }
+        block = new StringBuilder();
+        blockType = "paragraph";
+        headingLevel = 0;
+        pendingSpace = false;
+    }
+
+    private void endCell() {
+        if (cell == null) {
+            return;
+        }
+        row.add(cell.toString().trim());
+        cell = null;
+        pendingSpace = false;
+    }
+
+    /**
+     * Writes the record of the current table row, if it has any non empty cell. The text of the
+     * row is its cells separated by tabs
+     */
+    private void endRow() throws SAXException {
+        if (row == null) {
+            return;
+        }
+        endCell();
+        final List<String> cells = row;
+        row = null;
+        if (cells.stream().allMatch(String::isEmpty)) {
+            return;
+        }
+
+        final StringBuilder record = startRecord("table_row");
+        appendField(record, "text", String.join("\t", cells));
+        record.append(",\"table\":").append(tables - 1);
+        record.append(",\"row\":").append(tableRows++);
+        record.append(",\"cells\":[");
+        for (int i = 0; i < cells.size(); i++) {
+            if (i > 0) {
+                record.append(',');
+            }
+            appendString(record, cells.get(i));
+        }
+        record.append(']');
+        writeRecord(record);
+    }
+
+    /**
+     * Writes the record of an image, e.g. an image embedded in a document as embedded:image1.png.
+     * Its text is its alternative text
+     */
+    private void writeImage(Attributes atts) throws SAXException {
+        final String src = atts == null ? null : atts.getValue("src");
+        final String alt = atts == null ? null : atts.getValue("alt");
+        final StringBuilder record = startRecord("image");
+        appendField(record, "text", alt == null ? "" : alt.trim());
+        appendField(record, "src", src);
+        appendField(record, "alt", alt);
+        writeRecord(record);
+    }
+
+    private StringBuilder startRecord(String type) {
+        final StringBuilder record = new StringBuilder("{");
+        record.append("\"type\":\"").append(type).append('"');
+        record.append(",\"index\":").append(index);
+        record.append(",\"page\":").append(page > 0 ? String.valueOf(page) : "null");
+        return record;
+    }
+
+    private static void appendField(StringBuilder record, String key, String value) {
+        record.append(",\"").append(key).append("\":");
+        appendString(record, value);
+    }
+
+    /**
+     * Appends the value as a JSON string, or null
+     */
+    private static void appendString(StringBuilder record, String value) {
+        if (value == null) {
+            record.append("null");
+            return;
+        }
+        record.append('"');
+        for (int i = 0; i < value.length(); i++) {
+            final char c = value.charAt(i);
+            switch (c) {
+                case '"':
+                    record.append("\\\"");
+                    break;
+                case '\\':
+                    record.append("\\\\");
+                    break;
+                case '\n':
+                    record.append("\\n");
+                    break;
+                case '\r':
+                    record.append("\\r");
+                    break;
+                case '\t':
+                    record.append("\\t");
+                    break;
+                default:
+                    // The line and paragraph separators are escaped too, for the readers that split on them
+                    if (c < 0x20 || c == '\u2028' || c == '\u2029') {
+                        record.append(String.format("\\u%04x", (int) c));
+                    } else {
+                        record.append(c);
+                    }
+            }
+        }
+        record.append('"');
+    }
+
+    private void writeRecord(StringBuilder record) throws SAXException {
+        record.append("}\n");
+        try {
+            writer.write(record.toString());
+            index++;
+        } catch (IOException e) {
+            throw new SAXException("Error writing ndjson", e);
+        }
+    }
+
+    /**
+     * Returns the records written so far, when writing to a StringWriter. The block collected
+     * before the parse was interrupted, for example by a write limit, is included.
+     */
+    @Override
+    public String toString() {
+        if (!(writer instanceof StringWriter)) {
+            return super.toString();
+        }
+        try {
+            flushBlock();
+        } catch (SAXException e) {
+            // Writing to a StringWriter does not fail
+        }
+        return writer.toString();
+    }
+}
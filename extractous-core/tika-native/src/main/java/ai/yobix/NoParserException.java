@@ -0,0 +1,21 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.mime.MediaType;
+
+/**
+ * Thrown when the media type of a document is detected but no parser is available for it
+ */
+public class NoParserException extends TikaException {
+
+    private final MediaType mediaType;
+
+    public NoParserException(MediaType mediaType) {
+        super("No parser available for media type " + mediaType);
+        this.mediaType = mediaType;
+    }
+
+    public MediaType getMediaType() {
+        return mediaType;
+    }
+}
@@ -0,0 +1,220 @@
+package ai.yobix;
+
+import org.apache.commons.io.IOUtils;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.ocr.TesseractOCRConfig;
+import org.apache.tika.parser.ocr.TesseractOCRParser;
+import org.apache.tika.sax.XHTMLContentHandler;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Set;
+import java.util.regex.Matcher;
+import java.util.regex.Pattern;
+
+/**
+ * Parser that OCRs images with each language of the fallback list of the
+ * ExtendedTesseractOCRConfig, in order, and keeps the text with the highest mean word confidence.
+ * Tesseract is run by Tika's TesseractOCRParser with hOCR output, which gives the confidence of
+ * every word. The languages after the maximum number of attempts are not tried, and a language
+ * reaching the minimum confidence is kept right away. The kept language and its confidence, from
+ * 0 to 1, are set in the X-OCR-Language and X-OCR-Confidence metadata.
+ * Like the RustOcrParser, it replaces Tesseract when added after the default parsers.
+ */
+public class OcrLanguageFallback implements Parser {
+
+    public static final String OCR_LANGUAGE = "X-OCR-Language";
+    public static final String OCR_CONFIDENCE = "X-OCR-Confidence";
+
+    private final TesseractOCRParser tesseract;
+
+    public OcrLanguageFallback(TesseractOCRParser tesseract) {
+        this.tesseract = tesseract;
+    }
+
+    /**
+     * Returns the TesseractOCRParser among the given parser and the parsers it is composed of,
+     * or null if there is none
+     */
+    public static TesseractOCRParser findTesseract(Parser parser) {
+        if (parser instanceof TesseractOCRParser) {
+            return (TesseractOCRParser) parser;
+        }
+        if (parser instanceof ParserDecorator) {
+            return findTesseract(((ParserDecorator) parser).getWrappedParser());
+        }
+        if (parser instanceof CompositeParser) {
+            for (Parser component : ((CompositeParser) parser).getAllComponentParsers()) {
+                final TesseractOCRParser found = findTesseract(component);
+                if (found != null) {
+                    return found;
+                }
+            }
+        }
+        return null;
+    }
+
+    @Override
+    public Set<MediaType> getSupportedTypes(ParseContext context) {
+        // Empty when Tesseract is not installed
+        return tesseract.getSupportedTypes(context);
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        final TesseractOCRConfig config = context.get(TesseractOCRConfig.class);
+        if (!(config instanceof ExtendedTesseractOCRConfig)
+                || ((ExtendedTesseractOCRConfig) config).getLanguageFallback().isEmpty()) {
+            tesseract.parse(stream, handler, metadata, context);
+            return;
+        }
+        if (config.isSkipOcr()) {
+            return;
+        }
+        final ExtendedTesseractOCRConfig extended = (ExtendedTesseractOCRConfig) config;
+        final byte[] image = IOUtils.toByteArray(stream);
+
+        String bestLanguage = null;
+        HocrCollector best = null;
+        int attempts = 0;
+        for (String language : extended.getLanguageFallback()) {
+            if (attempts++ >= extended.getLanguageFallbackMaxAttempts()) {
+                break;
+            }
+            final HocrCollector attempt = ocr(image, language, extended, metadata, context);
+            if (best == null || attempt.getConfidence() > best.getConfidence()) {
+                best = attempt;
+                bestLanguage = language;
+            }
+            if (attempt.getConfidence() >= extended.getLanguageFallbackMinConfidence()) {
+                break;
+            }
+        }
+        if (best == null) {
+            return;
+        }
+        metadata.set(OCR_LANGUAGE, bestLanguage);
+        metadata.set(OCR_CONFIDENCE, Double.toString(best.getConfidence()));
+
+        final XHTMLContentHandler xhtml = new XHTMLContentHandler(handler, metadata);
+        xhtml.startDocument();
+        xhtml.startElement("div", "class", "ocr");
+        xhtml.characters(best.getText());
+        xhtml.endElement("div");
+        xhtml.endDocument();
+    }
+
+    /**
+     * OCRs the image with the given language, with the other settings of the config
+     */
+    private HocrCollector ocr(
+            byte[] image,
+            String language,
+            TesseractOCRConfig config,
+            Metadata metadata,
+            ParseContext context
+    ) throws IOException, SAXException, TikaException {
+        final TesseractOCRConfig attemptConfig = new TesseractOCRConfig();
+        attemptConfig.setDensity(config.getDensity());
+        attemptConfig.setDepth(config.getDepth());
+        attemptConfig.setTimeoutSeconds(config.getTimeoutSeconds());
+        attemptConfig.setEnableImagePreprocessing(config.isEnableImagePreprocessing());
+        attemptConfig.setApplyRotation(config.isApplyRotation());
+        attemptConfig.setLanguage(language);
+        attemptConfig.setOutputType(TesseractOCRConfig.OUTPUT_TYPE.HOCR);
+
+        final Metadata attemptMetadata = new Metadata();
+        attemptMetadata.set(Metadata.CONTENT_TYPE, metadata.get(Metadata.CONTENT_TYPE));
+        final HocrCollector collector = new HocrCollector();
+        context.set(TesseractOCRConfig.class, attemptConfig);
+        try (final TikaInputStream tis = TikaInputStream.get(image)) {
+            tesseract.parse(tis, collector, attemptMetadata, context);
+        } finally {
+            context.set(TesseractOCRConfig.class, config);
+        }
+        return collector;
+    }
+
+    /**
+     * Collects the text of the words of the hOCR passed through by the TesseractOCRParser, a
+     * line per hOCR line, and their mean confidence
+     */
+    private static class HocrCollector extends DefaultHandler {
+
+        private static final Pattern WORD_CONFIDENCE = Pattern.compile("x_wconf (\\d+)");
+
+        private final StringBuilder text = new StringBuilder();
+        private final StringBuilder word = new StringBuilder();
+        private int wordDepth = 0;
+        private boolean lineStart = true;
+        private long confidenceSum = 0;
+        private int words = 0;
+
+        /**
+         * Returns the mean confidence of the words from 0 to 1, 0 if there are none
+         */
+        double getConfidence() {
+            return words == 0 ? 0 : confidenceSum / (100.0 * words);
+        }
+
+        String getText() {
+            return text.toString().trim();
+        }
+
+        @Override
+        public void startElement(String uri, String localName, String qName, Attributes atts) {
+            if (wordDepth > 0) {
+                wordDepth++;
+                return;
+            }
+            final String className = atts.getValue("class");
+            if ("ocrx_word".equals(className)) {
+                wordDepth = 1;
+                word.setLength(0);
+                final String title = atts.getValue("title");
+                final Matcher matcher = WORD_CONFIDENCE.matcher(title == null ? "" : title);
+                if (matcher.find()) {
+                    confidenceSum += Integer.parseInt(matcher.group(1));
+                    words++;
+                }
+            } else if ("ocr_line".equals(className) && !lineStart) {
+                text.append('\n');
+                lineStart = true;
+            }
+        }
+
+        @Override
+        public void endElement(String uri, String localName, String qName) {
+            if (wordDepth == 0 || --wordDepth > 0) {
+                return;
+            }
+            final String value = word.toString().trim();
+            if (!value.isEmpty()) {
+                if (!lineStart) {
+                    text.append(' ');
+                }
+                text.append(value);
+                lineStart = false;
+            }
+        }
+
+        @Override
+        public void characters(char[] ch, int start, int length) {
+            if (wordDepth > 0) {
+                word.append(ch, start, length);
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+package ai.yobix;
+
+import org.apache.tika.config.TikaConfig;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ocr.TesseractOCRConfig;
+import org.apache.tika.parser.ocr.TesseractOCRParser;
+
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.List;
+import java.util.Set;
+import java.util.TreeSet;
+
+/**
+ * Checks that the languages of the OCR config are installed, as listed by the Tesseract found by
+ * Tika's TesseractOCRParser. A missing language either fails the parse with a
+ * MissingOcrLanguageException or, when the ExtendedTesseractOCRConfig allows it, is dropped in
+ * favor of the installed ones, with a warning in the X-OCR-Warning metadata. The languages are
+ * listed once, when the TesseractOCRParser is initialized.
+ */
+public class OcrLanguages {
+
+    public static final String OCR_WARNING = "X-OCR-Warning";
+
+    /**
+     * Language used when none of the requested ones is installed, if it is installed itself
+     */
+    private static final String DEFAULT_LANGUAGE = "eng";
+
+    private OcrLanguages() {
+    }
+
+    /**
+     * Returns the languages of the given TesseractOCRParser, empty when Tesseract is not
+     * installed
+     */
+    private static Set<String> installed(TesseractOCRParser tesseract) {
+        return tesseract == null ? Collections.emptySet() : tesseract.getLangs();
+    }
+
+    /**
+     * Returns the installed OCR languages, sorted alphabetically, empty when Tesseract is not
+     * installed
+     */
+    public static Set<String> installed() {
+        final TikaConfig config = TikaConfig.getDefaultConfig();
+        return new TreeSet<>(installed(OcrLanguageFallback.findTesseract(config.getParser())));
+    }
+
+    /**
+     * Checks the languages of the TesseractOCRConfig of the context. Nothing is checked when OCR
+     * is skipped, when a Rust OCR backend replaces Tesseract, or when Tesseract is not installed,
+     * in which case images are not OCR'd anyway.
+     */
+    public static void check(Parser parser, ParseContext context, Metadata metadata)
+            throws MissingOcrLanguageException {
+        final TesseractOCRConfig config = context.get(TesseractOCRConfig.class);
+        if (!(config instanceof ExtendedTesseractOCRConfig) || config.isSkipOcr()
+                || ((ExtendedTesseractOCRConfig) config).getOcrBackendHandle() != 0) {
+            return;
+        }
+        final Set<String> installed = installed(OcrLanguageFallback.findTesseract(parser));
+        if (installed.isEmpty()) {
+            return;
+        }
+
+        final List<String> kept = new ArrayList<>();
+        final List<String> missing = new ArrayList<>();
+        for (String language : config.getLanguage().split("\\+")) {
+            if (language.isEmpty()) {
+                continue;
+            }
+            (installed.contains(language) ? kept : missing).add(language);
+        }
+        if (missing.isEmpty()) {
+            return;
+        }
+        if (!((ExtendedTesseractOCRConfig) config).isFallbackOnMissingLanguage()) {
+            throw new MissingOcrLanguageException(missing.get(0));
+        }
+
+        if (kept.isEmpty()) {
+            kept.add(installed.contains(DEFAULT_LANGUAGE)
+                    ? DEFAULT_LANGUAGE : new TreeSet<>(installed).first());
+        }
+        final String fallback = String.join("+", kept);
+        // The config may be shared with other parses, so the fallback is set on a copy
+        final ExtendedTesseractOCRConfig fallbackConfig = ((ExtendedTesseractOCRConfig) config).copy();
+        fallbackConfig.setLanguage(fallback);
+        context.set(TesseractOCRConfig.class, fallbackConfig);
+        metadata.add(OCR_WARNING, "OCR language " + String.join("+", missing)
+                + " is not installed, using " + fallback);
+    }
+}
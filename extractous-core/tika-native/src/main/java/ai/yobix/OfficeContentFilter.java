@@ -0,0 +1,106 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.microsoft.OfficeParserConfig;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * Parser decorator that drops the text of charts and SmartArt diagrams of OOXML documents when
+ * the ExtendedOfficeParserConfig excludes them. Tika reads that text from the chart and diagram
+ * data parts related to a slide or document, not from the shapes referencing them, and writes it
+ * into divs with the "chart" and "diagram-data" classes. It is therefore not affected by
+ * OfficeParserConfig#setIncludeShapeBasedContent, which lets charts and SmartArt be kept while
+ * the text of the other shapes is left out.
+ */
+public class OfficeContentFilter extends ParserDecorator {
+
+    private static final String CHART_CLASS = "chart";
+    private static final String SMART_ART_CLASS = "diagram-data";
+
+    public OfficeContentFilter(Parser parser) {
+        super(parser);
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        final OfficeParserConfig config = context.get(OfficeParserConfig.class);
+        if (config instanceof ExtendedOfficeParserConfig) {
+            final ExtendedOfficeParserConfig extended = (ExtendedOfficeParserConfig) config;
+            if (!extended.isIncludeChartContent() || !extended.isIncludeSmartArtContent()) {
+                handler = new ExcludingHandler(
+                        handler, extended.isIncludeChartContent(), extended.isIncludeSmartArtContent());
+            }
+        }
+        super.parse(stream, handler, metadata, context);
+    }
+
+    /**
+     * Drops the divs of the excluded classes, with everything they contain
+     */
+    private static class ExcludingHandler extends ContentHandlerDecorator {
+
+        private final boolean includeCharts;
+        private final boolean includeSmartArt;
+        /**
+         * Depth of the elements within the excluded div, 0 when outside of it
+         */
+        private int skipDepth = 0;
+
+        ExcludingHandler(ContentHandler handler, boolean includeCharts, boolean includeSmartArt) {
+            super(handler);
+            this.includeCharts = includeCharts;
+            this.includeSmartArt = includeSmartArt;
+        }
+
+        private boolean isExcluded(String localName, Attributes atts) {
+            if (!"div".equals(localName)) {
+                return false;
+            }
+            final String cls = atts.getValue("class");
+            return (!includeCharts && CHART_CLASS.equals(cls)) || (!includeSmartArt && SMART_ART_CLASS.equals(cls));
+        }
+
+        @Override
+        public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+            if (skipDepth > 0 || isExcluded(localName, atts)) {
+                skipDepth++;
+                return;
+            }
+            super.startElement(uri, localName, name, atts);
+        }
+
+        @Override
+        public void endElement(String uri, String localName, String name) throws SAXException {
+            if (skipDepth > 0) {
+                skipDepth--;
+                return;
+            }
+            super.endElement(uri, localName, name);
+        }
+
+        @Override
+        public void characters(char[] ch, int start, int length) throws SAXException {
+            if (skipDepth == 0) {
+                super.characters(ch, start, length);
+            }
+        }
+
+        @Override
+        public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+            if (skipDepth == 0) {
+                super.ignorableWhitespace(ch, start, length);
+            }
+        }
+    }
+}
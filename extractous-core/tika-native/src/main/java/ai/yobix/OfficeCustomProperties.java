@@ -0,0 +1,52 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.Office;
+import org.apache.tika.parser.microsoft.OfficeParserConfig;
+
+/**
+ * Handles the custom (user defined) properties of Office documents, which Tika reports under the
+ * "custom:" metadata prefix, so they can be told apart from the standard document properties.
+ */
+public class OfficeCustomProperties {
+
+    /**
+     * Prefix used for all custom properties: custom:&lt;name&gt;
+     */
+    public static final String PREFIX = Office.USER_DEFINED_METADATA_NAME_PREFIX;
+
+    /**
+     * Returns whether custom properties should be extracted for the given config
+     */
+    public static boolean isEnabled(OfficeParserConfig officeConfig) {
+        return !(officeConfig instanceof ExtendedOfficeParserConfig)
+                || ((ExtendedOfficeParserConfig) officeConfig).isExtractCustomProperties();
+    }
+
+    /**
+     * Removes all the custom properties from the metadata
+     */
+    public static void remove(Metadata metadata) {
+        for (String name : metadata.names()) {
+            if (name.startsWith(PREFIX)) {
+                metadata.remove(name);
+            }
+        }
+    }
+
+    /**
+     * Returns a copy of the metadata without the custom properties. Used when the metadata is
+     * handed out while the parse still goes on in the background and can add to the original
+     */
+    public static Metadata without(Metadata metadata) {
+        final Metadata copy = new Metadata();
+        for (String name : metadata.names()) {
+            if (!name.startsWith(PREFIX)) {
+                for (String value : metadata.getValues(name)) {
+                    copy.add(name, value);
+                }
+            }
+        }
+        return copy;
+    }
+}
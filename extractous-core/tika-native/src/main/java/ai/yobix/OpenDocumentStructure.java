@@ -0,0 +1,457 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.Office;
+import org.apache.tika.metadata.OfficeOpenXMLExtended;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.microsoft.OfficeParserConfig;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.apache.tika.sax.XHTMLContentHandler;
+import org.apache.tika.utils.XMLReaderUtils;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.AttributesImpl;
+import org.xml.sax.helpers.DefaultHandler;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.charset.StandardCharsets;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.zip.ZipEntry;
+import java.util.zip.ZipFile;
+
+/**
+ * Parser decorator that brings OpenDocument files on par with their OOXML counterparts.
+ * The sheets of ODS spreadsheets are wrapped in a div of class "page" headed by the sheet name,
+ * and the slides of ODP presentations in divs of class "slide-content" and "slide-notes", like
+ * Tika does for XLSX and PPTX. Slide notes are dropped when the OfficeParserConfig excludes them.
+ * The statistics and generator of meta.xml are reported under the same keys as for OOXML files,
+ * e.g. meta:word-count and extended-properties:Application, when Tika does not report them.
+ */
+public class OpenDocumentStructure extends ParserDecorator {
+
+    private static final String ODF_PREFIX = "application/vnd.oasis.opendocument.";
+    private static final String SPREADSHEET = ODF_PREFIX + "spreadsheet";
+    private static final String PRESENTATION = ODF_PREFIX + "presentation";
+
+    private static final String META_NS = "urn:oasis:names:tc:opendocument:xmlns:meta:1.0";
+    private static final String TABLE_NS = "urn:oasis:names:tc:opendocument:xmlns:table:1.0";
+    private static final String TEXT_NS = "urn:oasis:names:tc:opendocument:xmlns:text:1.0";
+    private static final String DRAW_NS = "urn:oasis:names:tc:opendocument:xmlns:drawing:1.0";
+    private static final String PRESENTATION_NS = "urn:oasis:names:tc:opendocument:xmlns:presentation:1.0";
+
+    /**
+     * An ODF package starts with its uncompressed mimetype entry: a local file header of 30
+     * bytes, the entry name, then the media type after an extra field that is usually empty
+     */
+    private static final int MIMETYPE_OFFSET = 38;
+    private static final int PEEK_LENGTH = MIMETYPE_OFFSET + 80;
+
+    public OpenDocumentStructure(Parser parser) {
+        super(parser);
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        final String type = stream instanceof TikaInputStream ? peekMediaType((TikaInputStream) stream) : null;
+        if (type == null) {
+            super.parse(stream, handler, metadata, context);
+            return;
+        }
+
+        final TikaInputStream tis = (TikaInputStream) stream;
+        List<Section> sections = null;
+        Metadata packageMetadata = null;
+        try (final ZipFile zip = new ZipFile(tis.getFile())) {
+            if (type.startsWith(SPREADSHEET)) {
+                sections = readSheets(zip);
+            } else if (type.startsWith(PRESENTATION)) {
+                sections = readSlides(zip, includeSlideNotes(context));
+            }
+            packageMetadata = readMeta(zip);
+            if (type.startsWith(PRESENTATION)) {
+                packageMetadata.set(Office.SLIDE_COUNT, countSlides(sections));
+            }
+        } catch (IOException | TikaException | SAXException e) {
+            // Best effort, the regular parse reports what it can
+        }
+
+        if (sections != null) {
+            final Marker marker = type.startsWith(SPREADSHEET) ? Marker.TABLE : Marker.PARAGRAPH;
+            handler = new SectionHandler(handler, sections, marker);
+        }
+        super.parse(stream, handler, metadata, context);
+        if (packageMetadata != null) {
+            for (String name : packageMetadata.names()) {
+                if (metadata.get(name) == null) {
+                    metadata.set(name, packageMetadata.get(name));
+                }
+            }
+        }
+    }
+
+    /**
+     * Returns the media type of an ODF package, or null if the stream is not one
+     */
+    private static String peekMediaType(TikaInputStream stream) throws IOException {
+        final byte[] head = new byte[PEEK_LENGTH];
+        final int length = stream.peek(head);
+        if (length <= MIMETYPE_OFFSET || head[0] != 'P' || head[1] != 'K'
+                || !"mimetype".equals(new String(head, 30, 8, StandardCharsets.US_ASCII))) {
+            return null;
+        }
+        // The compressed size of the local file header, the same as the size of a stored entry,
+        // and the length of the extra field that precedes the data
+        final int size = (head[18] & 0xff) | (head[19] & 0xff) << 8 | (head[20] & 0xff) << 16 | (head[21] & 0xff) << 24;
+        final int offset = MIMETYPE_OFFSET + ((head[28] & 0xff) | (head[29] & 0xff) << 8);
+        if (size <= 0 || size > length - offset) {
+            return null;
+        }
+        final String type = new String(head, offset, size, StandardCharsets.US_ASCII);
+        return type.startsWith(ODF_PREFIX) ? type : null;
+    }
+
+    private static boolean includeSlideNotes(ParseContext context) {
+        final OfficeParserConfig config = context.get(OfficeParserConfig.class);
+        return config == null || config.isIncludeSlideNotes();
+    }
+
+    private static void parseEntry(ZipFile zip, String name, DefaultHandler handler)
+            throws IOException, TikaException, SAXException {
+        final ZipEntry entry = zip.getEntry(name);
+        if (entry == null) {
+            throw new IOException("Missing " + name);
+        }
+        try (final InputStream in = zip.getInputStream(entry)) {
+            XMLReaderUtils.parseSAX(in, handler, new ParseContext());
+        }
+    }
+
+    /**
+     * Returns one section per sheet, each covering the table of the sheet
+     */
+    private static List<Section> readSheets(ZipFile zip) throws IOException, TikaException, SAXException {
+        final List<Section> sections = new ArrayList<>();
+        parseEntry(zip, "content.xml", new DefaultHandler() {
+            private int tableDepth = 0;
+
+            @Override
+            public void startElement(String uri, String localName, String qName, Attributes atts) {
+                if (TABLE_NS.equals(uri) && "table".equals(localName)) {
+                    if (tableDepth == 0) {
+                        sections.add(new Section("page", atts.getValue(TABLE_NS, "name"), false, 1));
+                    }
+                    tableDepth++;
+                }
+            }
+
+            @Override
+            public void endElement(String uri, String localName, String qName) {
+                if (TABLE_NS.equals(uri) && "table".equals(localName)) {
+                    tableDepth--;
+                }
+            }
+        });
+        return sections;
+    }
+
+    /**
+     * Returns the sections of the slides, each covering the paragraphs of a slide or its notes
+     */
+    private static List<Section> readSlides(ZipFile zip, boolean includeNotes)
+            throws IOException, TikaException, SAXException {
+        final List<Section> sections = new ArrayList<>();
+        parseEntry(zip, "content.xml", new DefaultHandler() {
+            private Section content = null;
+            private Section notes = null;
+            private boolean inNotes = false;
+
+            @Override
+            public void startElement(String uri, String localName, String qName, Attributes atts) {
+                if (DRAW_NS.equals(uri) && "page".equals(localName)) {
+                    content = new Section("slide-content", null, false, 0);
+                    notes = new Section("slide-notes", null, !includeNotes, 0);
+                } else if (PRESENTATION_NS.equals(uri) && "notes".equals(localName)) {
+                    inNotes = true;
+                } else if (content != null && TEXT_NS.equals(uri)
+                        && ("p".equals(localName) || "h".equals(localName))) {
+                    (inNotes ? notes : content).markers++;
+                }
+            }
+
+            @Override
+            public void endElement(String uri, String localName, String qName) {
+                if (DRAW_NS.equals(uri) && "page".equals(localName) && content != null) {
+                    sections.add(content);
+                    if (notes.markers > 0) {
+                        sections.add(notes);
+                    }
+                    content = null;
+                } else if (PRESENTATION_NS.equals(uri) && "notes".equals(localName)) {
+                    inNotes = false;
+                }
+            }
+        });
+        return sections;
+    }
+
+    /**
+     * Reads the statistics and the generator of meta.xml, under the keys used for OOXML files
+     */
+    private static Metadata readMeta(ZipFile zip) throws IOException, TikaException, SAXException {
+        final Metadata metadata = new Metadata();
+        final StringBuilder generator = new StringBuilder();
+        parseEntry(zip, "meta.xml", new DefaultHandler() {
+            private boolean inGenerator = false;
+
+            @Override
+            public void startElement(String uri, String localName, String qName, Attributes atts) {
+                if (META_NS.equals(uri) && "document-statistic".equals(localName)) {
+                    for (int i = 0; i < atts.getLength(); i++) {
+                        if (META_NS.equals(atts.getURI(i))) {
+                            metadata.set("meta:" + atts.getLocalName(i), atts.getValue(i));
+                        }
+                    }
+                } else if (META_NS.equals(uri) && "generator".equals(localName)) {
+                    inGenerator = true;
+                }
+            }
+
+            @Override
+            public void endElement(String uri, String localName, String qName) {
+                if (META_NS.equals(uri) && "generator".equals(localName)) {
+                    inGenerator = false;
+                }
+            }
+
+            @Override
+            public void characters(char[] ch, int start, int length) {
+                if (inGenerator) {
+                    generator.append(ch, start, length);
+                }
+            }
+        });
+
+        final String application = generator.toString().trim();
+        if (!application.isEmpty()) {
+            metadata.set("generator", application);
+            metadata.set(OfficeOpenXMLExtended.APPLICATION, application);
+        }
+        return metadata;
+    }
+
+    private static int countSlides(List<Section> sections) {
+        int slides = 0;
+        for (Section section : sections) {
+            if ("slide-content".equals(section.cls)) {
+                slides++;
+            }
+        }
+        return slides;
+    }
+
+    /**
+     * A sheet, slide or slide notes: the div it is wrapped in and the number of marker elements
+     * of the XHTML output it covers
+     */
+    private static class Section {
+        final String cls;
+        final String title;
+        final boolean skip;
+        int markers;
+
+        Section(String cls, String title, boolean skip, int markers) {
+            this.cls = cls;
+            this.title = title;
+            this.skip = skip;
+            this.markers = markers;
+        }
+    }
+
+    /**
+     * Elements of the XHTML output that are counted to find the section of the content: the
+     * outermost tables for the sheets, the paragraphs and headings for the slides
+     */
+    private enum Marker {
+        TABLE, PARAGRAPH;
+
+        boolean matches(String localName, int tableDepth) {
+            if (this == TABLE) {
+                return "table".equals(localName) && tableDepth == 0;
+            }
+            return "p".equals(localName) || (localName.length() == 2 && localName.charAt(0) == 'h'
+                    && localName.charAt(1) >= '1' && localName.charAt(1) <= '6');
+        }
+    }
+
+    /**
+     * Wraps the blocks of the body in the divs of their sections. Each top level block belongs to
+     * the section of the next marker element, so the divs only open and close between blocks
+     * and the output stays well formed. Embedded documents, written in package-entry divs, are
+     * left outside of the sections.
+     */
+    private static class SectionHandler extends ContentHandlerDecorator {
+        private static final String XHTML = XHTMLContentHandler.XHTML;
+        private static final String PACKAGE_ENTRY = "package-entry";
+
+        private final List<Section> sections;
+        private final Marker marker;
+        /**
+         * Number of markers before the end of each section
+         */
+        private final int[] ends;
+
+        private int depth = 0;
+        private int bodyDepth = -1;
+        private int tableDepth = 0;
+        private int entryDepth = 0;
+        private int markers = 0;
+        /**
+         * Index of the open section, -1 before the first one
+         */
+        private int open = -1;
+        private boolean opened = false;
+
+        SectionHandler(ContentHandler handler, List<Section> sections, Marker marker) {
+            super(handler);
+            this.sections = sections;
+            this.marker = marker;
+            this.ends = new int[sections.size()];
+            int total = 0;
+            for (int i = 0; i < sections.size(); i++) {
+                total += sections.get(i).markers;
+                ends[i] = total;
+            }
+        }
+
+        private boolean dropping() {
+            return opened && sections.get(open).skip && depth > bodyDepth && entryDepth == 0;
+        }
+
+        /**
+         * Returns the section of the next marker, or the open one past the last marker
+         */
+        private int nextSection() {
+            for (int i = Math.max(open, 0); i < ends.length; i++) {
+                if (markers < ends[i]) {
+                    return i;
+                }
+            }
+            return open;
+        }
+
+        private void moveTo(int target) throws SAXException {
+            if (target == open && opened) {
+                return;
+            }
+            close();
+            for (int i = open + 1; i < target; i++) {
+                // Sections without content, such as empty slides
+                start(sections.get(i));
+                end(sections.get(i));
+            }
+            if (target >= 0) {
+                start(sections.get(target));
+                opened = true;
+            }
+            open = target;
+        }
+
+        private void close() throws SAXException {
+            if (opened) {
+                end(sections.get(open));
+                opened = false;
+            }
+        }
+
+        private void start(Section section) throws SAXException {
+            if (section.skip) {
+                return;
+            }
+            final AttributesImpl atts = new AttributesImpl();
+            atts.addAttribute("", "class", "class", "CDATA", section.cls);
+            super.startElement(XHTML, "div", "div", atts);
+            if (section.title != null) {
+                super.startElement(XHTML, "h1", "h1", new AttributesImpl());
+                final char[] title = section.title.toCharArray();
+                super.characters(title, 0, title.length);
+                super.endElement(XHTML, "h1", "h1");
+            }
+        }
+
+        private void end(Section section) throws SAXException {
+            if (!section.skip) {
+                super.endElement(XHTML, "div", "div");
+            }
+        }
+
+        @Override
+        public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+            if (bodyDepth >= 0 && depth == bodyDepth && entryDepth == 0) {
+                if ("div".equals(localName) && PACKAGE_ENTRY.equals(atts.getValue("class"))) {
+                    close();
+                    entryDepth = 1;
+                } else {
+                    moveTo(nextSection());
+                }
+            } else if (entryDepth > 0) {
+                entryDepth++;
+            }
+            depth++;
+
+            if (entryDepth == 0 && marker.matches(localName, tableDepth)) {
+                markers++;
+            }
+            if ("table".equals(localName)) {
+                tableDepth++;
+            }
+            if ("body".equals(localName) && bodyDepth < 0) {
+                bodyDepth = depth;
+            }
+            if (!dropping()) {
+                super.startElement(uri, localName, name, atts);
+            }
+        }
+
+        @Override
+        public void endElement(String uri, String localName, String name) throws SAXException {
+            if (depth == bodyDepth) {
+                moveTo(sections.size() - 1);
+                close();
+                bodyDepth = -1;
+            }
+            final boolean drop = dropping();
+            if ("table".equals(localName)) {
+                tableDepth--;
+            }
+            if (entryDepth > 0) {
+                entryDepth--;
+            }
+            depth--;
+            if (!drop) {
+                super.endElement(uri, localName, name);
+            }
+        }
+
+        @Override
+        public void characters(char[] ch, int start, int length) throws SAXException {
+            if (!dropping()) {
+                super.characters(ch, start, length);
+            }
+        }
+
+        @Override
+        public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+            if (!dropping()) {
+                super.ignorableWhitespace(ch, start, length);
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class OutlineListResult {
+
+    private final List<ExtractedOutlineItem> items;
+    private final byte status;
+    private final String errorMessage;
+
+    public OutlineListResult(List<ExtractedOutlineItem> items) {
+        this.items = items;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public OutlineListResult(byte status, String errorMessage) {
+        this.items = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the top level items of the outline, in outline order, or null if there is an error
+     * @return array of items
+     */
+    public ExtractedOutlineItem[] getItems() {
+        return items == null ? null : items.toArray(new ExtractedOutlineItem[0]);
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
@@ -0,0 +1,136 @@
+package ai.yobix;
+
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.sax.BodyContentHandler;
+import org.apache.tika.sax.ContentHandlerFactory;
+import org.apache.tika.sax.ToHTMLContentHandler;
+import org.apache.tika.sax.ToTextContentHandler;
+import org.apache.tika.sax.ToXMLContentHandler;
+import org.apache.tika.sax.WriteOutContentHandler;
+import org.xml.sax.ContentHandler;
+
+import java.io.OutputStream;
+import java.io.OutputStreamWriter;
+import java.io.StringWriter;
+import java.io.UnsupportedEncodingException;
+import java.nio.charset.Charset;
+
+/**
+ * Format of the extracted content, mirrors the OutputFormat enum of the Rust crate.
+ * Creates the content handlers that produce each format.
+ */
+public enum OutputFormat {
+    TEXT,
+    XHTML,
+    HTML,
+    MARKDOWN,
+    NDJSON,
+    CSV;
+
+    /**
+     * Creates a handler that collects up to maxLength characters. Its toString() returns the
+     * content. The csvDelimiter separates the cells of the CSV format
+     */
+    public ContentHandler newStringHandler(int maxLength, String csvDelimiter) {
+        switch (this) {
+            case XHTML:
+                return new WriteOutContentHandler(new ToXMLContentHandler(), maxLength);
+            case HTML:
+                return new WriteOutContentHandler(new ToHTMLContentHandler(), maxLength);
+            case MARKDOWN:
+                return new WriteOutContentHandler(new MarkdownContentHandler(new StringWriter()), maxLength);
+            case NDJSON:
+                return new WriteOutContentHandler(new NdjsonContentHandler(new StringWriter()), maxLength);
+            case CSV:
+                return new WriteOutContentHandler(
+                        new CsvContentHandler(new StringWriter(), csvDelimiter), maxLength);
+            default:
+                return new BodyContentHandler(new WriteOutContentHandler(maxLength));
+        }
+    }
+
+    /**
+     * Creates a handler that writes the content to the given stream. The csvDelimiter separates
+     * the cells of the CSV format
+     */
+    public ContentHandler newStreamHandler(OutputStream stream, String encoding, String csvDelimiter)
+            throws UnsupportedEncodingException {
+        switch (this) {
+            case XHTML:
+                return new ToXMLContentHandler(stream, encoding);
+            case HTML:
+                return new ToHTMLContentHandler(stream, encoding);
+            case MARKDOWN:
+                return new MarkdownContentHandler(new OutputStreamWriter(stream, encoding));
+            case NDJSON:
+                return new NdjsonContentHandler(new OutputStreamWriter(stream, encoding));
+            case CSV:
+                return new CsvContentHandler(new OutputStreamWriter(stream, encoding), csvDelimiter);
+            default:
+                return new BodyContentHandler(stream);
+        }
+    }
+
+    /**
+     * Creates a factory of handlers collecting up to maxLength characters each, used to parse
+     * embedded documents recursively. Every document gets its own budget: the factory is not a
+     * WriteLimiter, which RecursiveParserWrapper would apply to all the documents together, and
+     * a document reaching the limit is truncated instead of aborting the parse, so the documents
+     * after it are still extracted.
+     */
+    public ContentHandlerFactory newHandlerFactory(int maxLength, String csvDelimiter) {
+        return new PerDocumentHandlerFactory(this, maxLength, csvDelimiter);
+    }
+
+    private static class PerDocumentHandlerFactory implements ContentHandlerFactory {
+
+        private final OutputFormat format;
+        private final int maxLength;
+        private final String csvDelimiter;
+
+        PerDocumentHandlerFactory(OutputFormat format, int maxLength, String csvDelimiter) {
+            this.format = format;
+            this.maxLength = maxLength;
+            this.csvDelimiter = csvDelimiter;
+        }
+
+        public ContentHandler getNewContentHandler() {
+            final ContentHandler handler;
+            switch (format) {
+                case XHTML:
+                    handler = new ToXMLContentHandler();
+                    break;
+                case HTML:
+                    handler = new ToHTMLContentHandler();
+                    break;
+                case MARKDOWN:
+                    handler = new MarkdownContentHandler(new StringWriter());
+                    break;
+                case NDJSON:
+                    handler = new NdjsonContentHandler(new StringWriter());
+                    break;
+                case CSV:
+                    handler = new CsvContentHandler(new StringWriter(), csvDelimiter);
+                    break;
+                default:
+                    handler = new ToTextContentHandler();
+            }
+            // The ParseContext only records that the limit was reached, it is not read back
+            return new WriteOutContentHandler(handler, maxLength, false, new ParseContext());
+        }
+
+        public ContentHandler getNewContentHandler(OutputStream os, String encoding)
+                throws UnsupportedEncodingException {
+            return format.newStreamHandler(os, encoding, csvDelimiter);
+        }
+
+        public ContentHandler getNewContentHandler(OutputStream os, Charset charset) {
+            try {
+                return format.newStreamHandler(os, charset.name(), csvDelimiter);
+            } catch (UnsupportedEncodingException e) {
+                // Cannot happen, the name comes from a supported charset
+                throw new IllegalStateException(e);
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Locale;
+import java.util.concurrent.TimeoutException;
+
+/**
+ * Parser decorator that skips the pages whose OCR times out instead of failing the whole parse.
+ * The rendered pages of a PDF are OCR'd through this parser, which is set as the Parser of the
+ * ParseContext. The numbers of the skipped pages are recorded in the X-OCR-Skipped-Pages
+ * metadata of the document, and the OCR language and confidence of the other pages, when set by
+ * the OcrLanguageFallback, are copied to it.
+ */
+public class PageOcrTimeoutGuard extends ParserDecorator {
+
+    /**
+     * Metadata key listing the pages whose OCR timed out
+     */
+    public static final String OCR_SKIPPED_PAGES = "X-OCR-Skipped-Pages";
+
+    /**
+     * Metadata of the document being parsed, where the skipped pages are recorded
+     */
+    private Metadata documentMetadata = null;
+    /**
+     * Number of pages started so far, the page being OCR'd is the last one
+     */
+    private int pages = 0;
+    private int depth = 0;
+
+    public PageOcrTimeoutGuard(Parser parser) {
+        super(parser);
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        if (depth == 0) {
+            documentMetadata = metadata;
+            pages = 0;
+            handler = new PageCounter(handler);
+        }
+
+        final boolean pageOcr = depth > 0 && isPageOcr(metadata);
+        depth++;
+        try {
+            super.parse(stream, handler, metadata, context);
+            if (pageOcr) {
+                // The page OCR has its own metadata, so the language kept for it by the
+                // OcrLanguageFallback is copied to the document
+                copyValues(metadata, OcrLanguageFallback.OCR_LANGUAGE);
+                copyValues(metadata, OcrLanguageFallback.OCR_CONFIDENCE);
+            }
+        } catch (TikaException e) {
+            if (!pageOcr || !isTimeout(e)) {
+                throw e;
+            }
+            // The page is left without text and the parse goes on with the next one
+            documentMetadata.add(OCR_SKIPPED_PAGES, Integer.toString(pages));
+        } finally {
+            depth--;
+        }
+    }
+
+    /**
+     * Returns true for the rendered pages of a PDF, which are passed to the OCR as image/ocr-*
+     */
+    private static boolean isPageOcr(Metadata metadata) {
+        final String contentType = metadata.get(Metadata.CONTENT_TYPE);
+        return contentType != null && contentType.startsWith("image/ocr-");
+    }
+
+    private void copyValues(Metadata pageMetadata, String key) {
+        for (String value : pageMetadata.getValues(key)) {
+            documentMetadata.add(key, value);
+        }
+    }
+
+    static boolean isTimeout(Throwable e) {
+        for (Throwable t = e; t != null; t = t.getCause()) {
+            if (t instanceof TimeoutException) {
+                return true;
+            }
+            final String message = t.getMessage();
+            if (message != null && message.toLowerCase(Locale.ROOT).contains("timeout")) {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    /**
+     * Counts the pages of the document from the page divs of its XHTML
+     */
+    private class PageCounter extends ContentHandlerDecorator {
+
+        PageCounter(ContentHandler handler) {
+            super(handler);
+        }
+
+        @Override
+        public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+            if ("div".equals(localName) && "page".equals(atts.getValue("class"))) {
+                pages++;
+            }
+            super.startElement(uri, localName, name, atts);
+        }
+    }
+}
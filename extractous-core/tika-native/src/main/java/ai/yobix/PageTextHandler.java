@@ -0,0 +1,67 @@
+package ai.yobix;
+
+import org.xml.sax.Attributes;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+/**
+ * ContentHandler that collects the text of each page of a PDF, the div elements with the page
+ * class in the XHTML produced by Tika, and passes it to a Rust handler as soon as the page ends.
+ * The text outside of the pages, such as the text of embedded documents appended after the last
+ * page, is not passed on.
+ */
+public class PageTextHandler extends DefaultHandler {
+
+    private final long handle;
+    private final StringBuilder text = new StringBuilder();
+    private int pageNumber = 0;
+    /**
+     * Depth of the div elements opened in the current page, 0 outside of the pages
+     */
+    private int depth = 0;
+
+    public PageTextHandler(long handle) {
+        this.handle = handle;
+    }
+
+    /**
+     * Implemented in Rust, receives the text of a page, numbered from 1
+     */
+    private static native void page(long handle, int pageNumber, String text) throws SAXException;
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts) throws SAXException {
+        if (!"div".equals(localName.isEmpty() ? qName : localName)) {
+            return;
+        }
+        if (depth > 0) {
+            depth++;
+        } else if ("page".equals(atts.getValue("class"))) {
+            depth = 1;
+            pageNumber++;
+            text.setLength(0);
+        }
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        if (depth > 0 && "div".equals(localName.isEmpty() ? qName : localName)) {
+            depth--;
+            if (depth == 0) {
+                page(handle, pageNumber, text.toString());
+            }
+        }
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) {
+        if (depth > 0) {
+            text.append(ch, start, length);
+        }
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) {
+        characters(ch, start, length);
+    }
+}
@@ -0,0 +1,449 @@
+package ai.yobix;
+
+import org.apache.tika.config.TikaConfig;
+import org.apache.tika.detect.Detector;
+import org.apache.tika.detect.EncodingDetector;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.language.detect.LanguageResult;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.PasswordProvider;
+import org.apache.tika.sax.ContentHandlerFactory;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.ByteArrayInputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.io.OutputStream;
+import java.io.UnsupportedEncodingException;
+import java.nio.charset.StandardCharsets;
+import java.nio.file.Paths;
+import java.util.HashSet;
+import java.util.LinkedHashMap;
+import java.util.Locale;
+import java.util.Map;
+import java.util.Set;
+import java.util.concurrent.ConcurrentHashMap;
+
+/**
+ * Extractor level options that are not part of any Tika parser config.
+ * They are applied to the ParseContext of every parse call.
+ */
+public class ParseOptions {
+
+    /**
+     * Tika configurations loaded from the tikaConfig values. Loading one instantiates all its
+     * parsers, so each is loaded once and shared by the parses using it
+     */
+    private static final Map<String, TikaConfig> TIKA_CONFIGS = new ConcurrentHashMap<>();
+
+    /**
+     * Tika XML configuration, or the path of its file. Null to use the default configuration
+     */
+    private String tikaConfig = null;
+
+    /**
+     * BCP-47 language tag of the locale used by the parsers to format dates and numbers
+     */
+    private String locale = "en-US";
+
+    /**
+     * Declared media type of the input, used as a hint by the detector. Null when unknown
+     */
+    private String contentType = null;
+
+    /**
+     * Name of the OutputFormat of the extracted content
+     */
+    private String outputFormat = OutputFormat.TEXT.name();
+
+    /**
+     * Separator of the cells of the CSV output format
+     */
+    private String csvDelimiter = ",";
+
+    /**
+     * Name of the CharsetPolicy used to resolve the charset of text based formats
+     */
+    private String charsetPolicy = CharsetPolicy.PREFER_DECLARED.name();
+
+    /**
+     * Name of the DetectionMode used to detect the media type of the input
+     */
+    private String detectionMode = DetectionMode.CONTENT_AND_NAME.name();
+
+    /**
+     * Namespace prefixes of the metadata keys to return, the empty string standing for keys
+     * without a namespace. Null to return all the metadata
+     */
+    private Set<String> metadataNamespaces = null;
+
+    /**
+     * Metadata seeded before parsing, such as resourceName or Content-Length. The values found
+     * by the parsers take precedence
+     */
+    private final Map<String, String> metadataHints = new LinkedHashMap<>();
+
+    /**
+     * Names of the LineBreaks written after block elements and br elements in plain text
+     */
+    private String blockBreak = LineBreakHandler.LineBreak.NEWLINE.name();
+    private String brBreak = LineBreakHandler.LineBreak.NEWLINE.name();
+
+    /**
+     * Media types a document may be detected as to be parsed. Empty to accept all types
+     */
+    private final Set<String> acceptedMediaTypes = new HashSet<>();
+
+    /**
+     * Whether the language of the extracted text is detected, see LanguageDetection, and the
+     * language detected by the parse
+     */
+    private boolean detectLanguage = false;
+    private LanguageResult detectedLanguage = null;
+
+    /**
+     * Whether documents containing macros are rejected, see MacroDetector
+     */
+    private boolean rejectMacroDocuments = false;
+
+    /**
+     * Fully qualified class names of the parsers removed from the parse, see ParserFilter
+     */
+    private final Set<String> disabledParsers = new HashSet<>();
+
+    /**
+     * Maximum number of embedded documents of a recursive parse, -1 for no limit
+     */
+    private int maxEmbeddedResources = -1;
+
+    /**
+     * Size in bytes of the buffers between the parser and the reader of a stream
+     */
+    private int streamBufferSize = 8192;
+
+    /**
+     * Handle of the Rust callback receiving the progress of a parse, 0 when there is none
+     */
+    private long progressHandle = 0;
+
+    /**
+     * Handle of the Rust callback resolving the passwords of encrypted documents, 0 when there
+     * is none, see RustPasswordProvider
+     */
+    private long passwordHandle = 0;
+
+    /**
+     * HTTP settings of the url parses
+     */
+    private HttpConfig httpConfig = new HttpConfig();
+
+    public void setTikaConfig(String tikaConfig) {
+        this.tikaConfig = tikaConfig;
+    }
+
+    /**
+     * Returns the Tika configuration of the parse, the default one or the one loaded from the
+     * configured XML or file. An invalid configuration fails with an IOException
+     */
+    public TikaConfig getTikaConfig() throws IOException {
+        if (tikaConfig == null) {
+            return TikaConfig.getDefaultConfig();
+        }
+        final TikaConfig cached = TIKA_CONFIGS.get(tikaConfig);
+        if (cached != null) {
+            return cached;
+        }
+        final TikaConfig loaded = loadTikaConfig(tikaConfig);
+        final TikaConfig previous = TIKA_CONFIGS.putIfAbsent(tikaConfig, loaded);
+        return previous != null ? previous : loaded;
+    }
+
+    private static TikaConfig loadTikaConfig(String tikaConfig) throws IOException {
+        try {
+            if (tikaConfig.trim().startsWith("<")) {
+                try (InputStream stream = new ByteArrayInputStream(tikaConfig.getBytes(StandardCharsets.UTF_8))) {
+                    return new TikaConfig(stream);
+                }
+            }
+            return new TikaConfig(Paths.get(tikaConfig));
+        } catch (TikaException | SAXException e) {
+            throw new IOException("Invalid Tika config: " + e.getMessage(), e);
+        }
+    }
+
+    public String getLocale() {
+        return locale;
+    }
+
+    public void setLocale(String locale) {
+        this.locale = locale;
+    }
+
+    public String getContentType() {
+        return contentType;
+    }
+
+    public void setContentType(String contentType) {
+        this.contentType = contentType;
+    }
+
+    public void addAcceptedMediaType(String mediaType) {
+        final MediaType type = MediaType.parse(mediaType);
+        acceptedMediaTypes.add(type == null ? mediaType : type.getBaseType().toString());
+    }
+
+    /**
+     * Returns whether a document detected as the given base type may be parsed
+     */
+    public boolean acceptsMediaType(MediaType type) {
+        return acceptedMediaTypes.isEmpty() || acceptedMediaTypes.contains(type.toString());
+    }
+
+    public void setDetectLanguage(boolean detectLanguage) {
+        this.detectLanguage = detectLanguage;
+    }
+
+    /**
+     * Detects the language of the extracted text, if enabled, for finishMetadata to add it
+     */
+    public void detectLanguage(String text) throws IOException {
+        if (detectLanguage) {
+            detectedLanguage = LanguageDetection.detect(text);
+        }
+    }
+
+    public void addDisabledParser(String className) {
+        disabledParsers.add(className);
+    }
+
+    /**
+     * Returns the parser without the disabled parsers
+     */
+    public Parser filterParsers(Parser parser) {
+        return ParserFilter.filter(parser, disabledParsers);
+    }
+
+    public boolean isRejectMacroDocuments() {
+        return rejectMacroDocuments;
+    }
+
+    public void setRejectMacroDocuments(boolean rejectMacroDocuments) {
+        this.rejectMacroDocuments = rejectMacroDocuments;
+    }
+
+    public int getMaxEmbeddedResources() {
+        return maxEmbeddedResources;
+    }
+
+    public void setMaxEmbeddedResources(int maxEmbeddedResources) {
+        this.maxEmbeddedResources = maxEmbeddedResources;
+    }
+
+    public int getStreamBufferSize() {
+        return streamBufferSize;
+    }
+
+    public void setStreamBufferSize(int streamBufferSize) {
+        this.streamBufferSize = Math.max(streamBufferSize, 1);
+    }
+
+    public long getProgressHandle() {
+        return progressHandle;
+    }
+
+    public void setProgressHandle(long progressHandle) {
+        this.progressHandle = progressHandle;
+    }
+
+    public long getPasswordHandle() {
+        return passwordHandle;
+    }
+
+    public void setPasswordHandle(long passwordHandle) {
+        this.passwordHandle = passwordHandle;
+    }
+
+    public HttpConfig getHttpConfig() {
+        return httpConfig;
+    }
+
+    public void setHttpConfig(HttpConfig httpConfig) {
+        this.httpConfig = httpConfig;
+    }
+
+    public OutputFormat getOutputFormat() {
+        return OutputFormat.valueOf(outputFormat);
+    }
+
+    public void setOutputFormat(String outputFormat) {
+        this.outputFormat = outputFormat;
+    }
+
+    public void setCsvDelimiter(String csvDelimiter) {
+        this.csvDelimiter = csvDelimiter;
+    }
+
+    public CharsetPolicy getCharsetPolicy() {
+        return CharsetPolicy.valueOf(charsetPolicy);
+    }
+
+    public void setCharsetPolicy(String charsetPolicy) {
+        this.charsetPolicy = charsetPolicy;
+    }
+
+    public DetectionMode getDetectionMode() {
+        return DetectionMode.valueOf(detectionMode);
+    }
+
+    public void setDetectionMode(String detectionMode) {
+        this.detectionMode = detectionMode;
+    }
+
+    /**
+     * Returns the given detector applying the detection mode
+     */
+    public Detector wrapDetector(Detector detector) {
+        return getDetectionMode().wrap(detector);
+    }
+
+    public void setLineBreakPolicy(String blockBreak, String brBreak) {
+        this.blockBreak = blockBreak;
+        this.brBreak = brBreak;
+    }
+
+    /**
+     * Returns true if the line breaks of plain text are rewritten by a LineBreakHandler
+     */
+    private boolean rewritesLineBreaks() {
+        return getOutputFormat() == OutputFormat.TEXT
+                && !(blockBreak.equals(LineBreakHandler.LineBreak.NEWLINE.name())
+                && brBreak.equals(LineBreakHandler.LineBreak.NEWLINE.name()));
+    }
+
+    private ContentHandler applyLineBreakPolicy(ContentHandler handler) {
+        if (!rewritesLineBreaks()) {
+            return handler;
+        }
+        return new LineBreakHandler(handler,
+                LineBreakHandler.LineBreak.valueOf(blockBreak), LineBreakHandler.LineBreak.valueOf(brBreak));
+    }
+
+    /**
+     * Creates a handler for the output format that collects up to maxLength characters.
+     * Its toString() returns the content
+     */
+    public ContentHandler newStringHandler(int maxLength) {
+        return applyLineBreakPolicy(getOutputFormat().newStringHandler(maxLength, csvDelimiter));
+    }
+
+    /**
+     * Creates a handler for the output format that writes the content to the given stream
+     */
+    public ContentHandler newStreamHandler(OutputStream stream, String encoding)
+            throws UnsupportedEncodingException {
+        return applyLineBreakPolicy(getOutputFormat().newStreamHandler(stream, encoding, csvDelimiter));
+    }
+
+    /**
+     * Creates a factory of handlers for the output format collecting up to maxLength
+     * characters each, used to parse embedded documents recursively
+     */
+    public ContentHandlerFactory newHandlerFactory(int maxLength) {
+        final ContentHandlerFactory factory = getOutputFormat().newHandlerFactory(maxLength, csvDelimiter);
+        if (!rewritesLineBreaks()) {
+            return factory;
+        }
+        return new LineBreakHandler.Factory(factory,
+                LineBreakHandler.LineBreak.valueOf(blockBreak), LineBreakHandler.LineBreak.valueOf(brBreak));
+    }
+
+    public void addMetadataNamespace(String namespace) {
+        if (metadataNamespaces == null) {
+            metadataNamespaces = new HashSet<>();
+        }
+        metadataNamespaces.add(namespace);
+    }
+
+    public void addMetadataHint(String name, String value) {
+        metadataHints.put(name, value);
+    }
+
+    /**
+     * Prepares the metadata of a parse to be returned: drops the hints the parsers added a value
+     * to, so their findings take precedence, filters the namespaces and adds the detected
+     * language, whatever the namespaces
+     */
+    public Metadata finishMetadata(Metadata metadata) {
+        if (metadata != null) {
+            for (Map.Entry<String, String> hint : metadataHints.entrySet()) {
+                final String[] values = metadata.getValues(hint.getKey());
+                if (values.length > 1 && values[0].equals(hint.getValue())) {
+                    metadata.remove(hint.getKey());
+                    for (int i = 1; i < values.length; i++) {
+                        metadata.add(hint.getKey(), values[i]);
+                    }
+                }
+            }
+        }
+        final Metadata filtered = filterMetadata(metadata);
+        if (filtered != null && detectedLanguage != null) {
+            LanguageDetection.addTo(filtered, detectedLanguage);
+        }
+        return filtered;
+    }
+
+    /**
+     * Returns the metadata whose keys are in one of the selected namespaces, the namespace of a
+     * key being the part before its first colon. Returns the given metadata when no namespaces
+     * are selected
+     */
+    private Metadata filterMetadata(Metadata metadata) {
+        if (metadataNamespaces == null || metadata == null) {
+            return metadata;
+        }
+        final Metadata filtered = new Metadata();
+        for (String name : metadata.names()) {
+            final int colon = name.indexOf(':');
+            final String namespace = colon < 0 ? "" : name.substring(0, colon);
+            if (metadataNamespaces.contains(namespace)) {
+                for (String value : metadata.getValues(name)) {
+                    filtered.add(name, value);
+                }
+            }
+        }
+        return filtered;
+    }
+
+    /**
+     * Sets the options on the given Metadata before parsing
+     */
+    public void applyTo(Metadata metadata) {
+        for (Map.Entry<String, String> hint : metadataHints.entrySet()) {
+            metadata.set(hint.getKey(), hint.getValue());
+        }
+        if (contentType != null && !contentType.isEmpty()) {
+            metadata.set(Metadata.CONTENT_TYPE, contentType);
+        }
+    }
+
+    /**
+     * Sets the options on the given ParseContext
+     */
+    public void applyTo(ParseContext context) {
+        if (locale != null && !locale.isEmpty()) {
+            context.set(Locale.class, Locale.forLanguageTag(locale));
+        }
+        final EncodingDetector encodingDetector = getCharsetPolicy().newEncodingDetector();
+        if (encodingDetector != null) {
+            context.set(EncodingDetector.class, encodingDetector);
+        }
+        if (passwordHandle != 0) {
+            context.set(PasswordProvider.class,
+                    new RustPasswordProvider(passwordHandle, context.get(PasswordProvider.class)));
+        }
+    }
+}
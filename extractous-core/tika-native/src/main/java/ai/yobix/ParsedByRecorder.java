@@ -0,0 +1,79 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Arrays;
+import java.util.LinkedHashSet;
+import java.util.Set;
+
+/**
+ * Parser decorator that records the parsers that handled a document in its X-Parsed-By
+ * metadata. Tika tracks the parsers of each parse in X-TIKA:Parsed-By, this decorator adds the
+ * parsers of the nested parses, such as embedded documents and OCR'd pages, so the whole chain
+ * is listed in the metadata of the document, outermost parser first.
+ */
+public class ParsedByRecorder extends ParserDecorator {
+
+    /**
+     * Metadata key listing the fully qualified class names of the parsers that handled a document
+     */
+    public static final String PARSED_BY = "X-Parsed-By";
+
+    /**
+     * Parsers of the nested parses of the document being parsed
+     */
+    private final Set<String> nested = new LinkedHashSet<>();
+    private int depth = 0;
+
+    public ParsedByRecorder(Parser parser) {
+        super(parser);
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        if (depth == 0) {
+            nested.clear();
+        }
+        depth++;
+        try {
+            super.parse(stream, handler, metadata, context);
+        } finally {
+            depth--;
+            final Set<String> parsers = new LinkedHashSet<>(
+                    Arrays.asList(metadata.getValues(TikaCoreProperties.TIKA_PARSED_BY)));
+            if (depth > 0) {
+                nested.addAll(parsers);
+            } else {
+                parsers.addAll(nested);
+            }
+            set(metadata, parsers);
+        }
+    }
+
+    /**
+     * Records the parsers Tika tracked so far, for documents returned before their parse ended
+     */
+    public static Metadata record(Metadata metadata) {
+        if (metadata.get(PARSED_BY) == null) {
+            set(metadata, new LinkedHashSet<>(Arrays.asList(metadata.getValues(TikaCoreProperties.TIKA_PARSED_BY))));
+        }
+        return metadata;
+    }
+
+    private static void set(Metadata metadata, Set<String> parsers) {
+        metadata.remove(PARSED_BY);
+        for (String parser : parsers) {
+            metadata.add(PARSED_BY, parser);
+        }
+    }
+}
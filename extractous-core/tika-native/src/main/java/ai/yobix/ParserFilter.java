@@ -0,0 +1,64 @@
+package ai.yobix;
+
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.EmptyParser;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * Removes the disabled parsers, given by their fully qualified class names, from a parser and
+ * from the composite parsers it is made of, recursively. A disabled parser wrapped by decorators
+ * is removed with its decorators. The composite parsers that lose a component are rebuilt as
+ * plain CompositeParsers over the remaining ones, the others are kept as they are.
+ */
+public class ParserFilter {
+
+    /**
+     * Returns the parser without the disabled parsers, or the EmptyParser if it is disabled itself
+     */
+    public static Parser filter(Parser parser, Set<String> disabled) {
+        if (disabled.isEmpty()) {
+            return parser;
+        }
+        final Parser filtered = filterOrNull(parser, disabled);
+        return filtered == null ? EmptyParser.INSTANCE : filtered;
+    }
+
+    private static Parser filterOrNull(Parser parser, Set<String> disabled) {
+        if (isDisabled(parser, disabled)) {
+            return null;
+        }
+        if (!(parser instanceof CompositeParser)) {
+            return parser;
+        }
+
+        final CompositeParser composite = (CompositeParser) parser;
+        final List<Parser> kept = new ArrayList<>();
+        boolean changed = false;
+        for (Parser component : composite.getAllComponentParsers()) {
+            final Parser filtered = filterOrNull(component, disabled);
+            if (filtered != null) {
+                kept.add(filtered);
+            }
+            changed |= filtered != component;
+        }
+        return changed ? new CompositeParser(composite.getMediaTypeRegistry(), kept) : parser;
+    }
+
+    private static boolean isDisabled(Parser parser, Set<String> disabled) {
+        Parser current = parser;
+        while (true) {
+            if (disabled.contains(current.getClass().getName())) {
+                return true;
+            }
+            if (!(current instanceof ParserDecorator)) {
+                return false;
+            }
+            current = ((ParserDecorator) current).getWrappedParser();
+        }
+    }
+}
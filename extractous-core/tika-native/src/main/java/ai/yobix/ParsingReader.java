@@ -9,8 +9,6 @@ import org.xml.sax.ContentHandler;
 import org.apache.tika.exception.ZeroByteFileException;
 import org.apache.tika.metadata.Metadata;
 import org.apache.tika.metadata.TikaCoreProperties;
-import org.apache.tika.sax.BodyContentHandler;
-import org.apache.tika.sax.ToXMLContentHandler;
 
 public class ParsingReader extends Reader {
 
@@ -20,22 +18,23 @@ public class ParsingReader extends Reader {
     private final InputStream stream;
     private final Metadata metadata;
     private final ParseContext context;
-    private final boolean outputXml;
+    private final ParseOptions options;
     private final String encoding;
     private transient Throwable throwable;
 
     public ParsingReader(Parser parser, InputStream stream, Metadata metadata,
-                            ParseContext context, boolean outputXml, String encoding) throws IOException {
+                            ParseContext context, ParseOptions options, String encoding) throws IOException {
         this.parser = parser;
         this.stream = stream;
         this.metadata = metadata;
         this.context = context;
-        this.outputXml = outputXml;
+        this.options = options;
         this.encoding = encoding;
 
-        PipedInputStream pipedInputStream = new PipedInputStream();
+        // The handler writes the text to the pipe in the given encoding, so it is read back with it
+        PipedInputStream pipedInputStream = new PipedInputStream(options.getStreamBufferSize());
         this.pipedOutputStream = new PipedOutputStream(pipedInputStream);
-        this.reader = new BufferedReader(new InputStreamReader(pipedInputStream));
+        this.reader = new BufferedReader(new InputStreamReader(pipedInputStream, encoding));
 
         Executor executor = command -> {
             String name = metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY);
@@ -77,7 +76,7 @@ public class ParsingReader extends Reader {
 
         public void run() {
             try {
-                ContentHandler handler = outputXml ? new ToXMLContentHandler(pipedOutputStream, encoding) : new BodyContentHandler(pipedOutputStream);
+                ContentHandler handler = options.newStreamHandler(pipedOutputStream, encoding);
                 parser.parse(stream, handler, metadata, context);
             } catch (Throwable t) {
                 throwable = t;
@@ -0,0 +1,114 @@
+package ai.yobix;
+
+import org.apache.pdfbox.cos.COSBase;
+import org.apache.pdfbox.cos.COSName;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDDocumentNameDictionary;
+import org.apache.pdfbox.pdmodel.PDEmbeddedFilesNameTreeNode;
+import org.apache.pdfbox.pdmodel.common.PDNameTreeNode;
+import org.apache.pdfbox.pdmodel.common.filespecification.PDComplexFileSpecification;
+import org.apache.pdfbox.pdmodel.common.filespecification.PDEmbeddedFile;
+
+import java.io.IOException;
+import java.util.ArrayList;
+import java.util.Calendar;
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Collects the files attached to a PDF with PDFBox, from the EmbeddedFiles name tree of its
+ * catalog, with the original file name, description and AFRelationship of their file
+ * specification. The file name prefers the unicode name of the specification. The attachments
+ * that cannot be read are skipped, the others are still collected.
+ */
+public class PdfAttachmentCollector {
+
+    private static final COSName AF_RELATIONSHIP = COSName.getPDFName("AFRelationship");
+
+    /**
+     * Returns the attachments of the given PDF, in name order
+     */
+    public static List<ExtractedAttachment> collect(byte[] bytes) throws IOException {
+        final List<ExtractedAttachment> attachments = new ArrayList<>();
+        try (final PDDocument document = PDDocument.load(bytes)) {
+            final PDDocumentNameDictionary names = document.getDocumentCatalog().getNames();
+            final PDEmbeddedFilesNameTreeNode tree = names == null ? null : names.getEmbeddedFiles();
+            if (tree != null) {
+                collectNode(tree, attachments);
+            }
+        }
+        return attachments;
+    }
+
+    private static void collectNode(PDNameTreeNode<PDComplexFileSpecification> node,
+                                    List<ExtractedAttachment> attachments) throws IOException {
+        final Map<String, PDComplexFileSpecification> entries = node.getNames();
+        if (entries != null) {
+            for (Map.Entry<String, PDComplexFileSpecification> entry : entries.entrySet()) {
+                try {
+                    final ExtractedAttachment attachment = toAttachment(entry.getKey(), entry.getValue());
+                    if (attachment != null) {
+                        attachments.add(attachment);
+                    }
+                } catch (IOException | RuntimeException e) {
+                    // Skip this attachment only
+                }
+            }
+        }
+        final List<PDNameTreeNode<PDComplexFileSpecification>> kids = node.getKids();
+        if (kids != null) {
+            for (PDNameTreeNode<PDComplexFileSpecification> kid : kids) {
+                collectNode(kid, attachments);
+            }
+        }
+    }
+
+    /**
+     * Returns the attachment of the file specification, or null if it embeds no file
+     */
+    private static ExtractedAttachment toAttachment(String name, PDComplexFileSpecification spec)
+            throws IOException {
+        if (spec == null) {
+            return null;
+        }
+        PDEmbeddedFile file = spec.getEmbeddedFileUnicode();
+        if (file == null) {
+            file = spec.getEmbeddedFile();
+        }
+        if (file == null) {
+            file = spec.getEmbeddedFileDos();
+        }
+        if (file == null) {
+            file = spec.getEmbeddedFileMac();
+        }
+        if (file == null) {
+            file = spec.getEmbeddedFileUnix();
+        }
+        if (file == null) {
+            return null;
+        }
+
+        String fileName = emptyToNull(spec.getFileUnicode());
+        if (fileName == null) {
+            fileName = emptyToNull(spec.getFilename());
+        }
+        final COSBase relationship = spec.getCOSObject().getDictionaryObject(AF_RELATIONSHIP);
+        return new ExtractedAttachment(
+                name,
+                fileName,
+                emptyToNull(spec.getFileDescription()),
+                relationship instanceof COSName ? ((COSName) relationship).getName() : null,
+                emptyToNull(file.getSubtype()),
+                toIsoString(file.getCreationDate()),
+                toIsoString(file.getModDate()),
+                file.toByteArray());
+    }
+
+    private static String toIsoString(Calendar calendar) {
+        return calendar == null ? null : calendar.toInstant().toString();
+    }
+
+    private static String emptyToNull(String value) {
+        return value == null || value.trim().isEmpty() ? null : value;
+    }
+}
@@ -0,0 +1,66 @@
+package ai.yobix;
+
+import org.apache.pdfbox.cos.COSDictionary;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.pdmodel.interactive.documentnavigation.outline.PDDocumentOutline;
+import org.apache.pdfbox.pdmodel.interactive.documentnavigation.outline.PDOutlineItem;
+import org.apache.pdfbox.pdmodel.interactive.documentnavigation.outline.PDOutlineNode;
+
+import java.io.IOException;
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.IdentityHashMap;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * Collects the outline of a PDF, its bookmarks, with PDFBox, with the page each item points to
+ * through its destination or its GoTo action. The pages of the items whose destination cannot
+ * be resolved are left out, the items are still collected. Items already visited are skipped,
+ * so that malformed outlines looping on themselves terminate.
+ */
+public class PdfOutlineCollector {
+
+    /**
+     * Returns the top level items of the outline of the given PDF, in outline order
+     */
+    public static List<ExtractedOutlineItem> collect(byte[] bytes) throws IOException {
+        try (final PDDocument document = PDDocument.load(bytes)) {
+            final PDDocumentOutline outline = document.getDocumentCatalog().getDocumentOutline();
+            if (outline == null) {
+                return new ArrayList<>();
+            }
+            final Set<COSDictionary> visited = Collections.newSetFromMap(new IdentityHashMap<>());
+            return collectChildren(document, outline, visited);
+        }
+    }
+
+    private static List<ExtractedOutlineItem> collectChildren(PDDocument document, PDOutlineNode node,
+                                                              Set<COSDictionary> visited) {
+        final List<ExtractedOutlineItem> items = new ArrayList<>();
+        for (PDOutlineItem item : node.children()) {
+            if (!visited.add(item.getCOSObject())) {
+                break;
+            }
+            final String title = item.getTitle();
+            items.add(new ExtractedOutlineItem(
+                    title == null ? "" : title.trim(),
+                    page(document, item),
+                    collectChildren(document, item, visited)));
+        }
+        return items;
+    }
+
+    /**
+     * Returns the page the item points to, starting at 1, or 0 if it cannot be resolved
+     */
+    private static int page(PDDocument document, PDOutlineItem item) {
+        try {
+            final PDPage page = item.findDestinationPage(document);
+            return page == null ? 0 : document.getPages().indexOf(page) + 1;
+        } catch (IOException | RuntimeException e) {
+            return 0;
+        }
+    }
+}
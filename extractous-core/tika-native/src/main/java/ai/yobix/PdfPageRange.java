@@ -0,0 +1,110 @@
+package ai.yobix;
+
+import org.apache.pdfbox.io.MemoryUsageSetting;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.tika.detect.Detector;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.PagedText;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.PasswordProvider;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.io.OutputStream;
+import java.nio.file.Files;
+import java.nio.file.Path;
+
+/**
+ * Parser decorator that only parses the pages of a PDF within a range of page numbers,
+ * counted from 1, so the pages outside of it are neither rendered nor OCR'd. The PDF is copied
+ * without the other pages before it is parsed, decrypted with the password of the
+ * PasswordProvider if it is encrypted. The xmpTPg:NPages metadata keeps the page count of the
+ * whole document. Only the outer document is cut, PDFs embedded in it are parsed whole.
+ */
+public class PdfPageRange extends ParserDecorator {
+
+    private static final MediaType PDF = MediaType.application("pdf");
+
+    private final Detector detector;
+    private final int firstPage;
+    private final int lastPage;
+    private int depth = 0;
+
+    public PdfPageRange(Parser parser, Detector detector, int firstPage, int lastPage) {
+        super(parser);
+        this.detector = detector;
+        this.firstPage = Math.max(firstPage, 1);
+        this.lastPage = lastPage;
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        if (depth > 0) {
+            depth++;
+            try {
+                super.parse(stream, handler, metadata, context);
+            } finally {
+                depth--;
+            }
+            return;
+        }
+
+        depth++;
+        try (final TemporaryResources tmp = new TemporaryResources()) {
+            final TikaInputStream tis = TikaInputStream.get(stream, tmp, metadata);
+            if (!PDF.equals(detector.detect(tis, metadata).getBaseType())) {
+                super.parse(tis, handler, metadata, context);
+                return;
+            }
+
+            final Path cut = tmp.createTempFile(".pdf");
+            final int pageCount = cutPages(tis.getPath(), cut, metadata, context);
+            try (final TikaInputStream cutStream = TikaInputStream.get(cut)) {
+                super.parse(cutStream, handler, metadata, context);
+            }
+            metadata.set(PagedText.N_PAGES, pageCount);
+        } finally {
+            depth--;
+        }
+    }
+
+    /**
+     * Writes the pages of the source PDF within the range to the target. Returns the number of
+     * pages of the source
+     */
+    private int cutPages(Path source, Path target, Metadata metadata, ParseContext context)
+            throws IOException {
+        final PasswordProvider passwordProvider = context.get(PasswordProvider.class);
+        String password = passwordProvider == null ? null : passwordProvider.getPassword(metadata);
+        if (password == null) {
+            password = "";
+        }
+
+        try (final PDDocument document = PDDocument.load(
+                source.toFile(), password, MemoryUsageSetting.setupTempFileOnly())) {
+            final int pageCount = document.getNumberOfPages();
+            // Remove from the end, so the indices of the pages left to remove do not change
+            for (int i = pageCount; i >= 1; i--) {
+                if (i < firstPage || i > lastPage) {
+                    document.removePage(i - 1);
+                }
+            }
+            if (document.isEncrypted()) {
+                document.setAllSecurityToBeRemoved(true);
+            }
+            try (final OutputStream out = Files.newOutputStream(target)) {
+                document.save(out);
+            }
+            return pageCount;
+        }
+    }
+}
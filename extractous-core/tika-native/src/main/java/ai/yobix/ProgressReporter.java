@@ -0,0 +1,130 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.PagedText;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * Parser decorator that reports an estimate of the progress of a parse to the Rust callback
+ * behind the handle, as a fraction between 0 and 1. For paged documents, such as PDFs, the
+ * estimate is the share of the pages already parsed, which includes OCR'd pages. For other
+ * documents it is the share of the input stream consumed so far, which stays below 1 until the
+ * parse ends since parsers usually read the whole input before emitting content. The estimate
+ * is updated on the SAX events of the document and reported whenever it grows by at least a
+ * percent, with 0 when the parse starts and 1 when it ends. Nested parses, such as embedded
+ * documents and OCR'd images, are part of the progress of the outer document.
+ */
+public class ProgressReporter extends ParserDecorator {
+
+    /**
+     * Smallest increase of the estimate that is reported
+     */
+    private static final float MIN_STEP = 0.01f;
+
+    /**
+     * Highest estimate based on the input stream, as the content may still be emitted once all
+     * of it is read
+     */
+    private static final float MAX_STREAM_ESTIMATE = 0.99f;
+
+    private final long handle;
+    private int depth = 0;
+
+    public ProgressReporter(Parser parser, long handle) {
+        super(parser);
+        this.handle = handle;
+    }
+
+    private static native void progress(long handle, float fraction);
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        if (depth > 0) {
+            depth++;
+            try {
+                super.parse(stream, handler, metadata, context);
+            } finally {
+                depth--;
+            }
+            return;
+        }
+
+        depth++;
+        try {
+            final Progress progress = new Progress(handler, TikaInputStream.cast(stream), metadata);
+            progress(handle, 0f);
+            super.parse(stream, progress, metadata, context);
+            progress(handle, 1f);
+        } finally {
+            depth--;
+        }
+    }
+
+    /**
+     * Handler updating the estimate on the SAX events of the outer document
+     */
+    private class Progress extends ContentHandlerDecorator {
+
+        private final TikaInputStream stream;
+        private final long length;
+        private final Metadata metadata;
+        private int pages = 0;
+        private float reported = 0f;
+
+        Progress(ContentHandler handler, TikaInputStream stream, Metadata metadata) throws IOException {
+            super(handler);
+            this.stream = stream;
+            this.metadata = metadata;
+            // The length of a stream read from memory is only known from what is left to read
+            this.length = stream == null ? -1
+                    : stream.hasLength() ? stream.getLength() : stream.getPosition() + stream.available();
+        }
+
+        @Override
+        public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+            if ("div".equals(localName) && "page".equals(atts.getValue("class"))) {
+                pages++;
+            }
+            update();
+            super.startElement(uri, localName, name, atts);
+        }
+
+        @Override
+        public void characters(char[] ch, int start, int length) throws SAXException {
+            update();
+            super.characters(ch, start, length);
+        }
+
+        private void update() {
+            final float estimate = estimate();
+            if (estimate >= reported + MIN_STEP) {
+                reported = estimate;
+                progress(handle, estimate);
+            }
+        }
+
+        private float estimate() {
+            final Integer total = metadata.getInt(PagedText.N_PAGES);
+            if (total != null && total > 0 && pages > 0) {
+                // A page that just started is not parsed yet
+                return Math.min((float) (pages - 1) / total, 1f);
+            }
+            if (stream != null && length > 0) {
+                return Math.min((float) stream.getPosition() / length, MAX_STREAM_ESTIMATE);
+            }
+            return 0f;
+        }
+    }
+}
@@ -0,0 +1,21 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.mime.MediaType;
+
+/**
+ * Thrown when the detected media type of a document is not one of the accepted media types
+ */
+public class RejectedMediaTypeException extends TikaException {
+
+    private final MediaType mediaType;
+
+    public RejectedMediaTypeException(MediaType mediaType) {
+        super("Media type " + mediaType + " is not accepted");
+        this.mediaType = mediaType;
+    }
+
+    public MediaType getMediaType() {
+        return mediaType;
+    }
+}
@@ -0,0 +1,56 @@
+package ai.yobix;
+
+import org.xml.sax.Attributes;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+/**
+ * ContentHandler that passes the SAX events of the XHTML produced by Tika to a Rust handler:
+ * the start and end of every element, by local name, with the attributes of the start, and the
+ * text. The whitespace Tika adds between elements for readability is not passed on.
+ */
+public class RustContentHandler extends DefaultHandler {
+
+    private final long handle;
+
+    public RustContentHandler(long handle) {
+        this.handle = handle;
+    }
+
+    /**
+     * Implemented in Rust. The attributes are passed as an array of names and values, in pairs
+     */
+    private static native void startElement(long handle, String name, String[] attributes) throws SAXException;
+
+    /**
+     * Implemented in Rust
+     */
+    private static native void characters(long handle, String text) throws SAXException;
+
+    /**
+     * Implemented in Rust
+     */
+    private static native void endElement(long handle, String name) throws SAXException;
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts) throws SAXException {
+        final String[] attributes = new String[2 * atts.getLength()];
+        for (int i = 0; i < atts.getLength(); i++) {
+            attributes[2 * i] = atts.getLocalName(i).isEmpty() ? atts.getQName(i) : atts.getLocalName(i);
+            attributes[2 * i + 1] = atts.getValue(i);
+        }
+        startElement(handle, localName.isEmpty() ? qName : localName, attributes);
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        if (length > 0) {
+            characters(handle, new String(ch, start, length));
+        }
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        endElement(handle, localName.isEmpty() ? qName : localName);
+    }
+}
@@ -0,0 +1,40 @@
+package ai.yobix;
+
+import org.apache.tika.extractor.DocumentSelector;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+
+/**
+ * DocumentSelector that decides whether an embedded document is parsed by calling back into a
+ * Rust filter with the metadata known when the document is discovered.
+ */
+public class RustDocumentSelector implements DocumentSelector {
+
+    private final long handle;
+
+    public RustDocumentSelector(long handle) {
+        this.handle = handle;
+    }
+
+    /**
+     * Implemented in Rust, returns whether the embedded document should be parsed.
+     * A null name or media type and a negative size mean the value is not known.
+     */
+    private static native boolean select(long handle, String name, String mediaType, long size);
+
+    @Override
+    public boolean select(Metadata metadata) {
+        final String name = metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY);
+        final String mediaType = metadata.get(Metadata.CONTENT_TYPE);
+        long size = -1;
+        try {
+            final String length = metadata.get(Metadata.CONTENT_LENGTH);
+            if (length != null) {
+                size = Long.parseLong(length);
+            }
+        } catch (NumberFormatException e) {
+            // Leave the size unknown
+        }
+        return select(handle, name, mediaType, size);
+    }
+}
@@ -0,0 +1,50 @@
+package ai.yobix;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Objects;
+
+/**
+ * Reads the input of a parse from a Rust reader, identified by the handle of its callback.
+ * The reader is kept alive by the Rust side for as long as the parse may read it, and closing
+ * the stream only stops the reads.
+ */
+public class RustInputStream extends InputStream {
+
+    private final long handle;
+    private boolean closed = false;
+
+    public RustInputStream(long handle) {
+        this.handle = handle;
+    }
+
+    /**
+     * Implemented in Rust, reads up to len bytes from the reader behind the handle into b at off.
+     * Returns the number of bytes read, or -1 at the end of the input
+     */
+    private static native int read(long handle, byte[] b, int off, int len) throws IOException;
+
+    @Override
+    public int read() throws IOException {
+        final byte[] b = new byte[1];
+        final int read = read(b, 0, 1);
+        return read == -1 ? -1 : b[0] & 0xFF;
+    }
+
+    @Override
+    public int read(byte[] b, int off, int len) throws IOException {
+        Objects.checkFromIndexSize(off, len, b.length);
+        if (closed) {
+            throw new IOException("read on a closed InputStream");
+        }
+        if (len == 0) {
+            return 0;
+        }
+        return read(handle, b, off, len);
+    }
+
+    @Override
+    public void close() {
+        closed = true;
+    }
+}
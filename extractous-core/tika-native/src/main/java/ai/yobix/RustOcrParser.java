@@ -0,0 +1,69 @@
+package ai.yobix;
+
+import org.apache.commons.io.IOUtils;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ocr.TesseractOCRConfig;
+import org.apache.tika.sax.XHTMLContentHandler;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Arrays;
+import java.util.Collections;
+import java.util.HashSet;
+import java.util.Set;
+
+/**
+ * Parser that OCRs images by calling back into a Rust OcrBackend. It supports the same types as
+ * Tika's TesseractOCRParser, including the image/ocr-* types the PDF parser uses for rendered pages,
+ * so it replaces Tesseract when added after the default parsers.
+ */
+public class RustOcrParser implements Parser {
+
+    private static final Set<MediaType> SUPPORTED_TYPES = Collections.unmodifiableSet(new HashSet<>(Arrays.asList(
+            MediaType.image("png"), MediaType.image("jpeg"), MediaType.image("tiff"),
+            MediaType.image("bmp"), MediaType.image("gif"), MediaType.image("jp2"),
+            MediaType.image("jpx"), MediaType.image("x-portable-pixmap"),
+            MediaType.image("ocr-png"), MediaType.image("ocr-jpeg"), MediaType.image("ocr-tiff"),
+            MediaType.image("ocr-bmp"), MediaType.image("ocr-gif"), MediaType.image("ocr-jp2"),
+            MediaType.image("ocr-jpx"), MediaType.image("ocr-x-portable-pixmap")
+    )));
+
+    private final long handle;
+
+    public RustOcrParser(long handle) {
+        this.handle = handle;
+    }
+
+    /**
+     * Implemented in Rust, returns the text recognized in the image by the backend behind the handle
+     */
+    private static native String ocr(long handle, byte[] image) throws IOException;
+
+    @Override
+    public Set<MediaType> getSupportedTypes(ParseContext context) {
+        return SUPPORTED_TYPES;
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        final TesseractOCRConfig config = context.get(TesseractOCRConfig.class);
+        if (config != null && config.isSkipOcr()) {
+            return;
+        }
+        final String text = ocr(handle, IOUtils.toByteArray(stream));
+
+        final XHTMLContentHandler xhtml = new XHTMLContentHandler(handler, metadata);
+        xhtml.startDocument();
+        xhtml.startElement("div", "class", "ocr");
+        xhtml.characters(text);
+        xhtml.endElement("div");
+        xhtml.endDocument();
+    }
+}
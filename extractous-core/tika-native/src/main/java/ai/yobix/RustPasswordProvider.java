@@ -0,0 +1,39 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.parser.PasswordProvider;
+
+/**
+ * PasswordProvider that resolves the password of an encrypted document by calling back into a
+ * Rust provider with the name and media type of the document. Tika asks for it once per
+ * encrypted document, embedded documents included. When the Rust provider has no password, the
+ * fallback provider, such as the one of the PDF password, is asked instead.
+ */
+public class RustPasswordProvider implements PasswordProvider {
+
+    private final long handle;
+    private final PasswordProvider fallback;
+
+    public RustPasswordProvider(long handle, PasswordProvider fallback) {
+        this.handle = handle;
+        this.fallback = fallback;
+    }
+
+    /**
+     * Implemented in Rust, returns the password of the document or null if it has none.
+     * A null name or media type means the value is not known.
+     */
+    private static native String password(long handle, String name, String mediaType);
+
+    @Override
+    public String getPassword(Metadata metadata) {
+        final String name = metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY);
+        final String mediaType = metadata.get(Metadata.CONTENT_TYPE);
+        final String password = password(handle, name, mediaType);
+        if (password == null && fallback != null) {
+            return fallback.getPassword(metadata);
+        }
+        return password;
+    }
+}
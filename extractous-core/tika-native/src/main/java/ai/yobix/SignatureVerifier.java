@@ -0,0 +1,172 @@
+package ai.yobix;
+
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.interactive.digitalsignature.PDSignature;
+import org.apache.poi.openxml4j.opc.OPCPackage;
+import org.apache.poi.poifs.crypt.dsig.SignatureConfig;
+import org.apache.poi.poifs.crypt.dsig.SignatureInfo;
+import org.apache.poi.poifs.crypt.dsig.SignaturePart;
+import org.bouncycastle.cert.X509CertificateHolder;
+import org.bouncycastle.cms.CMSException;
+import org.bouncycastle.cms.CMSProcessableByteArray;
+import org.bouncycastle.cms.CMSSignedData;
+import org.bouncycastle.cms.SignerInformation;
+import org.bouncycastle.cms.jcajce.JcaSimpleSignerInfoVerifierBuilder;
+import org.bouncycastle.operator.OperatorCreationException;
+
+import java.io.ByteArrayInputStream;
+import java.io.IOException;
+import java.security.MessageDigest;
+import java.security.NoSuchAlgorithmException;
+import java.security.cert.CertificateException;
+import java.security.cert.X509Certificate;
+import java.util.Arrays;
+import java.util.Collection;
+import java.util.List;
+
+/**
+ * Checks the integrity of the digital signatures of PDF and OOXML documents: the signed bytes
+ * must match the signature, and the signature must verify with the certificate it embeds.
+ * The certificate itself is not validated, there is no check of its chain, trust, expiry or
+ * revocation, so a valid signature only proves the document was not modified since signing.
+ */
+public class SignatureVerifier {
+
+    public static final String UNSIGNED = "UNSIGNED";
+    public static final String VALID = "VALID";
+    public static final String INVALID = "INVALID";
+
+    /**
+     * The outcome of a verification: one of UNSIGNED, VALID and INVALID, with the signer when
+     * valid and the reason when invalid
+     */
+    public static class Outcome {
+        final String status;
+        final String detail;
+
+        Outcome(String status, String detail) {
+            this.status = status;
+            this.detail = detail;
+        }
+    }
+
+    /**
+     * Verifies every signature of a PDF. The first failing signature makes the document invalid,
+     * otherwise the signer of the first signature is returned.
+     */
+    public static Outcome verifyPdf(byte[] bytes) throws IOException {
+        try (final PDDocument document = PDDocument.load(bytes)) {
+            final List<PDSignature> signatures = document.getSignatureDictionaries();
+            if (signatures.isEmpty()) {
+                return new Outcome(UNSIGNED, null);
+            }
+
+            String signer = null;
+            boolean coversDocument = false;
+            for (PDSignature signature : signatures) {
+                final int[] byteRange = signature.getByteRange();
+                if (byteRange == null || byteRange.length != 4) {
+                    return new Outcome(INVALID, "Signature has no valid byte range");
+                }
+                coversDocument |= byteRange[2] + byteRange[3] == bytes.length;
+
+                final String name;
+                try {
+                    name = verifyPdfSignature(signature, bytes);
+                } catch (CMSException | OperatorCreationException | CertificateException
+                         | NoSuchAlgorithmException e) {
+                    return new Outcome(INVALID, "Signature cannot be verified: " + e.getMessage());
+                }
+                if (name == null) {
+                    return new Outcome(INVALID, "Signature does not match the signed content");
+                }
+                if (signer == null) {
+                    signer = name;
+                }
+            }
+
+            if (!coversDocument) {
+                return new Outcome(INVALID, "Document was modified after the last signature");
+            }
+            return new Outcome(VALID, signer);
+        }
+    }
+
+    /**
+     * Returns the signer of the signature if it verifies, null otherwise
+     */
+    private static String verifyPdfSignature(PDSignature signature, byte[] bytes)
+            throws IOException, CMSException, OperatorCreationException, CertificateException,
+            NoSuchAlgorithmException {
+        final byte[] contents = signature.getContents(bytes);
+        final byte[] signedContent = signature.getSignedContent(bytes);
+        final String subFilter = signature.getSubFilter();
+
+        final CMSSignedData cms;
+        if ("adbe.pkcs7.sha1".equals(subFilter)) {
+            // The signature encapsulates the SHA-1 digest of the signed content
+            cms = new CMSSignedData(contents);
+            final byte[] digest = MessageDigest.getInstance("SHA-1").digest(signedContent);
+            final Object encapsulated = cms.getSignedContent() == null ? null : cms.getSignedContent().getContent();
+            if (!(encapsulated instanceof byte[]) || !Arrays.equals(digest, (byte[]) encapsulated)) {
+                return null;
+            }
+        } else if ("adbe.pkcs7.detached".equals(subFilter) || "ETSI.CAdES.detached".equals(subFilter)) {
+            cms = new CMSSignedData(new CMSProcessableByteArray(signedContent), contents);
+        } else {
+            throw new CMSException("Unsupported signature type " + subFilter);
+        }
+
+        final Collection<SignerInformation> signers = cms.getSignerInfos().getSigners();
+        if (signers.isEmpty()) {
+            return null;
+        }
+        String name = null;
+        for (SignerInformation signerInfo : signers) {
+            @SuppressWarnings("unchecked")
+            final Collection<X509CertificateHolder> certificates = cms.getCertificates().getMatches(signerInfo.getSID());
+            if (certificates.isEmpty()) {
+                throw new CMSException("Signer certificate is missing");
+            }
+            final X509CertificateHolder certificate = certificates.iterator().next();
+            if (!signerInfo.verify(new JcaSimpleSignerInfoVerifierBuilder().build(certificate))) {
+                return null;
+            }
+            if (name == null) {
+                name = certificate.getSubject().toString();
+            }
+        }
+        return name;
+    }
+
+    /**
+     * Verifies every XML signature of an OOXML document, the same way as {@link #verifyPdf}
+     */
+    public static Outcome verifyOoxml(byte[] bytes) throws IOException {
+        try (final OPCPackage pkg = OPCPackage.open(new ByteArrayInputStream(bytes))) {
+            final SignatureInfo signatureInfo = new SignatureInfo();
+            signatureInfo.setSignatureConfig(new SignatureConfig());
+            signatureInfo.setOpcPackage(pkg);
+
+            boolean signed = false;
+            String signer = null;
+            for (SignaturePart part : signatureInfo.getSignatureParts()) {
+                signed = true;
+                if (!part.validate()) {
+                    return new Outcome(INVALID, "Signature does not match the signed content");
+                }
+                final X509Certificate certificate = part.getSigner();
+                if (signer == null && certificate != null) {
+                    signer = certificate.getSubjectX500Principal().getName();
+                }
+            }
+            if (!signed) {
+                return new Outcome(UNSIGNED, null);
+            }
+            return new Outcome(VALID, signer == null ? "" : signer);
+
+        } catch (org.apache.poi.openxml4j.exceptions.InvalidFormatException e) {
+            throw new IOException(e);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+package ai.yobix;
+
+import org.apache.poi.openxml4j.exceptions.InvalidFormatException;
+import org.apache.poi.openxml4j.opc.OPCPackage;
+import org.apache.poi.openxml4j.opc.PackagePart;
+import org.apache.poi.openxml4j.opc.PackageRelationshipCollection;
+import org.apache.poi.openxml4j.opc.PackageRelationshipTypes;
+import org.apache.poi.sl.usermodel.Placeholder;
+import org.apache.poi.xslf.usermodel.XMLSlideShow;
+import org.apache.poi.xslf.usermodel.XSLFGroupShape;
+import org.apache.poi.xslf.usermodel.XSLFNotes;
+import org.apache.poi.xslf.usermodel.XSLFShape;
+import org.apache.poi.xslf.usermodel.XSLFSlide;
+import org.apache.poi.xslf.usermodel.XSLFTable;
+import org.apache.poi.xslf.usermodel.XSLFTableCell;
+import org.apache.poi.xslf.usermodel.XSLFTableRow;
+import org.apache.poi.xslf.usermodel.XSLFTextShape;
+
+import java.io.ByteArrayInputStream;
+import java.io.IOException;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.EnumSet;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * Collects the slides of OOXML presentations with POI, each with its title, the text of its
+ * other shapes and its speaker notes, which the Tika parser flattens into the body text. The
+ * title is the text of the title placeholder. The body holds the text of the other text shapes,
+ * tables and groups, in shape order, without the slide number, date and footer placeholders.
+ * Documents that are not OOXML presentations have no slides.
+ */
+public class SlideCollector {
+
+    private static final byte[] ZIP_MAGIC = {'P', 'K', 3, 4};
+
+    private static final Set<Placeholder> TITLES = EnumSet.of(Placeholder.TITLE, Placeholder.CENTERED_TITLE);
+    private static final Set<Placeholder> SKIPPED =
+            EnumSet.of(Placeholder.SLIDE_NUMBER, Placeholder.DATETIME, Placeholder.FOOTER, Placeholder.HEADER);
+
+    /**
+     * Returns the slides of the given presentation, in slide order
+     */
+    public static List<ExtractedSlide> collect(byte[] bytes) throws IOException {
+        final List<ExtractedSlide> slides = new ArrayList<>();
+        if (bytes.length < ZIP_MAGIC.length || !Arrays.equals(Arrays.copyOf(bytes, ZIP_MAGIC.length), ZIP_MAGIC)) {
+            return slides;
+        }
+
+        final OPCPackage pkg;
+        try {
+            pkg = OPCPackage.open(new ByteArrayInputStream(bytes));
+        } catch (InvalidFormatException e) {
+            // A zip file that is not an OOXML package
+            return slides;
+        }
+        try {
+            if (!isPresentation(pkg)) {
+                return slides;
+            }
+            final XMLSlideShow show = new XMLSlideShow(pkg);
+            int index = 0;
+            for (XSLFSlide slide : show.getSlides()) {
+                final StringBuilder body = new StringBuilder();
+                appendShapes(slide.getShapes(), body);
+                slides.add(new ExtractedSlide(index++, emptyToNull(slide.getTitle()), body.toString().trim(),
+                        notes(slide)));
+            }
+        } finally {
+            // Read only access, revert closes the package without saving anything
+            pkg.revert();
+        }
+        return slides;
+    }
+
+    /**
+     * Returns whether the main part of the package is a presentation, including macro enabled
+     * presentations and slide shows
+     */
+    private static boolean isPresentation(OPCPackage pkg) {
+        final PackageRelationshipCollection relationships =
+                pkg.getRelationshipsByType(PackageRelationshipTypes.CORE_DOCUMENT);
+        if (relationships.size() == 0) {
+            return false;
+        }
+        final PackagePart main = pkg.getPart(relationships.getRelationship(0));
+        return main != null && main.getContentType().contains("presentationml");
+    }
+
+    private static void appendShapes(List<XSLFShape> shapes, StringBuilder body) {
+        for (XSLFShape shape : shapes) {
+            if (shape instanceof XSLFGroupShape) {
+                appendShapes(((XSLFGroupShape) shape).getShapes(), body);
+            } else if (shape instanceof XSLFTable) {
+                for (XSLFTableRow row : (XSLFTable) shape) {
+                    final List<String> cells = new ArrayList<>();
+                    for (XSLFTableCell cell : row) {
+                        cells.add(cell.getText());
+                    }
+                    appendLine(body, String.join("\t", cells));
+                }
+            } else if (shape instanceof XSLFTextShape) {
+                final XSLFTextShape text = (XSLFTextShape) shape;
+                final Placeholder placeholder = text.getTextType();
+                if (placeholder != null && (TITLES.contains(placeholder) || SKIPPED.contains(placeholder))) {
+                    continue;
+                }
+                appendLine(body, text.getText());
+            }
+        }
+    }
+
+    /**
+     * Returns the text of the body placeholders of the notes of the slide, or an empty string
+     */
+    private static String notes(XSLFSlide slide) {
+        final XSLFNotes notes = slide.getNotes();
+        if (notes == null) {
+            return "";
+        }
+        final StringBuilder text = new StringBuilder();
+        for (XSLFShape shape : notes.getShapes()) {
+            if (shape instanceof XSLFTextShape
+                    && ((XSLFTextShape) shape).getTextType() == Placeholder.BODY) {
+                appendLine(text, ((XSLFTextShape) shape).getText());
+            }
+        }
+        return text.toString().trim();
+    }
+
+    private static void appendLine(StringBuilder builder, String line) {
+        if (line == null || line.trim().isEmpty()) {
+            return;
+        }
+        if (builder.length() > 0) {
+            builder.append('\n');
+        }
+        builder.append(line);
+    }
+
+    private static String emptyToNull(String value) {
+        return value == null || value.trim().isEmpty() ? null : value.trim();
+    }
+}
@@ -0,0 +1,56 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class SlideListResult {
+
+    private final List<ExtractedSlide> slides;
+    private final byte status;
+    private final String errorMessage;
+
+    public SlideListResult(List<ExtractedSlide> slides) {
+        this.slides = slides;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public SlideListResult(byte status, String errorMessage) {
+        this.slides = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the slides of the document, in slide order, or null if there is an error
+     * @return array of slides
+     */
+    public ExtractedSlide[] getSlides() {
+        return slides == null ? null : slides.toArray(new ExtractedSlide[0]);
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 9: encrypted document
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+}
@@ -0,0 +1,213 @@
+package ai.yobix;
+
+import org.apache.commons.io.IOUtils;
+import org.apache.poi.ss.usermodel.Cell;
+import org.apache.poi.ss.usermodel.CellType;
+import org.apache.poi.ss.usermodel.DataFormatter;
+import org.apache.poi.ss.usermodel.Row;
+import org.apache.poi.ss.usermodel.Sheet;
+import org.apache.poi.ss.usermodel.Workbook;
+import org.apache.poi.ss.usermodel.WorkbookFactory;
+import org.apache.tika.config.TikaConfig;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.microsoft.OfficeParserConfig;
+import org.apache.tika.utils.XMLReaderUtils;
+import org.w3c.dom.Document;
+import org.w3c.dom.Element;
+import org.w3c.dom.Node;
+import org.w3c.dom.NodeList;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import javax.xml.transform.TransformerException;
+import javax.xml.transform.TransformerFactory;
+import javax.xml.transform.dom.DOMSource;
+import javax.xml.transform.stream.StreamResult;
+import java.io.ByteArrayInputStream;
+import java.io.ByteArrayOutputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Files;
+import java.util.ArrayList;
+import java.util.Enumeration;
+import java.util.List;
+import java.util.Set;
+import java.util.zip.CRC32;
+import java.util.zip.ZipEntry;
+import java.util.zip.ZipFile;
+import java.util.zip.ZipOutputStream;
+
+/**
+ * Parser decorator that renders the formula cells of spreadsheets as their formula, or as their
+ * formula and computed value, when the ExtendedOfficeParserConfig asks for it. Tika always
+ * writes the computed values, so the cached value of every formula cell is replaced by the text
+ * to output before the spreadsheet is parsed, everything else being parsed as usual.
+ * XLS and XLSX formulas use the Excel syntax, e.g. =SUM(B2:B3), and ODS formulas the
+ * OpenFormula syntax, e.g. =SUM([.B2:.B3]).
+ */
+public class SpreadsheetFormulas extends ParserDecorator {
+
+    private static final Set<MediaType> EXCEL_TYPES = Set.of(
+            MediaType.application("vnd.ms-excel"),
+            MediaType.application("vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+            MediaType.application("vnd.ms-excel.sheet.macroenabled.12"));
+    private static final MediaType ODS = MediaType.application("vnd.oasis.opendocument.spreadsheet");
+
+    private static final String TABLE_NS = "urn:oasis:names:tc:opendocument:xmlns:table:1.0";
+    private static final String TEXT_NS = "urn:oasis:names:tc:opendocument:xmlns:text:1.0";
+
+    public SpreadsheetFormulas(Parser parser) {
+        super(parser);
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        final OfficeParserConfig config = context.get(OfficeParserConfig.class);
+        final FormulaOutput output = config instanceof ExtendedOfficeParserConfig
+                ? ((ExtendedOfficeParserConfig) config).getFormulaOutput()
+                : FormulaOutput.COMPUTED_VALUE;
+        if (output == FormulaOutput.COMPUTED_VALUE || !(stream instanceof TikaInputStream)) {
+            super.parse(stream, handler, metadata, context);
+            return;
+        }
+
+        final TikaInputStream tis = (TikaInputStream) stream;
+        final MediaType type = TikaConfig.getDefaultConfig().getDetector().detect(tis, new Metadata()).getBaseType();
+        byte[] rewritten = null;
+        try {
+            if (EXCEL_TYPES.contains(type)) {
+                rewritten = rewriteWorkbook(tis, output);
+            } else if (ODS.equals(type)) {
+                rewritten = rewriteOds(tis, output);
+            }
+        } catch (Exception e) {
+            // Encrypted or malformed spreadsheets are parsed as they are, with computed values
+        }
+        if (rewritten == null) {
+            super.parse(stream, handler, metadata, context);
+            return;
+        }
+
+        try (final TikaInputStream replaced = TikaInputStream.get(rewritten)) {
+            super.parse(replaced, handler, metadata, context);
+        }
+    }
+
+    /**
+     * Replaces the cached values of the formula cells of an XLS or XLSX workbook with POI
+     */
+    private static byte[] rewriteWorkbook(TikaInputStream stream, FormulaOutput output) throws IOException {
+        try (final InputStream in = Files.newInputStream(stream.getPath());
+             final Workbook workbook = WorkbookFactory.create(in)) {
+            final DataFormatter formatter = new DataFormatter();
+            formatter.setUseCachedValuesForFormulaCells(true);
+
+            for (Sheet sheet : workbook) {
+                for (Row row : sheet) {
+                    for (Cell cell : row) {
+                        if (cell.getCellType() == CellType.FORMULA) {
+                            final String value = formatter.formatCellValue(cell);
+                            // Setting a value on a formula cell keeps the formula and sets the cached value
+                            cell.setCellValue(output.render("=" + cell.getCellFormula(), value));
+                        }
+                    }
+                }
+            }
+
+            final ByteArrayOutputStream out = new ByteArrayOutputStream();
+            workbook.write(out);
+            return out.toByteArray();
+        }
+    }
+
+    /**
+     * Replaces the paragraphs of the formula cells of an ODS spreadsheet, the displayed values
+     * Tika extracts, in a copy of the package
+     */
+    private static byte[] rewriteOds(TikaInputStream stream, FormulaOutput output)
+            throws IOException, TikaException, SAXException, TransformerException {
+        final ByteArrayOutputStream out = new ByteArrayOutputStream();
+        try (final ZipFile zip = new ZipFile(stream.getFile());
+             final ZipOutputStream zipOut = new ZipOutputStream(out)) {
+            final Enumeration<? extends ZipEntry> entries = zip.entries();
+            while (entries.hasMoreElements()) {
+                final ZipEntry entry = entries.nextElement();
+                byte[] data;
+                try (final InputStream in = zip.getInputStream(entry)) {
+                    data = IOUtils.toByteArray(in);
+                }
+                if ("content.xml".equals(entry.getName())) {
+                    data = rewriteOdsContent(data, output);
+                }
+
+                final ZipEntry copy = new ZipEntry(entry.getName());
+                if (entry.getMethod() == ZipEntry.STORED) {
+                    // The mimetype entry must stay first and uncompressed
+                    final CRC32 crc = new CRC32();
+                    crc.update(data);
+                    copy.setMethod(ZipEntry.STORED);
+                    copy.setSize(data.length);
+                    copy.setCompressedSize(data.length);
+                    copy.setCrc(crc.getValue());
+                }
+                zipOut.putNextEntry(copy);
+                zipOut.write(data);
+                zipOut.closeEntry();
+            }
+        }
+        return out.toByteArray();
+    }
+
+    private static byte[] rewriteOdsContent(byte[] content, FormulaOutput output)
+            throws IOException, TikaException, SAXException, TransformerException {
+        final Document document = XMLReaderUtils.buildDOM(new ByteArrayInputStream(content), new ParseContext());
+
+        final NodeList cells = document.getElementsByTagNameNS(TABLE_NS, "table-cell");
+        for (int i = 0; i < cells.getLength(); i++) {
+            final Element cell = (Element) cells.item(i);
+            if (!cell.hasAttributeNS(TABLE_NS, "formula")) {
+                continue;
+            }
+            // Formulas are prefixed by their namespace, e.g. of:=SUM([.B2:.B3])
+            String formula = cell.getAttributeNS(TABLE_NS, "formula");
+            final int start = formula.indexOf(":=");
+            if (start >= 0) {
+                formula = formula.substring(start + 1);
+            }
+
+            final List<Node> paragraphs = new ArrayList<>();
+            final StringBuilder value = new StringBuilder();
+            for (Node child = cell.getFirstChild(); child != null; child = child.getNextSibling()) {
+                if (TEXT_NS.equals(child.getNamespaceURI()) && "p".equals(child.getLocalName())) {
+                    if (value.length() > 0) {
+                        value.append('\n');
+                    }
+                    value.append(child.getTextContent());
+                    paragraphs.add(child);
+                }
+            }
+
+            final Element paragraph = document.createElementNS(TEXT_NS, "text:p");
+            paragraph.setTextContent(output.render(formula, value.toString()));
+            if (paragraphs.isEmpty()) {
+                cell.appendChild(paragraph);
+            } else {
+                cell.replaceChild(paragraph, paragraphs.get(0));
+                for (Node extra : paragraphs.subList(1, paragraphs.size())) {
+                    cell.removeChild(extra);
+                }
+            }
+        }
+
+        final ByteArrayOutputStream out = new ByteArrayOutputStream();
+        TransformerFactory.newInstance().newTransformer().transform(new DOMSource(document), new StreamResult(out));
+        return out.toByteArray();
+    }
+}
@@ -56,6 +56,12 @@ public class StringResult {
      * 0: OK
      * 1: IOException
      * 2: TikaException
+     * 4: NoParserException
+     * 5: RejectedMediaTypeException
+     * 6: unexpected end of the input
+     * 7: timeout
+     * 8: MissingOcrLanguageException
+     * 9: encrypted document
      */
     public byte getStatus() {
         return status;
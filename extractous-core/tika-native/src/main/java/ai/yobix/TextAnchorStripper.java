@@ -0,0 +1,79 @@
+package ai.yobix;
+
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.text.PDFTextStripper;
+import org.apache.pdfbox.text.TextPosition;
+
+import java.io.IOException;
+import java.io.StringWriter;
+import java.util.List;
+
+/**
+ * Extracts the text of a PDF with anchors that map ranges of the text back to the page and the
+ * box they are drawn in. An anchor covers a word or a line, from the per-character positions
+ * PDFBox reads from the content stream. The text is sorted by position, so it follows the
+ * reading order of the page instead of the order of the content stream.
+ */
+public class TextAnchorStripper extends PDFTextStripper {
+
+    private final boolean perLine;
+    private final StringWriter writer = new StringWriter();
+    private final AnchoredTextResult.Builder anchors = new AnchoredTextResult.Builder();
+    /**
+     * Whether the next word starts a new line anchor
+     */
+    private boolean lineStart = true;
+
+    public TextAnchorStripper(boolean perLine) throws IOException {
+        this.perLine = perLine;
+        setSortByPosition(true);
+    }
+
+    /**
+     * Returns the text of the document and its anchors
+     */
+    public AnchoredTextResult extract(PDDocument document) throws IOException {
+        writeText(document, writer);
+        return anchors.build(writer.toString());
+    }
+
+    @Override
+    protected void startPage(PDPage page) throws IOException {
+        lineStart = true;
+        super.startPage(page);
+    }
+
+    @Override
+    protected void writeLineSeparator() throws IOException {
+        lineStart = true;
+        super.writeLineSeparator();
+    }
+
+    @Override
+    protected void writeString(String text, List<TextPosition> textPositions) throws IOException {
+        final int offset = writer.getBuffer().length();
+        super.writeString(text, textPositions);
+        final int end = writer.getBuffer().length();
+        if (end == offset || textPositions.isEmpty()) {
+            return;
+        }
+
+        float left = Float.MAX_VALUE, top = Float.MAX_VALUE;
+        float right = -Float.MAX_VALUE, bottom = -Float.MAX_VALUE;
+        for (TextPosition position : textPositions) {
+            // Direction adjusted coordinates start at the top left of the page, y is the baseline
+            left = Math.min(left, position.getXDirAdj());
+            top = Math.min(top, position.getYDirAdj() - position.getHeightDir());
+            right = Math.max(right, position.getXDirAdj() + position.getWidthDirAdj());
+            bottom = Math.max(bottom, position.getYDirAdj());
+        }
+
+        if (perLine && !lineStart) {
+            anchors.extend(end, left, top, right, bottom);
+        } else {
+            anchors.add(offset, end, getCurrentPageNo(), left, top, right, bottom);
+        }
+        lineStart = false;
+    }
+}
@@ -1,22 +1,37 @@
 package ai.yobix;
 
 import org.apache.commons.io.input.ReaderInputStream;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.common.PDPageLabels;
+import org.apache.pdfbox.pdmodel.encryption.InvalidPasswordException;
+import org.apache.pdfbox.rendering.ImageType;
+import org.apache.pdfbox.rendering.PDFRenderer;
 import org.apache.tika.Tika;
 import org.apache.tika.config.TikaConfig;
+import org.apache.tika.detect.Detector;
+import org.apache.tika.exception.EncryptedDocumentException;
 import org.apache.tika.exception.TikaException;
 import org.apache.tika.exception.WriteLimitReachedException;
+import org.apache.tika.extractor.DocumentSelector;
+import org.apache.tika.extractor.EmbeddedDocumentExtractor;
 import org.apache.tika.io.TemporaryResources;
 import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.language.detect.LanguageResult;
 import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.mime.MediaTypeRegistry;
 import org.apache.tika.parser.AutoDetectParser;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.EmptyParser;
 import org.apache.tika.parser.ParseContext;
 import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.RecursiveParserWrapper;
 import org.apache.tika.parser.microsoft.OfficeParserConfig;
 import org.apache.tika.parser.ocr.TesseractOCRConfig;
+import org.apache.tika.parser.ocr.TesseractOCRParser;
 import org.apache.tika.parser.pdf.PDFParserConfig;
-import org.apache.tika.sax.BodyContentHandler;
-import org.apache.tika.sax.ToXMLContentHandler;
-import org.apache.tika.sax.WriteOutContentHandler;
+import org.apache.tika.sax.RecursiveParserWrapperHandler;
 import org.graalvm.nativeimage.IsolateThread;
 import org.graalvm.nativeimage.c.function.CEntryPoint;
 import org.graalvm.nativeimage.c.type.CCharPointer;
@@ -24,7 +39,11 @@ import org.graalvm.nativeimage.c.type.CConst;
 import org.graalvm.nativeimage.c.type.CTypeConversion;
 import org.xml.sax.ContentHandler;
 import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
 
+import javax.imageio.ImageIO;
+import java.awt.image.BufferedImage;
+import java.io.ByteArrayOutputStream;
 import java.io.IOException;
 import java.io.InputStream;
 import java.io.Reader;
@@ -37,11 +56,52 @@ import java.nio.charset.Charset;
 import java.nio.charset.StandardCharsets;
 import java.nio.file.Path;
 import java.nio.file.Paths;
+import java.util.Map;
+import java.util.Set;
+import java.util.SortedSet;
+import java.util.TreeSet;
 
 public class TikaNativeMain {
 
     private static final Tika tika = new Tika();
 
+    /**
+     * Metadata key used to return the media type hierarchy to the caller
+     */
+    private static final String MEDIA_TYPE_HIERARCHY = "X-Media-Type-Hierarchy";
+
+    /**
+     * Metadata key used to return the confidence of a detection to the Rust side
+     */
+    private static final String DETECTION_CONFIDENCE = "X-Detection-Confidence";
+
+    /**
+     * Metadata key used to return the supported media types to the caller
+     */
+    private static final String SUPPORTED_MEDIA_TYPES = "X-Supported-Media-Types";
+
+    /**
+     * Metadata key used to return the installed OCR languages to the caller
+     */
+    private static final String INSTALLED_OCR_LANGUAGES = "X-Installed-OCR-Languages";
+
+    /**
+     * Metadata keys used to return the extraction plan to the caller
+     */
+    private static final String SELECTED_PARSER = "X-Selected-Parser";
+    private static final String OCR_WOULD_RUN = "X-Ocr-Would-Run";
+
+    /**
+     * Metadata keys used to return the signer of a valid signature or the reason of an invalid one
+     */
+    private static final String SIGNATURE_SIGNER = "X-Signature-Signer";
+    private static final String SIGNATURE_REASON = "X-Signature-Reason";
+
+    /**
+     * Metadata key used to return the page labels of a PDF to the caller, one per page
+     */
+    private static final String PDF_PAGE_LABELS = "X-PDF-Page-Labels";
+
     /**
      * Parses the given file and returns its type as a mime type
      *
@@ -57,10 +117,311 @@ public class TikaNativeMain {
             return new StringResult(result, metadata);
 
         } catch (java.io.IOException e) {
-            return new StringResult((byte) 1, e.getMessage());
+            return new StringResult(errorStatus(e, (byte) 1), e.getMessage());
+        }
+    }
+
+    /**
+     * Detects the media type of the given array of bytes and returns its type hierarchy. The hierarchy
+     * goes from the most specific type to the most generic one and is stored in the metadata
+     * under the X-Media-Type-Hierarchy key. The content holds the detected type. The type is
+     * detected according to the DetectionMode of the options, with their metadata hints.
+     *
+     * @param data an array of bytes
+     * @return StringResult
+     */
+    public static StringResult mediaTypeHierarchy(ByteBuffer data, ParseOptions options) {
+        final Metadata metadata = new Metadata();
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            final TikaConfig config = options.getTikaConfig();
+            final MediaTypeRegistry registry = config.getMediaTypeRegistry();
+            options.applyTo(metadata);
+            final String detected = options.wrapDetector(config.getDetector()).detect(stream, metadata).toString();
+
+            MediaType type = MediaType.parse(detected);
+            while (type != null) {
+                metadata.add(MEDIA_TYPE_HIERARCHY, type.toString());
+                type = registry.getSupertype(type);
+            }
+            return new StringResult(detected, metadata);
+
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Detects the media type of the given array of bytes, without parsing it. The confidence of
+     * the detection is stored in the metadata under the X-Detection-Confidence key, see
+     * detectionConfidence.
+     *
+     * @param data an array of bytes
+     * @return StringResult holding the detected media type
+     */
+    public static StringResult detectBytes(ByteBuffer data, ParseOptions options) {
+        final Metadata metadata = new Metadata();
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            return detect(stream, metadata, options);
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Detects the media type of the given file, from its content and its name, without parsing
+     * it. See detectBytes.
+     *
+     * @param filePath the path of the file
+     * @return StringResult holding the detected media type
+     */
+    public static StringResult detectFile(String filePath, ParseOptions options) {
+        final Metadata metadata = new Metadata();
+
+        try (final TikaInputStream stream = TikaInputStream.get(Paths.get(filePath), metadata)) {
+            return detect(stream, metadata, options);
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "Could not open file: " + e.getMessage());
+        }
+    }
+
+    private static StringResult detect(TikaInputStream stream, Metadata metadata, ParseOptions options)
+            throws IOException {
+        final TikaConfig config = options.getTikaConfig();
+        final Detector detector = config.getDetector();
+        options.applyTo(metadata);
+
+        final MediaType detected = options.wrapDetector(detector).detect(stream, metadata).getBaseType();
+        final MediaType content = DetectionMode.CONTENT_ONLY.wrap(detector).detect(stream, metadata).getBaseType();
+        final MediaType name = DetectionMode.NAME_ONLY.wrap(detector).detect(stream, metadata).getBaseType();
+
+        metadata.set(DETECTION_CONFIDENCE,
+                detectionConfidence(detected, content, name, config.getMediaTypeRegistry()));
+        return new StringResult(detected.toString(), metadata);
+    }
+
+    /**
+     * Rates a detection from how the types detected from the content only and from the name only
+     * back it: HIGH when the content gives the type and the name, if any, agrees, MEDIUM when the
+     * content gives the type but the name disagrees, or when the name refines the type of the
+     * content, e.g. text/csv for text/plain, LOW when the type comes from the name only and NONE
+     * when nothing was detected.
+     */
+    private static String detectionConfidence(
+            MediaType detected,
+            MediaType content,
+            MediaType name,
+            MediaTypeRegistry registry
+    ) {
+        if (detected.equals(MediaType.OCTET_STREAM)) {
+            return "NONE";
+        }
+        if (detected.equals(content)) {
+            return detected.equals(name) || name.equals(MediaType.OCTET_STREAM) ? "HIGH" : "MEDIUM";
+        }
+        return registry.isSpecializationOf(detected, content) ? "MEDIUM" : "LOW";
+    }
+
+    /**
+     * Detects the language of the given text with Tika's Optimaize language detector. The
+     * language and its probability are stored in the metadata, see LanguageDetection.addTo,
+     * which is empty if the language cannot be told.
+     *
+     * @param text the text whose language is detected
+     * @return StringResult holding an empty content
+     */
+    public static StringResult detectLanguage(String text) {
+        final Metadata metadata = new Metadata();
+        try {
+            final LanguageResult language = LanguageDetection.detect(text);
+            if (language != null) {
+                LanguageDetection.addTo(metadata, language);
+            }
+            return new StringResult("", metadata);
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "Could not load the language models: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Returns the media types the default parser can handle, sorted alphabetically. The types are
+     * stored in the metadata under the X-Supported-Media-Types key.
+     *
+     * @return StringResult
+     */
+    public static StringResult supportedMediaTypes() {
+        final Metadata metadata = new Metadata();
+        final TikaConfig config = TikaConfig.getDefaultConfig();
+        final Parser parser = new AutoDetectParser(config.getDetector(), config.getParser(), new MatroskaParser());
+
+        final SortedSet<String> types = new TreeSet<>();
+        for (MediaType type : parser.getSupportedTypes(new ParseContext())) {
+            types.add(type.toString());
+        }
+        for (String type : types) {
+            metadata.add(SUPPORTED_MEDIA_TYPES, type);
+        }
+        return new StringResult(String.valueOf(types.size()), metadata);
+    }
+
+    /**
+     * Returns the languages installed for Tesseract, sorted alphabetically. The languages are
+     * stored in the metadata under the X-Installed-OCR-Languages key.
+     *
+     * @return StringResult
+     */
+    public static StringResult installedOcrLanguages() {
+        final Metadata metadata = new Metadata();
+        final Set<String> languages = OcrLanguages.installed();
+        for (String language : languages) {
+            metadata.add(INSTALLED_OCR_LANGUAGES, language);
+        }
+        return new StringResult(String.valueOf(languages.size()), metadata);
+    }
+
+    /**
+     * Detects the media type of the given array of bytes and selects the parser that would handle
+     * it, without parsing. The content holds the detected type, the metadata the class name of the
+     * selected parser under X-Selected-Parser and whether OCR would run under X-Ocr-Would-Run.
+     *
+     * @param data an array of bytes
+     * @return StringResult
+     */
+    public static StringResult explain(
+            ByteBuffer data,
+            PDFParserConfig pdfConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions options
+    ) {
+        final Metadata metadata = new Metadata();
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            final TikaConfig config = options.getTikaConfig();
+            final MediaTypeRegistry registry = config.getMediaTypeRegistry();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = createParser(config, pdfConfig, tesseractConfig, options);
+
+            parsecontext.set(Parser.class, parser);
+            ExtendedPDFParserConfig.applyTo(parsecontext, pdfConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            options.applyTo(metadata);
+
+            final MediaType type = options.wrapDetector(config.getDetector()).detect(stream, metadata).getBaseType();
+            final Parser selected = selectParser(parser, type, registry, parsecontext);
+
+            final boolean ocrWouldRun;
+            if (selected instanceof TesseractOCRParser || selected instanceof RustOcrParser
+                    || selected instanceof OcrLanguageFallback) {
+                ocrWouldRun = true;
+            } else if (MediaType.application("pdf").equals(type)) {
+                // Pages are OCR'd as image/ocr-* types, which are only supported when OCR is available
+                ocrWouldRun = pdfConfig.getOcrStrategy() != PDFParserConfig.OCR_STRATEGY.NO_OCR
+                        && selectParser(parser, MediaType.image("ocr-png"), registry, parsecontext) != null;
+            } else {
+                ocrWouldRun = false;
+            }
+
+            metadata.set(SELECTED_PARSER, selected == null ? "" : selected.getClass().getName());
+            metadata.set(OCR_WOULD_RUN, String.valueOf(ocrWouldRun));
+            return new StringResult(type.toString(), metadata);
+
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Resolves the parser that handles the given type, descending through composite and decorated
+     * parsers, and falling back to the supertypes of the type like CompositeParser does.
+     * Returns null if no parser supports the type.
+     */
+    private static Parser selectParser(
+            Parser parser,
+            MediaType type,
+            MediaTypeRegistry registry,
+            ParseContext context
+    ) {
+        while (parser instanceof CompositeParser || parser instanceof ParserDecorator) {
+            if (parser instanceof ParserDecorator) {
+                parser = ((ParserDecorator) parser).getWrappedParser();
+                continue;
+            }
+
+            final Map<MediaType, Parser> parsers = ((CompositeParser) parser).getParsers(context);
+            Parser next = null;
+            for (MediaType t = type; t != null && next == null; t = registry.getSupertype(t)) {
+                next = parsers.get(t);
+            }
+            if (next == null) {
+                return null;
+            }
+            parser = next;
+        }
+        return parser;
+    }
+
+    /**
+     * Throws a RejectedMediaTypeException if the detected media type of the stream is not
+     * accepted by the options, or if the document contains macros and the options reject macro
+     * documents, and a NoParserException if no parser, or only Tika's EmptyParser,
+     * is available for it. Empty streams are left to the parser. The OCR languages are checked
+     * first, whatever the type, see OcrLanguages.
+     */
+    private static void ensureParserAvailable(
+            TikaConfig config,
+            Parser parser,
+            TikaInputStream stream,
+            Metadata metadata,
+            ParseContext context,
+            ParseOptions options
+    ) throws IOException, NoParserException, RejectedMediaTypeException, MissingOcrLanguageException {
+        OcrLanguages.check(parser, context, metadata);
+
+        stream.mark(1);
+        final boolean empty = stream.read() == -1;
+        stream.reset();
+        if (empty) {
+            return;
+        }
+
+        final MediaType type = options.wrapDetector(config.getDetector()).detect(stream, metadata).getBaseType();
+        if (!options.acceptsMediaType(type)) {
+            throw new RejectedMediaTypeException(type);
+        }
+        if (options.isRejectMacroDocuments() && MacroDetector.hasMacros(stream)) {
+            throw new RejectedMediaTypeException(type);
+        }
+        final Parser selected = selectParser(parser, type, config.getMediaTypeRegistry(), context);
+        if (selected == null || selected instanceof EmptyParser) {
+            throw new NoParserException(type);
         }
     }
 
+    /**
+     * Returns the status of a failed call: 6 if the input ended unexpectedly, 7 if the parse
+     * timed out, 9 if the document is encrypted and the password is missing or wrong, or the
+     * given status otherwise. Callers do not read the message of statuses 6, 7 and 9, so the
+     * common failures of large batches are cheap on the Rust side.
+     */
+    static byte errorStatus(Throwable e, byte status) {
+        for (Throwable t = e; t != null; t = t.getCause()) {
+            if (t instanceof java.io.EOFException) {
+                return 6;
+            }
+            if (t instanceof EncryptedDocumentException || t instanceof InvalidPasswordException) {
+                return 9;
+            }
+        }
+        if (PageOcrTimeoutGuard.isTimeout(e)) {
+            return 7;
+        }
+        return status;
+    }
+
     /**
      * Parses the given file and returns its content as String.
      * To avoid unpredictable excess memory use, the returned string contains only up to maxLength
@@ -76,7 +437,7 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
-            boolean asXML
+            ParseOptions options
             // maybe replace with a single config class
     ) {
         try {
@@ -85,13 +446,69 @@ public class TikaNativeMain {
             final InputStream stream = TikaInputStream.get(path, metadata);
 
             String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML);
+                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, options);
             // No need to close the stream because parseToString does so
-            return new StringResult(result, metadata);
+            return new StringResult(result, options.finishMetadata(metadata));
         } catch (java.io.IOException e) {
-            return new StringResult((byte) 1, "Could not open file: " + e.getMessage());
+            return new StringResult(errorStatus(e, (byte) 1), "Could not open file: " + e.getMessage());
+        } catch (RejectedMediaTypeException e) {
+            return new StringResult((byte) 5, e.getMediaType().toString());
+        } catch (NoParserException e) {
+            return new StringResult((byte) 4, e.getMediaType().toString());
+        } catch (MissingOcrLanguageException e) {
+            return new StringResult((byte) 8, e.getLanguage());
         } catch (TikaException e) {
-            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+            return new StringResult(errorStatus(e, (byte) 2), "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given file for its metadata only, e.g. to catalog many files quickly. The content
+     * is discarded as it is parsed, OCR is skipped and the embedded documents are not parsed,
+     * as no Parser is set in the ParseContext. The content of the result is empty.
+     *
+     * @param filePath: the path of the file to be parsed
+     * @return StringResult
+     */
+    public static StringResult parseFileToMetadata(
+            String filePath,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            ParseOptions options
+    ) {
+        final Path path = Paths.get(filePath);
+        final Metadata metadata = new Metadata();
+        final TesseractOCRConfig tesseractConfig = new TesseractOCRConfig();
+        tesseractConfig.setSkipOcr(true);
+
+        try (final TikaInputStream stream = TikaInputStream.get(path, metadata)) {
+            final TikaConfig config = options.getTikaConfig();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = createParser(config, pdfConfig, tesseractConfig, options);
+
+            ExtendedPDFParserConfig.applyTo(parsecontext, pdfConfig);
+            parsecontext.set(OfficeParserConfig.class, officeConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            options.applyTo(parsecontext);
+            options.applyTo(metadata);
+            ensureParserAvailable(config, parser, stream, metadata, parsecontext, options);
+
+            parser.parse(stream, new DefaultHandler(), metadata, parsecontext);
+            if (!OfficeCustomProperties.isEnabled(officeConfig)) {
+                OfficeCustomProperties.remove(metadata);
+            }
+            return new StringResult("", options.finishMetadata(metadata));
+
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "Could not open file: " + e.getMessage());
+        } catch (RejectedMediaTypeException e) {
+            return new StringResult((byte) 5, e.getMediaType().toString());
+        } catch (NoParserException e) {
+            return new StringResult((byte) 4, e.getMediaType().toString());
+        } catch (MissingOcrLanguageException e) {
+            return new StringResult((byte) 8, e.getLanguage());
+        } catch (SAXException | TikaException e) {
+            return new StringResult(errorStatus(e, (byte) 2), "Parse error occurred : " + e.getMessage());
         }
     }
 
@@ -107,26 +524,32 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
-            boolean asXML
+            ParseOptions options
     ) {
         try {
             final URL url = new URI(urlString).toURL();
             final Metadata metadata = new Metadata();
-            final TikaInputStream stream = TikaInputStream.get(url, metadata);
+            final TikaInputStream stream = options.getHttpConfig().open(url, metadata);
 
             String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML);
+                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, options);
             // No need to close the stream because parseToString does so
-            return new StringResult(result, metadata);
+            return new StringResult(result, options.finishMetadata(metadata));
 
         } catch (MalformedURLException e) {
             return new StringResult((byte) 2, "Malformed URL error occurred " + e.getMessage());
         } catch (URISyntaxException e) {
             return new StringResult((byte) 2, "Malformed URI error occurred: " + e.getMessage());
         } catch (java.io.IOException e) {
-            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        } catch (RejectedMediaTypeException e) {
+            return new StringResult((byte) 5, e.getMediaType().toString());
+        } catch (NoParserException e) {
+            return new StringResult((byte) 4, e.getMediaType().toString());
+        } catch (MissingOcrLanguageException e) {
+            return new StringResult((byte) 8, e.getLanguage());
         } catch (TikaException e) {
-            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+            return new StringResult(errorStatus(e, (byte) 2), "Parse error occurred : " + e.getMessage());
         }
     }
 
@@ -142,7 +565,7 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
-            boolean asXML
+            ParseOptions options
     ) {
         final Metadata metadata = new Metadata();
         final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
@@ -150,13 +573,236 @@ public class TikaNativeMain {
 
         try {
             String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML);
+                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, options);
             // No need to close the stream because parseToString does so
-            return new StringResult(result, metadata);
+            return new StringResult(result, options.finishMetadata(metadata));
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        } catch (RejectedMediaTypeException e) {
+            return new StringResult((byte) 5, e.getMediaType().toString());
+        } catch (NoParserException e) {
+            return new StringResult((byte) 4, e.getMediaType().toString());
+        } catch (MissingOcrLanguageException e) {
+            return new StringResult((byte) 8, e.getLanguage());
+        } catch (TikaException e) {
+            return new StringResult(errorStatus(e, (byte) 2), "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given array of bytes and collects the e-mail addresses, phone numbers and URLs of
+     * the document with an EntityCollector. The content of the result is empty, its metadata
+     * holds the entities under the EntityCollector keys.
+     *
+     * @param data an array of bytes
+     * @return StringResult
+     */
+    public static StringResult parseBytesToEntities(
+            ByteBuffer data,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions options
+    ) {
+        final Metadata metadata = new Metadata();
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            final TikaConfig config = options.getTikaConfig();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = createParser(config, pdfConfig, tesseractConfig, options);
+
+            parsecontext.set(Parser.class, parser);
+            ExtendedPDFParserConfig.applyTo(parsecontext, pdfConfig);
+            parsecontext.set(OfficeParserConfig.class, officeConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            options.applyTo(parsecontext);
+            options.applyTo(metadata);
+            ensureParserAvailable(config, parser, stream, metadata, parsecontext, options);
+
+            final EntityCollector collector = new EntityCollector();
+            try {
+                parser.parse(stream, collector, metadata, parsecontext);
+            } catch (SAXException e) {
+                throw new TikaException("Unexpected SAX processing failure", e);
+            }
+            return new StringResult("", collector.getEntities());
+
         } catch (java.io.IOException e) {
-            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        } catch (RejectedMediaTypeException e) {
+            return new StringResult((byte) 5, e.getMediaType().toString());
+        } catch (NoParserException e) {
+            return new StringResult((byte) 4, e.getMediaType().toString());
+        } catch (MissingOcrLanguageException e) {
+            return new StringResult((byte) 8, e.getLanguage());
         } catch (TikaException e) {
-            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+            return new StringResult(errorStatus(e, (byte) 2), "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given array of bytes and passes the SAX events of the produced XHTML to the Rust
+     * handler behind handlerHandle, with a RustContentHandler. The content of the result is
+     * empty, its metadata holds the metadata of the document.
+     *
+     * @param data an array of bytes
+     * @param handlerHandle the handle of the Rust handler
+     * @return StringResult
+     */
+    public static StringResult parseBytesToHandler(
+            ByteBuffer data,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions options,
+            long handlerHandle
+    ) {
+        final Metadata metadata = new Metadata();
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            final TikaConfig config = options.getTikaConfig();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = createParser(config, pdfConfig, tesseractConfig, options);
+
+            parsecontext.set(Parser.class, parser);
+            ExtendedPDFParserConfig.applyTo(parsecontext, pdfConfig);
+            parsecontext.set(OfficeParserConfig.class, officeConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            options.applyTo(parsecontext);
+            options.applyTo(metadata);
+            ensureParserAvailable(config, parser, stream, metadata, parsecontext, options);
+
+            try {
+                parser.parse(stream, new RustContentHandler(handlerHandle), metadata, parsecontext);
+            } catch (SAXException e) {
+                throw new TikaException("Content handler failed: " + e.getMessage(), e);
+            }
+            return new StringResult("", options.finishMetadata(metadata));
+
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        } catch (RejectedMediaTypeException e) {
+            return new StringResult((byte) 5, e.getMediaType().toString());
+        } catch (NoParserException e) {
+            return new StringResult((byte) 4, e.getMediaType().toString());
+        } catch (MissingOcrLanguageException e) {
+            return new StringResult((byte) 8, e.getLanguage());
+        } catch (TikaException e) {
+            return new StringResult(errorStatus(e, (byte) 2), "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given file and passes the text of each of its pages to the Rust handler behind
+     * handlerHandle, as soon as the page is parsed, with a PageTextHandler. The content of the
+     * result is empty, its metadata holds the metadata of the document.
+     *
+     * @param filePath the path of the file
+     * @param handlerHandle the handle of the Rust handler
+     * @return StringResult
+     */
+    public static StringResult parseFileToPages(
+            String filePath,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions options,
+            long handlerHandle
+    ) {
+        final Path path = Paths.get(filePath);
+        final Metadata metadata = new Metadata();
+
+        try (final TikaInputStream stream = TikaInputStream.get(path, metadata)) {
+            final TikaConfig config = options.getTikaConfig();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = createParser(config, pdfConfig, tesseractConfig, options);
+
+            parsecontext.set(Parser.class, parser);
+            ExtendedPDFParserConfig.applyTo(parsecontext, pdfConfig);
+            parsecontext.set(OfficeParserConfig.class, officeConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            options.applyTo(parsecontext);
+            options.applyTo(metadata);
+            ensureParserAvailable(config, parser, stream, metadata, parsecontext, options);
+
+            try {
+                parser.parse(stream, new PageTextHandler(handlerHandle), metadata, parsecontext);
+            } catch (SAXException e) {
+                throw new TikaException("Page handler failed: " + e.getMessage(), e);
+            }
+            return new StringResult("", options.finishMetadata(metadata));
+
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "Could not open file: " + e.getMessage());
+        } catch (RejectedMediaTypeException e) {
+            return new StringResult((byte) 5, e.getMediaType().toString());
+        } catch (NoParserException e) {
+            return new StringResult((byte) 4, e.getMediaType().toString());
+        } catch (MissingOcrLanguageException e) {
+            return new StringResult((byte) 8, e.getLanguage());
+        } catch (TikaException e) {
+            return new StringResult(errorStatus(e, (byte) 2), "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given array of bytes and passes the bytes of each embedded document, recursively,
+     * to the Rust callback behind unpackerHandle, with an EmbeddedUnpacker. The content of the
+     * result is empty, its metadata holds the metadata of the container document.
+     *
+     * @param data an array of bytes
+     * @param unpackerHandle the handle of the Rust callback
+     * @return StringResult
+     */
+    public static StringResult unpackBytes(
+            ByteBuffer data,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions options,
+            long unpackerHandle
+    ) {
+        final Metadata metadata = new Metadata();
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            final TikaConfig config = options.getTikaConfig();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = createParser(config, pdfConfig, tesseractConfig, options);
+
+            parsecontext.set(Parser.class, parser);
+            ExtendedPDFParserConfig.applyTo(parsecontext, pdfConfig);
+            parsecontext.set(OfficeParserConfig.class, officeConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            options.applyTo(parsecontext);
+            options.applyTo(metadata);
+            ensureParserAvailable(config, parser, stream, metadata, parsecontext, options);
+
+            final EmbeddedUnpacker unpacker =
+                    new EmbeddedUnpacker(unpackerHandle, parser, config.getDetector(), parsecontext);
+            parsecontext.set(EmbeddedDocumentExtractor.class, unpacker);
+            try {
+                parser.parse(stream, new DefaultHandler(), metadata, parsecontext);
+            } catch (SAXException e) {
+                throw new TikaException("Unexpected SAX processing failure", e);
+            }
+            if (unpacker.getFailure() != null) {
+                throw unpacker.getFailure();
+            }
+            return new StringResult("", options.finishMetadata(metadata));
+
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        } catch (RejectedMediaTypeException e) {
+            return new StringResult((byte) 5, e.getMediaType().toString());
+        } catch (NoParserException e) {
+            return new StringResult((byte) 4, e.getMediaType().toString());
+        } catch (MissingOcrLanguageException e) {
+            return new StringResult((byte) 8, e.getLanguage());
+        } catch (TikaException e) {
+            return new StringResult(errorStatus(e, (byte) 2), "Parse error occurred : " + e.getMessage());
         }
     }
 
@@ -167,29 +813,24 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
-            boolean asXML
+            ParseOptions options
     ) throws IOException, TikaException {
-        ContentHandler handler;
-        ContentHandler handlerForParser;
-        if (asXML) {
-            handler = new WriteOutContentHandler(new ToXMLContentHandler(), maxLength);
-            handlerForParser = handler;
-        } else {
-            handler = new WriteOutContentHandler(maxLength);
-            handlerForParser = new BodyContentHandler(handler);
-        }
+        final ContentHandler handler = options.newStringHandler(maxLength);
 
         try {
-            final TikaConfig config = TikaConfig.getDefaultConfig();
+            final TikaConfig config = options.getTikaConfig();
             final ParseContext parsecontext = new ParseContext();
-            final Parser parser = new AutoDetectParser(config);
+            final Parser parser = createParser(config, pdfConfig, tesseractConfig, options);
 
             parsecontext.set(Parser.class, parser);
-            parsecontext.set(PDFParserConfig.class, pdfConfig);
+            ExtendedPDFParserConfig.applyTo(parsecontext, pdfConfig);
             parsecontext.set(OfficeParserConfig.class, officeConfig);
             parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            options.applyTo(parsecontext);
+            options.applyTo(metadata);
+            ensureParserAvailable(config, parser, TikaInputStream.get(stream), metadata, parsecontext, options);
 
-            parser.parse(stream, handlerForParser, metadata, parsecontext);
+            parser.parse(stream, handler, metadata, parsecontext);
         } catch (SAXException e) {
             if (!WriteLimitReachedException.isWriteLimitReached(e)) {
                 // This should never happen with BodyContentHandler...
@@ -198,9 +839,176 @@ public class TikaNativeMain {
         } finally {
             stream.close();
         }
-        return handler.toString();
+        if (!OfficeCustomProperties.isEnabled(officeConfig)) {
+            OfficeCustomProperties.remove(metadata);
+        }
+        final String content = handler.toString();
+        options.detectLanguage(content);
+        return content;
+    }
+
+
+    /**
+     * Creates the parser for a parse call. Matroska and WebM files, which Tika does not support,
+     * are parsed by the MatroskaParser. When a Rust OCR backend is set, images are OCR'd by
+     * the RustOcrParser, which overrides Tesseract because later parsers win for the same type.
+     * Likewise, when a fallback list of OCR languages is set, images are OCR'd by the
+     * OcrLanguageFallback, which tries the languages with Tesseract and keeps the best text.
+     * When OCR of inline images is disabled, the parser is wrapped in an InlineImageOcrGate.
+     * Pages whose OCR times out are skipped by the PageOcrTimeoutGuard, and chart or SmartArt
+     * text excluded by the ExtendedOfficeParserConfig is dropped by the OfficeContentFilter.
+     * OpenDocument files get the sheet and slide structure and the metadata of their OOXML
+     * counterparts from the OpenDocumentStructure, and formula cells are rendered as configured
+     * by the SpreadsheetFormulas. The ParsedByRecorder lists the parsers of the whole parse in
+     * the X-Parsed-By metadata. When a Rust progress callback is set, the ProgressReporter
+     * reports an estimate of the progress of the parse to it. When a page range is set, the
+     * PdfPageRange only passes those pages of a PDF to the parsers. The parsers disabled in the
+     * options are removed by the ParserFilter.
+     */
+    private static Parser createParser(
+            TikaConfig config,
+            PDFParserConfig pdfConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions options
+    ) {
+        final Detector detector = options.wrapDetector(config.getDetector());
+        final Parser configParser = options.filterParsers(config.getParser());
+        final Parser matroska = options.filterParsers(new MatroskaParser());
+        Parser parser = new AutoDetectParser(detector, configParser, matroska);
+        if (tesseractConfig instanceof ExtendedTesseractOCRConfig) {
+            final ExtendedTesseractOCRConfig extendedConfig = (ExtendedTesseractOCRConfig) tesseractConfig;
+            final long handle = extendedConfig.getOcrBackendHandle();
+            final TesseractOCRParser tesseract = OcrLanguageFallback.findTesseract(configParser);
+            if (handle != 0) {
+                parser = new AutoDetectParser(
+                        detector, configParser, matroska, options.filterParsers(new RustOcrParser(handle)));
+            } else if (!extendedConfig.getLanguageFallback().isEmpty() && tesseract != null) {
+                parser = new AutoDetectParser(
+                        detector, configParser, matroska,
+                        options.filterParsers(new OcrLanguageFallback(tesseract)));
+            }
+        }
+        if (pdfConfig instanceof ExtendedPDFParserConfig && !((ExtendedPDFParserConfig) pdfConfig).isOcrInlineImages()) {
+            parser = new InlineImageOcrGate(parser);
+        }
+        parser = new ParsedByRecorder(new PageOcrTimeoutGuard(
+                new OfficeContentFilter(new SpreadsheetFormulas(new OpenDocumentStructure(parser)))));
+        if (pdfConfig instanceof ExtendedPDFParserConfig && ((ExtendedPDFParserConfig) pdfConfig).hasPageRange()) {
+            final ExtendedPDFParserConfig extendedConfig = (ExtendedPDFParserConfig) pdfConfig;
+            parser = new PdfPageRange(
+                    parser, detector, extendedConfig.getFirstPage(), extendedConfig.getLastPage());
+        }
+        if (options.getProgressHandle() != 0) {
+            parser = new ProgressReporter(parser, options.getProgressHandle());
+        }
+        return parser;
+    }
+
+    /**
+     * Parses the given array of bytes and the documents embedded in it, recursively. Returns the
+     * metadata of every document, the container first, with its content under X-TIKA:content.
+     * When filterHandle is not 0, the Rust filter behind it decides which embedded documents
+     * are parsed.
+     *
+     * @param data an array of bytes
+     * @return MetadataListResult
+     */
+    public static MetadataListResult parseBytesRecursive(
+            ByteBuffer data,
+            int maxLength,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions options,
+            long filterHandle
+    ) {
+        final Metadata metadata = new Metadata();
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            return parseRecursive(stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, options,
+                    filterHandle);
+        } catch (java.io.IOException e) {
+            return new MetadataListResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
     }
 
+    /**
+     * Parses the given file and the documents embedded in it, recursively, see parseBytesRecursive.
+     *
+     * @param filePath the path of the file
+     * @return MetadataListResult
+     */
+    public static MetadataListResult parseFileRecursive(
+            String filePath,
+            int maxLength,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions options,
+            long filterHandle
+    ) {
+        final Metadata metadata = new Metadata();
+
+        try (final TikaInputStream stream = TikaInputStream.get(Paths.get(filePath), metadata)) {
+            return parseRecursive(stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, options,
+                    filterHandle);
+        } catch (java.io.IOException e) {
+            return new MetadataListResult(errorStatus(e, (byte) 1), "Could not open file: " + e.getMessage());
+        }
+    }
+
+    private static MetadataListResult parseRecursive(
+            TikaInputStream stream,
+            Metadata metadata,
+            int maxLength,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions options,
+            long filterHandle
+    ) {
+        try {
+            final TikaConfig config = options.getTikaConfig();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = new RecursiveParserWrapper(createParser(config, pdfConfig, tesseractConfig, options));
+
+            ExtendedPDFParserConfig.applyTo(parsecontext, pdfConfig);
+            parsecontext.set(OfficeParserConfig.class, officeConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            options.applyTo(parsecontext);
+            options.applyTo(metadata);
+            if (filterHandle != 0) {
+                parsecontext.set(DocumentSelector.class, new RustDocumentSelector(filterHandle));
+            }
+            ensureParserAvailable(config, parser, stream, metadata, parsecontext, options);
+
+            // Once the limit is reached the remaining embedded documents are skipped and the
+            // container metadata is flagged with X-TIKA:EXCEPTION:embedded_resource_limit_reached
+            final RecursiveParserWrapperHandler handler = new RecursiveParserWrapperHandler(
+                    options.newHandlerFactory(maxLength), options.getMaxEmbeddedResources());
+
+            try {
+                parser.parse(stream, handler, metadata, parsecontext);
+            } catch (SAXException e) {
+                if (!WriteLimitReachedException.isWriteLimitReached(e)) {
+                    throw new TikaException("Unexpected SAX processing failure", e);
+                }
+            }
+            return new MetadataListResult(handler.getMetadataList());
+
+        } catch (java.io.IOException e) {
+            return new MetadataListResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        } catch (RejectedMediaTypeException e) {
+            return new MetadataListResult((byte) 5, e.getMediaType().toString());
+        } catch (NoParserException e) {
+            return new MetadataListResult((byte) 4, e.getMediaType().toString());
+        } catch (MissingOcrLanguageException e) {
+            return new MetadataListResult((byte) 8, e.getLanguage());
+        } catch (TikaException e) {
+            return new MetadataListResult(errorStatus(e, (byte) 2), "Parse error occurred : " + e.getMessage());
+        }
+    }
 
     /**
      * Parses the given file and returns its content as Reader. The reader can be used
@@ -215,7 +1023,7 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
-            boolean asXML
+            ParseOptions options
     ) {
         try {
 //            System.out.println("pdfConfig.isExtractInlineImages = " + pdfConfig.isExtractInlineImages());
@@ -230,10 +1038,10 @@ public class TikaNativeMain {
             final Metadata metadata = new Metadata();
             final TikaInputStream stream = TikaInputStream.get(path, metadata);
 
-            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML);
+            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, options);
 
         } catch (java.io.IOException e) {
-            return new ReaderResult((byte) 1, "Could not open file: " + e.getMessage());
+            return new ReaderResult(errorStatus(e, (byte) 1), "Could not open file: " + e.getMessage());
         }
     }
 
@@ -250,21 +1058,21 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
-            boolean asXML
+            ParseOptions options
     ) {
         try {
             final URL url = new URI(urlString).toURL();
             final Metadata metadata = new Metadata();
-            final TikaInputStream stream = TikaInputStream.get(url, metadata);
+            final TikaInputStream stream = options.getHttpConfig().open(url, metadata);
 
-            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML);
+            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, options);
 
         } catch (MalformedURLException e) {
             return new ReaderResult((byte) 2, "Malformed URL error occurred " + e.getMessage());
         } catch (URISyntaxException e) {
             return new ReaderResult((byte) 3, "Malformed URI error occurred: " + e.getMessage());
         } catch (java.io.IOException e) {
-            return new ReaderResult((byte) 1, "IO error occurred: " + e.getMessage());
+            return new ReaderResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
         }
     }
 
@@ -281,7 +1089,7 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
-            boolean asXML
+            ParseOptions options
     ) {
 
 
@@ -289,7 +1097,29 @@ public class TikaNativeMain {
         final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
         final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
 
-        return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML);
+        return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, options);
+    }
+
+    /**
+     * Parses the input read from a Rust reader, through a RustInputStream, and returns a Reader
+     * to stream the content.
+     *
+     * @param handle the handle of the callback of the Rust reader
+     * @return ReaderResult
+     */
+    public static ReaderResult parseReader(
+            long handle,
+            String charsetName,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions options
+    ) {
+        final Metadata metadata = new Metadata();
+        final TikaInputStream stream =
+                TikaInputStream.get(new RustInputStream(handle), new TemporaryResources(), metadata);
+
+        return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, options);
     }
 
     private static ReaderResult parse(
@@ -299,37 +1129,345 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
-            boolean asXML
+            ParseOptions options
     ) {
         try {
 
-            final TikaConfig config = TikaConfig.getDefaultConfig();
+            final TikaConfig config = options.getTikaConfig();
             final ParseContext parsecontext = new ParseContext();
-            final Parser parser = new AutoDetectParser(config);
+            final Parser parser = createParser(config, pdfConfig, tesseractConfig, options);
             final Charset charset = Charset.forName(charsetName, StandardCharsets.UTF_8);
 
             parsecontext.set(Parser.class, parser);
-            parsecontext.set(PDFParserConfig.class, pdfConfig);
+            ExtendedPDFParserConfig.applyTo(parsecontext, pdfConfig);
             parsecontext.set(OfficeParserConfig.class, officeConfig);
             parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            options.applyTo(parsecontext);
+            options.applyTo(metadata);
+            ensureParserAvailable(config, parser, inputStream, metadata, parsecontext, options);
 
             //final Reader reader = new org.apache.tika.parser.ParsingReader(parser, inputStream, metadata, parsecontext);
-            final Reader reader = new ParsingReader(parser, inputStream, metadata, parsecontext, asXML, charset.name());
+            final Reader reader = new ParsingReader(parser, inputStream, metadata, parsecontext, options, charset.name());
 
             // Convert Reader which works with chars to ReaderInputStream which works with bytes
             ReaderInputStream readerInputStream = ReaderInputStream.builder()
                     .setReader(reader)
                     .setCharset(charset)
+                    .setBufferSize(options.getStreamBufferSize())
                     .get();
 
-            return new ReaderResult(readerInputStream, metadata);
+            // The parse goes on in the background, so only the parsers that already started are known.
+            // It can still add custom properties, so these are dropped from a copy rather than the original
+            Metadata result = ParsedByRecorder.record(metadata);
+            if (!OfficeCustomProperties.isEnabled(officeConfig)) {
+                result = OfficeCustomProperties.without(result);
+            }
+            return new ReaderResult(readerInputStream, options.finishMetadata(result));
 
         } catch (java.io.IOException e) {
-            return new ReaderResult((byte) 1, "IO error occurred: " + e.getMessage());
+            return new ReaderResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        } catch (RejectedMediaTypeException e) {
+            return new ReaderResult((byte) 5, e.getMediaType().toString());
+        } catch (NoParserException e) {
+            return new ReaderResult((byte) 4, e.getMediaType().toString());
+        } catch (MissingOcrLanguageException e) {
+            return new ReaderResult((byte) 8, e.getLanguage());
         }
 
     }
 
+    /**
+     * Renders a page of the given PDF to a PNG image, with PDFBox's PDFRenderer.
+     *
+     * @param data an array of bytes holding the PDF
+     * @param page the number of the page, starting at 1
+     * @param dpi the resolution of the image
+     * @return BytesResult holding the PNG image
+     */
+    public static BytesResult renderPdfPage(ByteBuffer data, int page, int dpi) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try (final PDDocument document = PDDocument.load(bytes)) {
+            if (page < 1 || page > document.getNumberOfPages()) {
+                return new BytesResult((byte) 1, "Page " + page + " out of range, the document has "
+                        + document.getNumberOfPages() + " pages");
+            }
+            final BufferedImage image = new PDFRenderer(document).renderImageWithDPI(page - 1, dpi, ImageType.RGB);
+
+            final ByteArrayOutputStream out = new ByteArrayOutputStream();
+            ImageIO.write(image, "png", out);
+            return new BytesResult(out.toByteArray());
+
+        } catch (java.io.IOException e) {
+            return new BytesResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Extracts the text of the given PDF with TextAnchorStripper, with an anchor per word or,
+     * when perLine is true, per line mapping the text back to its page and position.
+     *
+     * @param data an array of bytes holding the PDF
+     * @return AnchoredTextResult
+     */
+    public static AnchoredTextResult extractTextAnchors(ByteBuffer data, boolean perLine) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try (final PDDocument document = PDDocument.load(bytes)) {
+            return new TextAnchorStripper(perLine).extract(document);
+
+        } catch (java.io.IOException e) {
+            return new AnchoredTextResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Collects the fonts of the given PDF with FontCollector, with whether they are embedded
+     * and subset.
+     *
+     * @param data an array of bytes holding the PDF
+     * @return FontListResult
+     */
+    public static FontListResult extractPdfFonts(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try {
+            return new FontListResult(FontCollector.collect(bytes));
+
+        } catch (java.io.IOException e) {
+            return new FontListResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Returns the page labels of the given PDF, as defined by its PDPageLabels, e.g. iv for a
+     * page of the front matter or A-3 for a page of an appendix. The labels are stored in the
+     * metadata under the X-PDF-Page-Labels key, one per page in physical order. A page without
+     * label, or every page of a document without page labels, gets its physical page number. The
+     * content holds the number of pages.
+     *
+     * @param data an array of bytes holding the PDF
+     * @return StringResult
+     */
+    public static StringResult extractPdfPageLabels(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try (final PDDocument document = PDDocument.load(bytes)) {
+            final int pages = document.getNumberOfPages();
+            final PDPageLabels pageLabels = document.getDocumentCatalog().getPageLabels();
+            final String[] labels = pageLabels == null ? new String[0] : pageLabels.getLabelsByPageIndices();
+
+            final Metadata metadata = new Metadata();
+            for (int i = 0; i < pages; i++) {
+                final String label = i < labels.length ? labels[i] : null;
+                metadata.add(PDF_PAGE_LABELS, label == null || label.isEmpty() ? String.valueOf(i + 1) : label);
+            }
+            return new StringResult(String.valueOf(pages), metadata);
+
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Returns whether the given document contains macros, as told by MacroDetector, without
+     * parsing it. The content holds true or false.
+     *
+     * @param data an array of bytes holding the document
+     * @return StringResult
+     */
+    public static StringResult hasMacros(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try (final TikaInputStream stream = TikaInputStream.get(bytes)) {
+            return new StringResult(String.valueOf(MacroDetector.hasMacros(stream)), new Metadata());
+
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Collects the annotations of the given PDF with AnnotationCollector, with their author,
+     * text, page and position.
+     *
+     * @param data an array of bytes holding the PDF
+     * @return AnnotationListResult
+     */
+    public static AnnotationListResult extractPdfAnnotations(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try {
+            return new AnnotationListResult(AnnotationCollector.collect(bytes));
+
+        } catch (java.io.IOException e) {
+            return new AnnotationListResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Collects the files attached to the given PDF with PdfAttachmentCollector, with their
+     * original file name, description and relationship to the PDF.
+     *
+     * @param data an array of bytes holding the PDF
+     * @return AttachmentListResult
+     */
+    public static AttachmentListResult extractPdfAttachments(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try {
+            return new AttachmentListResult(PdfAttachmentCollector.collect(bytes));
+
+        } catch (java.io.IOException e) {
+            return new AttachmentListResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Collects the fields of the AcroForm of the given PDF with FormFieldCollector, with their
+     * kind and value.
+     *
+     * @param data an array of bytes holding the PDF
+     * @return FormFieldListResult
+     */
+    public static FormFieldListResult extractPdfForms(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try {
+            return new FormFieldListResult(FormFieldCollector.collect(bytes));
+
+        } catch (java.io.IOException e) {
+            return new FormFieldListResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Collects the outline of the given PDF with PdfOutlineCollector, with the page each item
+     * points to.
+     *
+     * @param data an array of bytes holding the PDF
+     * @return OutlineListResult
+     */
+    public static OutlineListResult extractPdfOutline(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try {
+            return new OutlineListResult(PdfOutlineCollector.collect(bytes));
+
+        } catch (java.io.IOException e) {
+            return new OutlineListResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Collects the comments of the given OOXML document with CommentCollector, with their
+     * author, timestamp and the comment they reply to.
+     *
+     * @param data an array of bytes holding the document
+     * @return CommentListResult
+     */
+    public static CommentListResult extractComments(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try {
+            return new CommentListResult(CommentCollector.collect(bytes));
+
+        } catch (java.io.IOException e) {
+            return new CommentListResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Collects the slides of the given OOXML presentation with SlideCollector, with their title,
+     * body text and notes.
+     *
+     * @param data an array of bytes holding the presentation
+     * @return SlideListResult
+     */
+    public static SlideListResult extractSlides(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try {
+            return new SlideListResult(SlideCollector.collect(bytes));
+
+        } catch (java.io.IOException e) {
+            return new SlideListResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Collects the images of the given document with ImageCollector, with their page and
+     * position for PDFs.
+     *
+     * @param data an array of bytes holding the document
+     * @return ImageListResult
+     */
+    public static ImageListResult extractImages(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+
+        try {
+            return new ImageListResult(ImageCollector.collect(bytes));
+
+        } catch (java.io.IOException e) {
+            return new ImageListResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        } catch (TikaException | SAXException e) {
+            return new ImageListResult(errorStatus(e, (byte) 2), "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Verifies the digital signatures of the given PDF or OOXML document with SignatureVerifier.
+     * The content holds UNSIGNED, VALID or INVALID, and the metadata the signer under the
+     * X-Signature-Signer key or the reason under the X-Signature-Reason key. Other formats are
+     * reported as UNSIGNED.
+     *
+     * @param data an array of bytes holding the document
+     * @return StringResult
+     */
+    public static StringResult verifySignature(ByteBuffer data) {
+        final byte[] bytes = new byte[data.remaining()];
+        data.get(bytes);
+        final Metadata metadata = new Metadata();
+
+        try (final TikaInputStream stream = TikaInputStream.get(bytes, metadata)) {
+            final MediaTypeRegistry registry = TikaConfig.getDefaultConfig().getMediaTypeRegistry();
+            final MediaType type = MediaType.parse(tika.detect(stream, metadata)).getBaseType();
+
+            final SignatureVerifier.Outcome outcome;
+            if (registry.isSpecializationOf(type, MediaType.application("pdf"))
+                    || type.equals(MediaType.application("pdf"))) {
+                outcome = SignatureVerifier.verifyPdf(bytes);
+            } else if (registry.isSpecializationOf(type, MediaType.application("x-tika-ooxml"))) {
+                outcome = SignatureVerifier.verifyOoxml(bytes);
+            } else {
+                outcome = new SignatureVerifier.Outcome(SignatureVerifier.UNSIGNED, null);
+            }
+
+            final Metadata result = new Metadata();
+            if (SignatureVerifier.VALID.equals(outcome.status)) {
+                result.set(SIGNATURE_SIGNER, outcome.detail);
+            } else if (SignatureVerifier.INVALID.equals(outcome.status)) {
+                result.set(SIGNATURE_REASON, outcome.detail);
+            }
+            return new StringResult(outcome.status, result);
+
+        } catch (java.io.IOException e) {
+            return new StringResult(errorStatus(e, (byte) 1), "IO error occurred: " + e.getMessage());
+        }
+    }
+
     /**
      * This is the main entry point of the native image build. @CEntryPoint is used
      * because we do not want to build an executable with a main method. The gradle nativeImagePlugin